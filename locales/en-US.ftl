@@ -0,0 +1,690 @@
+# English (United States). This is the fallback for messages missing from other languages.
+
+no-icon = No icon
+saved = ✔ Saved
+the-ledger-could-not-be-read = ⚠ The ledger could not be read
+nothing-has-been-changed-fix-the = Nothing has been changed. Fix the file and restart, or start over with an empty ledger.
+start-a-new-ledger-the-old = Start a new ledger (the old file is kept)
+this-ledger-is-encrypted = 🔒 This ledger is encrypted
+unlock = Unlock
+or-open-another-profile = Or open another profile:
+appearance = Appearance
+theme-label = Theme:
+new-transactions = New Transactions
+start-the-form-as = Start the form as:
+calendar = Calendar
+weeks-start-on = Weeks start on:
+months-start-on-day = Months start on day:
+budgets-and-the-this-month-filter = Budgets and the "This month" filter use this, e.g. to follow your payday.
+currency-format = Currency & Format
+balances-are-shown-in = Balances are shown in:
+the-currency-and-number-format-are = The currency and number format are stored in the ledger, so each profile has its own.
+backups = Backups
+keep-the-last = Keep the last
+copies-0-turns-backups-off = copies (0 turns backups off)
+profiles = Profiles
+each-profile-is-a-separate-ledger = Each profile is a separate ledger with its own categories and settings.
+open = Open
+data-file = Data File
+ledger-label = Ledger:
+open-file = 📂 Open…
+save-as = 💾 Save as…
+the-last-file-used-is-opened = The last file used is opened on the next start. Pass --data-file <path> to override it.
+sync = Sync
+point-this-at-a-ledger-in = Point this at a ledger in a synced folder (Dropbox, Syncthing, …) on each device.
+no-shared-file-chosen = No shared file chosen
+choose = Choose…
+export = Export
+export-to-excel = 📊 Export to Excel…
+encryption = Encryption
+the-ledger-and-its-backups-are = 🔒 The ledger and its backups are encrypted. The passphrase is asked for on start.
+the-ledger-is-stored-as-plain = The ledger is stored as plain text.
+remove-encryption = 🔓 Remove encryption
+there-is-no-way-to-recover = There is no way to recover the data if the passphrase is lost.
+restore-from-backup = Restore from backup
+no-backups-yet = No backups yet.
+restore = Restore
+edit-transaction = Edit Transaction
+add-new-transaction = Add New Transaction
+date-label = Date:
+description-label = Desc:
+amount-label = Amount:
+category-label = Category:
+split = ✂ Split
+cancel = Cancel
+note-label = Note:
+attach-receipt = 📎 Attach receipt
+goal-label = Goal:
+refund-of-label = Refund of:
+import-label = 📥 Import:
+csv = CSV…
+json = JSON…
+filters = Filters
+date-range = Date range
+this-week = This week
+this-month = This month
+last-month = Last month
+this-year = This year
+type = Type
+amount = Amount
+categories = Categories
+the-trash-is-empty = The trash is empty.
+empty-trash = Empty trash
+restore-deleted = ♻ Restore
+delete-forever = Delete forever
+set-category = Set category
+delete-selected = 🗑 Delete
+clear-selection = Clear selection
+yes = Yes
+template-label = 📋 Template:
+save-as-template = 💾 Save as template
+these-imported-rows-look-like-transactions = These imported rows look like transactions you already have.
+set-all-to = Set all to:
+imported-label = Imported:
+existing-label = Existing:
+apply = Apply
+splits-label = Splits:
+add-split = + Add split
+balance-history = Balance History
+filters-are-on-the-line-shows = Filters are on: the line shows the running total of the matching transactions only.
+no-transactions-yet-add-some-data = No transactions yet. Add some data to see the graph!
+expense-breakdown = Expense Breakdown
+no-expenses-to-show = No expenses to show.
+add-category = Add Category
+name-label = Name:
+save = Save
+add-subcategory = + Sub
+add = Add
+opening-balance-heading = Opening Balance
+opening-balance-label = Opening balance:
+the-home-currency-and-number-format = The home currency and number format are set in the Settings tab.
+add-rate = Add Rate
+rate-history = Rate History
+fetched = (fetched)
+set-monthly-budget = Set Monthly Budget
+limit-label = Limit:
+set-limit = Set
+no-budgets-yet-set-a-monthly = No budgets yet. Set a monthly limit for a category above.
+new-savings-goal = New Savings Goal
+target-label = Target:
+by-label = By:
+allocate-transactions-to-a-goal-with = Allocate transactions to a goal with the Goal dropdown in the transaction form.
+goals = Goals
+no-goals-yet = No goals yet.
+projected-no-contributions-yet = Projected: no contributions yet
+format-preset-label = Format preset:
+symbol-label = Symbol:
+use-code = Use code
+thousands-label = Thousands:
+decimal-label = Decimal:
+decimals-label = Decimals:
+redo-ctrl-y = Redo (Ctrl+Y)
+undo-ctrl-z = Undo (Ctrl+Z)
+theme = Theme
+forget-this-profile-the-file-is = Forget this profile (the file is kept)
+clear-search = Clear search
+select-all = Select all
+delete-template = Delete template
+show-the-home-currency-code-instead = Show the home currency code instead of a symbol
+passphrase = Passphrase
+profile-name = Profile name
+repeat = Repeat
+optional-details = Optional details
+search-payee-note-category-or-amount = Search payee, note, category or amount (Ctrl+F)
+min = Min
+max = Max
+template-name = Template name
+recently-deleted = Recently deleted
+confirm = Confirm
+possible-duplicates = Possible duplicates
+receipt = Receipt
+new-profile = ➕ New profile…
+rename-current = Rename current
+sync-now = 🔄 Sync now
+clear-filters = Clear filters
+unsaved-changes = ⚠ Unsaved changes
+expense = Expense
+income = Income
+all = All
+before = Before
+after = After
+space-after-symbol = Space after it
+point = Point
+tab-transactions = 📝 Transactions
+tab-analytics = 📈 Analytics
+tab-categories = 🏷 Categories
+tab-budgets = 🎯 Budgets
+tab-goals = 🏆 Goals
+tab-currency = 💱 Currency
+tab-data = 🗄 Data
+tab-settings = ⚙ Settings
+none = None
+status-pending = Pending
+status-cleared = Cleared
+status-reconciled = Reconciled
+both = Both
+comma = Comma
+space = Space
+apostrophe = Apostrophe
+other = Other
+update = Update
+review-skip = Skip
+review-merge = Merge into existing
+review-keep-both = Keep both
+theme-system = 🖥 System
+theme-light = ☀ Light
+theme-dark = 🌙 Dark
+monday = Monday
+sunday = Sunday
+goal-reached = ✅ Reached
+goal-on-track = On track
+goal-behind = Behind
+goal-overdue = Overdue
+column-date = Date
+column-category = Category
+column-description = Description
+column-amount = Amount
+column-type = Type
+column-status = Status
+column-amount-in = Amount ({ $currency })
+column-original-amount = Original amount
+column-note = Note
+column-month = Month
+column-income = Income
+column-expenses = Expenses
+column-net = Net
+filter-from = From
+filter-to = To
+filters-toggle = 🔎 Filters
+filters-toggle-active = 🔎 Filters ●
+unsaved-pending = ● Unsaved changes
+saved-at = ✔ Saved { $time }
+save-failed = ⚠ Could not save { $path }: { $error }
+backup-failed = Backup failed: { $error }
+backup-location = A copy of the ledger is saved to { $path } before every save. Restore one from the Data tab.
+backup-restored = Restored backup from { $time }
+backup-encrypted = That backup is encrypted
+backup-unreadable = That backup could not be read
+sync-other-passphrase = The shared copy is encrypted with a different passphrase
+sync-unreadable = The shared copy could not be read: { $error }
+sync-failed = Sync failed: { $error }
+sync-write-failed = Merged, but the shared copy could not be written: { $error }
+sync-created = Shared copy created
+sync-not-remembered = Could not remember the shared file: { $error }
+sync-up-to-date = Already up to date
+sync-merged = { $added } added, { $updated } updated, { $deleted } deleted from the other copy
+moved-aside = The old file was moved to { $path }
+move-aside-failed = { $error } (moving it aside failed: { $reason })
+file-unreadable = The file could not be read
+not-a-ledger = { $path } is not a ledger file
+opened-not-remembered = Opened, but could not remember the location: { $error }
+saved-not-remembered = Saved, but could not remember the location: { $error }
+settings-save-failed = Could not save the settings: { $error }
+profile-create-failed = Could not create the ledger: { $error }
+problem-stopped = It stopped unexpectedly
+problem-wrong-passphrase = Wrong passphrase
+problem-newer-format = The file was saved by a newer version of the app (format { $version }, this one reads up to { $supported })
+problem-not-a-ledger = The file does not contain a ledger
+problem-not-a-list = “{ $field }” in the file is not a list
+problem-no-config-folder = There is no config folder on this system
+problem-profile-name-taken = There already is a profile called “{ $name }”
+problem-profile-file-taken = Another profile already uses that file
+problem-missing-column = Not a { $format } export: the “{ $column }” column is missing
+problem-no-price = No price at { $pointer }
+problem-not-supported = Not supported on this system
+problem-tesseract-missing = The tesseract OCR program isn't installed
+problem-token-not-signed = Couldn't sign the token request
+problem-private-key = Private key: { $error }
+problem-bad-spreadsheet = That isn't a spreadsheet address or ID
+ledger-save-failed = Could not save the ledger
+ledger-encrypted = { $count ->
+    [0] Ledger encrypted
+    [one] Ledger encrypted, 1 unencrypted backup deleted
+   *[other] Ledger encrypted, { $count } unencrypted backups deleted
+}
+ledger-encrypted-backups-kept = Ledger encrypted, but old backups could not be deleted: { $error }
+encryption-removed = Encryption removed
+change-passphrase = Change passphrase
+encrypt-ledger = 🔒 Encrypt ledger
+exported = { $count ->
+    [one] Exported 1 transaction to { $path }
+   *[other] Exported { $count } transactions to { $path }
+}
+export-failed = Export failed: { $error }
+export-shown = 📤 Export shown ({ $count })
+attach-failed = Could not attach { $path }: { $error }
+book-balance = Book balance: { $amount }
+cleared-balance = Cleared balance: { $amount }
+import-csv = { $format } CSV…
+search-matches = { $shown } of { $total } match
+trash-toggle = 🗑 Trash ({ $count })
+upcoming = 📅 Upcoming ({ $count })
+refund-of = Refund of: { $description }
+trash-explained = Deleted transactions are removed for good after { $days } days.
+trash-item = { $days ->
+    [one] deleted { $deleted }, 1 day left
+   *[other] deleted { $deleted }, { $days } days left
+}
+selected-count = ☑ { $count } selected
+confirm-trash = { $count ->
+    [one] Move 1 transaction to the trash?
+   *[other] Move { $count } transactions to the trash?
+}
+confirm-recategorize = { $count ->
+    [one] Move 1 transaction to { $category }?
+   *[other] Move { $count } transactions to { $category }?
+}
+moved-to-trash = { $count ->
+    [one] Moved 1 transaction to the trash
+   *[other] Moved { $count } transactions to the trash
+}
+recategorized = { $skipped ->
+    [0] Moved { $count } transactions
+   *[other] Moved { $count } transactions, { $skipped } left as they were (split or wrong type)
+}
+import-failed = Import failed: { $error }
+imported = Imported { $count } from { $format } ({ $review } to review, { $skipped } transfers or left out, { $errors } unreadable)
+review-done = Review done: { $added } added, { $merged } merged, { $skipped } skipped
+split-remaining = Remaining: { $amount }
+chart-balance = Balance
+chart-projected = Projected
+chart-balance-at = Balance: { $balance }
+chart-tooltip = Date: { $date }
+    Transaction: { $description }
+    Amount: { $amount } ({ $kind })
+    Balance: { $balance }
+rates-fetched = Fetched { $count } rates for { $date }
+rates-fetch-failed = Fetch failed: { $error }
+fetching = Fetching...
+fetch-rates = 🌐 Fetch today's rates
+fetch-rates-daily = Fetch rates once a day when the ledger opens
+home-currency-changed = { $count ->
+    [one] 1 transaction without a currency was kept in { $currency }
+   *[other] { $count } transactions without a currency were kept in { $currency }
+}
+missing-rates = ⚠ No rate for: { $currencies } (amounts counted 1:1)
+budgets-for = Budgets for { $period }
+budget-over = Over by { $amount }
+budget-left = { $amount } left
+goal-by = by { $date }
+goal-projected = Projected: { $date }
+goal-needs = Needs { $amount }/month
+format-example = Example: { $positive }   { $negative }
+language = Language
+language-label = Language:
+use-language-format = Use its number format
+language-format-hint = Dates follow the language. Amounts keep the ledger's format unless you switch it here.
+month-1 = January
+month-2 = February
+month-3 = March
+month-4 = April
+month-5 = May
+month-6 = June
+month-7 = July
+month-8 = August
+month-9 = September
+month-10 = October
+month-11 = November
+month-12 = December
+quick-add-label = ⚡ Quick add:
+quick-add-hint = coffee 4.50 yesterday
+quick-add-incomplete = Type a description and an amount, e.g. "salary +3000 jan 31"
+period-today = Today
+period-month = Month
+period-year = Year
+all-time = All time
+period-income = Income: { $amount }
+period-expenses = Expenses: { $amount }
+period-net = Net: { $amount }
+group-by-label = Group by:
+group-none = Nothing
+group-day = Day
+group-month = Month
+weekday-1 = Mon
+weekday-2 = Tue
+weekday-3 = Wed
+weekday-4 = Thu
+weekday-5 = Fri
+weekday-6 = Sat
+weekday-7 = Sun
+summary-count = { $count ->
+    [one] 1 transaction
+   *[other] { $count } transactions
+}
+category-color = Color used in the list, charts and legend
+default-color = Restore the default color
+ui-scale-label = Size:
+ui-scale-hint = Ctrl+Plus and Ctrl+Minus change it too, Ctrl+0 resets it.
+reset = Reset
+welcome = 👋 Welcome!
+welcome-text = The ledger is empty. Start with your own transactions, bring them over from another app, or look around with some made-up ones.
+add-first-transaction = ➕ Add your first transaction
+import-from = 📥 Import from { $format }…
+load-demo-data = 🧪 Load demo data
+demo-data-hint = Three months of sample transactions. Search for "demo" to find and delete them later.
+demo-note = demo data
+undo = Undo
+saved-toast = ✔ Saved
+save-failed-toast = ⚠ Save failed: { $error }
+transaction-added = ✔ Transaction added
+transaction-updated = ✔ Transaction updated
+tray-title = Finance Tracker
+tray-quick-add = Quick add transaction
+tray-show-hide = Show/Hide window
+tray-quit = Quit
+system-tray = System Tray
+start-in-tray = Start minimized to the tray
+tray-unavailable = No system tray was found, so the app starts as a normal window.
+copy-shown = 📋 Copy ({ $count })
+copy-hint = Copies the transactions as a table that pastes into spreadsheets and chat
+copy-selected = 📋 Copy
+copied = ✔ Copied { $count ->
+    [one] 1 transaction
+   *[other] { $count } transactions
+}
+monthly-cash-flow = Monthly Cash Flow
+show-net-line = Show net
+chart-net = Net
+budget-vs-actual = Budget vs. Actual
+chart-budgeted = Budgeted
+chart-spent = Spent
+chart-over-budget = Over budget
+forecast-label = Forecast:
+forecast-off = Off
+forecast-months = { $count ->
+    [one] 1 month
+   *[other] { $count } months
+}
+chart-forecast = Forecast
+chart-forecast-at = Forecast for { $date }: { $balance }
+forecast-zero = ⚠ At this pace the balance reaches zero around { $date }
+forecast-end = Estimated balance on { $date }: { $balance }
+spending-by-category = Spending by Category
+top-expenses = Top Expenses
+largest-expenses = Largest expenses
+top-payees = Top payees by total
+times-count = { $count ->
+    [one] once
+   *[other] { $count } times
+}
+spending-stats = Spending statistics
+stats-total-spent = Total spent
+stats-average-daily = Average per day
+stats-average-expense = Average expense
+stats-median-expense = Median expense
+stats-busiest-day = Busiest spending day
+stats-no-spend-days = No-spend days
+stats-days-of = { $count } of { $total } days
+analytics-range-label = Show:
+last-90-days = Last 90 days
+year-to-date = Year to date
+last-year = Last year
+click-to-see-transactions = Click to see the transactions
+money-flow = Money Flow
+flow-total = Total
+flow-saved = Saved
+flow-from-savings = From savings
+export-image = Export image
+image-saved = Chart saved to { $path }
+image-save-failed = Couldn't save the image: { $error }
+recurring-bills = Recurring bills
+no-recurring-bills = No subscriptions or regular bills found yet.
+recurring-every = How often
+recurring-per-month = Per month
+recurring-next = Next expected
+recurring-monthly = Monthly
+recurring-weekly = Weekly
+recurring-days = Every { $count } days
+recurring-scheduled = Scheduled
+recurring-schedule = Schedule next
+recurring-schedule-hint = Add a copy of the last payment on the next expected date
+recurring-total = Total
+bill-scheduled = { $description } scheduled for { $date }
+amount-distribution = Amount Distribution
+all-categories = All categories
+histogram-empty = Nothing to show for this type and category.
+histogram-bar = { $count ->
+    [one] { $range }: 1 transaction, { $total } in total
+   *[other] { $range }: { $count } transactions, { $total } in total
+}
+spending-patterns = Spending Patterns
+average-by-weekday = Average spent per day of the week
+average-by-hour = Average spent per hour of the day
+pattern-tooltip = { $when }: { $amount } on average
+compare-periods = Compare Periods
+compare-period-a = Before:
+compare-period-b = After:
+compare-change = Change
+compare-total = Total
+pie-other = Other ({ $count })
+pie-total = Total
+expense-treemap = Treemap
+rolling-spending = Rolling Spending
+rolling-spending-line = Spent in the last { $days } days
+rolling-spending-average = Average
+rolling-spending-now = Last { $days } days: { $amount } (average over the range: { $average })
+tab-dashboard = Dashboard
+customize-dashboard = ⚙ Customize
+reset-dashboard = Reset to default
+dashboard-empty = Every widget is hidden. Use Customize to bring some back.
+widget-balance = Current balance
+widget-this-month = This month
+widget-budgets = Budgets
+widget-upcoming = Upcoming bills
+widget-balance-chart = Balance, last 3 months
+widget-recent = Recent transactions
+nothing-upcoming = Nothing due in the next 30 days.
+expected-from-history = Expected from past payments, not scheduled yet
+cli-usage =
+    Usage:
+      finance_tracker add <description> <amount> [--category Food/Groceries] [--income] [--date YYYY-MM-DD] [--note text]
+      finance_tracker list [--month YYYY-MM]
+      finance_tracker report [--month YYYY-MM] [--csv]
+      finance_tracker tui
+      finance_tracker bot
+    Add --data-file <path> to use another ledger. Without a command the window opens.
+    --wsl-compat opens it with X11 and software rendering, which is the default under WSL. --no-wsl-compat turns that off.
+cli-missing-value = { $option } needs a value
+cli-unknown-option = Unknown option { $option }
+cli-bad-amount = Not an amount: { $value }
+cli-bad-date = Not a date (YYYY-MM-DD): { $value }
+cli-bad-month = Not a month (YYYY-MM): { $value }
+cli-unknown-category = No category named { $name }
+cli-locked = The ledger is encrypted. Unlock it in the window instead.
+cli-added = Added { $description }: { $amount } ({ $category }) on { $date }
+cli-no-transactions = No transactions.
+cli-report-month = { $month }: income { $income }, expenses { $expenses }, net { $net }
+cli-no-tui = This build has no terminal UI. Build it with `cargo build --features tui`.
+tui-keys = ←/→ month   ↑/↓ select   a add   d delete   q quit
+tui-add-keys = Enter add   Esc cancel
+tui-confirm-delete = Move this transaction to the trash? y / n
+tui-deleted = Moved to the trash
+tab-rules = Rules
+new-rule = New rule
+rules-explained = Transactions whose description matches are filed automatically as they are added and on import. The first matching rule wins.
+rule-contains = Description contains:
+rule-regex = Regular expression
+rule-file-under = File under:
+rule-bad-regex = Not a valid regular expression: { $error }
+rule-bad-amount = Amounts have to be numbers
+apply-rules = Apply to existing transactions
+apply-rules-hint = Refiles every transaction a rule matches. Undo takes it back.
+no-rules-yet = No rules yet.
+rule-earlier = Check this rule before the one above
+rules-applied = { $count ->
+    [one] Refiled 1 transaction
+   *[other] Refiled { $count } transactions
+}
+script-hooks = Script hooks
+script-hooks-explained = A Rhai script here can define on_add(t) to change or refuse new transactions, on_import(t) to change or drop imported rows and on_report(list) to add a report to Analytics and the report command.
+script-label = Script:
+reload-script = Reload
+script-loaded = Loaded, with { $hooks }
+no-script = No script yet
+run-report-script = 📜 Run report script
+run-report-script-hint = Runs on_report from the script on the transactions in the range
+saving = Saving…
+importing = Importing { $format }…
+import-cancelled = Import cancelled, nothing was added
+import-errors = { $count ->
+    [one] 1 line couldn't be read
+   *[other] { $count } lines couldn't be read
+}
+import-row-unreadable = Line { $line }: { $error }
+import-row-bad-date = Line { $line }: “{ $value }” isn't a date
+import-row-bad-amount = Line { $line }: “{ $value }” isn't an amount
+tab-audit = 🕵 Audit
+audit-explained = Every added, changed and deleted transaction, newest first, as recorded when the ledger was saved. Kept in { $path }.
+audit-encrypted = This ledger is encrypted, and so is its journal. Only this passphrase opens it.
+journal-rekey-failed = The passphrase was not changed because the audit journal could not be re-encrypted: { $error }
+audit-failed = The audit journal couldn't be used: { $error }
+audit-search-hint = Description
+audit-reload = Reload
+audit-empty = Nothing recorded yet
+audit-added = Added
+audit-changed = Changed
+audit-deleted = Deleted
+audit-cause-edit = In the app
+audit-cause-import = Import
+audit-cause-undo = Undo/redo
+audit-cause-sync = Sync
+audit-cause-restore = Backup restored
+audit-cause-terminal = Command line
+error-bad-amount = { $field }: “{ $value }” isn't a number that can be used here
+error-bad-amount-advice = Type a positive number like 12.50 (sums like 12+4.5 work in the transaction form), then try again.
+error-missing-description = The transaction has no description
+error-missing-description-advice = Type what it was for, e.g. the shop or payee, then add it again.
+error-splits = The splits don't add up to the amount
+error-splits-advice = Make each split a number and adjust them until "Remaining" shows 0, or remove the splits.
+error-config = Your settings file couldn't be read: { $error }
+error-config-advice = Default settings are in use for now, and the file is left as it is. Fix or delete it, then restart; changing settings or profiles won't overwrite it meanwhile.
+budget-limit-field = Budget limit
+goal-target-field = Goal target
+opening-balance-field = Opening balance
+rate-field = Exchange rate
+diagnostics = Diagnostics
+log-file = Log file:
+log-memory-only = none, no config folder on this system
+copy-log = 📋 Copy
+recovered-changes = { $count ->
+    [one] Recovered 1 change that wasn't saved before the app last closed
+   *[other] Recovered { $count } changes that weren't saved before the app last closed
+}
+settings-file = The settings above are kept in { $path }, which can also be edited by hand while the app is closed.
+changed-on-disk = { $path } was changed by another program
+changed-on-disk-advice = Your changes since then are held back, not saved over it. Merge keeps both, matching transactions the way sync does.
+merge-changes = Merge
+reload-file = Reload
+reload-file-hint = Use the file as it is now and drop the changes made here since it last saved
+keep-mine = Keep mine
+keep-mine-hint = Save this version over the file, dropping the other program's changes
+file-gone = { $path } is no longer there
+tui-merged = { $done } (merged with changes made elsewhere)
+read-only = { $path } is open in another window, so it is read-only here
+read-only-advice = Changes made here won't be saved. Close the other window and press Try again to edit here.
+try-again = Try again
+try-again-hint = Changes made here in the meantime are replaced by what the other window saved
+cli-in-use = The ledger is open in another window or terminal. Close it first.
+tab-net-worth = 📈 Net worth
+net-worth-cash = Cash balance
+net-worth-holdings = Holdings
+net-worth-total = Net worth
+holdings-unpriced = Some holdings have no price yet, or no exchange rate for their price's currency, and are left out.
+new-holding = New holding
+quantity-label = Quantity:
+quantity-field = Quantity
+holdings = Holdings
+no-holdings-yet = No holdings yet.
+fetch-prices = 🌐 Fetch prices
+prices-fetched = Fetched { $count ->
+    [one] 1 price
+   *[other] { $count } prices
+}
+prices-fetch-failed = Fetched { $count }, { $failed } failed. { $symbol }: { $error }
+price-label = Price
+value-label = Value
+price-updated = Updated
+no-rate-for = No rate for { $currency }
+never = never
+price-source = Price source
+price-source-explained = Prices are fetched from this URL, in the home currency. The pointer says where the price is in the JSON answer. Both may use {"{"}symbol{"}"}, {"{"}currency{"}"} (USD) and {"{"}currency_lower{"}"} (usd).
+price-url = URL:
+price-pointer = JSON pointer:
+use-coingecko = Use CoinGecko (crypto, no key needed)
+bank-connection = Bank connection
+bank-explained = Pulls transactions from your bank through GoCardless Bank Account Data. Create API secrets and link your bank on their site, then enter the secrets and the account ID here.
+bank-secret-id = Secret ID:
+bank-secret-key = Secret key:
+bank-account-id = Account ID:
+bank-secret-stored = These are stored unencrypted in the settings file on this computer, which only your user account can read.
+bank-pull = 🏦 Pull transactions
+bank-last-pulled = Pulled up to { $date }
+bank-never-pulled = The first pull goes back 90 days
+bank-pulled = { $count ->
+    [one] 1 transaction to review
+   *[other] { $count } transactions to review
+}, { $skipped } skipped
+bank-pull-failed = Bank pull failed: { $error }
+bank-review = Transactions from the bank
+bank-review-explained = Filed by your rules. Ticked ones are added to the ledger. Ones that look like a transaction you already have start unticked.
+select-none = Select none
+bank-looks-like-existing = already in the ledger?
+bank-add-selected = Add { $count }
+bank-discard = Discard
+bank-added = { $count ->
+    [one] Added 1 transaction from the bank
+   *[other] Added { $count } transactions from the bank
+}
+audit-cause-bot = Telegram bot
+bot-heading = Telegram bot
+bot-explained = Add transactions by texting a bot, like "taxi 12.50". Create a bot with @BotFather in Telegram and paste its token here, then run `finance_tracker bot` on a computer that stays on. Message the bot once to learn your chat ID and add it below.
+bot-token = Bot token:
+bot-allowed-chats = Allowed chat IDs:
+bot-token-stored = The token is stored unencrypted in the settings file on this computer, which only your user account can read.
+bot-no-token = No bot token. Set one up under Data in the window first.
+bot-listening = Listening as @{ $name }. Press Ctrl+C to stop.
+bot-poll-failed = Couldn't reach Telegram, trying again shortly: { $error }
+bot-not-allowed = This chat isn't allowed to add transactions. Its ID is { $chat }; add it under Data in Finance Tracker.
+bot-not-understood = Didn't understand that. Send a description and an amount, like "taxi 12.50" or "salary +3000 yesterday".
+bot-help = Send a description and an amount, like "taxi 12.50" or "salary +3000 yesterday", and it's added to the ledger.
+webhooks = Webhooks
+webhooks-explained = Sends a JSON POST to each URL when a budget goes over its limit, a large transaction is added, or once a week with last week's totals. The text is in "message" (and "content", for Discord), with the numbers alongside.
+webhook-on-budget = Budget exceeded
+webhook-on-large = Large transaction
+webhook-on-weekly = Weekly summary
+webhook-send-test = Send test
+webhook-add = ➕ Add webhook
+webhook-large-from = Large transactions start at:
+webhook-test = Test from Finance Tracker
+webhook-budget-exceeded = { $category } is over budget: { $spent } of { $limit }
+webhook-large-transaction = Large transaction: { $description }, { $amount }
+webhook-weekly-summary = Week of { $from } to { $to }: income { $income }, expenses { $expenses }, net { $net }
+notifications = Notifications
+notify-on-budget-warning = When a budget reaches 80%
+notify-on-budget-exceeded = When a budget is exceeded
+notify-on-bills-due = When bills are due tomorrow
+notify-budget-spent = { $spent } of { $limit } spent
+notify-budget-warning = { $category } is at { $percent }% of its budget
+notify-budget-exceeded = { $category } is over budget
+notify-bills-due = { $count ->
+    [one] A bill is due tomorrow
+   *[other] { $count } bills are due tomorrow
+}, { $date }
+scan-receipt = 🧾 Scan receipt
+scan-receipt-hint = Attaches a photo of a receipt and fills in the shop, total and date from it. Needs the tesseract OCR program installed.
+receipt-reading = Reading the receipt…
+receipt-failed = Couldn't read the receipt: { $error }
+receipt-nothing-found = Nothing readable on the receipt. It's attached anyway.
+receipt-filled = Filled in from the receipt. Check it before adding.
+sheets-heading = Google Sheets
+sheets-explained = Exports the transactions and the monthly totals to the Transactions and Monthly tabs of a Google Sheet, replacing what was there. Create a service account in Google Cloud with the Sheets API enabled, download its JSON key, and share the sheet with the account's email address.
+sheets-key-file = Service account key:
+sheets-spreadsheet = Sheet:
+sheets-daily = Export once a day while the app is open
+sheets-export = 📤 Export to Google Sheets
+sheets-exporting = Exporting…
+sheets-last-exported = Last exported { $date } at { $time }
+sheets-export-failed = Export to Google Sheets failed: { $error }
+amount-must-be-positive = Must be a positive number
+duplicate-warning = { $description }, { $amount } on { $date } is already in the ledger. Add it again?
+add-anyway = Add anyway
@@ -0,0 +1,690 @@
+# Português (Brasil)
+
+no-icon = Sem ícone
+saved = ✔ Salvo
+the-ledger-could-not-be-read = ⚠ Não foi possível ler o livro
+nothing-has-been-changed-fix-the = Nada foi alterado. Corrija o arquivo e reinicie, ou comece de novo com um livro vazio.
+start-a-new-ledger-the-old = Começar um livro novo (o arquivo antigo é mantido)
+this-ledger-is-encrypted = 🔒 Este livro está criptografado
+unlock = Desbloquear
+or-open-another-profile = Ou abra outro perfil:
+appearance = Aparência
+theme-label = Tema:
+new-transactions = Novas transações
+start-the-form-as = Iniciar o formulário como:
+calendar = Calendário
+weeks-start-on = Semanas começam em:
+months-start-on-day = Meses começam no dia:
+budgets-and-the-this-month-filter = Os orçamentos e o filtro "Este mês" usam isto, por exemplo para acompanhar o dia do pagamento.
+currency-format = Moeda e formato
+balances-are-shown-in = Saldos exibidos em:
+the-currency-and-number-format-are = A moeda e o formato de números ficam no livro, então cada perfil tem os seus.
+backups = Backups
+keep-the-last = Manter as últimas
+copies-0-turns-backups-off = cópias (0 desativa os backups)
+profiles = Perfis
+each-profile-is-a-separate-ledger = Cada perfil é um livro separado, com suas próprias categorias e configurações.
+open = Abrir
+data-file = Arquivo de dados
+ledger-label = Livro:
+open-file = 📂 Abrir…
+save-as = 💾 Salvar como…
+the-last-file-used-is-opened = O último arquivo usado é aberto na próxima inicialização. Use --data-file <caminho> para escolher outro.
+sync = Sincronização
+point-this-at-a-ledger-in = Aponte para um livro numa pasta sincronizada (Dropbox, Syncthing, …) em cada dispositivo.
+no-shared-file-chosen = Nenhum arquivo compartilhado escolhido
+choose = Escolher…
+export = Exportar
+export-to-excel = 📊 Exportar para Excel…
+encryption = Criptografia
+the-ledger-and-its-backups-are = 🔒 O livro e seus backups estão criptografados. A senha é pedida ao iniciar.
+the-ledger-is-stored-as-plain = O livro é guardado como texto simples.
+remove-encryption = 🔓 Remover criptografia
+there-is-no-way-to-recover = Não há como recuperar os dados se a senha for perdida.
+restore-from-backup = Restaurar de um backup
+no-backups-yet = Nenhum backup ainda.
+restore = Restaurar
+edit-transaction = Editar transação
+add-new-transaction = Nova transação
+date-label = Data:
+description-label = Desc.:
+amount-label = Valor:
+category-label = Categoria:
+split = ✂ Dividir
+cancel = Cancelar
+note-label = Nota:
+attach-receipt = 📎 Anexar comprovante
+goal-label = Meta:
+refund-of-label = Reembolso de:
+import-label = 📥 Importar:
+csv = CSV…
+json = JSON…
+filters = Filtros
+date-range = Período
+this-week = Esta semana
+this-month = Este mês
+last-month = Mês passado
+this-year = Este ano
+type = Tipo
+amount = Valor
+categories = Categorias
+the-trash-is-empty = A lixeira está vazia.
+empty-trash = Esvaziar lixeira
+restore-deleted = ♻ Restaurar
+delete-forever = Excluir para sempre
+set-category = Definir categoria
+delete-selected = 🗑 Excluir
+clear-selection = Limpar seleção
+yes = Sim
+template-label = 📋 Modelo:
+save-as-template = 💾 Salvar como modelo
+these-imported-rows-look-like-transactions = Estas linhas importadas parecem transações que você já tem.
+set-all-to = Definir todas como:
+imported-label = Importada:
+existing-label = Existente:
+apply = Aplicar
+splits-label = Divisões:
+add-split = + Adicionar divisão
+balance-history = Histórico do saldo
+filters-are-on-the-line-shows = Há filtros ativos: a linha mostra o total acumulado apenas das transações correspondentes.
+no-transactions-yet-add-some-data = Nenhuma transação ainda. Adicione alguns dados para ver o gráfico!
+expense-breakdown = Divisão das despesas
+no-expenses-to-show = Nenhuma despesa para mostrar.
+add-category = Adicionar categoria
+name-label = Nome:
+save = Salvar
+add-subcategory = + Sub
+add = Adicionar
+opening-balance-heading = Saldo inicial
+opening-balance-label = Saldo inicial:
+the-home-currency-and-number-format = A moeda principal e o formato de números são definidos na aba Configurações.
+add-rate = Adicionar cotação
+rate-history = Histórico de cotações
+fetched = (obtida)
+set-monthly-budget = Definir orçamento mensal
+limit-label = Limite:
+set-limit = Definir
+no-budgets-yet-set-a-monthly = Nenhum orçamento ainda. Defina um limite mensal para uma categoria acima.
+new-savings-goal = Nova meta de economia
+target-label = Alvo:
+by-label = Até:
+allocate-transactions-to-a-goal-with = Associe transações a uma meta pelo campo Meta no formulário de transação.
+goals = Metas
+no-goals-yet = Nenhuma meta ainda.
+projected-no-contributions-yet = Previsão: nenhuma contribuição ainda
+format-preset-label = Formato predefinido:
+symbol-label = Símbolo:
+use-code = Usar código
+thousands-label = Milhares:
+decimal-label = Decimal:
+decimals-label = Casas decimais:
+redo-ctrl-y = Refazer (Ctrl+Y)
+undo-ctrl-z = Desfazer (Ctrl+Z)
+theme = Tema
+forget-this-profile-the-file-is = Esquecer este perfil (o arquivo é mantido)
+clear-search = Limpar busca
+select-all = Selecionar todas
+delete-template = Excluir modelo
+show-the-home-currency-code-instead = Mostrar o código da moeda principal em vez de um símbolo
+passphrase = Senha
+profile-name = Nome do perfil
+repeat = Repetir
+optional-details = Detalhes opcionais
+search-payee-note-category-or-amount = Buscar favorecido, nota, categoria ou valor (Ctrl+F)
+min = Mín.
+max = Máx.
+template-name = Nome do modelo
+recently-deleted = Excluídas recentemente
+confirm = Confirmar
+possible-duplicates = Possíveis duplicatas
+receipt = Comprovante
+new-profile = ➕ Novo perfil…
+rename-current = Renomear atual
+sync-now = 🔄 Sincronizar agora
+clear-filters = Limpar filtros
+unsaved-changes = ⚠ Alterações não salvas
+expense = Despesa
+income = Receita
+all = Todas
+before = Antes
+after = Depois
+space-after-symbol = Espaço depois
+point = Ponto
+tab-transactions = 📝 Transações
+tab-analytics = 📈 Análises
+tab-categories = 🏷 Categorias
+tab-budgets = 🎯 Orçamentos
+tab-goals = 🏆 Metas
+tab-currency = 💱 Moedas
+tab-data = 🗄 Dados
+tab-settings = ⚙ Configurações
+none = Nenhum
+status-pending = Pendente
+status-cleared = Compensada
+status-reconciled = Conciliada
+both = Ambos
+comma = Vírgula
+space = Espaço
+apostrophe = Apóstrofo
+other = Outro
+update = Atualizar
+review-skip = Ignorar
+review-merge = Mesclar com a existente
+review-keep-both = Manter ambas
+theme-system = 🖥 Sistema
+theme-light = ☀ Claro
+theme-dark = 🌙 Escuro
+monday = Segunda-feira
+sunday = Domingo
+goal-reached = ✅ Alcançada
+goal-on-track = No ritmo
+goal-behind = Atrasada
+goal-overdue = Vencida
+column-date = Data
+column-category = Categoria
+column-description = Descrição
+column-amount = Valor
+column-type = Tipo
+column-status = Situação
+column-amount-in = Valor ({ $currency })
+column-original-amount = Valor original
+column-note = Nota
+column-month = Mês
+column-income = Receitas
+column-expenses = Despesas
+column-net = Saldo
+filter-from = De
+filter-to = Até
+filters-toggle = 🔎 Filtros
+filters-toggle-active = 🔎 Filtros ●
+unsaved-pending = ● Alterações não salvas
+saved-at = ✔ Salvo às { $time }
+save-failed = ⚠ Não foi possível salvar { $path }: { $error }
+backup-failed = Falha no backup: { $error }
+backup-location = Uma cópia do livro é guardada em { $path } antes de cada salvamento. Restaure uma pela aba Dados.
+backup-restored = Backup de { $time } restaurado
+backup-encrypted = Esse backup está criptografado
+backup-unreadable = Não foi possível ler esse backup
+sync-other-passphrase = A cópia compartilhada está criptografada com outra senha
+sync-unreadable = Não foi possível ler a cópia compartilhada: { $error }
+sync-failed = Falha na sincronização: { $error }
+sync-write-failed = Mesclado, mas não foi possível gravar a cópia compartilhada: { $error }
+sync-created = Cópia compartilhada criada
+sync-not-remembered = Não foi possível memorizar o arquivo compartilhado: { $error }
+sync-up-to-date = Já está atualizado
+sync-merged = { $added } adicionadas, { $updated } atualizadas, { $deleted } excluídas a partir da outra cópia
+moved-aside = O arquivo antigo foi movido para { $path }
+move-aside-failed = { $error } (não foi possível movê-lo: { $reason })
+file-unreadable = Não foi possível ler o arquivo
+not-a-ledger = { $path } não é um arquivo de livro
+opened-not-remembered = Aberto, mas não foi possível memorizar o local: { $error }
+saved-not-remembered = Salvo, mas não foi possível memorizar o local: { $error }
+settings-save-failed = Não foi possível salvar as configurações: { $error }
+profile-create-failed = Não foi possível criar o livro: { $error }
+problem-stopped = Parou inesperadamente
+problem-wrong-passphrase = Senha incorreta
+problem-newer-format = O arquivo foi salvo por uma versão mais nova do app (formato { $version }, esta lê até o { $supported })
+problem-not-a-ledger = O arquivo não contém um livro
+problem-not-a-list = “{ $field }” no arquivo não é uma lista
+problem-no-config-folder = Não há pasta de configuração neste sistema
+problem-profile-name-taken = Já existe um perfil chamado “{ $name }”
+problem-profile-file-taken = Outro perfil já usa esse arquivo
+problem-missing-column = Não é uma exportação do { $format }: falta a coluna “{ $column }”
+problem-no-price = Nenhum preço em { $pointer }
+problem-not-supported = Não suportado neste sistema
+problem-tesseract-missing = O programa de OCR tesseract não está instalado
+problem-token-not-signed = Não foi possível assinar o pedido de token
+problem-private-key = Chave privada: { $error }
+problem-bad-spreadsheet = Isso não é um endereço ou ID de planilha
+ledger-save-failed = Não foi possível salvar o livro
+ledger-encrypted = { $count ->
+    [0] Livro criptografado
+    [one] Livro criptografado, { $count } backup sem criptografia excluído
+   *[other] Livro criptografado, { $count } backups sem criptografia excluídos
+}
+ledger-encrypted-backups-kept = Livro criptografado, mas não foi possível excluir os backups antigos: { $error }
+encryption-removed = Criptografia removida
+change-passphrase = Trocar senha
+encrypt-ledger = 🔒 Criptografar livro
+exported = { $count ->
+    [one] { $count } transação exportada para { $path }
+   *[other] { $count } transações exportadas para { $path }
+}
+export-failed = Falha na exportação: { $error }
+export-shown = 📤 Exportar exibidas ({ $count })
+attach-failed = Não foi possível anexar { $path }: { $error }
+book-balance = Saldo contábil: { $amount }
+cleared-balance = Saldo compensado: { $amount }
+import-csv = CSV do { $format }…
+search-matches = { $shown } de { $total } correspondem
+trash-toggle = 🗑 Lixeira ({ $count })
+upcoming = 📅 Próximas ({ $count })
+refund-of = Reembolso de: { $description }
+trash-explained = Transações excluídas são apagadas de vez após { $days } dias.
+trash-item = { $days ->
+    [one] excluída em { $deleted }, resta { $days } dia
+   *[other] excluída em { $deleted }, restam { $days } dias
+}
+selected-count = ☑ { $count } selecionadas
+confirm-trash = { $count ->
+    [one] Mover { $count } transação para a lixeira?
+   *[other] Mover { $count } transações para a lixeira?
+}
+confirm-recategorize = { $count ->
+    [one] Mover { $count } transação para { $category }?
+   *[other] Mover { $count } transações para { $category }?
+}
+moved-to-trash = { $count ->
+    [one] { $count } transação movida para a lixeira
+   *[other] { $count } transações movidas para a lixeira
+}
+recategorized = { $skipped ->
+    [0] { $count } transações movidas
+   *[other] { $count } transações movidas, { $skipped } mantidas como estavam (divididas ou de outro tipo)
+}
+import-failed = Falha na importação: { $error }
+imported = { $count } importadas do { $format } ({ $review } para revisar, { $skipped } transferências ou deixadas de fora, { $errors } ilegíveis)
+review-done = Revisão concluída: { $added } adicionadas, { $merged } mescladas, { $skipped } ignoradas
+split-remaining = Restante: { $amount }
+chart-balance = Saldo
+chart-projected = Projetado
+chart-balance-at = Saldo: { $balance }
+chart-tooltip = Data: { $date }
+    Transação: { $description }
+    Valor: { $amount } ({ $kind })
+    Saldo: { $balance }
+rates-fetched = { $count } cotações obtidas para { $date }
+rates-fetch-failed = Falha ao obter: { $error }
+fetching = Obtendo...
+fetch-rates = 🌐 Obter cotações de hoje
+fetch-rates-daily = Obter cotações uma vez por dia ao abrir o livro
+home-currency-changed = { $count ->
+    [one] 1 transação sem moeda foi mantida em { $currency }
+   *[other] { $count } transações sem moeda foram mantidas em { $currency }
+}
+missing-rates = ⚠ Sem cotação para: { $currencies } (valores contados 1:1)
+budgets-for = Orçamentos de { $period }
+budget-over = Excedido em { $amount }
+budget-left = Restam { $amount }
+goal-by = até { $date }
+goal-projected = Previsão: { $date }
+goal-needs = Precisa de { $amount }/mês
+format-example = Exemplo: { $positive }   { $negative }
+language = Idioma
+language-label = Idioma:
+use-language-format = Usar o formato de números dele
+language-format-hint = As datas seguem o idioma. Os valores mantêm o formato do livro, a menos que você o troque aqui.
+month-1 = janeiro
+month-2 = fevereiro
+month-3 = março
+month-4 = abril
+month-5 = maio
+month-6 = junho
+month-7 = julho
+month-8 = agosto
+month-9 = setembro
+month-10 = outubro
+month-11 = novembro
+month-12 = dezembro
+quick-add-label = ⚡ Adição rápida:
+quick-add-hint = café 4,50 ontem
+quick-add-incomplete = Digite uma descrição e um valor, por exemplo "salário +3000 31 jan"
+period-today = Hoje
+period-month = Mês
+period-year = Ano
+all-time = Todo o período
+period-income = Receitas: { $amount }
+period-expenses = Despesas: { $amount }
+period-net = Saldo: { $amount }
+group-by-label = Agrupar por:
+group-none = Nada
+group-day = Dia
+group-month = Mês
+weekday-1 = seg
+weekday-2 = ter
+weekday-3 = qua
+weekday-4 = qui
+weekday-5 = sex
+weekday-6 = sáb
+weekday-7 = dom
+summary-count = { $count ->
+    [one] { $count } transação
+   *[other] { $count } transações
+}
+category-color = Cor usada na lista, nos gráficos e na legenda
+default-color = Restaurar a cor padrão
+ui-scale-label = Tamanho:
+ui-scale-hint = Ctrl+Mais e Ctrl+Menos também o alteram, Ctrl+0 o restaura.
+reset = Restaurar
+welcome = 👋 Boas-vindas!
+welcome-text = O livro está vazio. Comece com suas próprias transações, traga-as de outro aplicativo ou explore com algumas inventadas.
+add-first-transaction = ➕ Adicionar a primeira transação
+import-from = 📥 Importar do { $format }…
+load-demo-data = 🧪 Carregar dados de exemplo
+demo-data-hint = Três meses de transações de exemplo. Busque por "demo" para encontrá-las e excluí-las depois.
+demo-note = demo
+undo = Desfazer
+saved-toast = ✔ Salvo
+save-failed-toast = ⚠ Falha ao salvar: { $error }
+transaction-added = ✔ Transação adicionada
+transaction-updated = ✔ Transação atualizada
+tray-title = Controle Financeiro
+tray-quick-add = Adicionar transação rápida
+tray-show-hide = Mostrar/Ocultar janela
+tray-quit = Sair
+system-tray = Bandeja do sistema
+start-in-tray = Iniciar minimizado na bandeja
+tray-unavailable = Nenhuma bandeja do sistema foi encontrada, então o app abre como uma janela normal.
+copy-shown = 📋 Copiar ({ $count })
+copy-hint = Copia as transações como uma tabela que pode ser colada em planilhas e conversas
+copy-selected = 📋 Copiar
+copied = ✔ { $count ->
+    [one] { $count } transação copiada
+   *[other] { $count } transações copiadas
+}
+monthly-cash-flow = Fluxo de caixa mensal
+show-net-line = Mostrar saldo líquido
+chart-net = Líquido
+budget-vs-actual = Orçado vs. realizado
+chart-budgeted = Orçado
+chart-spent = Gasto
+chart-over-budget = Acima do orçamento
+forecast-label = Previsão:
+forecast-off = Desligada
+forecast-months = { $count ->
+    [one] { $count } mês
+   *[other] { $count } meses
+}
+chart-forecast = Previsão
+chart-forecast-at = Previsão para { $date }: { $balance }
+forecast-zero = ⚠ Neste ritmo o saldo chega a zero por volta de { $date }
+forecast-end = Saldo estimado em { $date }: { $balance }
+spending-by-category = Gastos por categoria
+top-expenses = Maiores despesas
+largest-expenses = Maiores gastos
+top-payees = Principais beneficiários por total
+times-count = { $count ->
+    [one] { $count } vez
+   *[other] { $count } vezes
+}
+spending-stats = Estatísticas de gastos
+stats-total-spent = Total gasto
+stats-average-daily = Média por dia
+stats-average-expense = Despesa média
+stats-median-expense = Despesa mediana
+stats-busiest-day = Dia de mais gastos
+stats-no-spend-days = Dias sem gastos
+stats-days-of = { $count } de { $total } dias
+analytics-range-label = Mostrar:
+last-90-days = Últimos 90 dias
+year-to-date = Ano até hoje
+last-year = Ano passado
+click-to-see-transactions = Clique para ver as transações
+money-flow = Fluxo do dinheiro
+flow-total = Total
+flow-saved = Poupado
+flow-from-savings = Das economias
+export-image = Exportar imagem
+image-saved = Gráfico salvo em { $path }
+image-save-failed = Não foi possível salvar a imagem: { $error }
+recurring-bills = Contas recorrentes
+no-recurring-bills = Nenhuma assinatura ou conta regular encontrada ainda.
+recurring-every = Frequência
+recurring-per-month = Por mês
+recurring-next = Próxima prevista
+recurring-monthly = Mensal
+recurring-weekly = Semanal
+recurring-days = A cada { $count } dias
+recurring-scheduled = Agendada
+recurring-schedule = Agendar próxima
+recurring-schedule-hint = Adiciona uma cópia do último pagamento na próxima data prevista
+recurring-total = Total
+bill-scheduled = { $description } agendada para { $date }
+amount-distribution = Distribuição de valores
+all-categories = Todas as categorias
+histogram-empty = Nada para mostrar neste tipo e categoria.
+histogram-bar = { $count ->
+    [one] { $range }: { $count } transação, { $total } no total
+   *[other] { $range }: { $count } transações, { $total } no total
+}
+spending-patterns = Padrões de gasto
+average-by-weekday = Gasto médio por dia da semana
+average-by-hour = Gasto médio por hora do dia
+pattern-tooltip = { $when }: { $amount } em média
+compare-periods = Comparar períodos
+compare-period-a = Antes:
+compare-period-b = Depois:
+compare-change = Variação
+compare-total = Total
+pie-other = Outras ({ $count })
+pie-total = Total
+expense-treemap = Mapa de árvore
+rolling-spending = Gasto contínuo
+rolling-spending-line = Gasto nos últimos { $days } dias
+rolling-spending-average = Média
+rolling-spending-now = Últimos { $days } dias: { $amount } (média no período: { $average })
+tab-dashboard = Painel
+customize-dashboard = ⚙ Personalizar
+reset-dashboard = Restaurar padrão
+dashboard-empty = Todos os widgets estão ocultos. Use Personalizar para mostrar alguns.
+widget-balance = Saldo atual
+widget-this-month = Este mês
+widget-budgets = Orçamentos
+widget-upcoming = Próximas contas
+widget-balance-chart = Saldo, últimos 3 meses
+widget-recent = Transações recentes
+nothing-upcoming = Nada a vencer nos próximos 30 dias.
+expected-from-history = Previsto pelos pagamentos anteriores, ainda não agendado
+cli-usage =
+    Uso:
+      finance_tracker add <descrição> <valor> [--category Alimentação/Mercado] [--income] [--date AAAA-MM-DD] [--note texto]
+      finance_tracker list [--month AAAA-MM]
+      finance_tracker report [--month AAAA-MM] [--csv]
+      finance_tracker tui
+      finance_tracker bot
+    Adicione --data-file <caminho> para usar outro livro. Sem um comando, a janela é aberta.
+    --wsl-compat abre a janela com X11 e renderização por software, o padrão no WSL. --no-wsl-compat desativa isso.
+cli-missing-value = { $option } precisa de um valor
+cli-unknown-option = Opção desconhecida { $option }
+cli-bad-amount = Não é um valor: { $value }
+cli-bad-date = Não é uma data (AAAA-MM-DD): { $value }
+cli-bad-month = Não é um mês (AAAA-MM): { $value }
+cli-unknown-category = Nenhuma categoria chamada { $name }
+cli-locked = O livro está criptografado. Desbloqueie-o na janela.
+cli-added = Adicionado { $description }: { $amount } ({ $category }) em { $date }
+cli-no-transactions = Nenhuma transação.
+cli-report-month = { $month }: receitas { $income }, despesas { $expenses }, saldo { $net }
+cli-no-tui = Esta versão não tem a interface de terminal. Compile com `cargo build --features tui`.
+tui-keys = ←/→ mês   ↑/↓ selecionar   a adicionar   d excluir   q sair
+tui-add-keys = Enter adicionar   Esc cancelar
+tui-confirm-delete = Mover esta transação para a lixeira? y / n
+tui-deleted = Movida para a lixeira
+tab-rules = Regras
+new-rule = Nova regra
+rules-explained = Transações cuja descrição corresponde são categorizadas automaticamente ao adicionar e na importação. A primeira regra que corresponder vale.
+rule-contains = Descrição contém:
+rule-regex = Expressão regular
+rule-file-under = Categorizar como:
+rule-bad-regex = Expressão regular inválida: { $error }
+rule-bad-amount = Os valores precisam ser números
+apply-rules = Aplicar às transações existentes
+apply-rules-hint = Recategoriza todas as transações que correspondem a alguma regra. Desfazer reverte.
+no-rules-yet = Nenhuma regra ainda.
+rule-earlier = Verificar esta regra antes da de cima
+rules-applied = { $count ->
+    [one] { $count } transação recategorizada
+   *[other] { $count } transações recategorizadas
+}
+script-hooks = Scripts
+script-hooks-explained = Um script Rhai aqui pode definir on_add(t) para alterar ou recusar novas transações, on_import(t) para alterar ou descartar linhas importadas e on_report(list) para adicionar um relatório à Análise e ao comando report.
+script-label = Script:
+reload-script = Recarregar
+script-loaded = Carregado, com { $hooks }
+no-script = Nenhum script ainda
+run-report-script = 📜 Executar relatório do script
+run-report-script-hint = Executa on_report do script nas transações do período
+saving = Salvando…
+importing = Importando do { $format }…
+import-cancelled = Importação cancelada, nada foi adicionado
+import-errors = { $count ->
+    [one] 1 linha não pôde ser lida
+   *[other] { $count } linhas não puderam ser lidas
+}
+import-row-unreadable = Linha { $line }: { $error }
+import-row-bad-date = Linha { $line }: “{ $value }” não é uma data
+import-row-bad-amount = Linha { $line }: “{ $value }” não é um valor
+tab-audit = 🕵 Auditoria
+audit-explained = Cada transação adicionada, alterada ou excluída, as mais recentes primeiro, registrada quando o livro foi salvo. Guardado em { $path }.
+audit-encrypted = Este livro é criptografado, e o registro também. Só esta senha o abre.
+journal-rekey-failed = A senha não foi alterada porque não foi possível criptografar de novo o registro de auditoria: { $error }
+audit-failed = Não foi possível usar o registro de auditoria: { $error }
+audit-search-hint = Descrição
+audit-reload = Recarregar
+audit-empty = Nada registrado ainda
+audit-added = Adicionada
+audit-changed = Alterada
+audit-deleted = Excluída
+audit-cause-edit = No app
+audit-cause-import = Importação
+audit-cause-undo = Desfazer/refazer
+audit-cause-sync = Sincronização
+audit-cause-restore = Backup restaurado
+audit-cause-terminal = Linha de comando
+error-bad-amount = { $field }: “{ $value }” não é um número que possa ser usado aqui
+error-bad-amount-advice = Digite um número positivo como 12,50 (somas como 12+4,5 funcionam no formulário de transação) e tente de novo.
+error-missing-description = A transação não tem descrição
+error-missing-description-advice = Digite do que se trata, por exemplo a loja ou o favorecido, e adicione de novo.
+error-splits = As divisões não somam o valor
+error-splits-advice = Deixe cada divisão como um número e ajuste até "Restante" mostrar 0, ou remova as divisões.
+error-config = Não foi possível ler seu arquivo de configurações: { $error }
+error-config-advice = As configurações padrão estão em uso por enquanto e o arquivo foi mantido como está. Corrija ou apague o arquivo e reinicie; enquanto isso, mudar configurações ou perfis não o sobrescreve.
+budget-limit-field = Limite do orçamento
+goal-target-field = Valor da meta
+opening-balance-field = Saldo inicial
+rate-field = Taxa de câmbio
+diagnostics = Diagnóstico
+log-file = Arquivo de log:
+log-memory-only = nenhum, não há pasta de configuração neste sistema
+copy-log = 📋 Copiar
+recovered-changes = { $count ->
+    [one] Recuperada 1 alteração que não foi salva antes de o app fechar
+   *[other] Recuperadas { $count } alterações que não foram salvas antes de o app fechar
+}
+settings-file = As configurações acima ficam em { $path }, que também pode ser editado à mão com o app fechado.
+changed-on-disk = { $path } foi alterado por outro programa
+changed-on-disk-advice = Suas alterações desde então estão retidas, sem sobrescrever o arquivo. Mesclar mantém as duas, juntando as transações como a sincronização faz.
+merge-changes = Mesclar
+reload-file = Recarregar
+reload-file-hint = Usar o arquivo como está agora e descartar as alterações feitas aqui desde que ele foi salvo
+keep-mine = Manter as minhas
+keep-mine-hint = Salvar esta versão sobre o arquivo, descartando as alterações do outro programa
+file-gone = { $path } não existe mais
+tui-merged = { $done } (mesclado com alterações feitas em outro lugar)
+read-only = { $path } está aberto em outra janela, então aqui ele é somente leitura
+read-only-advice = As alterações feitas aqui não serão salvas. Feche a outra janela e clique em Tentar de novo para editar aqui.
+try-again = Tentar de novo
+try-again-hint = As alterações feitas aqui enquanto isso são substituídas pelo que a outra janela salvou
+cli-in-use = O livro está aberto em outra janela ou terminal. Feche-o primeiro.
+tab-net-worth = 📈 Patrimônio
+net-worth-cash = Saldo em caixa
+net-worth-holdings = Investimentos
+net-worth-total = Patrimônio líquido
+holdings-unpriced = Alguns investimentos ainda não têm preço, ou não há taxa de câmbio para a moeda do preço, e ficam de fora.
+new-holding = Novo investimento
+quantity-label = Quantidade:
+quantity-field = Quantidade
+holdings = Investimentos
+no-holdings-yet = Nenhum investimento ainda.
+fetch-prices = 🌐 Buscar preços
+prices-fetched = { $count ->
+    [one] 1 preço buscado
+   *[other] { $count } preços buscados
+}
+prices-fetch-failed = { $count } buscados, { $failed } falharam. { $symbol }: { $error }
+price-label = Preço
+value-label = Valor
+price-updated = Atualizado
+no-rate-for = Sem taxa para { $currency }
+never = nunca
+price-source = Fonte de preços
+price-source-explained = Os preços são buscados nesta URL, na moeda principal. O ponteiro diz onde está o preço na resposta JSON. Ambos podem usar {"{"}symbol{"}"}, {"{"}currency{"}"} (USD) e {"{"}currency_lower{"}"} (usd).
+price-url = URL:
+price-pointer = Ponteiro JSON:
+use-coingecko = Usar CoinGecko (cripto, sem chave)
+bank-connection = Conexão com o banco
+bank-explained = Busca transações do seu banco pelo GoCardless Bank Account Data. Crie as chaves da API e conecte seu banco no site deles, depois informe as chaves e o ID da conta aqui.
+bank-secret-id = ID da chave:
+bank-secret-key = Chave secreta:
+bank-account-id = ID da conta:
+bank-secret-stored = Elas ficam sem criptografia no arquivo de configurações deste computador, que só a sua conta de usuário pode ler.
+bank-pull = 🏦 Buscar transações
+bank-last-pulled = Buscado até { $date }
+bank-never-pulled = A primeira busca volta 90 dias
+bank-pulled = { $count ->
+    [one] 1 transação para revisar
+   *[other] { $count } transações para revisar
+}, { $skipped } ignoradas
+bank-pull-failed = Falha ao buscar do banco: { $error }
+bank-review = Transações do banco
+bank-review-explained = Classificadas pelas suas regras. As marcadas são adicionadas ao livro. As que parecem uma transação que você já tem começam desmarcadas.
+select-none = Desmarcar todas
+bank-looks-like-existing = já está no livro?
+bank-add-selected = Adicionar { $count }
+bank-discard = Descartar
+bank-added = { $count ->
+    [one] 1 transação do banco adicionada
+   *[other] { $count } transações do banco adicionadas
+}
+audit-cause-bot = Bot do Telegram
+bot-heading = Bot do Telegram
+bot-explained = Adicione transações mandando mensagens a um bot, como "táxi 12,50". Crie um bot com o @BotFather no Telegram e cole o token dele aqui, depois rode `finance_tracker bot` num computador que fique ligado. Mande uma mensagem ao bot para saber o ID do seu chat e adicione-o abaixo.
+bot-token = Token do bot:
+bot-allowed-chats = IDs de chat permitidos:
+bot-token-stored = O token fica sem criptografia no arquivo de configurações deste computador, que só a sua conta de usuário pode ler.
+bot-no-token = Nenhum token de bot. Configure um em Dados na janela primeiro.
+bot-listening = Ouvindo como @{ $name }. Pressione Ctrl+C para parar.
+bot-poll-failed = Não foi possível falar com o Telegram, tentando de novo em breve: { $error }
+bot-not-allowed = Este chat não pode adicionar transações. O ID dele é { $chat }; adicione-o em Dados no Controle Financeiro.
+bot-not-understood = Não entendi. Mande uma descrição e um valor, como "táxi 12,50" ou "salário +3000 ontem".
+bot-help = Mande uma descrição e um valor, como "táxi 12,50" ou "salário +3000 ontem", e ele é adicionado ao livro.
+webhooks = Webhooks
+webhooks-explained = Envia um POST em JSON para cada URL quando um orçamento passa do limite, quando uma transação grande é adicionada ou uma vez por semana com os totais da semana anterior. O texto vai em "message" (e "content", para o Discord), com os números junto.
+webhook-on-budget = Orçamento estourado
+webhook-on-large = Transação grande
+webhook-on-weekly = Resumo semanal
+webhook-send-test = Enviar teste
+webhook-add = ➕ Adicionar webhook
+webhook-large-from = Transações grandes a partir de:
+webhook-test = Teste do Controle Financeiro
+webhook-budget-exceeded = { $category } passou do orçamento: { $spent } de { $limit }
+webhook-large-transaction = Transação grande: { $description }, { $amount }
+webhook-weekly-summary = Semana de { $from } a { $to }: receitas { $income }, despesas { $expenses }, saldo { $net }
+notifications = Notificações
+notify-on-budget-warning = Quando um orçamento chega a 80%
+notify-on-budget-exceeded = Quando um orçamento é estourado
+notify-on-bills-due = Quando há contas vencendo amanhã
+notify-budget-spent = { $spent } de { $limit } gastos
+notify-budget-warning = { $category } está em { $percent }% do orçamento
+notify-budget-exceeded = { $category } passou do orçamento
+notify-bills-due = { $count ->
+    [one] Uma conta vence amanhã
+   *[other] { $count } contas vencem amanhã
+}, { $date }
+scan-receipt = 🧾 Ler recibo
+scan-receipt-hint = Anexa a foto de um recibo e preenche a loja, o total e a data a partir dela. Precisa do programa de OCR tesseract instalado.
+receipt-reading = Lendo o recibo…
+receipt-failed = Não foi possível ler o recibo: { $error }
+receipt-nothing-found = Nada legível no recibo. Ele foi anexado mesmo assim.
+receipt-filled = Preenchido a partir do recibo. Confira antes de adicionar.
+sheets-heading = Google Planilhas
+sheets-explained = Exporta as transações e os totais mensais para as abas Transactions e Monthly de uma Planilha Google, substituindo o que havia lá. Crie uma conta de serviço no Google Cloud com a API do Sheets ativada, baixe a chave JSON dela e compartilhe a planilha com o e-mail da conta.
+sheets-key-file = Chave da conta de serviço:
+sheets-spreadsheet = Planilha:
+sheets-daily = Exportar uma vez por dia enquanto o app estiver aberto
+sheets-export = 📤 Exportar para o Google Planilhas
+sheets-exporting = Exportando…
+sheets-last-exported = Exportado pela última vez em { $date } às { $time }
+sheets-export-failed = A exportação para o Google Planilhas falhou: { $error }
+amount-must-be-positive = Precisa ser um número positivo
+duplicate-warning = { $description }, { $amount } em { $date } já está no livro. Adicionar de novo?
+add-anyway = Adicionar mesmo assim
@@ -0,0 +1,37 @@
+use chrono::NaiveDate;
+use std::collections::HashMap;
+
+use crate::filter::Filter;
+
+// What the Analytics charts draw, kept between frames. Rebuilt when the ledger changes
+// (every change goes through save_data, which bumps the revision), when the filter or
+// the range changes, and not otherwise.
+#[derive(Default)]
+pub struct Aggregates {
+    key: Option<Key>,
+    // Indexes into the transactions that pass the filter, oldest first, each with the
+    // balance after it
+    pub line: Vec<(usize, f64)>,
+    pub cash_flow: Vec<(i32, u32, f64, f64)>,
+    pub category_months: (Vec<i32>, Vec<(String, Vec<f64>)>),
+    // Net expenses in the range per category, and per (category, subcategory)
+    pub category_totals: HashMap<String, f64>,
+    pub subcategory_totals: HashMap<(String, String), f64>,
+}
+
+#[derive(Clone, PartialEq)]
+pub struct Key {
+    pub revision: u64,
+    pub filter: Filter,
+    pub bounds: Option<(NaiveDate, NaiveDate)>,
+}
+
+impl Aggregates {
+    pub fn is_current(&self, key: &Key) -> bool {
+        self.key.as_ref() == Some(key)
+    }
+
+    pub fn built_for(key: Key) -> Self {
+        Self { key: Some(key), ..Self::default() }
+    }
+}
@@ -0,0 +1,271 @@
+use chrono::{Datelike, Local, NaiveDate};
+
+use crate::{FinanceApp, audit, export, forecast, i18n};
+use crate::aggregates::{Aggregates, Key};
+use crate::model::{Transaction, TransactionType, month_index};
+
+impl FinanceApp {
+    // How a transaction counts towards expense categories, in the home currency:
+    // expenses add per category/split, refunds subtract from the refunded expense's categories,
+    // and any other income doesn't count.
+    pub fn expense_contributions(&self, t: &Transaction) -> Vec<(String, Option<String>, f64)> {
+        let (source, sign) = match t.trans_type {
+            TransactionType::Expense => (t, 1.0),
+            TransactionType::Income => match t.refund_of.and_then(|id| self.transaction_index(id)) {
+                Some(idx) => (&self.transactions[idx], -1.0),
+                None => return Vec::new(),
+            },
+        };
+
+        let amount = self.home_amount(t) * sign;
+        source.category_shares().into_iter()
+            .map(|(category, subcategory, share)| {
+                (category.to_string(), subcategory.map(|s| s.to_string()), amount * share)
+            })
+            .collect()
+    }
+
+    // Net expense totals per top-level category between two days (inclusive), in the home
+    // currency. Subcategories roll up into their parent and split transactions count per split.
+    pub fn category_spending(&self, from: NaiveDate, to: NaiveDate) -> std::collections::HashMap<String, f64> {
+        let mut totals = std::collections::HashMap::new();
+        for t in &self.transactions {
+            let day = t.date.date_naive();
+            if t.is_scheduled() || day < from || day > to {
+                continue;
+            }
+            for (category, _, amount) in self.expense_contributions(t) {
+                *totals.entry(category).or_insert(0.0) += amount;
+            }
+        }
+        totals
+    }
+
+    // Spending in this budget month of each budgeted category the changes touched, as
+    // (category, limit, before, after). Before is worked out by taking the changes back out.
+    pub fn budget_changes(&self, entries: &[audit::Entry]) -> Vec<(String, f64, f64, f64)> {
+        let (from, to) = self.settings.month_containing(Local::now().date_naive());
+        let counts = |t: &Transaction| !t.is_scheduled() && (from..=to).contains(&t.date.date_naive());
+        let after = self.category_spending(from, to);
+        let mut before = after.clone();
+        for entry in entries {
+            for (t, sign) in [(&entry.after, -1.0), (&entry.before, 1.0)] {
+                let Some(t) = t.as_ref().filter(|t| counts(t)) else { continue };
+                for (category, _, amount) in self.expense_contributions(t) {
+                    *before.entry(category).or_insert(0.0) += amount * sign;
+                }
+            }
+        }
+        self.budgets.iter()
+            .filter_map(|b| {
+                let was = before.get(&b.category).copied().unwrap_or(0.0);
+                let is = after.get(&b.category).copied().unwrap_or(0.0);
+                (was != is).then(|| (b.category.clone(), b.monthly_limit, was, is))
+            })
+            .collect()
+    }
+
+    // Transactions plus monthly and per-category totals for the Excel export.
+    // Upcoming transactions are listed but left out of the totals.
+    pub fn build_report(&self, transactions: &[&Transaction]) -> export::Report {
+        let mut sorted = transactions.to_vec();
+        sorted.sort_by_key(|t| t.date);
+
+        let mut months: Vec<(i32, u32, f64, f64)> = Vec::new();
+        let mut categories: std::collections::HashMap<String, f64> = std::collections::HashMap::new();
+        let mut rows = Vec::new();
+
+        for t in sorted {
+            rows.push(export::ReportRow {
+                date: t.date.date_naive(),
+                description: t.description.clone(),
+                category: t.category_path(),
+                kind: match t.trans_type {
+                    TransactionType::Income => "Income",
+                    TransactionType::Expense => "Expense",
+                },
+                status: if t.is_scheduled() { "Upcoming" } else { t.status.name() },
+                amount: self.signed_amount(t),
+                original: t.currency.as_ref().map(|code| (t.amount, code.clone())),
+                note: t.note.clone(),
+            });
+            if t.is_scheduled() {
+                continue;
+            }
+
+            let (year, month) = (t.date.year(), t.date.month());
+            if months.last().map(|m| (m.0, m.1)) != Some((year, month)) {
+                months.push((year, month, 0.0, 0.0));
+            }
+            let entry = months.last_mut().expect("pushed above");
+            // Refunds reduce expenses rather than counting as income
+            if t.trans_type == TransactionType::Income && t.refund_of.is_none() {
+                entry.2 += self.home_amount(t);
+            }
+            for (category, _, amount) in self.expense_contributions(t) {
+                entry.3 += amount;
+                *categories.entry(category).or_insert(0.0) += amount;
+            }
+        }
+
+        let mut categories: Vec<(String, f64)> = categories.into_iter().collect();
+        categories.sort_by(|a, b| b.1.total_cmp(&a.1));
+
+        export::Report {
+            currency: self.rates.home_currency.clone(),
+            rows,
+            months,
+            categories,
+        }
+    }
+
+    // First and last day of the Analytics range, None for all time
+    pub fn analytics_bounds(&self) -> Option<(NaiveDate, NaiveDate)> {
+        self.analytics_range.range(Local::now().date_naive(), &self.settings)
+    }
+
+    pub fn in_analytics_range(&self, day: NaiveDate) -> bool {
+        self.analytics_bounds().is_none_or(|(from, to)| from <= day && day <= to)
+    }
+
+    // Past transactions in the Analytics range that pass the filter
+    pub fn analytics_transactions(&self) -> impl Iterator<Item = &Transaction> {
+        let bounds = self.analytics_bounds();
+        self.transactions.iter().filter(move |t| {
+            let day = t.date.date_naive();
+            !t.is_scheduled()
+                && bounds.is_none_or(|(from, to)| from <= day && day <= to)
+                && self.filter.matches(t, self.home_amount(t))
+        })
+    }
+
+    // Brings the Analytics cache up to date, doing the work only when something it
+    // depends on changed since the last frame
+    pub fn refresh_aggregates(&mut self) {
+        let key = Key {
+            revision: self.revision,
+            filter: self.filter.clone(),
+            bounds: self.analytics_bounds(),
+        };
+        if self.aggregates.is_current(&key) {
+            return;
+        }
+        let mut aggregates = Aggregates::built_for(key);
+
+        let mut sorted: Vec<usize> = (0..self.transactions.len())
+            .filter(|&i| self.filter.matches(&self.transactions[i], self.home_amount(&self.transactions[i])))
+            .collect();
+        sorted.sort_by_key(|&i| self.transactions[i].date);
+        // The opening balance seeds the line but is not counted as income
+        let mut balance = if self.filter.is_active() { 0.0 } else { self.opening_balance };
+        aggregates.line = sorted.into_iter()
+            .map(|i| {
+                balance += self.signed_amount(&self.transactions[i]);
+                (i, balance)
+            })
+            .collect();
+
+        for t in self.analytics_transactions() {
+            for (category, subcategory, amount) in self.expense_contributions(t) {
+                // Other parts of a split don't belong in "Food only"
+                if !self.filter.categories.is_empty() && !self.filter.categories.contains(&category) {
+                    continue;
+                }
+                if let Some(sub) = subcategory {
+                    *aggregates.subcategory_totals.entry((category.clone(), sub)).or_insert(0.0) += amount;
+                }
+                *aggregates.category_totals.entry(category).or_insert(0.0) += amount;
+            }
+        }
+        // Fully refunded categories drop out of the chart
+        aggregates.category_totals.retain(|_, amount| *amount > 0.005);
+        aggregates.subcategory_totals.retain(|_, amount| *amount > 0.005);
+
+        aggregates.cash_flow = self.monthly_cash_flow();
+        aggregates.category_months = self.monthly_category_spending();
+        self.aggregates = aggregates;
+    }
+
+    // Estimated balance over the next `forecast_months`, starting from today's
+    pub fn balance_forecast(&self, balance: f64) -> Option<forecast::Forecast> {
+        if self.forecast_months == 0 {
+            return None;
+        }
+        let today = Local::now().date_naive();
+        let until = today.checked_add_months(chrono::Months::new(self.forecast_months))?;
+        let (mut past, mut scheduled) = (Vec::new(), Vec::new());
+        for t in &self.transactions {
+            let entry = forecast::Entry { day: t.date.date_naive(), description: &t.description, amount: self.signed_amount(t) };
+            if t.is_scheduled() { scheduled.push(entry) } else { past.push(entry) }
+        }
+        Some(forecast::project(&past, &scheduled, today, balance, until))
+    }
+
+    // (year, month, income, expenses) for every month from the first transaction the
+    // filter lets through to the last, quiet months included. Refunds come off expenses
+    // as everywhere else.
+    pub fn monthly_cash_flow(&self) -> Vec<(i32, u32, f64, f64)> {
+        let mut totals: std::collections::BTreeMap<i32, (f64, f64)> = std::collections::BTreeMap::new();
+        for t in self.analytics_transactions() {
+            let entry = totals.entry(month_index(t.date.date_naive())).or_default();
+            if t.trans_type == TransactionType::Income && t.refund_of.is_none() {
+                entry.0 += self.home_amount(t);
+            }
+            for (category, _, amount) in self.expense_contributions(t) {
+                // Other parts of a split don't belong in "Food only"
+                if self.filter.categories.is_empty() || self.filter.categories.contains(&category) {
+                    entry.1 += amount;
+                }
+            }
+        }
+        let (Some(&first), Some(&last)) = (totals.keys().next(), totals.keys().next_back()) else {
+            return Vec::new();
+        };
+        (first..=last)
+            .map(|index| {
+                let (income, expenses) = totals.get(&index).copied().unwrap_or_default();
+                (index.div_euclid(12), index.rem_euclid(12) as u32 + 1, income, expenses)
+            })
+            .collect()
+    }
+
+    // Month indexes (see month_index) from the first expense the filter lets through to
+    // the last, and each category's net expenses in those months, biggest spender first
+    pub fn monthly_category_spending(&self) -> (Vec<i32>, Vec<(String, Vec<f64>)>) {
+        let mut totals: std::collections::HashMap<String, std::collections::BTreeMap<i32, f64>> = std::collections::HashMap::new();
+        for t in self.analytics_transactions() {
+            let month = month_index(t.date.date_naive());
+            for (category, _, amount) in self.expense_contributions(t) {
+                // Other parts of a split don't belong in "Food only"
+                if self.filter.categories.is_empty() || self.filter.categories.contains(&category) {
+                    *totals.entry(category).or_default().entry(month).or_default() += amount;
+                }
+            }
+        }
+        let first = totals.values().filter_map(|m| m.keys().next()).min().copied();
+        let last = totals.values().filter_map(|m| m.keys().next_back()).max().copied();
+        let (Some(first), Some(last)) = (first, last) else {
+            return (Vec::new(), Vec::new());
+        };
+        let months: Vec<i32> = (first..=last).collect();
+        let mut categories: Vec<(String, Vec<f64>)> = totals.into_iter()
+            .map(|(category, by_month)| {
+                // A month of nothing but refunds would stack below zero
+                let amounts = months.iter().map(|m| by_month.get(m).copied().unwrap_or(0.0).max(0.0)).collect();
+                (category, amounts)
+            })
+            .collect();
+        categories.retain(|(_, amounts)| amounts.iter().any(|a| *a > 0.005));
+        categories.sort_by(|a, b| b.1.iter().sum::<f64>().total_cmp(&a.1.iter().sum::<f64>()));
+        (months, categories)
+    }
+
+    // "March 2025", or the exact days when budget months start on another day than the 1st
+    pub fn budget_month_label(&self, from: NaiveDate, to: NaiveDate) -> String {
+        if self.settings.month_start == 1 {
+            i18n::month_year(from)
+        } else {
+            format!("{} – {}", i18n::date(from), i18n::date(to))
+        }
+    }
+}
@@ -0,0 +1,52 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use uuid::Uuid;
+
+// Receipt images are copied here and referenced from the JSON by file name
+pub const ATTACHMENTS_DIR: &str = "attachments";
+
+pub const IMAGE_EXTENSIONS: [&str; 6] = ["png", "jpg", "jpeg", "gif", "bmp", "webp"];
+
+pub fn attachment_path(name: &str) -> PathBuf {
+    Path::new(ATTACHMENTS_DIR).join(name)
+}
+
+// URI understood by egui's image loaders
+pub fn attachment_uri(name: &str) -> String {
+    let path = attachment_path(name);
+    let absolute = fs::canonicalize(&path).unwrap_or(path);
+    format!("file://{}", absolute.display())
+}
+
+// Copies the file into the attachments folder under a name prefixed with the owning
+// transaction's ID and returns that name
+pub fn import_attachment(source: &Path, owner: Uuid) -> io::Result<String> {
+    fs::create_dir_all(ATTACHMENTS_DIR)?;
+
+    let original = source.file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_else(|| "receipt".to_string());
+    let mut name = format!("{}_{}", owner, original);
+
+    let mut counter = 1;
+    while attachment_path(&name).exists() {
+        name = format!("{}_{}_{}", owner, counter, original);
+        counter += 1;
+    }
+
+    fs::copy(source, attachment_path(&name))?;
+    Ok(name)
+}
+
+pub fn remove_attachment(name: &str) {
+    let _ = fs::remove_file(attachment_path(name));
+}
+
+// Native picker limited to image files
+pub fn pick_images() -> Vec<PathBuf> {
+    rfd::FileDialog::new()
+        .add_filter("Images", &IMAGE_EXTENSIONS)
+        .pick_files()
+        .unwrap_or_default()
+}
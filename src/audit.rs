@@ -0,0 +1,170 @@
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::io::{self, BufRead, Write};
+use std::path::{Path, PathBuf};
+use uuid::Uuid;
+
+use crate::crypto::LineKey;
+use crate::model::Transaction;
+
+// Append-only record of every change to the transactions, one JSON object per line in a
+// file beside the ledger. Changes are found at save time by comparing with the last save,
+// so it doesn't matter which screen (or the command line) made them. For an encrypted
+// ledger each line is sealed with the ledger's LineKey instead.
+
+// What led to a change, set just before the save that records it
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize, Default, Debug)]
+pub enum Cause {
+    #[default]
+    Edit,
+    Import,
+    Undo,
+    Sync,
+    Restore,
+    Terminal,
+    Bot,
+}
+
+// `before` is None for an added transaction, `after` None for a deleted one
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Entry {
+    pub time: DateTime<Local>,
+    pub cause: Cause,
+    pub before: Option<Transaction>,
+    pub after: Option<Transaction>,
+}
+
+pub fn journal_path(data_path: &Path) -> PathBuf {
+    let stem = data_path.file_stem()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_else(|| "finance_data".to_string());
+    data_path.with_file_name(format!("{}-audit.jsonl", stem))
+}
+
+// The transactions as last saved, to compare the next save with
+pub fn index(transactions: &[Transaction]) -> HashMap<Uuid, Transaction> {
+    transactions.iter().map(|t| (t.id, t.clone())).collect()
+}
+
+// Added and changed transactions in ledger order, then deleted ones oldest first
+pub fn changes(base: &HashMap<Uuid, Transaction>, transactions: &[Transaction], cause: Cause) -> Vec<Entry> {
+    let time = Local::now();
+    let mut entries: Vec<Entry> = transactions.iter()
+        .filter(|t| base.get(&t.id) != Some(t))
+        .map(|t| Entry { time, cause, before: base.get(&t.id).cloned(), after: Some(t.clone()) })
+        .collect();
+    let kept: std::collections::HashSet<Uuid> = transactions.iter().map(|t| t.id).collect();
+    let mut deleted: Vec<&Transaction> = base.values().filter(|t| !kept.contains(&t.id)).collect();
+    deleted.sort_by_key(|t| t.date);
+    entries.extend(deleted.into_iter().map(|t| Entry { time, cause, before: Some(t.clone()), after: None }));
+    entries
+}
+
+// Where an encrypted ledger's LineKey is kept
+pub fn key_path(data_path: &Path) -> PathBuf {
+    let stem = data_path.file_stem()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_else(|| "finance_data".to_string());
+    data_path.with_file_name(format!("{}-audit.key", stem))
+}
+
+// The entries as journal lines, sealed when there's a key
+pub fn lines(entries: &[Entry], key: Option<&LineKey>) -> io::Result<Vec<u8>> {
+    let mut lines = Vec::new();
+    for entry in entries {
+        let json = serde_json::to_vec(entry)?;
+        match key {
+            Some(key) => lines.extend(key.seal(&json).map_err(io::Error::other)?.into_bytes()),
+            None => lines.extend(json),
+        }
+        lines.push(b'\n');
+    }
+    Ok(lines)
+}
+
+pub fn append(path: &Path, entries: &[Entry], key: Option<&LineKey>) -> io::Result<()> {
+    let mut file = std::fs::OpenOptions::new().create(true).append(true).open(path)?;
+    file.write_all(&lines(entries, key)?)
+}
+
+// Oldest first. A line cut short by a crash is skipped rather than losing the rest, and
+// so are sealed lines when there's no key to open them.
+pub fn read(path: &Path, key: Option<&LineKey>) -> io::Result<Vec<Entry>> {
+    let file = match std::fs::File::open(path) {
+        Ok(file) => file,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e),
+    };
+    let mut entries = Vec::new();
+    for line in io::BufReader::new(file).lines() {
+        let line = line?;
+        let entry = if line.starts_with('{') {
+            serde_json::from_str(&line).ok()
+        } else {
+            key.and_then(|key| key.open(&line).ok()).and_then(|json| serde_json::from_slice(&json).ok())
+        };
+        entries.extend(entry);
+    }
+    Ok(entries)
+}
+
+// Writes the whole journal again, sealed with `key` or in the clear without one. Used
+// when a ledger is encrypted, decrypted or gets a new passphrase.
+pub fn rewrite(path: &Path, old_key: Option<&LineKey>, key: Option<&LineKey>) -> io::Result<()> {
+    if !path.exists() {
+        return Ok(());
+    }
+    let entries = read(path, old_key)?;
+    crate::storage::write_atomic(path, &lines(&entries, key)?)
+}
+
+// Fields that differ between the two versions, as (name, before, after). `modified` is
+// left out, it changes with everything else.
+pub fn changed_fields(before: &Transaction, after: &Transaction) -> Vec<(String, String, String)> {
+    let (Ok(serde_json::Value::Object(before)), Ok(serde_json::Value::Object(after))) =
+        (serde_json::to_value(before), serde_json::to_value(after))
+    else {
+        return Vec::new();
+    };
+    let show = |value: Option<&serde_json::Value>| match value {
+        Some(serde_json::Value::String(text)) => text.clone(),
+        Some(serde_json::Value::Null) | None => "–".to_string(),
+        Some(other) => other.to_string(),
+    };
+    after.keys()
+        .filter(|key| key.as_str() != "modified" && before.get(*key) != after.get(*key))
+        .map(|key| (key.clone(), show(before.get(key)), show(after.get(key))))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::test_transaction;
+
+    #[test]
+    fn sealed_lines_need_the_key() {
+        let dir = std::env::temp_dir().join(format!("audit-test-{}", Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("ledger-audit.jsonl");
+        let key = LineKey::generate();
+        let before = test_transaction("coffee", 4.5);
+        let entries = changes(&HashMap::new(), std::slice::from_ref(&before), Cause::Edit);
+
+        append(&path, &entries, None).unwrap();
+        rewrite(&path, None, Some(&key)).unwrap();
+        let text = std::fs::read_to_string(&path).unwrap();
+        assert!(!text.contains("coffee"));
+        assert_eq!(read(&path, None).unwrap().len(), 0);
+
+        append(&path, &entries, Some(&key)).unwrap();
+        let read_back = read(&path, Some(&key)).unwrap();
+        assert_eq!(read_back.len(), 2);
+        assert!(read_back[0].after.as_ref() == Some(&before));
+
+        rewrite(&path, Some(&key), None).unwrap();
+        assert_eq!(read(&path, None).unwrap().len(), 2);
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,266 @@
+use chrono::{Duration, Local, NaiveDate};
+use eframe::egui;
+use serde::Deserialize;
+use std::sync::Arc;
+use std::sync::mpsc::{Receiver, TryRecvError, channel};
+use uuid::Uuid;
+
+use crate::error::Problem;
+use crate::import::{self, ImportedRow};
+use crate::model::Transaction;
+use crate::rules::{Matcher, Rule};
+use crate::scripting::Hooks;
+use crate::settings::BankConnection;
+
+// Pulls an account's transactions through GoCardless Bank Account Data (formerly
+// Nordigen), which is free for personal use. Linking the bank happens on their site;
+// what's needed here is the API secret and the account ID it gives. Nothing is added
+// until the user has gone through what came in.
+
+const API_URL: &str = "https://bankaccountdata.gocardless.com/api/v2";
+
+// How far back the first pull goes
+const FIRST_PULL_DAYS: i64 = 90;
+
+// A pulled transaction waiting for the user
+pub struct Staged {
+    pub transaction: Transaction,
+    // A transaction already in the ledger it looks like
+    pub duplicate_of: Option<Uuid>,
+    pub include: bool,
+}
+
+pub struct Pulled {
+    pub staged: Vec<Staged>,
+    // Without a date or amount, or left out by the script
+    pub skipped: usize,
+    // Latest day seen, where the next pull starts
+    pub newest: Option<NaiveDate>,
+}
+
+#[derive(Deserialize)]
+struct Token {
+    access: String,
+}
+
+#[derive(Deserialize)]
+struct Answer {
+    transactions: Lists,
+}
+
+#[derive(Deserialize)]
+struct Lists {
+    #[serde(default)]
+    booked: Vec<BankTransaction>,
+    #[serde(default)]
+    pending: Vec<BankTransaction>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct BankTransaction {
+    booking_date: Option<NaiveDate>,
+    value_date: Option<NaiveDate>,
+    transaction_amount: Amount,
+    creditor_name: Option<String>,
+    debtor_name: Option<String>,
+    remittance_information_unstructured: Option<String>,
+    #[serde(default)]
+    remittance_information_unstructured_array: Vec<String>,
+}
+
+#[derive(Deserialize)]
+struct Amount {
+    amount: String,
+    currency: String,
+}
+
+fn call(request: ehttp::Request) -> Result<Vec<u8>, String> {
+    let response = ehttp::fetch_blocking(&request)?;
+    if !response.ok {
+        return Err(format!("{} {} {}", response.status, response.status_text, response.text().unwrap_or_default()));
+    }
+    Ok(response.bytes)
+}
+
+fn fetch(connection: &BankConnection, since: NaiveDate) -> Result<Lists, String> {
+    let credentials = serde_json::json!({
+        "secret_id": connection.secret_id.trim(),
+        "secret_key": connection.secret_key.trim(),
+    });
+    let mut request = ehttp::Request::post(format!("{}/token/new/", API_URL), credentials.to_string().into_bytes());
+    request.headers = ehttp::Headers::new(&[("Accept", "application/json"), ("Content-Type", "application/json")]);
+    let token: Token = serde_json::from_slice(&call(request)?).map_err(|e| e.to_string())?;
+
+    let url = format!("{}/accounts/{}/transactions/?date_from={}", API_URL, connection.account_id.trim(), since);
+    let mut request = ehttp::Request::get(url);
+    let bearer = format!("Bearer {}", token.access);
+    request.headers = ehttp::Headers::new(&[("Accept", "application/json"), ("Authorization", &bearer)]);
+    let answer: Answer = serde_json::from_slice(&call(request)?).map_err(|e| e.to_string())?;
+    Ok(answer.transactions)
+}
+
+// The other party names the transaction when the bank gives one, the payment reference
+// goes in the note
+fn to_row(t: &BankTransaction, booked: bool) -> Option<ImportedRow> {
+    let date = t.booking_date.or(t.value_date)?;
+    let amount: f64 = t.transaction_amount.amount.trim().parse().ok()?;
+    if amount == 0.0 {
+        return None;
+    }
+    let is_income = amount > 0.0;
+    let reference = t.remittance_information_unstructured.clone()
+        .unwrap_or_else(|| t.remittance_information_unstructured_array.join(" "))
+        .trim()
+        .to_string();
+    let party = if is_income { &t.debtor_name } else { &t.creditor_name };
+    let (description, note) = match party.as_deref().map(str::trim).filter(|p| !p.is_empty()) {
+        Some(party) => (party.to_string(), reference),
+        None => (reference, String::new()),
+    };
+    Some(ImportedRow {
+        date,
+        description,
+        amount: amount.abs(),
+        is_income,
+        category: None,
+        subcategory: None,
+        note,
+        cleared: booked,
+    })
+}
+
+fn stage(
+    lists: Lists,
+    home_currency: &str,
+    rules: &[Rule],
+    hooks: Option<&Hooks>,
+    existing: &[Transaction],
+) -> Result<Pulled, String> {
+    let matcher = Matcher::new(rules);
+    let mut pulled = Pulled { staged: Vec::new(), skipped: 0, newest: None };
+    let all = lists.booked.iter().map(|t| (t, true)).chain(lists.pending.iter().map(|t| (t, false)));
+    for (bank_transaction, booked) in all {
+        let Some(row) = to_row(bank_transaction, booked) else {
+            pulled.skipped += 1;
+            continue;
+        };
+        pulled.newest = pulled.newest.max(Some(row.date));
+        let Some(mut t) = import::to_transaction(row, &matcher, hooks)? else {
+            pulled.skipped += 1;
+            continue;
+        };
+        let currency = bank_transaction.transaction_amount.currency.trim().to_uppercase();
+        if !currency.is_empty() && currency != home_currency {
+            t.currency = Some(currency);
+        }
+        let duplicate_of = import::probable_duplicate(existing, &t);
+        pulled.staged.push(Staged { transaction: t, duplicate_of, include: duplicate_of.is_none() });
+    }
+    pulled.staged.sort_by_key(|s| s.transaction.date);
+    Ok(pulled)
+}
+
+// A pull running in the background. Poll it once per frame.
+pub struct BankPull {
+    rx: Receiver<Result<Pulled, Problem>>,
+}
+
+impl BankPull {
+    // None while it's still running
+    pub fn poll(&self) -> Option<Result<Pulled, Problem>> {
+        match self.rx.try_recv() {
+            Ok(result) => Some(result),
+            Err(TryRecvError::Empty) => None,
+            Err(TryRecvError::Disconnected) => Some(Err(Problem::Stopped)),
+        }
+    }
+}
+
+// Fetches from the last pulled day (or three months back) and files what came in with
+// the rules and the script's on_import, like a CSV import
+pub fn start(
+    ctx: &egui::Context,
+    connection: BankConnection,
+    home_currency: String,
+    rules: Vec<Rule>,
+    hooks: Option<Arc<Hooks>>,
+    existing: Vec<Transaction>,
+) -> BankPull {
+    let (tx, rx) = channel();
+    let ctx = ctx.clone();
+    let since = connection.last_pulled
+        .unwrap_or_else(|| Local::now().date_naive() - Duration::days(FIRST_PULL_DAYS));
+    std::thread::spawn(move || {
+        let pulled = fetch(&connection, since)
+            .and_then(|lists| stage(lists, &home_currency, &rules, hooks.as_deref(), &existing))
+            .map_err(Problem::Other);
+        let _ = tx.send(pulled);
+        ctx.request_repaint();
+    });
+    BankPull { rx }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::{TransactionStatus, TransactionType};
+
+    fn lists(json: serde_json::Value) -> Lists {
+        serde_json::from_value(json).unwrap()
+    }
+
+    #[test]
+    fn the_other_party_names_the_transaction() {
+        let lists = lists(serde_json::json!({
+            "booked": [
+                {
+                    "bookingDate": "2026-03-02",
+                    "transactionAmount": { "amount": "-42.50", "currency": "EUR" },
+                    "creditorName": "Grocer",
+                    "debtorName": "Me",
+                    "remittanceInformationUnstructured": "card 1234"
+                },
+                {
+                    "valueDate": "2026-03-01",
+                    "transactionAmount": { "amount": "1500.00", "currency": "EUR" },
+                    "debtorName": "Employer",
+                    "remittanceInformationUnstructuredArray": ["salary", "march"]
+                }
+            ]
+        }));
+        let spent = to_row(&lists.booked[0], true).unwrap();
+        assert_eq!((spent.description.as_str(), spent.note.as_str()), ("Grocer", "card 1234"));
+        assert_eq!(spent.amount, 42.5);
+        assert!(!spent.is_income);
+        let paid = to_row(&lists.booked[1], true).unwrap();
+        assert_eq!((paid.description.as_str(), paid.note.as_str()), ("Employer", "salary march"));
+        assert!(paid.is_income);
+        assert_eq!(paid.date, NaiveDate::from_ymd_opt(2026, 3, 1).unwrap());
+    }
+
+    #[test]
+    fn pending_ones_come_in_as_pending() {
+        let lists = lists(serde_json::json!({
+            "booked": [
+                { "bookingDate": "2026-03-02", "transactionAmount": { "amount": "-10", "currency": "EUR" }, "creditorName": "Cafe" },
+                { "transactionAmount": { "amount": "-3", "currency": "EUR" }, "creditorName": "No date" }
+            ],
+            "pending": [
+                { "bookingDate": "2026-03-05", "transactionAmount": { "amount": "-20", "currency": "USD" }, "creditorName": "Shop" },
+                { "bookingDate": "2026-03-06", "transactionAmount": { "amount": "0.00", "currency": "EUR" }, "creditorName": "Check" }
+            ]
+        }));
+        let pulled = stage(lists, "EUR", &[], None, &[]).unwrap();
+        assert_eq!(pulled.skipped, 2);
+        let staged: Vec<_> = pulled.staged.iter().map(|s| &s.transaction).collect();
+        assert_eq!(staged.len(), 2);
+        assert_eq!((staged[0].description.as_str(), staged[0].status), ("Cafe", TransactionStatus::Cleared));
+        assert_eq!((staged[1].description.as_str(), staged[1].status), ("Shop", TransactionStatus::Pending));
+        assert_eq!(staged[0].currency, None);
+        assert_eq!(staged[1].currency.as_deref(), Some("USD"));
+        assert!(staged.iter().all(|t| t.trans_type == TransactionType::Expense));
+        // Pending ones count too, the next pull starts from them
+        assert_eq!(pulled.newest, NaiveDate::from_ymd_opt(2026, 3, 5));
+    }
+}
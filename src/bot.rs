@@ -0,0 +1,144 @@
+use serde::Deserialize;
+use serde::de::DeserializeOwned;
+use std::time::Duration;
+
+use crate::i18n::{self, t};
+use crate::settings::BotConnection;
+use crate::{audit, config, FinanceApp};
+
+// Companion mode for logging from a phone: `finance_tracker bot` long-polls a Telegram
+// bot and adds each message like "taxi 12.50" the way the quick-add box would. Only chats
+// listed in the settings are answered; anyone else is told their chat ID so it can be
+// added. The ledger is loaded again for every message, so a window open on the same file
+// sees the change on disk and offers to merge it.
+
+const API_URL: &str = "https://api.telegram.org";
+
+// How long Telegram holds a getUpdates open when nothing comes in, in seconds
+const POLL_SECONDS: u64 = 50;
+
+// Wait after a failed poll, so a lost connection isn't retried in a tight loop
+const RETRY_AFTER: Duration = Duration::from_secs(15);
+
+#[derive(Deserialize)]
+struct Answer<T> {
+    ok: bool,
+    result: Option<T>,
+    description: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct Me {
+    username: String,
+}
+
+#[derive(Deserialize)]
+struct Update {
+    update_id: i64,
+    message: Option<Message>,
+}
+
+#[derive(Deserialize)]
+struct Message {
+    chat: Chat,
+    text: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct Chat {
+    id: i64,
+}
+
+fn call<T: DeserializeOwned>(token: &str, method: &str, body: serde_json::Value) -> Result<T, String> {
+    let mut request = ehttp::Request::post(format!("{}/bot{}/{}", API_URL, token, method), body.to_string().into_bytes());
+    request.headers = ehttp::Headers::new(&[("Content-Type", "application/json")]);
+    // The token is part of the URL, which connection errors repeat
+    let response = ehttp::fetch_blocking(&request).map_err(|e| e.replace(token, "<token>"))?;
+    let answer: Answer<T> = serde_json::from_slice(&response.bytes)
+        .map_err(|e| format!("{} {}: {}", response.status, response.status_text, e))?;
+    match answer {
+        Answer { ok: true, result: Some(result), .. } => Ok(result),
+        Answer { description, .. } => Err(description.unwrap_or_else(|| format!("{} {}", response.status, response.status_text))),
+    }
+}
+
+// The answer to one message from an allowed chat
+fn add_from(text: &str) -> String {
+    // /start, /help and whatever else Telegram clients send
+    if text.starts_with('/') {
+        return t!("bot-help");
+    }
+    // Read as it is on disk: replaying the write-ahead log is for the window that has
+    // the ledger open, which may be in the middle of writing it
+    let mut app = FinanceApp::read_data(config::resolve_data_file());
+    if let Some(error) = app.load_error.take() {
+        return error.to_string();
+    }
+    if app.locked {
+        return t!("cli-locked");
+    }
+    let Some(transaction) = app.parse_quick(text) else {
+        return t!("bot-not-understood");
+    };
+    if let Err(e) = app.add_transaction(transaction) {
+        return e;
+    }
+    let added = app.transactions.last().expect("just added");
+    let reply = t!(
+        "cli-added",
+        description = added.description.clone(),
+        amount = app.money(app.signed_amount(added)),
+        category = added.category_path(),
+        date = i18n::date(added.date.date_naive()),
+    );
+    app.audit_cause = audit::Cause::Bot;
+    app.save_data();
+    match app.save_error {
+        Some(error) => error,
+        None => reply,
+    }
+}
+
+// Runs until it's stopped. Only a bad token ends it; a lost connection is retried.
+pub fn serve(connection: &BotConnection) -> Result<(), String> {
+    let token = connection.token.trim();
+    if token.is_empty() {
+        return Err(t!("bot-no-token"));
+    }
+    let me: Me = call(token, "getMe", serde_json::json!({}))?;
+    tracing::info!(bot = %me.username, "bot started");
+    println!("{}", t!("bot-listening", name = me.username));
+
+    let mut offset = 0;
+    loop {
+        let body = serde_json::json!({ "offset": offset, "timeout": POLL_SECONDS, "allowed_updates": ["message"] });
+        let updates: Vec<Update> = match call(token, "getUpdates", body) {
+            Ok(updates) => updates,
+            Err(e) => {
+                tracing::warn!("bot poll failed: {}", e);
+                eprintln!("{}", t!("bot-poll-failed", error = e));
+                std::thread::sleep(RETRY_AFTER);
+                continue;
+            }
+        };
+        for update in updates {
+            // Confirmed with the next poll, so each message is only handled once
+            offset = update.update_id + 1;
+            let Some(Message { chat, text: Some(text) }) = update.message else { continue };
+            // Read again each time, so a chat allowed in the window works right away
+            let allowed = config::AppConfig::load().settings.bot.allowed_chats.contains(&chat.id);
+            let reply = if allowed {
+                add_from(text.trim())
+            } else {
+                tracing::warn!(chat = chat.id, "bot message from a chat that isn't allowed");
+                t!("bot-not-allowed", chat = chat.id)
+            };
+            println!("{}", reply);
+            let sent: Result<serde_json::Value, String> =
+                call(token, "sendMessage", serde_json::json!({ "chat_id": chat.id, "text": reply }));
+            if let Err(e) = sent {
+                tracing::warn!("bot reply failed: {}", e);
+            }
+        }
+    }
+}
@@ -0,0 +1,47 @@
+use eframe::egui::Color32;
+use serde::{Deserialize, Serialize};
+
+// Share of the limit at which a budget turns from green to amber
+pub const WARN_THRESHOLD: f64 = 0.8;
+
+// Monthly spending limit for one expense category, in the home currency
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Budget {
+    pub category: String,
+    pub monthly_limit: f64,
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum BudgetStatus {
+    OnTrack,
+    Approaching,
+    Exceeded,
+}
+
+impl BudgetStatus {
+    pub fn from_spend(spent: f64, limit: f64) -> Self {
+        if limit <= 0.0 || spent > limit {
+            BudgetStatus::Exceeded
+        } else if spent >= limit * WARN_THRESHOLD {
+            BudgetStatus::Approaching
+        } else {
+            BudgetStatus::OnTrack
+        }
+    }
+
+    pub fn color(&self) -> Color32 {
+        match self {
+            BudgetStatus::OnTrack => Color32::from_rgb(100, 200, 100),
+            BudgetStatus::Approaching => Color32::from_rgb(255, 165, 0),
+            BudgetStatus::Exceeded => Color32::from_rgb(220, 50, 50),
+        }
+    }
+}
+
+// Adds or updates the limit for a category
+pub fn set_budget(budgets: &mut Vec<Budget>, category: &str, monthly_limit: f64) {
+    match budgets.iter_mut().find(|b| b.category == category) {
+        Some(existing) => existing.monthly_limit = monthly_limit,
+        None => budgets.push(Budget { category: category.to_string(), monthly_limit }),
+    }
+}
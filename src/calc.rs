@@ -0,0 +1,168 @@
+// Amount fields take simple arithmetic, e.g. `12.99+4.50*2` for a receipt with several
+// items. + - * / and parentheses with the usual precedence; a comma works as a decimal
+// point, and thousands may be grouped with the other mark (see parse_number). A plain
+// number is just that number.
+
+pub fn evaluate(input: &str) -> Option<f64> {
+    let chars: Vec<char> = input.chars().filter(|c| !c.is_whitespace()).collect();
+    if chars.is_empty() {
+        return None;
+    }
+    let mut parser = Parser { chars, pos: 0 };
+    let value = parser.sum()?;
+    // 12.99+4.50*2 would otherwise be stored as 21.990000000000002
+    let value = (value * 1e6).round() / 1e6;
+    (parser.pos == parser.chars.len() && value.is_finite()).then_some(value)
+}
+
+// True for input that is more than a single number, so the result is worth showing
+pub fn is_expression(input: &str) -> bool {
+    let trimmed = input.trim().trim_start_matches(['-', '+']);
+    trimmed.contains(['+', '-', '*', '/', '(', ')'])
+}
+
+struct Parser {
+    chars: Vec<char>,
+    pos: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.pos).copied()
+    }
+
+    fn sum(&mut self) -> Option<f64> {
+        let mut value = self.product()?;
+        while let Some(op @ ('+' | '-')) = self.peek() {
+            self.pos += 1;
+            let rhs = self.product()?;
+            value = if op == '+' { value + rhs } else { value - rhs };
+        }
+        Some(value)
+    }
+
+    fn product(&mut self) -> Option<f64> {
+        let mut value = self.factor()?;
+        while let Some(op @ ('*' | '/')) = self.peek() {
+            self.pos += 1;
+            let rhs = self.factor()?;
+            value = if op == '*' { value * rhs } else { value / rhs };
+        }
+        Some(value)
+    }
+
+    fn factor(&mut self) -> Option<f64> {
+        match self.peek()? {
+            '-' => {
+                self.pos += 1;
+                Some(-self.factor()?)
+            }
+            '+' => {
+                self.pos += 1;
+                self.factor()
+            }
+            '(' => {
+                self.pos += 1;
+                let value = self.sum()?;
+                if self.peek() != Some(')') {
+                    return None;
+                }
+                self.pos += 1;
+                Some(value)
+            }
+            _ => self.number(),
+        }
+    }
+
+    fn number(&mut self) -> Option<f64> {
+        let start = self.pos;
+        while self.peek().is_some_and(|c| c.is_ascii_digit() || c == '.' || c == ',') {
+            self.pos += 1;
+        }
+        let text: String = self.chars[start..self.pos].iter().collect();
+        parse_number(&text)
+    }
+}
+
+// Digits with `.` and `,` marks, as typed or as banks export them: "4.50", "4,50",
+// "1.234,56", "1,234.56" and "1,234,567". Shared by the amount fields, quick add and
+// import so they all read a number the same way.
+//
+// The last mark is the decimal point when it's used once and differs from the others,
+// which group thousands. A lone comma before exactly three digits groups too, so
+// "1,234" is 1234, while a lone dot is always the decimal point. Groups after the first
+// must have three digits, so typos like "4..50" or "1,23.45" are refused rather than
+// read as something else.
+pub fn parse_number(text: &str) -> Option<f64> {
+    let marks: Vec<char> = text.chars().filter(|c| matches!(c, '.' | ',')).collect();
+    let groups: Vec<&str> = text.split(['.', ',']).collect();
+    if groups.iter().any(|g| !g.bytes().all(|b| b.is_ascii_digit())) {
+        return None;
+    }
+    let Some(&last) = marks.last() else {
+        return text.parse().ok();
+    };
+
+    let lone = marks.len() == 1;
+    let decimal = if lone {
+        !(last == ',' && groups[1].len() == 3 && !groups[0].is_empty())
+    } else {
+        marks[..marks.len() - 1].iter().all(|&m| m != last)
+    };
+    let (whole, fraction) = if decimal {
+        (&groups[..groups.len() - 1], groups[groups.len() - 1])
+    } else {
+        (&groups[..], "")
+    };
+    // Grouping marks all the same, "1.234.567,89" but not "1.234,567,89"
+    let grouping = &marks[..whole.len() - 1];
+    if grouping.windows(2).any(|w| w[0] != w[1]) {
+        return None;
+    }
+    // ".5" and "4." are fine on their own, an empty group anywhere else isn't
+    let (first, rest) = (whole[0], &whole[1..]);
+    if (first.is_empty() && !(lone && decimal))
+        || rest.iter().any(|g| g.len() != 3)
+        || (decimal && fraction.is_empty() && (!lone || first.is_empty()))
+    {
+        return None;
+    }
+    format!("{}.{}", whole.concat(), fraction).parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn numbers_with_either_decimal_mark() {
+        assert_eq!(parse_number("4.50"), Some(4.5));
+        assert_eq!(parse_number("4,50"), Some(4.5));
+        assert_eq!(parse_number("1.234,56"), Some(1234.56));
+        assert_eq!(parse_number("1,234.56"), Some(1234.56));
+        assert_eq!(parse_number("1,234,567"), Some(1234567.0));
+        assert_eq!(parse_number("1.234.567,8"), Some(1234567.8));
+        assert_eq!(parse_number("1,234"), Some(1234.0));
+        assert_eq!(parse_number("1.234"), Some(1.234));
+        assert_eq!(parse_number(".5"), Some(0.5));
+        assert_eq!(parse_number("4."), Some(4.0));
+    }
+
+    #[test]
+    fn malformed_groups_are_refused() {
+        for text in ["4..50", "1,23.45", "1.234,567,89", "1,2345,678", ".", "", "1,234.", ",,5"] {
+            assert_eq!(parse_number(text), None, "{}", text);
+        }
+    }
+
+    #[test]
+    fn arithmetic() {
+        assert_eq!(evaluate("12.99+4.50*2"), Some(21.99));
+        assert_eq!(evaluate("(1+2)*3"), Some(9.0));
+        assert_eq!(evaluate("-5+2"), Some(-3.0));
+        assert_eq!(evaluate("1.234,56 + 1"), Some(1235.56));
+        assert_eq!(evaluate("4..50"), None);
+        assert_eq!(evaluate("2*(3"), None);
+        assert_eq!(evaluate("1/0"), None);
+    }
+}
@@ -0,0 +1,265 @@
+use chrono::{Local, NaiveDate};
+use uuid::Uuid;
+
+use crate::i18n::{self, t};
+use crate::model::{CategoryKind, Transaction, TransactionStatus, TransactionType, local_from_naive};
+use crate::period::Period;
+use crate::quick_add::QuickEntry;
+use crate::{audit, bot, calc, config, export, FinanceApp};
+
+// Subcommands for scripts and terminals, on the same data file as the window:
+//   add "coffee" 4.50 [--category Food[/Groceries]] [--income] [--date 2024-12-31] [--note ...]
+//   list [--month 2024-12]
+//   report [--month 2024-12] [--csv]
+//   tui, when built with the tui feature
+//   bot, which adds what's texted to the Telegram bot set up in the settings
+// Anything else opens the window, see wsl_compat in lib.rs for its flags.
+pub enum Command {
+    Add {
+        description: String,
+        amount: f64,
+        income: bool,
+        // "Food" or "Food/Groceries". None guesses it like the quick-add box does.
+        category: Option<String>,
+        date: NaiveDate,
+        note: String,
+    },
+    List { month: Option<Period> },
+    Report { month: Option<Period>, csv: bool },
+    Tui,
+    Bot,
+}
+
+// None when the first argument isn't a subcommand. --data-file is left to config.
+pub fn command_from_args() -> Option<Result<Command, String>> {
+    let mut args = Vec::new();
+    let mut raw = std::env::args().skip(1);
+    while let Some(arg) = raw.next() {
+        if arg == "--data-file" {
+            raw.next();
+        } else if !arg.starts_with("--data-file=") {
+            args.push(arg);
+        }
+    }
+    let name = args.first()?.clone();
+    if !matches!(name.as_str(), "add" | "list" | "report" | "tui" | "bot") {
+        return None;
+    }
+    Some(parse(&name, &args[1..]))
+}
+
+fn parse(name: &str, args: &[String]) -> Result<Command, String> {
+    let mut positional = Vec::new();
+    let mut options: Vec<(&str, Option<&str>)> = Vec::new();
+    let mut rest = args.iter();
+    while let Some(arg) = rest.next() {
+        match arg.as_str() {
+            "--income" | "--csv" => options.push((arg, None)),
+            "--category" | "--date" | "--note" | "--month" => {
+                let value = rest.next().ok_or_else(|| t!("cli-missing-value", option = arg.as_str()))?;
+                options.push((arg, Some(value)));
+            }
+            other if other.starts_with("--") => return Err(t!("cli-unknown-option", option = other)),
+            other => positional.push(other),
+        }
+    }
+    let option = |key: &str| options.iter().find(|(k, _)| *k == key).and_then(|(_, v)| *v);
+    let flag = |key: &str| options.iter().any(|(k, _)| *k == key);
+    let month = option("--month").map(parse_month).transpose()?;
+
+    match name {
+        "add" => {
+            let [description, amount] = positional[..] else { return Err(t!("cli-usage")) };
+            // "+3000" is income, like in the quick-add box
+            let (income, amount) = match amount.strip_prefix('+') {
+                Some(rest) => (true, rest),
+                None => (flag("--income"), amount),
+            };
+            let amount = calc::evaluate(amount)
+                .filter(|a| *a > 0.0)
+                .ok_or_else(|| t!("cli-bad-amount", value = amount))?;
+            let date = match option("--date") {
+                Some(value) => NaiveDate::parse_from_str(value, "%Y-%m-%d")
+                    .map_err(|_| t!("cli-bad-date", value = value))?,
+                None => Local::now().date_naive(),
+            };
+            Ok(Command::Add {
+                description: description.trim().to_string(),
+                amount,
+                income,
+                category: option("--category").map(str::to_string),
+                date,
+                note: option("--note").unwrap_or_default().trim().to_string(),
+            })
+        }
+        _ if !positional.is_empty() => Err(t!("cli-usage")),
+        "list" => Ok(Command::List { month }),
+        "tui" => Ok(Command::Tui),
+        "bot" => Ok(Command::Bot),
+        _ => Ok(Command::Report { month, csv: flag("--csv") }),
+    }
+}
+
+fn parse_month(value: &str) -> Result<Period, String> {
+    NaiveDate::parse_from_str(&format!("{}-01", value), "%Y-%m-%d")
+        .map(Period::month_of)
+        .map_err(|_| t!("cli-bad-month", value = value))
+}
+
+// Loads the ledger the window would open, runs the command and saves if it changed anything
+pub fn run(command: Command) -> Result<(), String> {
+    // Only what changes the ledger takes it over like a window does; list, report and the
+    // bot's startup just read it, and leave the write-ahead log to whoever has it open
+    let changes_ledger = matches!(command, Command::Add { .. } | Command::Tui);
+    let path = config::resolve_data_file();
+    let mut app = if changes_ledger { FinanceApp::load_data(path) } else { FinanceApp::read_data(path) };
+    if let Some(error) = app.load_error.take() {
+        return Err(error.to_string());
+    }
+    // Its lock is held until the command is done
+    if app.read_only {
+        return Err(t!("cli-in-use"));
+    }
+    // The terminal UI asks for the passphrase itself
+    if matches!(command, Command::Tui) {
+        #[cfg(feature = "tui")]
+        return crate::tui::run(app);
+        #[cfg(not(feature = "tui"))]
+        return Err(t!("cli-no-tui"));
+    }
+    if app.locked {
+        return Err(t!("cli-locked"));
+    }
+
+    match command {
+        Command::Add { description, amount, income, category, date, note } => {
+            let transaction = app.cli_transaction(description, amount, income, category, date, note)?;
+            app.add_transaction(transaction)?;
+            // As the script's on_add left it
+            let added = app.transactions.last().expect("just added");
+            println!("{}", t!(
+                "cli-added",
+                description = added.description.clone(),
+                amount = app.money(app.signed_amount(added)),
+                category = added.category_path(),
+                date = i18n::date(added.date.date_naive()),
+            ));
+            app.audit_cause = audit::Cause::Terminal;
+            app.save_data();
+            match app.save_error {
+                Some(error) => Err(error),
+                None => Ok(()),
+            }
+        }
+        Command::List { month } => {
+            let mut listed: Vec<&Transaction> = app.transactions.iter()
+                .filter(|t| month.is_none_or(|m| m.contains(t.date.date_naive())))
+                .collect();
+            listed.sort_by_key(|t| t.date);
+            if listed.is_empty() {
+                println!("{}", t!("cli-no-transactions"));
+            }
+            for t in listed {
+                println!(
+                    "{}  {:<30} {:<24} {:>14}",
+                    t.date.format("%Y-%m-%d"),
+                    t.description,
+                    t.category_path(),
+                    app.money(app.signed_amount(t)),
+                );
+            }
+            Ok(())
+        }
+        Command::Report { month, csv } => {
+            let included: Vec<&Transaction> = app.transactions.iter()
+                .filter(|t| month.is_none_or(|m| m.contains(t.date.date_naive())))
+                .collect();
+            let report = app.build_report(&included);
+            if csv {
+                return export::write_csv_to(std::io::stdout(), &report);
+            }
+            for &(year, month, income, expenses) in &report.months {
+                let label = NaiveDate::from_ymd_opt(year, month, 1).map(i18n::month_year).unwrap_or_default();
+                println!("{}", t!(
+                    "cli-report-month",
+                    month = label,
+                    income = app.money(income),
+                    expenses = app.money(expenses),
+                    net = app.money(income - expenses),
+                ));
+            }
+            for (category, amount) in &report.categories {
+                println!("  {:<30} {:>14}", category, app.money(*amount));
+            }
+            match app.hooks.as_ref().and_then(|hooks| hooks.on_report(&included)) {
+                Some(output) => output.map(|text| println!("\n{}", text)),
+                None => Ok(()),
+            }
+        }
+        // Loads the ledger again for every message
+        Command::Bot => bot::serve(&app.settings.bot),
+        // Started above
+        Command::Tui => Ok(()),
+    }
+}
+
+impl FinanceApp {
+    // A named category has to exist; without one it is guessed from the description
+    fn cli_transaction(
+        &self,
+        description: String,
+        amount: f64,
+        income: bool,
+        category: Option<String>,
+        date: NaiveDate,
+        note: String,
+    ) -> Result<Transaction, String> {
+        let (trans_type, category, subcategory) = match category {
+            Some(name) => {
+                let (name, sub) = match name.split_once('/') {
+                    Some((name, sub)) => (name.trim(), Some(sub.trim())),
+                    None => (name.trim(), None),
+                };
+                let found = self.categories.iter()
+                    .find(|c| c.name.eq_ignore_ascii_case(name))
+                    .ok_or_else(|| t!("cli-unknown-category", name = name))?;
+                let subcategory = match sub {
+                    Some(sub) => Some(found.subcategories.iter()
+                        .find(|s| s.eq_ignore_ascii_case(sub))
+                        .cloned()
+                        .ok_or_else(|| t!("cli-unknown-category", name = format!("{}/{}", found.name, sub)))?),
+                    None => None,
+                };
+                let trans_type = if income || found.kind == CategoryKind::Income {
+                    TransactionType::Income
+                } else {
+                    TransactionType::Expense
+                };
+                (trans_type, found.name.clone(), subcategory)
+            }
+            None => self.guess_category(&QuickEntry {
+                description: description.clone(),
+                amount,
+                trans_type: income.then_some(TransactionType::Income),
+                date,
+            }),
+        };
+        Ok(Transaction {
+            id: Uuid::new_v4(),
+            description,
+            amount,
+            trans_type,
+            category,
+            subcategory,
+            currency: None,
+            splits: Vec::new(),
+            note,
+            attachments: Vec::new(),
+            goal: None,
+            status: TransactionStatus::default(),
+            refund_of: None,
+            date: local_from_naive(date.and_time(Local::now().time())),
+            modified: Some(Local::now()),
+        })
+    }
+}
@@ -0,0 +1,206 @@
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::error::Problem;
+use crate::storage;
+use crate::settings::Settings;
+
+// A named ledger file (personal, business, ...). Categories and settings live in the
+// ledger itself, so each profile has its own.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Profile {
+    pub name: String,
+    pub data_file: PathBuf,
+    // Shared ledger in a synced folder; differs per device so it isn't kept in the ledger
+    #[serde(default)]
+    pub sync_file: Option<PathBuf>,
+}
+
+// Per-user settings kept outside the ledger, in config.toml in the OS config folder, so
+// they are found no matter which directory the app is started from and can be edited
+// by hand
+#[derive(Serialize, Deserialize, Default)]
+pub struct AppConfig {
+    #[serde(default)]
+    pub last_data_file: Option<PathBuf>,
+    #[serde(default)]
+    pub profiles: Vec<Profile>,
+    #[serde(default)]
+    pub settings: Settings,
+    // Sync file from before profiles, moved onto the first profile on load
+    #[serde(default, skip_serializing)]
+    sync_file: Option<PathBuf>,
+}
+
+fn absolute(path: &Path) -> PathBuf {
+    fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf())
+}
+
+fn same_file(a: &Path, b: &Path) -> bool {
+    absolute(a) == absolute(b)
+}
+
+impl AppConfig {
+    fn path() -> Option<PathBuf> {
+        config_file()
+    }
+
+    // Where earlier versions kept it, read until the first save writes config.toml
+    fn json_path() -> Option<PathBuf> {
+        dirs::config_dir().map(|dir| dir.join("finance_tracker").join("config.json"))
+    }
+
+    // Defaults when it can't be read, for the places that only look
+    pub fn load() -> Self {
+        Self::read().unwrap_or_default()
+    }
+
+    // Defaults when there is no config yet, an error when there is one that can't be read
+    pub fn read() -> Result<Self, Problem> {
+        let (Some(path), Some(json_path)) = (Self::path(), Self::json_path()) else { return Ok(Self::default()) };
+        let mut config: Self = match fs::read_to_string(&path) {
+            Ok(text) => toml::from_str(&text).map_err(|e| Problem::Other(format!("{}: {}", path.display(), e)))?,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => match fs::read(&json_path) {
+                Ok(bytes) => serde_json::from_slice(&bytes).map_err(|e| Problem::Other(format!("{}: {}", json_path.display(), e)))?,
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => Self::default(),
+                Err(e) => return Err(Problem::Other(format!("{}: {}", json_path.display(), e))),
+            },
+            Err(e) => return Err(Problem::Other(format!("{}: {}", path.display(), e))),
+        };
+
+        // The file in use before profiles existed becomes the first one
+        if config.profiles.is_empty() {
+            if let Some(data_file) = config.last_data_file.clone() {
+                config.profiles.push(Profile {
+                    name: "Personal".to_string(),
+                    data_file,
+                    sync_file: config.sync_file.take(),
+                });
+            }
+        }
+        Ok(config)
+    }
+
+    pub fn save(&self) -> Result<(), Problem> {
+        let path = Self::path().ok_or(Problem::NoConfigFolder)?;
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+        }
+        // Through a Value, which puts plain keys ahead of tables as TOML needs
+        let text = toml::Value::try_from(self)
+            .and_then(|value| toml::to_string(&value))
+            .map_err(|e| e.to_string())?;
+        Ok(storage::write_private(&path, text.as_bytes()).map_err(|e| e.to_string())?)
+    }
+
+    pub fn profile_for(&self, data_file: &Path) -> Option<&Profile> {
+        self.profiles.iter().find(|p| same_file(&p.data_file, data_file))
+    }
+
+    fn profile_for_mut(&mut self, data_file: &Path) -> Option<&mut Profile> {
+        self.profiles.iter_mut().find(|p| same_file(&p.data_file, data_file))
+    }
+}
+
+pub fn config_file() -> Option<PathBuf> {
+    dirs::config_dir().map(|dir| dir.join("finance_tracker").join("config.toml"))
+}
+
+// Script with the user's hooks, see scripting.rs. Per user like the settings.
+pub fn hooks_file() -> Option<PathBuf> {
+    dirs::config_dir().map(|dir| dir.join("finance_tracker").join("hooks.rhai"))
+}
+
+// `--data-file <path>` or `--data-file=<path>`
+pub fn data_file_from_args() -> Option<PathBuf> {
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        if arg == "--data-file" {
+            return args.next().map(PathBuf::from);
+        }
+        if let Some(value) = arg.strip_prefix("--data-file=") {
+            return Some(PathBuf::from(value));
+        }
+    }
+    None
+}
+
+// Command line first, then the remembered file, then the old default in the working directory
+pub fn resolve_data_file() -> PathBuf {
+    data_file_from_args()
+        .or_else(|| AppConfig::load().last_data_file)
+        .unwrap_or_else(|| PathBuf::from(storage::DATA_FILE))
+}
+
+// Remembers the file for the next start. A file that isn't a profile yet becomes one,
+// named after the file.
+pub fn remember_data_file(path: &Path) -> Result<(), Problem> {
+    let mut config = AppConfig::read()?;
+    config.last_data_file = Some(absolute(path));
+    if config.profile_for(path).is_none() {
+        let name = path.file_stem()
+            .map(|s| s.to_string_lossy().to_string())
+            .unwrap_or_else(|| "Ledger".to_string());
+        config.profiles.push(Profile { name, data_file: absolute(path), sync_file: None });
+    }
+    config.save()
+}
+
+pub fn remember_sync_file(data_file: &Path, sync_file: &Path) -> Result<(), Problem> {
+    let mut config = AppConfig::read()?;
+    if config.profile_for(data_file).is_none() {
+        remember_data_file(data_file)?;
+        config = AppConfig::read()?;
+    }
+    if let Some(profile) = config.profile_for_mut(data_file) {
+        profile.sync_file = Some(sync_file.to_path_buf());
+    }
+    config.save()
+}
+
+pub fn save_settings(settings: &Settings) -> Result<(), Problem> {
+    let mut config = AppConfig::read()?;
+    config.settings = settings.clone();
+    config.save()
+}
+
+pub fn add_profile(name: &str, data_file: &Path) -> Result<(), Problem> {
+    let mut config = AppConfig::read()?;
+    if config.profiles.iter().any(|p| p.name == name) {
+        return Err(Problem::ProfileNameTaken(name.to_string()));
+    }
+    if config.profile_for(data_file).is_some() {
+        return Err(Problem::ProfileFileTaken);
+    }
+    config.profiles.push(Profile { name: name.to_string(), data_file: absolute(data_file), sync_file: None });
+    config.save()
+}
+
+pub fn rename_profile(data_file: &Path, name: &str) -> Result<(), Problem> {
+    let mut config = AppConfig::read()?;
+    if config.profiles.iter().any(|p| p.name == name) {
+        return Err(Problem::ProfileNameTaken(name.to_string()));
+    }
+    if let Some(profile) = config.profile_for_mut(data_file) {
+        profile.name = name.to_string();
+    }
+    config.save()
+}
+
+// Forgets the profile; its ledger file is left on disk
+pub fn remove_profile(data_file: &Path) -> Result<(), Problem> {
+    let mut config = AppConfig::read()?;
+    config.profiles.retain(|p| !same_file(&p.data_file, data_file));
+    config.save()
+}
+
+// Points the profile using `old` at its new location after "Save as"
+pub fn move_profile(old: &Path, new: &Path) -> Result<(), Problem> {
+    let mut config = AppConfig::read()?;
+    if let Some(profile) = config.profile_for_mut(old) {
+        profile.data_file = absolute(new);
+    }
+    config.save()?;
+    remember_data_file(new)
+}
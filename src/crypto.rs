@@ -0,0 +1,119 @@
+use age::secrecy::{ExposeSecret, SecretString};
+use std::io::{Read, Write};
+use std::iter;
+use std::path::Path;
+
+use crate::error::Problem;
+use crate::storage;
+
+// Every age file starts with this line, which is how encrypted ledgers are recognized
+const AGE_HEADER: &[u8] = b"age-encryption.org/v1";
+
+// scrypt cost. age picks ~1s by default, far too slow when every edit saves.
+const WORK_FACTOR: u8 = 15;
+
+pub fn is_encrypted(bytes: &[u8]) -> bool {
+    bytes.starts_with(AGE_HEADER)
+}
+
+pub fn encrypt(plaintext: &[u8], passphrase: &str) -> Result<Vec<u8>, String> {
+    let mut recipient = age::scrypt::Recipient::new(SecretString::from(passphrase.to_string()));
+    recipient.set_work_factor(WORK_FACTOR);
+    let encryptor = age::Encryptor::with_recipients(iter::once(&recipient as &dyn age::Recipient))
+        .map_err(|e| e.to_string())?;
+
+    let mut out = Vec::new();
+    let mut writer = encryptor.wrap_output(&mut out).map_err(|e| e.to_string())?;
+    writer.write_all(plaintext).map_err(|e| e.to_string())?;
+    writer.finish().map_err(|e| e.to_string())?;
+    Ok(out)
+}
+
+pub fn decrypt(ciphertext: &[u8], passphrase: &str) -> Result<Vec<u8>, Problem> {
+    let decryptor = age::Decryptor::new(ciphertext).map_err(|e| e.to_string())?;
+    let identity = age::scrypt::Identity::new(SecretString::from(passphrase.to_string()));
+    let mut reader = decryptor.decrypt(iter::once(&identity as &dyn age::Identity))
+        .map_err(|e| match e {
+            age::DecryptError::DecryptionFailed | age::DecryptError::NoMatchingKeys => Problem::WrongPassphrase,
+            other => Problem::Other(other.to_string()),
+        })?;
+
+    let mut plaintext = Vec::new();
+    reader.read_to_end(&mut plaintext).map_err(|e| e.to_string())?;
+    Ok(plaintext)
+}
+
+// Key for the audit journal and write-ahead log of an encrypted ledger. Those are
+// appended a save at a time and read line by line, and the passphrase's scrypt is far
+// too slow to pay per line, so each ledger gets its own X25519 key instead. The key is
+// kept next to the ledger, encrypted with the passphrase.
+#[derive(Clone)]
+pub struct LineKey(age::x25519::Identity);
+
+impl LineKey {
+    pub fn generate() -> Self {
+        Self(age::x25519::Identity::generate())
+    }
+
+    // None when there's no key file yet
+    pub fn load(path: &Path, passphrase: &str) -> Result<Option<Self>, String> {
+        let bytes = match std::fs::read(path) {
+            Ok(bytes) => bytes,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(e.to_string()),
+        };
+        let text = String::from_utf8(decrypt(&bytes, passphrase).map_err(|e| e.to_string())?).map_err(|e| e.to_string())?;
+        text.trim().parse().map(|identity| Some(Self(identity))).map_err(|e: &str| e.to_string())
+    }
+
+    pub fn save(&self, path: &Path, passphrase: &str) -> Result<(), String> {
+        let encrypted = encrypt(self.0.to_string().expose_secret().as_bytes(), passphrase)?;
+        storage::write_atomic(path, &encrypted).map_err(|e| e.to_string())
+    }
+
+    // One line of text, hex so it can't be mistaken for a JSON one
+    pub fn seal(&self, plaintext: &[u8]) -> Result<String, String> {
+        let recipient = self.0.to_public();
+        let encryptor = age::Encryptor::with_recipients(iter::once(&recipient as &dyn age::Recipient))
+            .map_err(|e| e.to_string())?;
+        let mut out = Vec::new();
+        let mut writer = encryptor.wrap_output(&mut out).map_err(|e| e.to_string())?;
+        writer.write_all(plaintext).map_err(|e| e.to_string())?;
+        writer.finish().map_err(|e| e.to_string())?;
+        Ok(out.iter().map(|b| format!("{:02x}", b)).collect())
+    }
+
+    pub fn open(&self, line: &str) -> Result<Vec<u8>, String> {
+        let bytes = (0..line.len())
+            .step_by(2)
+            .map(|i| line.get(i..i + 2).and_then(|pair| u8::from_str_radix(pair, 16).ok()))
+            .collect::<Option<Vec<u8>>>()
+            .ok_or("not an encrypted line")?;
+        let decryptor = age::Decryptor::new(&bytes[..]).map_err(|e| e.to_string())?;
+        let mut reader = decryptor.decrypt(iter::once(&self.0 as &dyn age::Identity)).map_err(|e| e.to_string())?;
+        let mut plaintext = Vec::new();
+        reader.read_to_end(&mut plaintext).map_err(|e| e.to_string())?;
+        Ok(plaintext)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn line_key_only_opens_with_its_passphrase() {
+        let dir = std::env::temp_dir().join(format!("line-key-test-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("ledger-audit.key");
+        assert!(LineKey::load(&path, "secret").unwrap().is_none());
+
+        let key = LineKey::generate();
+        key.save(&path, "secret").unwrap();
+        let sealed = key.seal(b"{\"amount\":4.5}").unwrap();
+        assert_eq!(LineKey::load(&path, "wrong").err(), Some(Problem::WrongPassphrase.to_string()));
+        let loaded = LineKey::load(&path, "secret").unwrap().unwrap();
+        assert_eq!(loaded.open(&sealed).unwrap(), b"{\"amount\":4.5}");
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
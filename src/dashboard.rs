@@ -0,0 +1,78 @@
+use serde::{Deserialize, Serialize};
+
+use crate::i18n::t;
+
+// The boxes on the Dashboard tab. Which ones show and in what order is kept in the
+// settings.
+#[derive(Clone, Copy, PartialEq, Debug, Serialize, Deserialize)]
+pub enum Widget {
+    Balance,
+    ThisMonth,
+    Budgets,
+    UpcomingBills,
+    BalanceChart,
+    Recent,
+}
+
+impl Widget {
+    pub const ALL: [Widget; 6] = [
+        Widget::Balance,
+        Widget::ThisMonth,
+        Widget::Budgets,
+        Widget::UpcomingBills,
+        Widget::BalanceChart,
+        Widget::Recent,
+    ];
+
+    pub fn label(&self) -> String {
+        match self {
+            Widget::Balance => t!("widget-balance"),
+            Widget::ThisMonth => t!("widget-this-month"),
+            Widget::Budgets => t!("widget-budgets"),
+            Widget::UpcomingBills => t!("widget-upcoming"),
+            Widget::BalanceChart => t!("widget-balance-chart"),
+            Widget::Recent => t!("widget-recent"),
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct Layout {
+    // Top to bottom, and whether each is shown
+    pub widgets: Vec<(Widget, bool)>,
+}
+
+impl Default for Layout {
+    fn default() -> Self {
+        Self { widgets: Widget::ALL.iter().map(|w| (*w, true)).collect() }
+    }
+}
+
+impl Layout {
+    // Shown widgets, in order. Ones added in a later version than the settings were
+    // saved with go at the end.
+    pub fn shown(&self) -> Vec<Widget> {
+        let missing = Widget::ALL.into_iter().filter(|w| !self.widgets.iter().any(|(saved, _)| saved == w));
+        self.widgets.iter()
+            .filter(|(_, shown)| *shown)
+            .map(|(widget, _)| *widget)
+            .chain(missing)
+            .collect()
+    }
+
+    // Every widget, saved ones first, so the customize list has them all
+    pub fn complete(&mut self) {
+        for widget in Widget::ALL {
+            if !self.widgets.iter().any(|(saved, _)| *saved == widget) {
+                self.widgets.push((widget, true));
+            }
+        }
+    }
+
+    // Swaps with the one above; nothing happens for the first
+    pub fn move_up(&mut self, index: usize) {
+        if index > 0 && index < self.widgets.len() {
+            self.widgets.swap(index - 1, index);
+        }
+    }
+}
@@ -0,0 +1,73 @@
+use chrono::{Duration, Local, NaiveDate};
+use uuid::Uuid;
+
+use crate::{local_from_naive, Transaction, TransactionStatus, TransactionType};
+
+// Three months of made-up activity for trying the app out on an empty ledger. Every
+// entry is noted as demo data, so searching "demo" finds them all for deleting.
+
+// (every how many days, first one how many days ago, description, category, amount, income)
+const RECURRING: [(i64, i64, &str, &str, f64, bool); 7] = [
+    (30, 2, "Salary", "Salary", 3200.0, true),
+    (30, 1, "Rent", "Housing", 1200.0, false),
+    (30, 9, "Electricity bill", "Utilities", 85.0, false),
+    (30, 12, "Internet", "Utilities", 45.0, false),
+    (7, 3, "Groceries", "Food", 72.0, false),
+    (7, 5, "Fuel", "Transport", 40.0, false),
+    (3, 0, "Coffee", "Food", 4.5, false),
+];
+
+// (days ago, description, category, amount, income)
+const ONE_OFF: [(i64, &str, &str, f64, bool); 9] = [
+    (10, "Cinema", "Entertainment", 24.0, false),
+    (40, "Concert tickets", "Entertainment", 65.0, false),
+    (20, "Pharmacy", "Health", 18.4, false),
+    (55, "Dentist", "Health", 120.0, false),
+    (33, "Programming book", "Education", 35.0, false),
+    (15, "Running shoes", "Shopping", 79.9, false),
+    (25, "Birthday gift from grandma", "Gifts", 50.0, true),
+    (50, "Dividend", "Investments", 42.0, true),
+    (70, "Freelance website", "Business", 600.0, true),
+];
+
+const DAYS: i64 = 90;
+
+pub fn sample_transactions(today: NaiveDate, note: &str) -> Vec<Transaction> {
+    let mut samples = Vec::new();
+    for (every, first, description, category, amount, income) in RECURRING {
+        let mut ago = first;
+        let mut index = 0;
+        while ago < DAYS {
+            // Vary the everyday spending a little so the charts aren't flat
+            let amount = if every < 30 { amount * (1.0 + ((index * 7) % 5) as f64 * 0.08) } else { amount };
+            samples.push((ago, description, category, (amount * 100.0).round() / 100.0, income));
+            ago += every;
+            index += 1;
+        }
+    }
+    samples.extend(ONE_OFF);
+
+    samples.into_iter()
+        .map(|(ago, description, category, amount, income)| {
+            let day = today - Duration::days(ago);
+            Transaction {
+                id: Uuid::new_v4(),
+                description: description.to_string(),
+                amount,
+                trans_type: if income { TransactionType::Income } else { TransactionType::Expense },
+                category: category.to_string(),
+                subcategory: None,
+                currency: None,
+                splits: Vec::new(),
+                note: note.to_string(),
+                attachments: Vec::new(),
+                goal: None,
+                // The last week is still waiting to clear at the bank
+                status: if ago > 7 { TransactionStatus::Cleared } else { TransactionStatus::Pending },
+                refund_of: None,
+                date: local_from_naive(day.and_hms_opt(12, 0, 0).unwrap_or_default()),
+                modified: Some(Local::now()),
+            }
+        })
+        .collect()
+}
@@ -0,0 +1,105 @@
+use crate::i18n::t;
+
+// Problems that used to be dropped without a word. Shown in a banner above the current
+// tab, saying what went wrong and what to do about it, until dismissed or fixed.
+#[derive(Clone, PartialEq, Debug)]
+pub enum AppError {
+    // Text in a number field that isn't a number the field takes
+    BadAmount { field: String, value: String },
+    MissingDescription,
+    // The split amounts don't add up to the total, or one isn't a number
+    SplitsDontAddUp,
+    // config.toml is there but can't be read. It is left alone instead of being
+    // replaced with defaults, so settings and profiles aren't lost.
+    Config(Problem),
+}
+
+impl AppError {
+    pub fn message(&self) -> String {
+        match self {
+            AppError::BadAmount { field, value } => t!("error-bad-amount", field = field.clone(), value = value.clone()),
+            AppError::MissingDescription => t!("error-missing-description"),
+            AppError::SplitsDontAddUp => t!("error-splits"),
+            AppError::Config(error) => t!("error-config", error = error.to_string()),
+        }
+    }
+
+    pub fn advice(&self) -> String {
+        match self {
+            AppError::BadAmount { .. } => t!("error-bad-amount-advice"),
+            AppError::MissingDescription => t!("error-missing-description-advice"),
+            AppError::SplitsDontAddUp => t!("error-splits-advice"),
+            AppError::Config(_) => t!("error-config-advice"),
+        }
+    }
+}
+
+// Why something couldn't be done, worded only when it's shown: translations are per
+// thread (see i18n.rs), and much of this comes from threads of their own or from before
+// the UI language is known.
+#[derive(Clone, PartialEq, Debug)]
+pub enum Problem {
+    // From the OS, a library or a server, shown as it is
+    Other(String),
+    // A background job's thread went away without an answer
+    Stopped,
+    WrongPassphrase,
+    NewerFormat { version: u32, supported: u32 },
+    NotALedger,
+    NotAList(&'static str),
+    NoConfigFolder,
+    ProfileNameTaken(String),
+    ProfileFileTaken,
+    MissingColumn { format: &'static str, column: &'static str },
+    NoPrice(String),
+    // Desktop notifications, which only Linux has so far
+    #[cfg(not(target_os = "linux"))]
+    NotSupported,
+    TesseractMissing,
+    #[cfg(feature = "sheets")]
+    TokenNotSigned,
+    #[cfg(feature = "sheets")]
+    PrivateKey(String),
+    #[cfg(feature = "sheets")]
+    BadSpreadsheet,
+}
+
+impl Problem {
+    pub fn message(&self) -> String {
+        match self {
+            Problem::Other(text) => text.clone(),
+            Problem::Stopped => t!("problem-stopped"),
+            Problem::WrongPassphrase => t!("problem-wrong-passphrase"),
+            Problem::NewerFormat { version, supported } => t!("problem-newer-format", version = *version, supported = *supported),
+            Problem::NotALedger => t!("problem-not-a-ledger"),
+            Problem::NotAList(field) => t!("problem-not-a-list", field = *field),
+            Problem::NoConfigFolder => t!("problem-no-config-folder"),
+            Problem::ProfileNameTaken(name) => t!("problem-profile-name-taken", name = name.clone()),
+            Problem::ProfileFileTaken => t!("problem-profile-file-taken"),
+            Problem::MissingColumn { format, column } => t!("problem-missing-column", format = *format, column = *column),
+            Problem::NoPrice(pointer) => t!("problem-no-price", pointer = pointer.clone()),
+            #[cfg(not(target_os = "linux"))]
+            Problem::NotSupported => t!("problem-not-supported"),
+            Problem::TesseractMissing => t!("problem-tesseract-missing"),
+            #[cfg(feature = "sheets")]
+            Problem::TokenNotSigned => t!("problem-token-not-signed"),
+            #[cfg(feature = "sheets")]
+            Problem::PrivateKey(error) => t!("problem-private-key", error = error.clone()),
+            #[cfg(feature = "sheets")]
+            Problem::BadSpreadsheet => t!("problem-bad-spreadsheet"),
+        }
+    }
+}
+
+impl std::fmt::Display for Problem {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.write_str(&self.message())
+    }
+}
+
+// So `?` works on the many calls that fail with a plain message
+impl From<String> for Problem {
+    fn from(text: String) -> Self {
+        Problem::Other(text)
+    }
+}
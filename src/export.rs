@@ -0,0 +1,222 @@
+use chrono::{Datelike, NaiveDate};
+use rust_xlsxwriter::{Color, ExcelDateTime, Format, FormatAlign, FormatBorder, Workbook, Worksheet, XlsxError};
+use std::path::{Path, PathBuf};
+
+use crate::i18n::t;
+
+// What goes into the workbook, already converted to the home currency
+pub struct Report {
+    pub currency: String,
+    pub rows: Vec<ReportRow>,
+    // (year, month, income, expenses), oldest first
+    pub months: Vec<(i32, u32, f64, f64)>,
+    // (category, net spending), largest first
+    pub categories: Vec<(String, f64)>,
+}
+
+pub struct ReportRow {
+    pub date: NaiveDate,
+    pub description: String,
+    pub category: String,
+    pub kind: &'static str,
+    pub status: &'static str,
+    // Negative for expenses
+    pub amount: f64,
+    // Original amount and currency, for foreign-currency transactions
+    pub original: Option<(f64, String)>,
+    pub note: String,
+}
+
+// Column titles shared by every export, in the app's language
+pub fn transaction_headers(currency: &str) -> [String; 8] {
+    [
+        t!("column-date"),
+        t!("column-description"),
+        t!("column-category"),
+        t!("column-type"),
+        t!("column-status"),
+        t!("column-amount-in", currency = currency.to_string()),
+        t!("column-original-amount"),
+        t!("column-note"),
+    ]
+}
+
+pub fn monthly_headers() -> [String; 4] {
+    [t!("column-month"), t!("column-income"), t!("column-expenses"), t!("column-net")]
+}
+
+pub fn pick_xlsx_target() -> Option<PathBuf> {
+    pick_target("Excel workbook", "xlsx", "finances.xlsx")
+}
+
+pub fn write_xlsx(path: &Path, report: &Report) -> Result<(), XlsxError> {
+    let header = Format::new()
+        .set_bold()
+        .set_background_color(Color::RGB(0xD9E1F2))
+        .set_border_bottom(FormatBorder::Thin);
+    let money_format = format!("#,##0.00 \"{}\";[Red]-#,##0.00 \"{}\"", report.currency, report.currency);
+    let money = Format::new().set_num_format(&money_format);
+    let percent = Format::new().set_num_format("0.0%");
+    let date = Format::new().set_num_format("yyyy-mm-dd").set_align(FormatAlign::Left);
+    let total_money = money.clone().set_bold().set_border_top(FormatBorder::Thin);
+    let total_label = Format::new().set_bold().set_border_top(FormatBorder::Thin);
+
+    let mut workbook = Workbook::new();
+
+    // 1. Every transaction
+    let sheet = workbook.add_worksheet().set_name("Transactions")?;
+    write_header(sheet, &transaction_headers(&report.currency), &header)?;
+    for (i, row) in report.rows.iter().enumerate() {
+        let r = i as u32 + 1;
+        let day = ExcelDateTime::from_ymd(row.date.year() as u16, row.date.month() as u8, row.date.day() as u8)?;
+        sheet.write_datetime_with_format(r, 0, &day, &date)?;
+        sheet.write_string(r, 1, &row.description)?;
+        sheet.write_string(r, 2, &row.category)?;
+        sheet.write_string(r, 3, row.kind)?;
+        sheet.write_string(r, 4, row.status)?;
+        sheet.write_number_with_format(r, 5, row.amount, &money)?;
+        if let Some((amount, code)) = &row.original {
+            sheet.write_string(r, 6, format!("{:.2} {}", amount, code))?;
+        }
+        sheet.write_string(r, 7, &row.note)?;
+    }
+    let last = report.rows.len() as u32;
+    sheet.autofilter(0, 0, last, 7)?;
+    sheet.set_freeze_panes(1, 0)?;
+    set_widths(sheet, &[12.0, 32.0, 24.0, 10.0, 12.0, 16.0, 16.0, 40.0])?;
+
+    // 2. Income and expenses per month
+    let sheet = workbook.add_worksheet().set_name("Monthly Summary")?;
+    write_header(sheet, &monthly_headers(), &header)?;
+    for (i, (year, month, income, expenses)) in report.months.iter().enumerate() {
+        let r = i as u32 + 1;
+        sheet.write_string(r, 0, format!("{}-{:02}", year, month))?;
+        sheet.write_number_with_format(r, 1, *income, &money)?;
+        sheet.write_number_with_format(r, 2, *expenses, &money)?;
+        sheet.write_formula_with_format(r, 3, format!("=B{}-C{}", r + 1, r + 1).as_str(), &money)?;
+    }
+    let total_row = report.months.len() as u32 + 1;
+    sheet.write_string_with_format(total_row, 0, "Total", &total_label)?;
+    for (col, letter) in [(1, 'B'), (2, 'C'), (3, 'D')] {
+        let formula = format!("=SUM({}2:{}{})", letter, letter, total_row);
+        sheet.write_formula_with_format(total_row, col, formula.as_str(), &total_money)?;
+    }
+    sheet.set_freeze_panes(1, 0)?;
+    set_widths(sheet, &[12.0, 16.0, 16.0, 16.0])?;
+
+    // 3. Net spending per category
+    let sheet = workbook.add_worksheet().set_name("Categories")?;
+    write_header(sheet, &["Category", "Spent", "Share"], &header)?;
+    let total_row = report.categories.len() as u32 + 1;
+    for (i, (category, amount)) in report.categories.iter().enumerate() {
+        let r = i as u32 + 1;
+        sheet.write_string(r, 0, category)?;
+        sheet.write_number_with_format(r, 1, *amount, &money)?;
+        let formula = format!("=IF(B{}=0,0,B{}/B{})", total_row + 1, r + 1, total_row + 1);
+        sheet.write_formula_with_format(r, 2, formula.as_str(), &percent)?;
+    }
+    sheet.write_string_with_format(total_row, 0, "Total", &total_label)?;
+    let formula = format!("=SUM(B2:B{})", total_row);
+    sheet.write_formula_with_format(total_row, 1, formula.as_str(), &total_money)?;
+    set_widths(sheet, &[24.0, 16.0, 10.0])?;
+
+    workbook.save(path)
+}
+
+fn write_header(sheet: &mut Worksheet, titles: &[impl AsRef<str>], format: &Format) -> Result<(), XlsxError> {
+    for (col, title) in titles.iter().enumerate() {
+        sheet.write_string_with_format(0, col as u16, title.as_ref(), format)?;
+    }
+    Ok(())
+}
+
+fn set_widths(sheet: &mut Worksheet, widths: &[f64]) -> Result<(), XlsxError> {
+    for (col, width) in widths.iter().enumerate() {
+        sheet.set_column_width(col as u16, *width)?;
+    }
+    Ok(())
+}
+
+pub fn pick_target(filter_name: &str, extension: &str, file_name: &str) -> Option<PathBuf> {
+    rfd::FileDialog::new()
+        .add_filter(filter_name, &[extension])
+        .set_file_name(file_name)
+        .save_file()
+}
+
+// Same columns as the Transactions sheet, amounts with a plain '.' decimal point
+pub fn write_csv(path: &Path, report: &Report) -> Result<(), String> {
+    let file = std::fs::File::create(path).map_err(|e| e.to_string())?;
+    write_csv_to(file, report)
+}
+
+pub fn write_csv_to(out: impl std::io::Write, report: &Report) -> Result<(), String> {
+    let mut writer = csv::Writer::from_writer(out);
+    writer.write_record(transaction_headers(&report.currency))
+        .map_err(|e| e.to_string())?;
+    for row in &report.rows {
+        let original = row.original.as_ref()
+            .map(|(amount, code)| format!("{:.2} {}", amount, code))
+            .unwrap_or_default();
+        writer.write_record([
+            row.date.format("%Y-%m-%d").to_string(),
+            row.description.clone(),
+            row.category.clone(),
+            row.kind.to_string(),
+            row.status.to_string(),
+            format!("{:.2}", row.amount),
+            original,
+            row.note.clone(),
+        ]).map_err(|e| e.to_string())?;
+    }
+    writer.flush().map_err(|e| e.to_string())
+}
+
+// The transactions exactly as they are stored, for loading into other tools
+pub fn write_json<T: serde::Serialize>(path: &Path, data: &T) -> Result<(), String> {
+    let bytes = serde_json::to_vec_pretty(data).map_err(|e| e.to_string())?;
+    std::fs::write(path, bytes).map_err(|e| e.to_string())
+}
+
+// Same columns again as tab-separated text for the clipboard, which spreadsheets and
+// chat apps paste as a table. Numbers use `decimal` so they paste as numbers in
+// spreadsheets set to that locale.
+pub fn to_tsv(report: &Report, decimal: char) -> String {
+    let number = |value: f64| format!("{:.2}", value).replace('.', &decimal.to_string());
+    // A tab or line break inside a field would start a new cell
+    let clean = |text: &str| text.replace(['\t', '\r', '\n'], " ");
+    let mut text = transaction_headers(&report.currency).map(|title| clean(&title)).join("\t");
+    text.push('\n');
+    for row in &report.rows {
+        let original = row.original.as_ref()
+            .map(|(amount, code)| format!("{} {}", number(*amount), code))
+            .unwrap_or_default();
+        let fields = [
+            row.date.format("%Y-%m-%d").to_string(),
+            clean(&row.description),
+            clean(&row.category),
+            row.kind.to_string(),
+            row.status.to_string(),
+            number(row.amount),
+            original,
+            clean(&row.note),
+        ];
+        text.push_str(&fields.join("\t"));
+        text.push('\n');
+    }
+    text
+}
+
+// A screenshot of a chart
+pub fn write_png(path: &Path, image: &eframe::egui::ColorImage) -> Result<(), String> {
+    let bytes: Vec<u8> = image.pixels.iter().flat_map(|pixel| pixel.to_array()).collect();
+    image::save_buffer_with_format(
+        path,
+        &bytes,
+        image.size[0] as u32,
+        image.size[1] as u32,
+        image::ExtendedColorType::Rgba8,
+        image::ImageFormat::Png,
+    )
+    .map_err(|e| e.to_string())
+}
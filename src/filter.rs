@@ -0,0 +1,64 @@
+use chrono::{Datelike, NaiveDate};
+use std::collections::BTreeSet;
+
+use crate::{Transaction, TransactionType};
+
+// Sidebar filter shared by the transaction list and the analytics
+#[derive(Default, Clone, PartialEq)]
+pub struct Filter {
+    pub from: Option<NaiveDate>,
+    pub to: Option<NaiveDate>,
+    // Empty means every category
+    pub categories: BTreeSet<String>,
+    pub trans_type: Option<TransactionType>,
+    pub min_amount: String,
+    pub max_amount: String,
+}
+
+impl Filter {
+    pub fn is_active(&self) -> bool {
+        self.from.is_some()
+            || self.to.is_some()
+            || !self.categories.is_empty()
+            || self.trans_type.is_some()
+            || self.min().is_some()
+            || self.max().is_some()
+    }
+
+    fn min(&self) -> Option<f64> {
+        self.min_amount.trim().replace(',', ".").parse().ok()
+    }
+
+    fn max(&self) -> Option<f64> {
+        self.max_amount.trim().replace(',', ".").parse().ok()
+    }
+
+    // `home_amount` is the transaction's amount in the home currency, which the
+    // min/max bounds are compared against
+    pub fn matches(&self, t: &Transaction, home_amount: f64) -> bool {
+        let day = t.date.date_naive();
+        if self.from.is_some_and(|from| day < from) || self.to.is_some_and(|to| day > to) {
+            return false;
+        }
+        if self.trans_type.is_some_and(|kind| kind != t.trans_type) {
+            return false;
+        }
+        if self.min().is_some_and(|min| home_amount < min) || self.max().is_some_and(|max| home_amount > max) {
+            return false;
+        }
+        // A split transaction matches if any part is in a selected category
+        self.categories.is_empty()
+            || t.category_shares().iter().any(|(category, _, _)| self.categories.contains(*category))
+    }
+
+    // Weeks and months follow the first-day settings, see settings.rs
+    pub fn set_range(&mut self, from: NaiveDate, to: NaiveDate) {
+        self.from = Some(from);
+        self.to = Some(to);
+    }
+
+    pub fn this_year(&mut self, today: NaiveDate) {
+        self.from = NaiveDate::from_ymd_opt(today.year(), 1, 1);
+        self.to = Some(today);
+    }
+}
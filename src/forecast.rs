@@ -0,0 +1,157 @@
+use chrono::{Duration, Months, NaiveDate};
+use std::collections::HashMap;
+
+// Where the balance is heading over the next months: the transactions that keep coming
+// back (same description every week to every month, give or take a few days) on their
+// next dates, plus the average daily net of everything else.
+
+// One transaction, signed and in the home currency
+pub struct Entry<'a> {
+    pub day: NaiveDate,
+    pub description: &'a str,
+    pub amount: f64,
+}
+
+// How far back the everyday average looks
+const HISTORY_DAYS: i64 = 90;
+// Less history than this still counts as this many days, so two days of data don't get
+// stretched over three months
+const MIN_HISTORY_DAYS: i64 = 30;
+
+// A subscription or bill: the same description coming back every `every` days
+pub struct Recurring {
+    key: String,
+    // As it was last written
+    pub description: String,
+    pub every: i64,
+    pub last: NaiveDate,
+    pub amount: f64,
+}
+
+impl Recurring {
+    pub fn monthly(&self) -> bool {
+        (27..=32).contains(&self.every)
+    }
+
+    // Occurrence `n` after the last one; monthly ones keep their day of the month
+    fn nth(&self, n: u32) -> Option<NaiveDate> {
+        if self.monthly() {
+            self.last.checked_add_months(Months::new(n))
+        } else {
+            Some(self.last + Duration::days(self.every * n as i64))
+        }
+    }
+
+    // When it is expected next, after today
+    pub fn next_after(&self, today: NaiveDate) -> Option<NaiveDate> {
+        (1..).map_while(|n| self.nth(n)).find(|day| *day > today)
+    }
+
+    // What it comes to in an average month
+    pub fn per_month(&self) -> f64 {
+        if self.monthly() {
+            self.amount
+        } else {
+            self.amount * 365.25 / 12.0 / self.every as f64
+        }
+    }
+
+    pub fn matches(&self, description: &str) -> bool {
+        key(description) == self.key
+    }
+}
+
+fn key(description: &str) -> String {
+    description.trim().to_lowercase()
+}
+
+// At least three times, at steady intervals between a week and a month, and not
+// overdue by more than half an interval
+pub fn find_recurring(past: &[Entry], today: NaiveDate) -> Vec<Recurring> {
+    let mut groups: HashMap<String, Vec<&Entry>> = HashMap::new();
+    for entry in past {
+        groups.entry(key(entry.description)).or_default().push(entry);
+    }
+
+    let mut found = Vec::new();
+    for (key, mut entries) in groups {
+        if entries.len() < 3 {
+            continue;
+        }
+        entries.sort_by_key(|e| e.day);
+        let mut gaps: Vec<i64> = entries.windows(2).map(|w| (w[1].day - w[0].day).num_days()).collect();
+        gaps.sort_unstable();
+        let every = gaps[gaps.len() / 2];
+        let slack = (every / 5).max(3);
+        if !(6..=35).contains(&every) || gaps.iter().any(|gap| (gap - every).abs() > slack) {
+            continue;
+        }
+        let last = entries[entries.len() - 1];
+        if (today - last.day).num_days() > every + every / 2 {
+            continue;
+        }
+        found.push(Recurring {
+            key,
+            description: last.description.trim().to_string(),
+            every,
+            last: last.day,
+            amount: last.amount,
+        });
+    }
+    found
+}
+
+pub struct Forecast {
+    // Balance at the end of each day after today, up to and including `until`
+    pub points: Vec<(NaiveDate, f64)>,
+    // First day the balance drops below zero, if it starts at or above it
+    pub zero_on: Option<NaiveDate>,
+}
+
+// `past` is everything up to today, `scheduled` the future-dated transactions already
+// entered. Those replace a recurring one expected around the same day.
+pub fn project(past: &[Entry], scheduled: &[Entry], today: NaiveDate, balance: f64, until: NaiveDate) -> Forecast {
+    let recurring = find_recurring(past, today);
+
+    let since = today - Duration::days(HISTORY_DAYS);
+    let first = past.iter().map(|e| e.day).min().unwrap_or(today);
+    let days = (today - first.max(since)).num_days().clamp(MIN_HISTORY_DAYS, HISTORY_DAYS);
+    let everyday: f64 = past.iter()
+        .filter(|e| e.day > since && !recurring.iter().any(|r| r.matches(e.description)))
+        .map(|e| e.amount)
+        .sum();
+    let daily = everyday / days as f64;
+
+    let mut changes: HashMap<NaiveDate, f64> = HashMap::new();
+    for entry in scheduled {
+        *changes.entry(entry.day).or_default() += entry.amount;
+    }
+    for r in &recurring {
+        let mut n = 1;
+        while let Some(day) = r.nth(n) {
+            if day > until {
+                break;
+            }
+            let entered = scheduled.iter()
+                .any(|e| r.matches(e.description) && (e.day - day).num_days().abs() <= r.every / 2);
+            if day > today && !entered {
+                *changes.entry(day).or_default() += r.amount;
+            }
+            n += 1;
+        }
+    }
+
+    let mut points = Vec::new();
+    let mut zero_on = None;
+    let mut running = balance;
+    let mut day = today;
+    while day < until {
+        day = day.succ_opt().unwrap_or(until);
+        running += daily + changes.get(&day).copied().unwrap_or(0.0);
+        if running < 0.0 && balance >= 0.0 && zero_on.is_none() {
+            zero_on = Some(day);
+        }
+        points.push((day, running));
+    }
+    Forecast { points, zero_on }
+}
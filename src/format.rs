@@ -0,0 +1,131 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize, Debug)]
+pub enum SymbolPosition {
+    Before,
+    After,
+}
+
+// How money is shown everywhere in the UI
+#[derive(Clone, PartialEq, Serialize, Deserialize, Debug)]
+pub struct MoneyFormat {
+    pub symbol: String,
+    pub position: SymbolPosition,
+    // None disables digit grouping
+    pub thousands_separator: Option<char>,
+    pub decimal_separator: char,
+    pub decimals: usize,
+    // "R$ 1.234,56" rather than "R$1.234,56". Letter codes like "CHF" get the space
+    // either way, and a symbol after the number always has one.
+    #[serde(default)]
+    pub space_after_symbol: bool,
+}
+
+impl Default for MoneyFormat {
+    fn default() -> Self {
+        Self::preset("en-US").unwrap_or_else(|| Self {
+            symbol: "$".to_string(),
+            position: SymbolPosition::Before,
+            thousands_separator: Some(','),
+            decimal_separator: '.',
+            decimals: 2,
+            space_after_symbol: false,
+        })
+    }
+}
+
+// (id, label) of the built-in locale presets. Each label is what the preset shows for
+// 1234.56.
+pub const PRESETS: [(&str, &str); 7] = [
+    ("en-US", "$1,234.56"),
+    ("en-GB", "£1,234.56"),
+    ("de-DE", "1.234,56 €"),
+    ("fr-FR", "1\u{202F}234,56 €"),
+    ("de-CH", "CHF 1'234.56"),
+    ("pt-BR", "R$ 1.234,56"),
+    ("ja-JP", "¥1,235"),
+];
+
+impl MoneyFormat {
+    pub fn preset(id: &str) -> Option<Self> {
+        let (symbol, position, space, thousands, decimal, decimals) = match id {
+            "en-US" => ("$", SymbolPosition::Before, false, Some(','), '.', 2),
+            "en-GB" => ("£", SymbolPosition::Before, false, Some(','), '.', 2),
+            "de-DE" => ("€", SymbolPosition::After, true, Some('.'), ',', 2),
+            "fr-FR" => ("€", SymbolPosition::After, true, Some('\u{202F}'), ',', 2),
+            "de-CH" => ("CHF", SymbolPosition::Before, true, Some('\''), '.', 2),
+            "pt-BR" => ("R$", SymbolPosition::Before, true, Some('.'), ',', 2),
+            "ja-JP" => ("¥", SymbolPosition::Before, false, Some(','), '.', 0),
+            _ => return None,
+        };
+        Some(Self {
+            symbol: symbol.to_string(),
+            position,
+            thousands_separator: thousands,
+            decimal_separator: decimal,
+            decimals,
+            space_after_symbol: space,
+        })
+    }
+
+    // Number with grouping and the locale's decimal mark, no symbol
+    pub fn number(&self, value: f64) -> String {
+        let fixed = format!("{:.*}", self.decimals, value.abs());
+        let (int_part, frac_part) = match fixed.split_once('.') {
+            Some((i, f)) => (i, Some(f)),
+            None => (fixed.as_str(), None),
+        };
+
+        let mut grouped = String::new();
+        for (i, digit) in int_part.chars().enumerate() {
+            if i > 0 && (int_part.len() - i) % 3 == 0 {
+                if let Some(sep) = self.thousands_separator {
+                    grouped.push(sep);
+                }
+            }
+            grouped.push(digit);
+        }
+
+        let mut out = String::new();
+        // Don't print "-0.00" for tiny negative values
+        if value < 0.0 && fixed.chars().any(|c| c.is_ascii_digit() && c != '0') {
+            out.push('-');
+        }
+        out.push_str(&grouped);
+        if let Some(frac) = frac_part {
+            out.push(self.decimal_separator);
+            out.push_str(frac);
+        }
+        out
+    }
+
+    // Number with the currency symbol, e.g. "-$1,234.56" or "-1.234,56 €"
+    pub fn money(&self, value: f64) -> String {
+        let number = self.number(value);
+        let (sign, digits) = match number.strip_prefix('-') {
+            Some(rest) => ("-", rest.to_string()),
+            None => ("", number),
+        };
+        match self.position {
+            SymbolPosition::Before => {
+                // Letter codes like "CHF" read better with a space
+                let spaced = self.space_after_symbol || self.symbol.chars().all(|c| c.is_alphabetic());
+                let space = if spaced { " " } else { "" };
+                format!("{}{}{}{}", sign, self.symbol, space, digits)
+            }
+            SymbolPosition::After => format!("{}{} {}", sign, digits, self.symbol),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn preset_labels_match_what_they_show() {
+        for (id, label) in PRESETS {
+            assert_eq!(MoneyFormat::preset(id).unwrap().money(1234.56), label, "{}", id);
+        }
+    }
+}
@@ -0,0 +1,94 @@
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::i18n::t;
+
+// A savings target. Progress comes from the transactions allocated to it.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Goal {
+    pub id: Uuid,
+    pub name: String,
+    pub target_amount: f64,
+    pub target_date: NaiveDate,
+    pub created: NaiveDate,
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum GoalOutlook {
+    Reached,
+    OnTrack,
+    Behind,
+    Overdue,
+}
+
+impl GoalOutlook {
+    pub fn label(&self) -> String {
+        match self {
+            GoalOutlook::Reached => t!("goal-reached"),
+            GoalOutlook::OnTrack => t!("goal-on-track"),
+            GoalOutlook::Behind => t!("goal-behind"),
+            GoalOutlook::Overdue => t!("goal-overdue"),
+        }
+    }
+}
+
+impl Goal {
+    pub fn new(name: &str, target_amount: f64, target_date: NaiveDate, today: NaiveDate) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            name: name.to_string(),
+            target_amount,
+            target_date,
+            created: today,
+        }
+    }
+
+    pub fn fraction(&self, saved: f64) -> f64 {
+        if self.target_amount <= 0.0 {
+            return 1.0;
+        }
+        (saved / self.target_amount).clamp(0.0, 1.0)
+    }
+
+    // Compares saved money against a straight line from creation to the target date
+    pub fn outlook(&self, saved: f64, today: NaiveDate) -> GoalOutlook {
+        if saved >= self.target_amount {
+            return GoalOutlook::Reached;
+        }
+        if today > self.target_date {
+            return GoalOutlook::Overdue;
+        }
+
+        let total_days = (self.target_date - self.created).num_days().max(1) as f64;
+        let elapsed_days = (today - self.created).num_days().max(0) as f64;
+        let expected = self.target_amount * (elapsed_days / total_days).min(1.0);
+
+        if saved >= expected {
+            GoalOutlook::OnTrack
+        } else {
+            GoalOutlook::Behind
+        }
+    }
+
+    // Date the target would be hit at the average pace so far
+    pub fn projected_completion(&self, saved: f64, today: NaiveDate) -> Option<NaiveDate> {
+        if saved >= self.target_amount {
+            return Some(today);
+        }
+        let elapsed_days = (today - self.created).num_days().max(1) as f64;
+        let per_day = saved / elapsed_days;
+        if per_day <= 0.0 {
+            return None;
+        }
+        let days_left = ((self.target_amount - saved) / per_day).ceil() as i64;
+        today.checked_add_signed(chrono::Duration::days(days_left))
+    }
+
+    // Amount per month still needed to finish on time
+    pub fn monthly_needed(&self, saved: f64, today: NaiveDate) -> f64 {
+        let remaining = (self.target_amount - saved).max(0.0);
+        let months_left = ((self.target_date - today).num_days() as f64 / 30.44).max(1.0);
+        remaining / months_left
+    }
+}
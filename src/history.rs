@@ -0,0 +1,101 @@
+// Undo/redo over whole-ledger snapshots. Every change goes through `save_data`, so
+// recording the serialized ledger there covers adds, edits, deletes, bulk operations
+// and imports alike.
+
+// Older steps are dropped beyond this many, or once the snapshots add up to more than
+// BYTE_LIMIT. Each step is a full copy of the ledger, so a ledger of a few megabytes
+// would otherwise hold hundreds of them in memory.
+const LIMIT: usize = 50;
+const BYTE_LIMIT: usize = 64 * 1024 * 1024;
+
+#[derive(Default)]
+pub struct History {
+    undo: Vec<Vec<u8>>,
+    redo: Vec<Vec<u8>>,
+    // The ledger as of the last recorded save
+    current: Option<Vec<u8>>,
+}
+
+impl History {
+    pub fn is_seeded(&self) -> bool {
+        self.current.is_some()
+    }
+
+    // Starting point after loading; nothing to undo yet
+    pub fn reset(&mut self, state: Vec<u8>) {
+        self.undo.clear();
+        self.redo.clear();
+        self.current = Some(state);
+    }
+
+    // Called with the new state on every save. Saving an unchanged ledger is not a step.
+    pub fn record(&mut self, state: Vec<u8>) {
+        if self.current.as_ref() == Some(&state) {
+            return;
+        }
+        if let Some(previous) = self.current.replace(state) {
+            self.undo.push(previous);
+        }
+        self.redo.clear();
+        self.trim();
+    }
+
+    // The last step always stays, however big, so the latest change can be undone
+    fn trim(&mut self) {
+        let mut bytes: usize = self.undo.iter().map(Vec::len).sum();
+        let mut drop = 0;
+        while self.undo.len() - drop > 1 && (self.undo.len() - drop > LIMIT || bytes > BYTE_LIMIT) {
+            bytes -= self.undo[drop].len();
+            drop += 1;
+        }
+        self.undo.drain(..drop);
+    }
+
+    pub fn can_undo(&self) -> bool {
+        !self.undo.is_empty()
+    }
+
+    pub fn can_redo(&self) -> bool {
+        !self.redo.is_empty()
+    }
+
+    // Returns the state to go back to
+    pub fn undo(&mut self) -> Option<Vec<u8>> {
+        let state = self.undo.pop()?;
+        if let Some(current) = self.current.replace(state.clone()) {
+            self.redo.push(current);
+        }
+        Some(state)
+    }
+
+    pub fn redo(&mut self) -> Option<Vec<u8>> {
+        let state = self.redo.pop()?;
+        if let Some(current) = self.current.replace(state.clone()) {
+            self.undo.push(current);
+        }
+        Some(state)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn big_ledgers_keep_fewer_steps() {
+        let mut history = History::default();
+        history.reset(vec![0; BYTE_LIMIT / 4]);
+        for i in 1..=10u8 {
+            history.record(vec![i; BYTE_LIMIT / 4]);
+        }
+        assert_eq!(history.undo.len(), 4);
+        assert_eq!(history.undo().unwrap()[0], 9);
+
+        let mut history = History::default();
+        history.reset(vec![0]);
+        history.record(vec![1; BYTE_LIMIT + 1]);
+        history.record(vec![2; BYTE_LIMIT + 1]);
+        assert_eq!(history.undo.len(), 1);
+        assert!(history.can_undo());
+    }
+}
@@ -0,0 +1,135 @@
+use chrono::{DateTime, Local};
+use eframe::egui;
+use serde::{Deserialize, Serialize};
+use std::sync::mpsc::{Receiver, channel};
+use uuid::Uuid;
+
+use crate::error::Problem;
+
+// Investments and crypto held outside the ledger's cash, counted in the net worth at the
+// latest fetched price. `symbol` is whatever the price provider calls it.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Holding {
+    pub id: Uuid,
+    pub name: String,
+    pub symbol: String,
+    pub quantity: f64,
+}
+
+impl Holding {
+    pub fn new(name: &str, symbol: &str, quantity: f64) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            name: name.to_string(),
+            symbol: symbol.trim().to_string(),
+            quantity,
+        }
+    }
+}
+
+// The last price fetched for a symbol, kept in the ledger so the net worth still shows
+// something offline
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Price {
+    pub symbol: String,
+    pub currency: String,
+    pub price: f64,
+    pub fetched: DateTime<Local>,
+}
+
+// Where prices come from: a URL and the JSON pointer to the price in its answer. Both
+// may use {symbol}, {currency} (USD) and {currency_lower} (usd).
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+pub struct PriceProvider {
+    pub url: String,
+    pub pointer: String,
+}
+
+// CoinGecko's simple price API, which needs no key. Symbols are its coin IDs.
+impl Default for PriceProvider {
+    fn default() -> Self {
+        Self {
+            url: "https://api.coingecko.com/api/v3/simple/price?ids={symbol}&vs_currencies={currency_lower}".to_string(),
+            pointer: "/{symbol}/{currency_lower}".to_string(),
+        }
+    }
+}
+
+fn fill(template: &str, symbol: &str, currency: &str) -> String {
+    template
+        .replace("{symbol}", symbol)
+        .replace("{currency_lower}", &currency.to_lowercase())
+        .replace("{currency}", currency)
+}
+
+// Latest price of `symbol`, None when it was never fetched
+pub fn latest<'a>(prices: &'a [Price], symbol: &str) -> Option<&'a Price> {
+    prices.iter().find(|p| p.symbol == symbol)
+}
+
+// One price per symbol, replacing the older one
+pub fn set_price(prices: &mut Vec<Price>, price: Price) {
+    match prices.iter_mut().find(|p| p.symbol == price.symbol) {
+        Some(existing) if existing.fetched <= price.fetched => *existing = price,
+        Some(_) => {}
+        None => prices.push(price),
+    }
+}
+
+// Fetches running in the background, one request per symbol. Poll it once per frame.
+pub struct PriceFetch {
+    rx: Receiver<(String, Result<f64, Problem>)>,
+    pub currency: String,
+    remaining: usize,
+    pub fetched: usize,
+    // Symbol and what went wrong
+    pub failures: Vec<(String, Problem)>,
+}
+
+impl PriceFetch {
+    // Prices that came in since the last frame
+    pub fn poll(&mut self) -> Vec<Price> {
+        let mut prices = Vec::new();
+        for (symbol, answer) in self.rx.try_iter() {
+            self.remaining -= 1;
+            match answer {
+                Ok(price) => prices.push(Price { symbol, currency: self.currency.clone(), price, fetched: Local::now() }),
+                Err(e) => self.failures.push((symbol, e)),
+            }
+        }
+        self.fetched += prices.len();
+        prices
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.remaining == 0
+    }
+}
+
+pub fn fetch_prices(provider: &PriceProvider, symbols: Vec<String>, currency: &str, ctx: &egui::Context) -> PriceFetch {
+    let (tx, rx) = channel();
+    let remaining = symbols.len();
+    for symbol in symbols {
+        let tx = tx.clone();
+        let ctx = ctx.clone();
+        let pointer = fill(&provider.pointer, &symbol, currency);
+        let request = ehttp::Request::get(fill(&provider.url, &symbol, currency));
+        ehttp::fetch(request, move |result| {
+            let parsed = result.map_err(Problem::Other).and_then(|response| {
+                if !response.ok {
+                    return Err(Problem::Other(format!("{} {}", response.status, response.status_text)));
+                }
+                let json: serde_json::Value = serde_json::from_slice(&response.bytes).map_err(|e| Problem::Other(e.to_string()))?;
+                // Some providers quote prices as strings
+                match json.pointer(&pointer) {
+                    Some(serde_json::Value::Number(n)) => n.as_f64().ok_or_else(|| Problem::Other(n.to_string())),
+                    Some(serde_json::Value::String(s)) => s.trim().parse::<f64>().map_err(|e| Problem::Other(e.to_string())),
+                    _ => Err(Problem::NoPrice(pointer)),
+                }
+            });
+            let _ = tx.send((symbol, parsed));
+            ctx.request_repaint();
+        });
+    }
+    PriceFetch { rx, currency: currency.to_string(), remaining, fetched: 0, failures: Vec::new() }
+}
@@ -0,0 +1,134 @@
+use chrono::{DateTime, Datelike, Local, NaiveDate};
+use fluent::{FluentArgs, FluentBundle, FluentResource};
+use serde::{Deserialize, Serialize};
+use std::cell::{Cell, RefCell};
+
+// UI translations, one Fluent file per language in locales/. English is the fallback
+// for anything a translation is missing.
+#[derive(Clone, Copy, PartialEq, Debug, Default, Serialize, Deserialize)]
+pub enum Language {
+    #[default]
+    English,
+    Portuguese,
+}
+
+impl Language {
+    pub const ALL: [Language; 2] = [Language::English, Language::Portuguese];
+
+    // Always in the language itself, so it can be found from any other
+    pub fn label(&self) -> &'static str {
+        match self {
+            Language::English => "English",
+            Language::Portuguese => "Português (Brasil)",
+        }
+    }
+
+    fn id(&self) -> &'static str {
+        match self {
+            Language::English => "en-US",
+            Language::Portuguese => "pt-BR",
+        }
+    }
+
+    fn source(&self) -> &'static str {
+        match self {
+            Language::English => include_str!("../locales/en-US.ftl"),
+            Language::Portuguese => include_str!("../locales/pt-BR.ftl"),
+        }
+    }
+
+    // Number format preset used where this language is spoken, see format.rs
+    pub fn number_preset(&self) -> &'static str {
+        match self {
+            Language::English => "en-US",
+            Language::Portuguese => "pt-BR",
+        }
+    }
+
+    fn date_pattern(&self) -> &'static str {
+        match self {
+            Language::English => "%Y-%m-%d",
+            Language::Portuguese => "%d/%m/%Y",
+        }
+    }
+}
+
+fn bundle(language: Language) -> FluentBundle<FluentResource> {
+    let id = language.id().parse().expect("valid language id");
+    let mut bundle = FluentBundle::new(vec![id]);
+    // The isolation marks around arguments show up as boxes in egui
+    bundle.set_use_isolating(false);
+    let resource = FluentResource::try_new(language.source().to_string())
+        .unwrap_or_else(|(resource, _)| resource);
+    let _ = bundle.add_resource(resource);
+    bundle
+}
+
+struct Translations {
+    current: FluentBundle<FluentResource>,
+    fallback: FluentBundle<FluentResource>,
+}
+
+// The UI only runs on the main thread, so per-thread state is enough
+thread_local! {
+    static LANGUAGE: Cell<Language> = const { Cell::new(Language::English) };
+    static TRANSLATIONS: RefCell<Option<Translations>> = const { RefCell::new(None) };
+}
+
+pub fn language() -> Language {
+    LANGUAGE.with(|l| l.get())
+}
+
+pub fn set_language(language: Language) {
+    LANGUAGE.with(|l| l.set(language));
+    TRANSLATIONS.with(|t| *t.borrow_mut() = None);
+}
+
+fn format(bundle: &FluentBundle<FluentResource>, id: &str, args: Option<&FluentArgs>) -> Option<String> {
+    let pattern = bundle.get_message(id)?.value()?;
+    let mut errors = Vec::new();
+    Some(bundle.format_pattern(pattern, args, &mut errors).into_owned())
+}
+
+// Text for a message ID; the ID itself if no language has it
+pub fn tr_args(id: &str, args: Option<&FluentArgs>) -> String {
+    TRANSLATIONS.with(|cell| {
+        let mut cell = cell.borrow_mut();
+        let translations = cell.get_or_insert_with(|| Translations {
+            current: bundle(language()),
+            fallback: bundle(Language::English),
+        });
+        format(&translations.current, id, args)
+            .or_else(|| format(&translations.fallback, id, args))
+            .unwrap_or_else(|| id.to_string())
+    })
+}
+
+pub fn tr(id: &str) -> String {
+    tr_args(id, None)
+}
+
+// t!("id") or t!("id", name = value, ...) for messages with arguments
+macro_rules! t {
+    ($id:expr) => {
+        $crate::i18n::tr($id)
+    };
+    ($id:expr, $($key:ident = $value:expr),+ $(,)?) => {{
+        let mut args = fluent::FluentArgs::new();
+        $(args.set(stringify!($key), $value);)+
+        $crate::i18n::tr_args($id, Some(&args))
+    }};
+}
+pub(crate) use t;
+
+pub fn date(day: NaiveDate) -> String {
+    day.format(language().date_pattern()).to_string()
+}
+
+pub fn date_time(time: &DateTime<Local>) -> String {
+    format!("{} {}", date(time.date_naive()), time.format("%H:%M"))
+}
+
+pub fn month_year(day: NaiveDate) -> String {
+    format!("{} {}", tr(&format!("month-{}", day.month())), day.year())
+}
@@ -0,0 +1,456 @@
+use chrono::{Local, NaiveDate};
+use eframe::egui;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{Receiver, TryRecvError, channel};
+use uuid::Uuid;
+
+use crate::calc;
+use crate::error::Problem;
+use crate::model::{OTHER_CATEGORY, Transaction, TransactionStatus, TransactionType, local_from_naive};
+use crate::rules::{Matcher, Rule};
+use crate::scripting::Hooks;
+
+// Exports from other budgeting apps we know the column layout of
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum ImportFormat {
+    Ynab,
+    Mint,
+}
+
+impl ImportFormat {
+    pub fn label(&self) -> &'static str {
+        match self {
+            ImportFormat::Ynab => "YNAB",
+            ImportFormat::Mint => "Mint",
+        }
+    }
+}
+
+// One row of an export, already converted to our conventions:
+// positive amount plus a direction, category/subcategory names.
+pub struct ImportedRow {
+    pub date: NaiveDate,
+    pub description: String,
+    pub amount: f64,
+    pub is_income: bool,
+    pub category: Option<String>,
+    pub subcategory: Option<String>,
+    pub note: String,
+    pub cleared: bool,
+}
+
+// Why a line of the file didn't make it in. Worded by the window, the import thread
+// doesn't know the language.
+pub enum RowProblem {
+    Unreadable(String),
+    Date(String),
+    Amount(String),
+}
+
+pub struct RowError {
+    pub line: u64,
+    pub problem: RowProblem,
+}
+
+pub struct ImportResult {
+    pub rows: Vec<ImportedRow>,
+    // Transfers between accounts and empty amounts
+    pub skipped: usize,
+    pub errors: Vec<RowError>,
+}
+
+fn line_of(record: &csv::StringRecord) -> u64 {
+    record.position().map(|p| p.line()).unwrap_or_default()
+}
+
+pub fn pick_csv() -> Option<PathBuf> {
+    rfd::FileDialog::new()
+        .add_filter("CSV", &["csv"])
+        .pick_file()
+}
+
+// `progress` gets the share of the file read so far and returns false to stop there
+pub fn import_file(path: &Path, format: ImportFormat, mut progress: impl FnMut(f32) -> bool) -> Result<ImportResult, Problem> {
+    let size = std::fs::metadata(path).map(|m| m.len().max(1)).unwrap_or(1) as f32;
+    let mut reader = csv::ReaderBuilder::new()
+        .flexible(true)
+        .from_path(path)
+        .map_err(|e| e.to_string())?;
+
+    // Header names, with the byte order mark some exports start with removed
+    let headers: Vec<String> = reader.headers()
+        .map_err(|e| e.to_string())?
+        .iter()
+        .map(|h| h.trim_start_matches('\u{feff}').trim().to_string())
+        .collect();
+    let column = |name: &'static str| -> Result<usize, Problem> {
+        headers.iter()
+            .position(|h| h.eq_ignore_ascii_case(name))
+            .ok_or(Problem::MissingColumn { format: format.label(), column: name })
+    };
+
+    let mut result = ImportResult { rows: Vec::new(), skipped: 0, errors: Vec::new() };
+    let mut keep_going = |record: &csv::StringRecord| {
+        progress(record.position().map(|p| p.byte() as f32 / size).unwrap_or_default())
+    };
+    match format {
+        ImportFormat::Ynab => {
+            let date = column("Date")?;
+            let payee = column("Payee")?;
+            let group = column("Category Group")?;
+            let category = column("Category")?;
+            let memo = column("Memo")?;
+            let outflow = column("Outflow")?;
+            let inflow = column("Inflow")?;
+            let cleared = column("Cleared").ok();
+
+            for record in reader.records() {
+                let record = match record {
+                    Ok(record) => record,
+                    Err(e) => {
+                        let line = e.position().map(|p| p.line()).unwrap_or_default();
+                        result.errors.push(RowError { line, problem: RowProblem::Unreadable(e.to_string()) });
+                        continue;
+                    }
+                };
+                if !keep_going(&record) {
+                    break;
+                }
+                let get = |i: usize| record.get(i).unwrap_or("").trim();
+
+                // No accounts here, so moving money between them isn't a transaction
+                if get(payee).starts_with("Transfer :") {
+                    result.skipped += 1;
+                    continue;
+                }
+                let Some(day) = parse_date(get(date)) else {
+                    result.errors.push(RowError { line: line_of(&record), problem: RowProblem::Date(get(date).to_string()) });
+                    continue;
+                };
+                // Outflow and Inflow are separate, both positive. An empty one is nothing,
+                // anything else has to be a number.
+                let flow = |i: usize| if get(i).is_empty() { Some(0.0) } else { parse_amount(get(i)) };
+                let (Some(paid_in), Some(paid_out)) = (flow(inflow), flow(outflow)) else {
+                    let value = if flow(inflow).is_none() { get(inflow) } else { get(outflow) };
+                    result.errors.push(RowError { line: line_of(&record), problem: RowProblem::Amount(value.to_string()) });
+                    continue;
+                };
+                let net = paid_in - paid_out;
+                if net == 0.0 {
+                    result.skipped += 1;
+                    continue;
+                }
+
+                // Group/category maps onto our category/subcategory. Income shows up
+                // under the "Inflow" group, which doesn't say anything useful.
+                let (cat, sub) = match (get(group), get(category)) {
+                    ("", _) | ("Inflow", _) => (None, None),
+                    (g, "") => (Some(g.to_string()), None),
+                    (g, c) => (Some(g.to_string()), Some(c.to_string())),
+                };
+
+                result.rows.push(ImportedRow {
+                    date: day,
+                    description: get(payee).to_string(),
+                    amount: net.abs(),
+                    is_income: net > 0.0,
+                    category: cat,
+                    subcategory: sub,
+                    note: get(memo).to_string(),
+                    cleared: cleared.map(|i| get(i) != "Uncleared").unwrap_or(true),
+                });
+            }
+        }
+        ImportFormat::Mint => {
+            let date = column("Date")?;
+            let description = column("Description")?;
+            let amount = column("Amount")?;
+            let kind = column("Transaction Type")?;
+            let category = column("Category")?;
+            let notes = column("Notes").ok();
+
+            for record in reader.records() {
+                let record = match record {
+                    Ok(record) => record,
+                    Err(e) => {
+                        let line = e.position().map(|p| p.line()).unwrap_or_default();
+                        result.errors.push(RowError { line, problem: RowProblem::Unreadable(e.to_string()) });
+                        continue;
+                    }
+                };
+                if !keep_going(&record) {
+                    break;
+                }
+                let get = |i: usize| record.get(i).unwrap_or("").trim();
+
+                if matches!(get(category), "Transfer" | "Credit Card Payment") {
+                    result.skipped += 1;
+                    continue;
+                }
+                let Some(day) = parse_date(get(date)) else {
+                    result.errors.push(RowError { line: line_of(&record), problem: RowProblem::Date(get(date).to_string()) });
+                    continue;
+                };
+                let Some(value) = parse_amount(get(amount)) else {
+                    result.errors.push(RowError { line: line_of(&record), problem: RowProblem::Amount(get(amount).to_string()) });
+                    continue;
+                };
+
+                // Amounts are always positive, the direction is in "Transaction Type"
+                let cat = match get(category) {
+                    "" | "Uncategorized" => None,
+                    c => Some(c.to_string()),
+                };
+                result.rows.push(ImportedRow {
+                    date: day,
+                    description: get(description).to_string(),
+                    amount: value.abs(),
+                    is_income: get(kind).eq_ignore_ascii_case("credit"),
+                    category: cat,
+                    subcategory: None,
+                    note: notes.map(|i| get(i).to_string()).unwrap_or_default(),
+                    // Mint only saw what the bank had already posted
+                    cleared: true,
+                });
+            }
+        }
+    }
+    Ok(result)
+}
+
+// Both tools write US-style dates by default, YNAB follows the budget's setting
+fn parse_date(text: &str) -> Option<NaiveDate> {
+    ["%m/%d/%Y", "%Y-%m-%d", "%d/%m/%Y", "%d.%m.%Y", "%m/%d/%y"].iter()
+        .find_map(|fmt| NaiveDate::parse_from_str(text, fmt).ok())
+}
+
+// Accepts "$1,234.56", "1.234,56 €", "(12.00)" and plain numbers
+pub fn parse_amount(text: &str) -> Option<f64> {
+    let negative = text.starts_with('-') || text.starts_with('(');
+    let cleaned: String = text.chars()
+        .filter(|c| c.is_ascii_digit() || *c == '.' || *c == ',')
+        .collect();
+    let value = calc::parse_number(&cleaned)?;
+    Some(if negative { -value } else { value })
+}
+
+// Imported rows this many days away from an existing one can still be the same transaction
+// (card payments often post a day or two late)
+pub const DATE_TOLERANCE_DAYS: i64 = 3;
+
+// Share of words the two descriptions have in common, 0 to 1. Bank text like
+// "AMAZON MKTPLACE PMTS" against "Amazon" counts as a full match.
+pub fn description_similarity(a: &str, b: &str) -> f64 {
+    let words = |s: &str| -> Vec<String> {
+        s.split(|c: char| !c.is_alphanumeric())
+            .filter(|w| !w.is_empty())
+            .map(|w| w.to_lowercase())
+            .collect()
+    };
+    let (a, b) = (words(a), words(b));
+    if a.is_empty() || b.is_empty() {
+        return 0.0;
+    }
+    let (a_text, b_text) = (a.join(" "), b.join(" "));
+    if a_text.contains(&b_text) || b_text.contains(&a_text) {
+        return 1.0;
+    }
+    let shared = a.iter().filter(|w| b.contains(w)).count();
+    shared as f64 / a.len().max(b.len()) as f64
+}
+
+// Closest existing transaction that looks like the same one: same type and amount,
+// a few days apart at most, and a similar description
+pub fn probable_duplicate(existing: &[Transaction], incoming: &Transaction) -> Option<Uuid> {
+    existing.iter()
+        .filter(|t| t.trans_type == incoming.trans_type && (t.amount - incoming.amount).abs() < 0.005)
+        .filter_map(|t| {
+            let days = (t.date.date_naive() - incoming.date.date_naive()).num_days().abs();
+            let similarity = description_similarity(&t.description, &incoming.description);
+            (days <= DATE_TOLERANCE_DAYS && similarity >= 0.5)
+                .then_some((t.id, days, similarity))
+        })
+        .min_by(|a, b| a.1.cmp(&b.1).then(b.2.total_cmp(&a.2)))
+        .map(|(id, _, _)| id)
+}
+
+// An import's transactions, ready to be added in one go
+pub struct Prepared {
+    pub format: ImportFormat,
+    pub new: Vec<Transaction>,
+    // Rows that look like a transaction already in the ledger, with that one's id
+    pub duplicates: Vec<(Transaction, Uuid)>,
+    // Transfers, empty amounts and rows the script left out
+    pub skipped: usize,
+    pub errors: Vec<RowError>,
+}
+
+// Turns the rows into transactions filed by the rules and the script's on_import, and
+// sets aside the ones that look like something in `existing`. Stops early once cancelled.
+fn prepare(
+    result: ImportResult,
+    format: ImportFormat,
+    rules: &[Rule],
+    hooks: Option<&Hooks>,
+    existing: &[Transaction],
+    cancelled: &AtomicBool,
+) -> Result<Prepared, Problem> {
+    let matcher = Matcher::new(rules);
+    let mut prepared = Prepared {
+        format,
+        new: Vec::new(),
+        duplicates: Vec::new(),
+        skipped: result.skipped,
+        errors: result.errors,
+    };
+    for row in result.rows {
+        if cancelled.load(Ordering::Relaxed) {
+            break;
+        }
+        let Some(t) = to_transaction(row, &matcher, hooks)? else {
+            prepared.skipped += 1;
+            continue;
+        };
+        match probable_duplicate(existing, &t) {
+            Some(id) => prepared.duplicates.push((t, id)),
+            None => prepared.new.push(t),
+        }
+    }
+    Ok(prepared)
+}
+
+// A row as a transaction filed by the rules. The script's on_import can change it or
+// leave it out (None); a script error stops the import before anything is added.
+pub fn to_transaction(row: ImportedRow, matcher: &Matcher, hooks: Option<&Hooks>) -> Result<Option<Transaction>, String> {
+    let trans_type = if row.is_income { TransactionType::Income } else { TransactionType::Expense };
+    // Noon keeps the day stable whatever the time zone
+    let date = local_from_naive(row.date.and_hms_opt(12, 0, 0).unwrap_or_default());
+    let mut t = Transaction {
+        id: Uuid::new_v4(),
+        description: row.description,
+        amount: row.amount,
+        trans_type,
+        category: row.category.unwrap_or_else(|| OTHER_CATEGORY.to_string()),
+        subcategory: row.subcategory,
+        currency: None,
+        splits: Vec::new(),
+        note: row.note,
+        attachments: Vec::new(),
+        goal: None,
+        status: if row.cleared { TransactionStatus::Cleared } else { TransactionStatus::Pending },
+        refund_of: None,
+        date,
+        modified: Some(Local::now()),
+    };
+    matcher.apply(&mut t, row.amount);
+    match hooks {
+        Some(hooks) => hooks.on_import(t),
+        None => Ok(Some(t)),
+    }
+}
+
+enum Message {
+    Progress(f32),
+    Done(Result<Prepared, Problem>),
+}
+
+// An import running in the background. Poll it once per frame; nothing is added to the
+// ledger until it's done.
+pub struct ImportTask {
+    rx: Receiver<Message>,
+    cancel: Arc<AtomicBool>,
+    pub format: ImportFormat,
+    // Share of the file read, 0 to 1
+    pub progress: f32,
+}
+
+impl ImportTask {
+    pub fn cancel(&self) {
+        self.cancel.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancel.load(Ordering::Relaxed)
+    }
+
+    // None while it's still running
+    pub fn poll(&mut self) -> Option<Result<Prepared, Problem>> {
+        loop {
+            match self.rx.try_recv() {
+                Ok(Message::Progress(progress)) => self.progress = progress,
+                Ok(Message::Done(result)) => return Some(result),
+                Err(TryRecvError::Empty) => return None,
+                Err(TryRecvError::Disconnected) => return Some(Err(Problem::Stopped)),
+            }
+        }
+    }
+}
+
+// Reads and prepares the file on a thread of its own. The rules, script and existing
+// transactions are as they were when it started.
+pub fn start(
+    ctx: &egui::Context,
+    path: PathBuf,
+    format: ImportFormat,
+    rules: Vec<Rule>,
+    hooks: Option<Arc<Hooks>>,
+    existing: Vec<Transaction>,
+) -> ImportTask {
+    let (tx, rx) = channel();
+    let cancel = Arc::new(AtomicBool::new(false));
+    let cancelled = cancel.clone();
+    let ctx = ctx.clone();
+    std::thread::spawn(move || {
+        let mut shown = 0.0;
+        let result = import_file(&path, format, |progress| {
+            // A repaint per percent is plenty
+            if progress - shown >= 0.01 {
+                shown = progress;
+                let _ = tx.send(Message::Progress(progress));
+                ctx.request_repaint();
+            }
+            !cancelled.load(Ordering::Relaxed)
+        });
+        // A cancelled import's result is thrown away by the window
+        let prepared = result.and_then(|result| prepare(result, format, &rules, hooks.as_deref(), &existing, &cancelled));
+        let _ = tx.send(Message::Done(prepared));
+        ctx.request_repaint();
+    });
+    ImportTask { rx, cancel, format, progress: 0.0 }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn amounts_from_either_locale() {
+        assert_eq!(parse_amount("$1,234.56"), Some(1234.56));
+        assert_eq!(parse_amount("1.234,56 €"), Some(1234.56));
+        assert_eq!(parse_amount("R$ 4,50"), Some(4.5));
+        assert_eq!(parse_amount("(12.00)"), Some(-12.0));
+        assert_eq!(parse_amount("-3000"), Some(-3000.0));
+        assert_eq!(parse_amount("1,23,4.00"), None);
+        assert_eq!(parse_amount("n/a"), None);
+    }
+
+    #[test]
+    fn ynab_flows_that_are_not_numbers_are_reported() {
+        let path = std::env::temp_dir().join(format!("ynab-import-test-{}.csv", std::process::id()));
+        std::fs::write(&path, "\
+Date,Payee,Category Group,Category,Memo,Outflow,Inflow
+03/01/2026,Grocer,Food,Groceries,,12.50,
+03/02/2026,Employer,Inflow,Ready to Assign,,,1500.00
+03/03/2026,Cafe,Food,Eating out,,n/a,
+").unwrap();
+        let result = import_file(&path, ImportFormat::Ynab, |_| true).unwrap();
+        let _ = std::fs::remove_file(&path);
+        let rows: Vec<_> = result.rows.iter().map(|r| (r.description.as_str(), r.amount, r.is_income)).collect();
+        assert_eq!(rows, [("Grocer", 12.5, false), ("Employer", 1500.0, true)]);
+        assert_eq!(result.errors.len(), 1);
+        assert_eq!(result.errors[0].line, 4);
+        assert!(matches!(&result.errors[0].problem, RowProblem::Amount(value) if value == "n/a"));
+    }
+}
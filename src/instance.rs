@@ -0,0 +1,59 @@
+use std::fs::{File, OpenOptions};
+use std::path::{Path, PathBuf};
+
+// Keeps a second window (or terminal UI) from saving over the first one's changes to
+// the same ledger. Held as an OS file lock on a file beside the ledger, so a crash
+// releases it and no stale lock is left behind.
+pub struct InstanceLock {
+    _file: File,
+}
+
+pub fn lock_path(data_path: &Path) -> PathBuf {
+    let mut name = data_path.file_name().map(|n| n.to_os_string()).unwrap_or_default();
+    name.push(".lock");
+    data_path.with_file_name(name)
+}
+
+// Another instance has the ledger open
+pub struct InUse;
+
+// Ok(None) when the lock can't be taken for some other reason (a read-only folder, a
+// file system without locks), which is no reason to keep the user out
+pub fn acquire(data_path: &Path) -> Result<Option<InstanceLock>, InUse> {
+    let path = lock_path(data_path);
+    let file = match OpenOptions::new().create(true).truncate(false).write(true).open(&path) {
+        Ok(file) => file,
+        Err(e) => {
+            tracing::warn!(path = %path.display(), "no instance lock: {}", e);
+            return Ok(None);
+        }
+    };
+    match file.try_lock() {
+        Ok(()) => Ok(Some(InstanceLock { _file: file })),
+        Err(std::fs::TryLockError::WouldBlock) => Err(InUse),
+        Err(std::fs::TryLockError::Error(e)) => {
+            tracing::warn!(path = %path.display(), "no instance lock: {}", e);
+            Ok(None)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn a_second_instance_waits_for_the_first() {
+        let dir = std::env::temp_dir().join(format!("instance-lock-test-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let ledger = dir.join("finance_data.json");
+        assert_eq!(lock_path(&ledger), dir.join("finance_data.json.lock"));
+
+        let first = acquire(&ledger).ok().flatten().expect("first instance gets the lock");
+        assert!(acquire(&ledger).is_err());
+        // Closing the first window lets the next one in
+        drop(first);
+        assert!(matches!(acquire(&ledger), Ok(Some(_))));
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
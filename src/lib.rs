@@ -0,0 +1,2078 @@
+use chrono::{DateTime, Local, NaiveDate};
+use eframe::egui;
+use egui::Color32;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use uuid::Uuid;
+
+mod aggregates;
+mod analytics;
+mod attachments;
+mod audit;
+#[cfg(feature = "bank")]
+mod bank;
+mod bot;
+mod budgets;
+mod calc;
+mod cli;
+mod config;
+mod crypto;
+mod dashboard;
+mod demo;
+mod error;
+mod export;
+mod filter;
+mod forecast;
+mod format;
+mod goals;
+mod history;
+mod holdings;
+mod i18n;
+mod import;
+mod instance;
+mod logging;
+mod migrations;
+mod model;
+mod notify;
+mod period;
+mod quick_add;
+mod rates;
+mod receipt;
+mod reports;
+mod rules;
+mod saver;
+mod sankey;
+mod scripting;
+mod settings;
+#[cfg(feature = "sheets")]
+mod sheets;
+mod storage;
+mod suggest;
+mod sync;
+mod theme;
+mod toast;
+mod trash;
+mod tray;
+mod treemap;
+#[cfg(feature = "tui")]
+mod tui;
+mod ui;
+mod wal;
+mod webhooks;
+use aggregates::Aggregates;
+use budgets::Budget;
+use error::{AppError, Problem};
+use filter::Filter;
+use format::MoneyFormat;
+use goals::Goal;
+use history::History;
+use holdings::{Holding, Price, PriceFetch};
+use import::ImportTask;
+use i18n::t;
+use model::{CategoryDef, CategoryKind, OTHER_CATEGORY, Template, Transaction, TransactionStatus, TransactionType, default_categories, default_category_name, local_from_naive};
+use period::{AnalyticsRange, Grouping, Period};
+use rates::{RateFetch, RateTable};
+use rules::Rule;
+use saver::{Failure, Saver};
+use scripting::Hooks;
+use settings::Settings;
+use storage::LoadError;
+use sync::Tombstone;
+use theme::ThemeChoice;
+use trash::Trashed;
+use tray::TrayCommand;
+use ui::charts::AnalyticsChart;
+use ui::transactions::{BulkAction, ImportReview, InlineEdit, SortColumn, SplitRow};
+
+#[derive(Serialize, Deserialize)]
+struct FinanceApp {
+    // Layout version of the saved file, see migrations.rs
+    #[serde(default)]
+    version: u32,
+    transactions: Vec<Transaction>,
+    #[serde(default = "default_categories")]
+    categories: Vec<CategoryDef>,
+    #[serde(default)]
+    rates: RateTable,
+    #[serde(default)]
+    budgets: Vec<Budget>,
+    #[serde(default)]
+    goals: Vec<Goal>,
+    // Investments and crypto, and the prices last fetched for them
+    #[serde(default)]
+    holdings: Vec<Holding>,
+    #[serde(default)]
+    prices: Vec<Price>,
+    #[serde(default)]
+    templates: Vec<Template>,
+    // Auto-categorization, first match wins
+    #[serde(default)]
+    rules: Vec<Rule>,
+    #[serde(default)]
+    money_format: MoneyFormat,
+    // Balance (home currency) before the first recorded transaction
+    #[serde(default)]
+    opening_balance: f64,
+    // How many backups of the data file to keep, 0 disables them
+    #[serde(default = "default_backup_count")]
+    backup_count: usize,
+    // Deleted transaction IDs, so syncing doesn't resurrect them
+    #[serde(default)]
+    deleted: Vec<Tombstone>,
+    // Recently deleted transactions, restorable until purged
+    #[serde(default)]
+    trash: Vec<Trashed>,
+    
+    #[serde(skip)]
+    opening_input: String,
+    #[serde(skip)]
+    input_date: NaiveDate, 
+    #[serde(skip)]
+    input_desc: String,
+    #[serde(skip)]
+    input_amount: String,
+    #[serde(skip)]
+    input_type: TransactionType,
+    #[serde(skip)]
+    input_category: String,
+    #[serde(skip)]
+    input_subcategory: Option<String>,
+    #[serde(skip)]
+    input_currency: String,
+    #[serde(skip)]
+    input_splits: Vec<SplitRow>,
+    #[serde(skip)]
+    input_note: String,
+    #[serde(skip)]
+    input_attachments: Vec<String>,
+    #[serde(skip)]
+    input_goal: Option<Uuid>,
+    #[serde(skip)]
+    input_refund_of: Option<Uuid>,
+    #[serde(skip)]
+    current_tab: Tab,
+    #[serde(skip)]
+    analytics_chart: AnalyticsChart,
+    // Dashboard showing the list of widgets to show, hide and reorder
+    #[serde(skip)]
+    customizing_dashboard: bool,
+    #[serde(skip)]
+    show_net_line: bool,
+    // What the amount distribution chart counts; None for every category
+    #[serde(skip)]
+    histogram_type: TransactionType,
+    #[serde(skip)]
+    histogram_category: Option<String>,
+    // The two periods compared under the charts, last month and this one to begin with
+    #[serde(skip)]
+    compare_periods: Option<[(NaiveDate, NaiveDate); 2]>,
+    // Where the chart and the expense pie were last drawn, for saving them as images
+    #[serde(skip)]
+    chart_rect: Option<egui::Rect>,
+    #[serde(skip)]
+    breakdown_rect: Option<egui::Rect>,
+    // Part of the window to save, and the file name to suggest, once the screenshot arrives
+    #[serde(skip)]
+    pending_capture: Option<(egui::Rect, &'static str)>,
+    // How many months ahead the balance chart estimates, 0 for none
+    #[serde(skip)]
+    forecast_months: u32,
+    // How much history the Analytics tab covers
+    #[serde(skip)]
+    analytics_range: AnalyticsRange,
+    #[serde(skip)]
+    editing_id: Option<Uuid>, // Tracks which item we are editing
+    // ID the next new transaction will get, so attachments can be named before it is saved
+    #[serde(skip)]
+    draft_id: Uuid,
+    // Transaction already in the ledger that the form would add again, until the user
+    // adds it anyway or changes something
+    #[serde(skip)]
+    duplicate_of: Option<Uuid>,
+    #[serde(skip)]
+    quick_input: String,
+    #[serde(skip)]
+    period: Period,
+    #[serde(skip)]
+    grouping: Grouping,
+    #[serde(skip)]
+    search_query: String,
+    #[serde(skip)]
+    filter: Filter,
+    #[serde(skip)]
+    sort_column: SortColumn,
+    #[serde(skip)]
+    sort_ascending: bool,
+    #[serde(skip)]
+    selected: BTreeSet<Uuid>,
+    #[serde(skip)]
+    bulk_category: String,
+    #[serde(skip)]
+    bulk_subcategory: Option<String>,
+    #[serde(skip)]
+    bulk_confirm: Option<BulkAction>,
+    #[serde(skip)]
+    bulk_status: Option<String>,
+    #[serde(skip)]
+    show_trash: bool,
+    #[serde(skip)]
+    history: History,
+    #[serde(skip)]
+    toasts: toast::Toasts,
+    // None until the window is up, or when there is no tray
+    #[serde(skip)]
+    tray: Option<tray::Tray>,
+    // Kept in the config file, not the ledger
+    #[serde(skip)]
+    settings: Settings,
+    #[serde(skip)]
+    settings_status: Option<String>,
+    // User script, loaded with the settings
+    #[serde(skip)]
+    hooks: Option<Arc<Hooks>>,
+    #[serde(skip)]
+    hooks_error: Option<String>,
+    // Output of the script's on_report, run from Analytics
+    #[serde(skip)]
+    script_report: Option<Result<String, String>>,
+    #[serde(skip)]
+    show_filters: bool,
+    #[serde(skip)]
+    expanded_note: Option<Uuid>,
+    #[serde(skip)]
+    inline_edit: Option<InlineEdit>,
+    #[serde(skip)]
+    preview_attachment: Option<String>,
+    #[serde(skip)]
+    attachment_error: Option<String>,
+    #[serde(skip)]
+    import_status: Option<String>,
+    #[serde(skip)]
+    import_task: Option<ImportTask>,
+    // Lines of the last import that couldn't be read, worded for the list
+    #[serde(skip)]
+    import_errors: Vec<String>,
+    // Imported rows that matched an existing transaction, waiting for a decision
+    #[serde(skip)]
+    import_review: Vec<ImportReview>,
+    #[serde(skip)]
+    backup_status: Option<String>,
+    // Last save failure, shown above the tabs until a save succeeds
+    #[serde(skip)]
+    save_error: Option<String>,
+    // Shown in the banner above the tabs
+    #[serde(skip)]
+    error: Option<AppError>,
+    // The log window, Ctrl+Shift+D
+    #[serde(skip)]
+    show_diagnostics: bool,
+    // Where this ledger is read from and saved to
+    #[serde(skip)]
+    data_path: PathBuf,
+    #[serde(skip)]
+    data_file_status: Option<String>,
+
+    // Encryption. While `locked` the file on disk hasn't been read yet and must not be overwritten.
+    #[serde(skip)]
+    passphrase: Option<String>,
+    #[serde(skip)]
+    locked: bool,
+    #[serde(skip)]
+    unlock_input: String,
+    #[serde(skip)]
+    unlock_error: Option<String>,
+    #[serde(skip)]
+    new_passphrase: String,
+    #[serde(skip)]
+    confirm_passphrase: String,
+    #[serde(skip)]
+    encryption_status: Option<String>,
+    // Set when the data file exists but couldn't be read. Saving is off so it isn't
+    // overwritten. Worded when shown, the UI language isn't known yet when it's set.
+    #[serde(skip)]
+    load_error: Option<Problem>,
+    #[serde(skip)]
+    export_status: Option<String>,
+    #[serde(skip)]
+    sync_file: Option<PathBuf>,
+    #[serde(skip)]
+    sync_status: Option<String>,
+
+    // Profiles, as listed in the config file
+    #[serde(skip)]
+    profiles: Vec<config::Profile>,
+    #[serde(skip)]
+    profile_name: Option<String>,
+    #[serde(skip)]
+    new_profile_name: String,
+    #[serde(skip)]
+    profile_status: Option<String>,
+
+    // Autosave. `saved_fingerprint` is the state last written to disk; `dirty` is
+    // refreshed from it every few seconds.
+    #[serde(skip)]
+    saved_fingerprint: u64,
+    #[serde(skip)]
+    dirty: bool,
+    #[serde(skip)]
+    last_saved: Option<DateTime<Local>>,
+    #[serde(skip)]
+    last_autosave_check: f64,
+    // Writes saves in the background, only in the window
+    #[serde(skip)]
+    saver: Option<Saver>,
+    // The data file's modification time as of the last load or save. When another
+    // program (a sync client, the command line, a second window) changes it, saves are
+    // held until the user reloads, merges or keeps this version.
+    #[serde(skip)]
+    disk_modified: Option<std::time::SystemTime>,
+    #[serde(skip)]
+    changed_on_disk: bool,
+    // Held while this window has the ledger open. Without it another instance has it
+    // and this one is read-only, so the two don't save over each other.
+    #[serde(skip)]
+    instance_lock: Option<instance::InstanceLock>,
+    #[serde(skip)]
+    read_only: bool,
+
+    // Audit journal: the transactions as last saved, what the next save is down to,
+    // and the journal as shown in the Audit tab (None until it's opened)
+    #[serde(skip)]
+    audit_base: HashMap<Uuid, Transaction>,
+    #[serde(skip)]
+    audit_cause: audit::Cause,
+    #[serde(skip)]
+    audit_log: Option<Vec<audit::Entry>>,
+    // Changes waiting for their save to be written before webhooks and notifications
+    // go out about them, with the fingerprint of the save that has them
+    #[serde(skip)]
+    unannounced: Vec<(u64, Vec<audit::Entry>)>,
+    #[serde(skip)]
+    audit_error: Option<String>,
+    #[serde(skip)]
+    audit_search: String,
+    // Seals the journal and write-ahead log of an encrypted ledger, see journal_key
+    #[serde(skip)]
+    journal_key: Option<crypto::LineKey>,
+    // Bumped by every save, which is how the Analytics cache notices a change
+    #[serde(skip)]
+    revision: u64,
+    #[serde(skip)]
+    aggregates: Aggregates,
+
+    // Category manager state
+    #[serde(skip)]
+    new_cat_name: String,
+    #[serde(skip)]
+    new_cat_color: [u8; 3],
+    #[serde(skip)]
+    new_cat_icon: String,
+    #[serde(skip)]
+    new_cat_kind: CategoryKind,
+    #[serde(skip)]
+    renaming_category: Option<(usize, String)>,
+    #[serde(skip)]
+    new_subcat: Option<(usize, String)>,
+
+    // Exchange rate panel state
+    #[serde(skip)]
+    rate_from: String,
+    #[serde(skip)]
+    rate_to: String,
+    #[serde(skip)]
+    rate_value: String,
+    #[serde(skip)]
+    rate_date: NaiveDate,
+    #[serde(skip)]
+    rate_fetch: Option<RateFetch>,
+    #[serde(skip)]
+    rate_status: Option<String>,
+    home_currency_input: String,
+
+    // Budget editor state
+    #[serde(skip)]
+    budget_category: String,
+    #[serde(skip)]
+    budget_limit: String,
+    // A day in the budget month the Analytics comparison shows
+    #[serde(skip)]
+    budget_chart_day: NaiveDate,
+
+    // Goal editor state
+    #[serde(skip)]
+    goal_name: String,
+    #[serde(skip)]
+    goal_amount: String,
+    #[serde(skip)]
+    goal_date: NaiveDate,
+
+    // Holding editor and price fetch state
+    #[serde(skip)]
+    holding_name: String,
+    #[serde(skip)]
+    holding_symbol: String,
+    #[serde(skip)]
+    holding_quantity: String,
+    #[serde(skip)]
+    price_fetch: Option<PriceFetch>,
+    #[serde(skip)]
+    price_status: Option<String>,
+
+    // Bank pull in progress, and what it brought in until the user has been through it
+    #[cfg(feature = "bank")]
+    #[serde(skip)]
+    bank_pull: Option<bank::BankPull>,
+    #[cfg(feature = "bank")]
+    #[serde(skip)]
+    bank_pulled: Option<bank::Pulled>,
+    #[cfg(feature = "bank")]
+    #[serde(skip)]
+    bank_status: Option<String>,
+
+    // Export to Google Sheets in progress
+    #[cfg(feature = "sheets")]
+    #[serde(skip)]
+    sheets_export: Option<sheets::SheetsExport>,
+    #[cfg(feature = "sheets")]
+    #[serde(skip)]
+    sheets_status: Option<String>,
+    // Last daily export started, so a failing one isn't retried every frame
+    #[cfg(feature = "sheets")]
+    #[serde(skip)]
+    sheets_tried: Option<DateTime<Local>>,
+
+    // Receipt being read for the add form
+    #[serde(skip)]
+    receipt_scan: Option<receipt::ReceiptScan>,
+    #[serde(skip)]
+    receipt_status: Option<String>,
+
+    // Template picker state
+    #[serde(skip)]
+    template_name: String,
+    #[serde(skip)]
+    selected_template: Option<usize>,
+
+    // Rule editor state
+    #[serde(skip)]
+    rule_pattern: String,
+    #[serde(skip)]
+    rule_regex: bool,
+    #[serde(skip)]
+    rule_type: Option<TransactionType>,
+    #[serde(skip)]
+    rule_min: String,
+    #[serde(skip)]
+    rule_max: String,
+    #[serde(skip)]
+    rule_category: String,
+    #[serde(skip)]
+    rule_subcategory: Option<String>,
+    #[serde(skip)]
+    rules_status: Option<String>,
+
+    // Webhook being added in the settings
+    #[serde(skip)]
+    webhook_url: String,
+}
+
+#[derive(PartialEq, Default)]
+enum Tab {
+    #[default]
+    Dashboard,
+    Transactions,
+    Graph,
+    Categories,
+    Rules,
+    Rates,
+    Budgets,
+    Goals,
+    NetWorth,
+    Data,
+    Audit,
+    Settings,
+}
+
+// Widget IDs focus is moved to, by the shortcuts and after a quick add
+const DESC_FIELD: &str = "input_desc";
+
+const SEARCH_FIELD: &str = "search_query";
+
+const QUICK_FIELD: &str = "quick_input";
+
+// How often unsaved changes are looked for and written
+const AUTOSAVE_SECONDS: f64 = 5.0;
+
+fn default_backup_count() -> usize {
+    5
+}
+
+impl Default for FinanceApp {
+    fn default() -> Self {
+        Self {
+            version: migrations::CURRENT_VERSION,
+            transactions: Vec::new(),
+            categories: default_categories(),
+            rates: RateTable::default(),
+            budgets: Vec::new(),
+            goals: Vec::new(),
+            holdings: Vec::new(),
+            prices: Vec::new(),
+            templates: Vec::new(),
+            rules: Vec::new(),
+            money_format: MoneyFormat::default(),
+            opening_balance: 0.0,
+            backup_count: default_backup_count(),
+            deleted: Vec::new(),
+            trash: Vec::new(),
+            opening_input: String::new(),
+            input_date: Local::now().date_naive(), 
+            input_desc: String::new(),
+            input_amount: String::new(),
+            input_type: TransactionType::Expense,
+            input_category: "Food".to_string(),
+            input_subcategory: None,
+            input_currency: "USD".to_string(),
+            input_splits: Vec::new(),
+            input_note: String::new(),
+            input_attachments: Vec::new(),
+            input_goal: None,
+            input_refund_of: None,
+            current_tab: Tab::Dashboard,
+            analytics_chart: AnalyticsChart::default(),
+            customizing_dashboard: false,
+            show_net_line: true,
+            histogram_type: TransactionType::Expense,
+            histogram_category: None,
+            compare_periods: None,
+            chart_rect: None,
+            breakdown_rect: None,
+            pending_capture: None,
+            forecast_months: 3,
+            analytics_range: AnalyticsRange::default(),
+            editing_id: None,
+            draft_id: Uuid::new_v4(),
+            duplicate_of: None,
+            quick_input: String::new(),
+            period: Period::default(),
+            grouping: Grouping::default(),
+            search_query: String::new(),
+            filter: Filter::default(),
+            sort_column: SortColumn::Date,
+            sort_ascending: false,
+            selected: BTreeSet::new(),
+            bulk_category: default_category_name(),
+            bulk_subcategory: None,
+            bulk_confirm: None,
+            bulk_status: None,
+            show_trash: false,
+            history: History::default(),
+            toasts: toast::Toasts::default(),
+            tray: None,
+            settings: Settings::default(),
+            settings_status: None,
+            hooks: None,
+            hooks_error: None,
+            script_report: None,
+            show_filters: false,
+            expanded_note: None,
+            inline_edit: None,
+            preview_attachment: None,
+            attachment_error: None,
+            import_status: None,
+            import_task: None,
+            import_errors: Vec::new(),
+            import_review: Vec::new(),
+            backup_status: None,
+            save_error: None,
+            error: None,
+            show_diagnostics: false,
+            data_path: PathBuf::from(storage::DATA_FILE),
+            data_file_status: None,
+            passphrase: None,
+            locked: false,
+            unlock_input: String::new(),
+            unlock_error: None,
+            new_passphrase: String::new(),
+            confirm_passphrase: String::new(),
+            encryption_status: None,
+            load_error: None,
+            export_status: None,
+            sync_file: None,
+            sync_status: None,
+            profiles: Vec::new(),
+            profile_name: None,
+            new_profile_name: String::new(),
+            profile_status: None,
+            saved_fingerprint: 0,
+            dirty: false,
+            last_saved: None,
+            last_autosave_check: 0.0,
+            saver: None,
+            disk_modified: None,
+            changed_on_disk: false,
+            instance_lock: None,
+            read_only: false,
+            audit_base: HashMap::new(),
+            audit_cause: audit::Cause::default(),
+            audit_log: None,
+            unannounced: Vec::new(),
+            audit_error: None,
+            journal_key: None,
+            audit_search: String::new(),
+            revision: 0,
+            aggregates: Aggregates::default(),
+            new_cat_name: String::new(),
+            new_cat_color: [160, 160, 160],
+            new_cat_icon: String::new(),
+            new_cat_kind: CategoryKind::Expense,
+            renaming_category: None,
+            new_subcat: None,
+            rate_from: String::new(),
+            rate_to: "USD".to_string(),
+            rate_value: String::new(),
+            rate_date: Local::now().date_naive(),
+            rate_fetch: None,
+            rate_status: None,
+            home_currency_input: "USD".to_string(),
+            budget_category: "Food".to_string(),
+            budget_limit: String::new(),
+            budget_chart_day: Local::now().date_naive(),
+            goal_name: String::new(),
+            goal_amount: String::new(),
+            goal_date: Local::now().date_naive() + chrono::Duration::days(365),
+            holding_name: String::new(),
+            holding_symbol: String::new(),
+            holding_quantity: String::new(),
+            price_fetch: None,
+            price_status: None,
+            #[cfg(feature = "bank")]
+            bank_pull: None,
+            #[cfg(feature = "bank")]
+            bank_pulled: None,
+            #[cfg(feature = "bank")]
+            bank_status: None,
+            #[cfg(feature = "sheets")]
+            sheets_export: None,
+            #[cfg(feature = "sheets")]
+            sheets_status: None,
+            #[cfg(feature = "sheets")]
+            sheets_tried: None,
+            receipt_scan: None,
+            receipt_status: None,
+            template_name: String::new(),
+            selected_template: None,
+            rule_pattern: String::new(),
+            rule_regex: false,
+            rule_type: None,
+            rule_min: String::new(),
+            rule_max: String::new(),
+            rule_category: default_category_name(),
+            rule_subcategory: None,
+            rules_status: None,
+            webhook_url: String::new(),
+        }
+    }
+}
+
+impl FinanceApp {
+    fn save_data(&mut self) {
+        self.revision += 1;
+        if self.locked || self.load_error.is_some() || self.read_only {
+            return;
+        }
+        let bytes = match serde_json::to_vec(&self) {
+            Ok(bytes) => bytes,
+            Err(e) => {
+                self.save_error = Some(e.to_string());
+                return;
+            }
+        };
+        let fingerprint = storage::fingerprint(&bytes);
+        self.history.record(bytes.clone());
+        self.record_changes(fingerprint);
+        // Not over someone else's changes; the write-ahead log has these meanwhile
+        if self.changed_on_disk || self.file_changed_elsewhere() {
+            self.changed_on_disk = true;
+            self.dirty = true;
+            return;
+        }
+
+        // The window hands the writing to its saver thread; the command line has none
+        if let Some(saver) = &mut self.saver {
+            saver.queue(saver::Job {
+                path: self.data_path.clone(),
+                bytes,
+                passphrase: self.passphrase.clone(),
+                backup_count: self.backup_count,
+                fingerprint,
+            });
+            return;
+        }
+        // Keep the previous version around before overwriting it
+        let result = storage::backup(&self.data_path, self.backup_count)
+            .map_err(|e| Failure::Backup(e.to_string()))
+            .and_then(|_| self.write_ledger(&self.data_path, bytes).map_err(Failure::Write));
+        self.finish_save(fingerprint, result);
+        self.clear_wal();
+    }
+
+    fn finish_save(&mut self, fingerprint: u64, result: Result<(), Failure>) {
+        let result = result.map_err(|failure| match failure {
+            Failure::Backup(e) => t!("backup-failed", error = e),
+            Failure::Write(e) => e,
+        });
+        match result {
+            Ok(()) => {
+                tracing::info!(path = %self.data_path.display(), "saved");
+                self.disk_modified = storage::modified(&self.data_path);
+                self.save_error = None;
+                self.saved_fingerprint = fingerprint;
+                self.dirty = false;
+                self.last_saved = Some(Local::now());
+                self.announce_saved(fingerprint);
+            }
+            Err(e) => {
+                // Autosave retries, so only a new problem gets a toast and a log line
+                if self.save_error.as_ref() != Some(&e) {
+                    tracing::error!(path = %self.data_path.display(), "save failed: {}", e);
+                    self.toasts.error(t!("save-failed-toast", error = e.clone()));
+                }
+                self.save_error = Some(e);
+                self.dirty = true;
+            }
+        }
+    }
+
+    // Adds what changed since the last save to the write-ahead log and the audit
+    // journal, sealed for an encrypted ledger so they don't give its contents away
+    fn record_changes(&mut self, fingerprint: u64) {
+        let cause = std::mem::take(&mut self.audit_cause);
+        let entries = audit::changes(&self.audit_base, &self.transactions, cause);
+        // A save without changes of its own still writes the ones a failed or deferred
+        // save left behind, so it's marked too
+        if !entries.is_empty() || !self.unannounced.is_empty() {
+            self.unannounced.push((fingerprint, entries.clone()));
+        }
+        if entries.is_empty() {
+            return;
+        }
+        self.audit_base = audit::index(&self.transactions);
+        let key = match self.journal_key() {
+            Ok(key) => key,
+            Err(e) => {
+                // Better a gap in the journal than lines in the clear
+                tracing::warn!("journal key unavailable: {}", e);
+                self.audit_error = Some(e);
+                return;
+            }
+        };
+        // Like the replay and the clear, only with the lock
+        if self.instance_lock.is_some() {
+            if let Err(e) = wal::append(&wal::wal_path(&self.data_path), &entries, key.as_ref()) {
+                tracing::warn!("write-ahead log not written: {}", e);
+            }
+        }
+        self.audit_error = audit::append(&audit::journal_path(&self.data_path), &entries, key.as_ref()).err().map(|e| e.to_string());
+        if let Some(error) = &self.audit_error {
+            tracing::warn!("audit journal not written: {}", error);
+        }
+        if let Some(log) = &mut self.audit_log {
+            log.extend(entries);
+        }
+    }
+
+    // Webhooks and notifications for the changes that the save with this fingerprint
+    // put on disk, and any older ones it carried along
+    fn announce_saved(&mut self, fingerprint: u64) {
+        let Some(last) = self.unannounced.iter().rposition(|(saved, _)| *saved == fingerprint) else { return };
+        let entries: Vec<audit::Entry> = self.unannounced.drain(..=last).flat_map(|(_, entries)| entries).collect();
+        if !entries.is_empty() {
+            self.webhooks_for_changes(&entries);
+            self.notify_for_changes(&entries);
+        }
+    }
+
+    // The key sealing this ledger's journal and write-ahead log, None while the ledger
+    // isn't encrypted. Read from its file, or made, the first time it's needed.
+    fn journal_key(&mut self) -> Result<Option<crypto::LineKey>, String> {
+        let Some(passphrase) = self.passphrase.as_deref() else { return Ok(None) };
+        if self.journal_key.is_none() {
+            let path = audit::key_path(&self.data_path);
+            let key = match crypto::LineKey::load(&path, passphrase)? {
+                Some(key) => key,
+                None => {
+                    let key = crypto::LineKey::generate();
+                    key.save(&path, passphrase)?;
+                    key
+                }
+            };
+            self.journal_key = Some(key);
+        }
+        Ok(self.journal_key.clone())
+    }
+
+    // Rewrites the journal and write-ahead log for a new passphrase: sealed when
+    // encrypting, in the clear when encryption is removed, with the key file to match
+    fn rekey_journal(&mut self, passphrase: Option<&str>) -> Result<(), String> {
+        let old_key = self.journal_key()?;
+        let key = passphrase.map(|_| old_key.clone().unwrap_or_else(crypto::LineKey::generate));
+        let key_path = audit::key_path(&self.data_path);
+        if let (Some(key), Some(passphrase)) = (&key, passphrase) {
+            key.save(&key_path, passphrase)?;
+        }
+        for path in [audit::journal_path(&self.data_path), wal::wal_path(&self.data_path)] {
+            audit::rewrite(&path, old_key.as_ref(), key.as_ref()).map_err(|e| e.to_string())?;
+        }
+        if key.is_none() {
+            match std::fs::remove_file(&key_path) {
+                Err(e) if e.kind() != std::io::ErrorKind::NotFound => return Err(e.to_string()),
+                _ => {}
+            }
+        }
+        self.journal_key = key;
+        Ok(())
+    }
+
+    // Typing the field again fixed whatever the banner was about
+    fn clear_input_error(&mut self) {
+        if !matches!(self.error, Some(AppError::Config(_))) {
+            self.error = None;
+        }
+    }
+
+    // Picks up saves the saver thread finished since the last frame
+    fn poll_saves(&mut self) {
+        let finished = self.saver.as_mut().map(Saver::poll).unwrap_or_default();
+        for saved in finished {
+            self.finish_save(saved.fingerprint, saved.result);
+        }
+        self.clear_wal();
+    }
+
+    // Saves and waits for it, for the places that act on whether it worked
+    fn save_and_wait(&mut self) {
+        self.save_data();
+        let finished = self.saver.as_mut().map(Saver::wait).unwrap_or_default();
+        for saved in finished {
+            self.finish_save(saved.fingerprint, saved.result);
+        }
+        self.clear_wal();
+    }
+
+    // Compares the data file with how it was after the last load or save. Saves still
+    // being written would look like someone else's, so nothing is said until they're done.
+    fn file_changed_elsewhere(&self) -> bool {
+        let busy = self.saver.as_ref().is_some_and(Saver::is_busy);
+        !busy && storage::modified(&self.data_path) != self.disk_modified
+    }
+
+    // Re-reads the data file after another program changed it. With `merge` this
+    // ledger's changes are merged with the file's, otherwise they are dropped.
+    fn reload_changed_file(&mut self, merge: bool) {
+        let fresh = match Self::read_file(&self.data_path, self.passphrase.as_deref()) {
+            Ok(fresh) => fresh,
+            Err(LoadError::Missing) => {
+                self.toasts.error(t!("file-gone", path = self.data_path.display().to_string()));
+                return;
+            }
+            Err(LoadError::Locked) => {
+                self.toasts.error(t!("sync-other-passphrase"));
+                return;
+            }
+            Err(LoadError::Unreadable(e)) => {
+                self.toasts.error(t!("sync-unreadable", error = e.to_string()));
+                return;
+            }
+        };
+        tracing::info!(path = %self.data_path.display(), merge, "reloading after an outside change");
+        let disk_modified = fresh.disk_modified;
+        if merge {
+            let report = sync::merge(self, fresh);
+            self.toasts.info(report.summary());
+        } else {
+            // What the other program changed goes in the journal
+            let audit_base = std::mem::take(&mut self.audit_base);
+            self.replace_ledger(fresh, true);
+            self.audit_base = audit_base;
+            self.refresh_profiles();
+            self.load_settings();
+        }
+        self.disk_modified = disk_modified;
+        self.changed_on_disk = false;
+        self.audit_cause = audit::Cause::Sync;
+        self.save_data();
+    }
+
+    // Opens the current ledger for writing, or read-only when another window or the
+    // terminal UI already has it
+    fn take_instance_lock(&mut self) {
+        self.instance_lock = None;
+        let lock = instance::acquire(&self.data_path);
+        self.hold_instance_lock(lock);
+    }
+
+    // What instance::acquire gave for the current ledger
+    fn hold_instance_lock(&mut self, lock: Result<Option<instance::InstanceLock>, instance::InUse>) {
+        match lock {
+            Ok(lock) => {
+                self.instance_lock = lock;
+                self.read_only = false;
+            }
+            Err(instance::InUse) => {
+                tracing::warn!(path = %self.data_path.display(), "open in another instance, read-only here");
+                self.instance_lock = None;
+                self.read_only = true;
+            }
+        }
+    }
+
+    // Takes over once the other instance has closed, starting from what it saved
+    fn retry_instance_lock(&mut self) {
+        self.take_instance_lock();
+        if !self.read_only {
+            self.reload_changed_file(false);
+        }
+    }
+
+    // Keeps this version, writing it over the other program's
+    fn overwrite_changed_file(&mut self) {
+        self.disk_modified = storage::modified(&self.data_path);
+        self.changed_on_disk = false;
+        self.save_data();
+    }
+
+    // Empties the write-ahead log once every save queued so far is on disk. A failed
+    // save keeps it, the ledger on disk is still missing those changes. Without the lock
+    // the log isn't this window's to empty.
+    fn clear_wal(&mut self) {
+        let busy = self.saver.as_ref().is_some_and(Saver::is_busy);
+        if busy || self.save_error.is_some() || self.dirty || self.instance_lock.is_none() {
+            return;
+        }
+        if let Err(e) = wal::clear(&wal::wal_path(&self.data_path)) {
+            tracing::warn!("write-ahead log not cleared: {}", e);
+        }
+    }
+
+    // Puts back changes from the write-ahead log that never reached the ledger, after a
+    // crash or the power going, and saves them. Only the instance holding the lock does,
+    // the log may be another one's changes still on their way to disk.
+    fn recover_unsaved(&mut self) {
+        if self.locked || self.load_error.is_some() || self.read_only || self.instance_lock.is_none() {
+            return;
+        }
+        let path = wal::wal_path(&self.data_path);
+        let key = match self.journal_key() {
+            Ok(key) => key,
+            Err(e) => {
+                tracing::warn!(path = %path.display(), "write-ahead log unreadable: {}", e);
+                return;
+            }
+        };
+        let entries = match audit::read(&path, key.as_ref()) {
+            Ok(entries) => entries,
+            Err(e) => {
+                tracing::warn!(path = %path.display(), "write-ahead log unreadable: {}", e);
+                return;
+            }
+        };
+        let recovered = wal::replay(&mut self.transactions, entries);
+        if recovered == 0 {
+            return;
+        }
+        tracing::warn!(path = %self.data_path.display(), changes = recovered, "recovered unsaved changes");
+        // Already in the audit journal from before the crash
+        self.audit_base = audit::index(&self.transactions);
+        self.migrate_categories();
+        self.save_data();
+        self.toasts.info(t!("recovered-changes", count = recovered));
+    }
+
+    fn write_ledger(&self, path: &Path, bytes: Vec<u8>) -> Result<(), String> {
+        saver::write_ledger(path, bytes, self.passphrase.as_deref())
+    }
+
+    // Merges the shared copy into this ledger, then writes the result back to both places
+    fn sync_now(&mut self) {
+        let Some(shared) = self.sync_file.clone() else { return };
+        let report = match Self::read_file(&shared, self.passphrase.as_deref()) {
+            Ok(remote) => Some(sync::merge(self, remote)),
+            // Nothing there yet: this ledger becomes the shared copy
+            Err(LoadError::Missing) => None,
+            Err(LoadError::Locked) => {
+                tracing::warn!(shared = %shared.display(), "sync: shared copy has another passphrase");
+                self.sync_status = Some(t!("sync-other-passphrase"));
+                return;
+            }
+            Err(LoadError::Unreadable(e)) => {
+                tracing::warn!(shared = %shared.display(), "sync: shared copy unreadable: {}", e);
+                self.sync_status = Some(t!("sync-unreadable", error = e.to_string()));
+                return;
+            }
+        };
+
+        self.audit_cause = audit::Cause::Sync;
+        self.save_and_wait();
+        if let Some(error) = &self.save_error {
+            self.sync_status = Some(t!("sync-failed", error = error.clone()));
+            return;
+        }
+        let written = serde_json::to_vec(&self)
+            .map_err(|e| e.to_string())
+            .and_then(|bytes| self.write_ledger(&shared, bytes));
+        match &written {
+            Ok(()) => tracing::info!(shared = %shared.display(), "synced"),
+            Err(e) => tracing::error!(shared = %shared.display(), "sync: writing the shared copy failed: {}", e),
+        }
+        self.sync_status = Some(match (written, report) {
+            (Err(e), _) => t!("sync-write-failed", error = e),
+            (Ok(()), Some(report)) => report.summary(),
+            (Ok(()), None) => t!("sync-created"),
+        });
+    }
+
+    fn undo(&mut self) {
+        if let Some(state) = self.history.undo() {
+            self.restore_snapshot(&state);
+        }
+    }
+
+    fn redo(&mut self) {
+        if let Some(state) = self.history.redo() {
+            self.restore_snapshot(&state);
+        }
+    }
+
+    // Puts the ledger data back to a recorded state, leaving the rest of the UI alone
+    fn restore_snapshot(&mut self, bytes: &[u8]) {
+        let Ok(snapshot) = serde_json::from_slice::<FinanceApp>(bytes) else { return };
+        self.transactions = snapshot.transactions;
+        self.categories = snapshot.categories;
+        self.rates = snapshot.rates;
+        self.budgets = snapshot.budgets;
+        self.goals = snapshot.goals;
+        self.holdings = snapshot.holdings;
+        self.prices = snapshot.prices;
+        self.templates = snapshot.templates;
+        self.rules = snapshot.rules;
+        self.money_format = snapshot.money_format;
+        self.opening_balance = snapshot.opening_balance;
+        self.backup_count = snapshot.backup_count;
+        self.deleted = snapshot.deleted;
+        self.trash = snapshot.trash;
+        self.audit_cause = audit::Cause::Undo;
+        self.opening_input = self.money_format.number(self.opening_balance);
+
+        // Stop editing a transaction the undo took away
+        if self.editing_id.is_some_and(|id| self.transaction_index(id).is_none()) {
+            self.editing_id = None;
+            self.clear_inputs();
+        }
+        self.save_data();
+    }
+
+    // Ctrl+S saves, Ctrl+N starts a new transaction, Ctrl+F searches, Esc cancels editing,
+    // Ctrl+Shift+D shows the log.
+    // Ctrl+Z undoes, Ctrl+Y or Ctrl+Shift+Z redoes and Del trashes the selected rows, but
+    // only while no text field has focus (those keep their own undo and Del).
+    fn handle_shortcuts(&mut self, ctx: &egui::Context) {
+        let command = |key| egui::KeyboardShortcut::new(egui::Modifiers::COMMAND, key);
+        let diagnostics = egui::KeyboardShortcut::new(egui::Modifiers::COMMAND | egui::Modifiers::SHIFT, egui::Key::D);
+        let (save, new, find, escape, toggle_diagnostics) = ctx.input_mut(|i| (
+            i.consume_shortcut(&command(egui::Key::S)),
+            i.consume_shortcut(&command(egui::Key::N)),
+            i.consume_shortcut(&command(egui::Key::F)),
+            i.key_pressed(egui::Key::Escape),
+            i.consume_shortcut(&diagnostics),
+        ));
+        if toggle_diagnostics {
+            self.show_diagnostics = !self.show_diagnostics;
+        }
+        if save {
+            self.save_and_wait();
+            if self.save_error.is_none() {
+                self.toasts.info(t!("saved-toast"));
+            }
+        }
+        if new || find {
+            self.current_tab = Tab::Transactions;
+            let field = if new { DESC_FIELD } else { SEARCH_FIELD };
+            ctx.memory_mut(|m| m.request_focus(egui::Id::new(field)));
+        }
+        // An Esc meant for a cell being edited in the list leaves the form alone
+        if escape && self.editing_id.is_some() && self.inline_edit.is_none() {
+            self.cancel_edit();
+        }
+
+        if ctx.wants_keyboard_input() {
+            return;
+        }
+        if self.current_tab == Tab::Transactions
+            && !self.selected.is_empty()
+            && ctx.input(|i| i.key_pressed(egui::Key::Delete))
+        {
+            self.bulk_confirm = Some(BulkAction::Delete);
+        }
+        let (undo, redo) = ctx.input(|i| {
+            let command = i.modifiers.command;
+            let z = i.key_pressed(egui::Key::Z);
+            (
+                command && !i.modifiers.shift && z,
+                command && (i.key_pressed(egui::Key::Y) || (i.modifiers.shift && z)),
+            )
+        });
+        if undo {
+            self.undo();
+        } else if redo {
+            self.redo();
+        }
+    }
+
+    fn current_fingerprint(&self) -> u64 {
+        serde_json::to_vec(self).map(|bytes| storage::fingerprint(&bytes)).unwrap_or_default()
+    }
+
+    // Catches any change that wasn't saved right away (fields that save on focus loss,
+    // failed saves, ...) and writes it out
+    fn autosave(&mut self, ctx: &egui::Context) {
+        let now = ctx.input(|i| i.time);
+        // A save still being written would look like a change that never got saved
+        let busy = self.saver.as_ref().is_some_and(Saver::is_busy);
+        if now - self.last_autosave_check >= AUTOSAVE_SECONDS && !busy {
+            self.last_autosave_check = now;
+            if !self.changed_on_disk && !self.locked && self.load_error.is_none() && self.file_changed_elsewhere() {
+                tracing::warn!(path = %self.data_path.display(), "changed by another program");
+                // Read-only has nothing of its own worth keeping, it just follows along
+                if self.read_only {
+                    self.reload_changed_file(false);
+                } else {
+                    self.changed_on_disk = true;
+                }
+            }
+            self.dirty = self.current_fingerprint() != self.saved_fingerprint;
+            if self.dirty && !self.changed_on_disk {
+                self.save_data();
+            }
+        }
+        // Keep checking while the window is idle
+        ctx.request_repaint_after(std::time::Duration::from_secs_f64(AUTOSAVE_SECONDS));
+    }
+
+    // A missing file starts an empty ledger at that location. An encrypted one
+    // starts locked until the passphrase is entered.
+    // Opens the ledger to work on it. The lock comes first, so no other instance saves
+    // between the read and the lock; then whatever the write-ahead log kept is put back.
+    fn load_data(path: PathBuf) -> Self {
+        let lock = instance::acquire(&path);
+        let mut app = Self::read_data(path);
+        app.hold_instance_lock(lock);
+        app.recover_unsaved();
+        app
+    }
+
+    // The ledger as it is on disk, without the lock or the write-ahead log
+    fn read_data(path: PathBuf) -> Self {
+        let mut app = match Self::read_file(&path, None) {
+            Ok(app) => app,
+            Err(LoadError::Locked) => Self { locked: true, ..Self::default() },
+            Err(LoadError::Unreadable(e)) => {
+                tracing::error!(path = %path.display(), "ledger unreadable: {}", e);
+                Self { load_error: Some(e), ..Self::default() }
+            }
+            Err(LoadError::Missing) => Self::default(),
+        };
+        tracing::info!(path = %path.display(), locked = app.locked, transactions = app.transactions.len(), "opened");
+        app.data_path = path;
+        app.refresh_profiles();
+        app.load_settings();
+        app
+    }
+
+    fn read_file(path: &Path, passphrase: Option<&str>) -> Result<Self, LoadError> {
+        // Only a file that isn't there starts a new ledger; one that can't be read
+        // right now must not be saved over with an empty one
+        let bytes = std::fs::read(path).map_err(|e| match e.kind() {
+            std::io::ErrorKind::NotFound => LoadError::Missing,
+            _ => LoadError::Unreadable(Problem::Other(e.to_string())),
+        })?;
+        let encrypted = crypto::is_encrypted(&bytes);
+        let bytes = if encrypted {
+            let passphrase = passphrase.ok_or(LoadError::Locked)?;
+            crypto::decrypt(&bytes, passphrase).map_err(LoadError::Unreadable)?
+        } else {
+            bytes
+        };
+        let data = serde_json::from_slice::<serde_json::Value>(&bytes)
+            .map_err(|e| LoadError::Unreadable(Problem::Other(e.to_string())))?;
+        let data = migrations::migrate(data).map_err(LoadError::Unreadable)?;
+        let app = serde_json::from_value::<FinanceApp>(data)
+            .map_err(|e| LoadError::Unreadable(Problem::Other(e.to_string())))?;
+
+        // Return loaded app but reset input fields and editing state
+        let mut app = FinanceApp {
+            transactions: app.transactions,
+            categories: app.categories,
+            rates: app.rates,
+            budgets: app.budgets,
+            goals: app.goals,
+            holdings: app.holdings,
+            prices: app.prices,
+            templates: app.templates,
+            rules: app.rules,
+            money_format: app.money_format,
+            opening_balance: app.opening_balance,
+            backup_count: app.backup_count,
+            deleted: app.deleted,
+            trash: app.trash,
+            passphrase: if encrypted { passphrase.map(str::to_string) } else { None },
+            ..Self::default()
+        };
+        app.opening_input = app.money_format.number(app.opening_balance);
+        app.migrate_categories();
+        app.input_category = app.default_category_for(app.input_type);
+        app.input_currency = app.rates.home_currency.clone();
+        app.rate_to = app.rates.home_currency.clone();
+        app.home_currency_input = app.rates.home_currency.clone();
+        app.saved_fingerprint = app.current_fingerprint();
+        app.audit_base = audit::index(&app.transactions);
+        app.disk_modified = storage::modified(path);
+        Ok(app)
+    }
+
+    // Swaps in a ledger read from disk, keeping what belongs to the window rather than
+    // the ledger: the background saver, the tray icon and the open tab. With `same_file`
+    // the path, its instance lock and read-only mode stay as well.
+    fn replace_ledger(&mut self, fresh: Self, same_file: bool) {
+        let saver = self.saver.take();
+        let tray = self.tray.take();
+        let tab = std::mem::take(&mut self.current_tab);
+        let file = same_file.then(|| (std::mem::take(&mut self.data_path), self.instance_lock.take(), self.read_only));
+        *self = fresh;
+        self.saver = saver;
+        self.tray = tray;
+        self.current_tab = tab;
+        if let Some((data_path, instance_lock, read_only)) = file {
+            self.data_path = data_path;
+            self.instance_lock = instance_lock;
+            self.read_only = read_only;
+        }
+    }
+
+    // Reads the encrypted ledger at the current path with the passphrase
+    fn unlock(&mut self, passphrase: &str) -> Result<(), String> {
+        let app = Self::read_file(&self.data_path, Some(passphrase)).map_err(|e| match e {
+            LoadError::Unreadable(e) => e.to_string(),
+            _ => t!("file-unreadable"),
+        })?;
+        self.replace_ledger(app, true);
+        self.refresh_profiles();
+        self.load_settings();
+        self.recover_unsaved();
+        Ok(())
+    }
+
+    // Replaces the ledger with a backup. The current data is backed up by the
+    // save below, so a restore can itself be undone.
+    fn restore_backup(&mut self, path: &Path) {
+        match Self::read_file(path, self.passphrase.as_deref()) {
+            Ok(restored) => {
+                // Keep the current backup and encryption settings
+                let backup_count = self.backup_count;
+                let passphrase = self.passphrase.take();
+                let disk_modified = self.disk_modified;
+                // The restore itself goes in the journal
+                let audit_base = std::mem::take(&mut self.audit_base);
+                self.replace_ledger(restored, true);
+                self.disk_modified = disk_modified;
+                self.backup_count = backup_count;
+                self.passphrase = passphrase;
+                self.audit_base = audit_base;
+                self.audit_cause = audit::Cause::Restore;
+                self.refresh_profiles();
+                self.load_settings();
+                self.current_tab = Tab::Data;
+                self.save_data();
+                self.backup_status = Some(t!("backup-restored", time = storage::backup_label(path)));
+            }
+            Err(LoadError::Locked) => self.backup_status = Some(t!("backup-encrypted")),
+            Err(_) => self.backup_status = Some(t!("backup-unreadable")),
+        }
+    }
+
+    // Opens another ledger file after saving the current one. A file that doesn't
+    // exist yet starts an empty ledger there.
+    fn open_data_file(&mut self, path: PathBuf) {
+        let reopening = path == self.data_path;
+        self.save_and_wait();
+        // Locked before it's read, like at startup. The file's own lock would be in the
+        // way of reopening it, so that one goes first.
+        if reopening {
+            self.instance_lock = None;
+        }
+        let lock = instance::acquire(&path);
+        let opened = match Self::read_file(&path, None) {
+            Ok(opened) => opened,
+            Err(LoadError::Locked) => Self { locked: true, ..Self::default() },
+            Err(LoadError::Missing) => Self::default(),
+            Err(LoadError::Unreadable(_)) => {
+                if reopening {
+                    self.hold_instance_lock(lock);
+                }
+                self.data_file_status = Some(t!("not-a-ledger", path = path.display().to_string()));
+                return;
+            }
+        };
+        // Lets go of the old ledger's lock, the new one's is already held
+        self.replace_ledger(opened, false);
+        self.data_path = path;
+        self.hold_instance_lock(lock);
+        self.data_file_status = config::remember_data_file(&self.data_path).err()
+            .map(|e| t!("opened-not-remembered", error = e.to_string()));
+        self.refresh_profiles();
+        self.load_settings();
+        self.recover_unsaved();
+    }
+
+    // Reads the user settings and script and starts the add form with the preferred type
+    fn load_settings(&mut self) {
+        self.settings = match config::AppConfig::read() {
+            Ok(config) => config.settings,
+            Err(e) => {
+                tracing::warn!("config unreadable: {}", e);
+                self.error = Some(AppError::Config(e));
+                Settings::default()
+            }
+        };
+        i18n::set_language(self.settings.language);
+        self.input_type = self.settings.default_type;
+        self.input_category = self.default_category_for(self.input_type);
+        self.input_subcategory = None;
+        self.load_hooks();
+    }
+
+    fn load_hooks(&mut self) {
+        let loaded = config::hooks_file().map(|path| Hooks::load(&path)).transpose().map(Option::flatten);
+        (self.hooks, self.hooks_error) = match loaded {
+            Ok(hooks) => (hooks.map(Arc::new), None),
+            Err(e) => {
+                tracing::warn!("script not loaded: {}", e);
+                (None, Some(e))
+            }
+        };
+        self.script_report = None;
+    }
+
+    // Adds a transaction the user entered, after the script's on_add, which can change
+    // or refuse it
+    fn add_transaction(&mut self, t: Transaction) -> Result<(), String> {
+        let t = match &self.hooks {
+            Some(hooks) => hooks.on_add(t)?,
+            None => t,
+        };
+        self.transactions.push(t);
+        Ok(())
+    }
+
+    fn save_settings(&mut self) {
+        self.settings_status = config::save_settings(&self.settings).err()
+            .map(|e| t!("settings-save-failed", error = e.to_string()));
+    }
+
+    fn set_ui_scale(&mut self, ctx: &egui::Context, scale: f32) {
+        self.settings.ui_scale = scale;
+        ctx.set_zoom_factor(scale);
+        self.save_settings();
+    }
+
+    // Kept up to date every frame and written to the config file on exit
+    fn track_window(&mut self, ctx: &egui::Context) {
+        // Viewport rects are in UI points, which the zoom scales
+        let zoom = ctx.zoom_factor();
+        let (inner, outer, maximized) = ctx.input(|i| {
+            let viewport = i.viewport();
+            (viewport.inner_rect, viewport.outer_rect, viewport.maximized)
+        });
+        let window = &mut self.settings.window;
+        if let Some(maximized) = maximized {
+            window.maximized = maximized;
+        }
+        if window.maximized {
+            return;
+        }
+        if let Some(inner) = inner {
+            window.size = [inner.width() * zoom, inner.height() * zoom];
+        }
+        if let Some(outer) = outer {
+            window.position = Some([outer.min.x * zoom, outer.min.y * zoom]);
+        }
+    }
+
+    // Ctrl+Plus/Minus zoom too; remember where they left it
+    fn track_zoom(&mut self, ctx: &egui::Context) {
+        let zoom = ctx.zoom_factor();
+        if (zoom - self.settings.ui_scale).abs() > 0.001 {
+            self.settings.ui_scale = zoom;
+            self.save_settings();
+        }
+    }
+
+    // Whatever was picked in the tray icon's menu
+    fn handle_tray(&mut self, ctx: &egui::Context) {
+        while let Some(command) = self.tray.as_ref().and_then(tray::Tray::poll) {
+            match command {
+                TrayCommand::QuickAdd => {
+                    show_window(ctx);
+                    self.current_tab = Tab::Transactions;
+                    ctx.memory_mut(|m| m.request_focus(egui::Id::new(QUICK_FIELD)));
+                }
+                TrayCommand::ToggleWindow => {
+                    if ctx.input(|i| i.viewport().minimized == Some(true)) {
+                        show_window(ctx);
+                    } else {
+                        ctx.send_viewport_cmd(egui::ViewportCommand::Minimized(true));
+                    }
+                }
+                TrayCommand::Quit => ctx.send_viewport_cmd(egui::ViewportCommand::Close),
+            }
+        }
+    }
+
+    fn set_theme(&mut self, ctx: &egui::Context, theme: ThemeChoice) {
+        self.settings.theme = theme;
+        ctx.set_theme(theme.preference());
+        self.save_settings();
+    }
+
+    // Picks up the profile list and this ledger's sync file from the config
+    fn refresh_profiles(&mut self) {
+        let config = config::AppConfig::load();
+        self.sync_file = config.profile_for(&self.data_path).and_then(|p| p.sync_file.clone());
+        self.profile_name = config.profile_for(&self.data_path).map(|p| p.name.clone());
+        self.profiles = config.profiles;
+    }
+
+    // Starts an empty ledger in a new file and switches to it
+    fn create_profile(&mut self, name: &str, path: PathBuf) {
+        let mut fresh = Self { data_path: path.clone(), ..Self::default() };
+        fresh.save_data();
+        if let Some(error) = fresh.save_error {
+            self.profile_status = Some(t!("profile-create-failed", error = error.clone()));
+            return;
+        }
+        match config::add_profile(name, &path) {
+            Ok(()) => {
+                self.new_profile_name.clear();
+                self.profile_status = None;
+                self.open_data_file(path);
+            }
+            Err(e) => self.profile_status = Some(e.to_string()),
+        }
+    }
+
+    // Some(passphrase) encrypts (or re-keys) the ledger, None stores it as plain text again
+    fn set_passphrase(&mut self, passphrase: Option<String>) {
+        let encrypting = passphrase.is_some();
+        // Before the ledger, so the journal is never left readable next to it
+        if let Err(e) = self.rekey_journal(passphrase.as_deref()) {
+            tracing::warn!("journal not re-encrypted: {}", e);
+            self.encryption_status = Some(t!("journal-rekey-failed", error = e));
+            return;
+        }
+        self.passphrase = passphrase;
+        self.save_and_wait();
+        if self.save_error.is_some() {
+            self.encryption_status = Some(t!("ledger-save-failed"));
+            return;
+        }
+
+        self.encryption_status = Some(if encrypting {
+            match storage::remove_plaintext_backups(&self.data_path) {
+                Ok(n) => t!("ledger-encrypted", count = n),
+                Err(e) => t!("ledger-encrypted-backups-kept", error = e.to_string()),
+            }
+        } else {
+            t!("encryption-removed")
+        });
+        self.new_passphrase.clear();
+        self.confirm_passphrase.clear();
+    }
+
+    // Writes the current ledger to a new location and keeps using it there
+    fn move_data_file(&mut self, path: PathBuf) {
+        let old = std::mem::replace(&mut self.data_path, path);
+        // Chosen in the save dialog, so whatever is there may be replaced
+        self.disk_modified = storage::modified(&self.data_path);
+        self.take_instance_lock();
+        self.save_and_wait();
+        if self.save_error.is_none() {
+            self.data_file_status = config::move_profile(&old, &self.data_path).err()
+                .map(|e| t!("saved-not-remembered", error = e.to_string()));
+            self.refresh_profiles();
+        }
+    }
+
+    // Makes sure every category referenced by a transaction exists in the registry.
+    // Older files (or hand-edited ones) may mention names the registry doesn't know.
+    fn migrate_categories(&mut self) {
+        if !self.categories.iter().any(|c| c.name == OTHER_CATEGORY) {
+            self.categories.push(CategoryDef::new(OTHER_CATEGORY, [160, 160, 160], CategoryKind::Both));
+        }
+        for i in 0..self.transactions.len() {
+            let t = &self.transactions[i];
+            if self.find_category(&t.category).is_none() {
+                let kind = match t.trans_type {
+                    TransactionType::Income => CategoryKind::Income,
+                    TransactionType::Expense => CategoryKind::Expense,
+                };
+                let def = CategoryDef::new(&t.category, [160, 160, 160], kind);
+                self.categories.push(def);
+            }
+            if let Some(sub) = &t.subcategory {
+                if let Some(def) = self.categories.iter_mut().find(|c| c.name == t.category) {
+                    if !def.subcategories.contains(sub) {
+                        def.subcategories.push(sub.clone());
+                    }
+                }
+            }
+            for split in &t.splits {
+                if self.find_category(&split.category).is_none() {
+                    self.categories.push(CategoryDef::new(&split.category, [160, 160, 160], CategoryKind::Both));
+                }
+            }
+        }
+    }
+
+    fn transaction_index(&self, id: Uuid) -> Option<usize> {
+        self.transactions.iter().position(|t| t.id == id)
+    }
+
+    fn find_category(&self, name: &str) -> Option<&CategoryDef> {
+        self.categories.iter().find(|c| c.name == name)
+    }
+
+    fn category_color(&self, name: &str) -> Color32 {
+        self.find_category(name).map(|c| c.color32()).unwrap_or(Color32::GRAY)
+    }
+
+    fn category_icon(&self, name: &str) -> &str {
+        self.find_category(name).map(|c| c.icon.as_str()).unwrap_or("")
+    }
+
+    // Transaction amount converted to the home currency at the rate of its date
+    fn home_amount(&self, t: &Transaction) -> f64 {
+        match &t.currency {
+            Some(code) => self.rates.to_home(t.amount, code, t.date.date_naive()),
+            None => t.amount,
+        }
+    }
+
+    // Amount in the home currency formatted with the user's symbol and separators
+    fn money(&self, value: f64) -> String {
+        self.money_format.money(value)
+    }
+
+    // Positive for income, negative for expenses, in the home currency
+    fn signed_amount(&self, t: &Transaction) -> f64 {
+        match t.trans_type {
+            TransactionType::Income => self.home_amount(t),
+            TransactionType::Expense => -self.home_amount(t),
+        }
+    }
+
+    // First category of the given kind, used when switching Income/Expense in the form
+    fn default_category_for(&self, t: TransactionType) -> String {
+        self.categories.iter()
+            .find(|c| c.kind.matches(t) && c.name != OTHER_CATEGORY)
+            .map(|c| c.name.clone())
+            .unwrap_or_else(default_category_name)
+    }
+}
+
+impl eframe::App for FinanceApp {
+    fn on_exit(&mut self, _gl: Option<&eframe::glow::Context>) {
+        self.save_and_wait();
+        self.save_settings();
+    }
+
+    fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+        self.poll_saves();
+        self.poll_import();
+        self.poll_receipt_scan();
+        #[cfg(feature = "bank")]
+        {
+            self.poll_bank_pull();
+            self.show_bank_review(ctx);
+        }
+        #[cfg(feature = "sheets")]
+        self.poll_sheets_export();
+        self.autosave(ctx);
+        self.show_diagnostics_window(ctx);
+        if let Some(ids) = self.toasts.show(ctx) {
+            self.restore_from_trash(&ids);
+        }
+        self.track_zoom(ctx);
+        self.track_window(ctx);
+        self.handle_tray(ctx);
+        self.save_capture(ctx);
+        if !self.locked && self.load_error.is_none() {
+            // Freshly loaded or unlocked ledgers start the undo history from here
+            if !self.history.is_seeded() {
+                if self.purge_trash() {
+                    self.save_data();
+                }
+                if let Ok(bytes) = serde_json::to_vec(&self) {
+                    self.history.reset(bytes);
+                }
+            }
+            self.send_weekly_summary();
+            self.notify_bills_due();
+            self.fetch_rates_if_due(ctx);
+            self.poll_rate_fetch();
+            #[cfg(feature = "sheets")]
+            self.export_to_sheets_if_due(ctx);
+            self.handle_shortcuts(ctx);
+            self.show_filter_panel(ctx);
+        }
+        egui::CentralPanel::default().show(ctx, |ui| {
+            if self.locked {
+                self.show_unlock_ui(ui);
+                return;
+            }
+            if self.load_error.is_some() {
+                self.show_load_error_ui(ui);
+                return;
+            }
+            ui.horizontal(|ui| {
+                ui.selectable_value(&mut self.current_tab, Tab::Dashboard, t!("tab-dashboard"));
+                ui.selectable_value(&mut self.current_tab, Tab::Transactions, t!("tab-transactions"));
+                ui.selectable_value(&mut self.current_tab, Tab::Graph, t!("tab-analytics"));
+                ui.selectable_value(&mut self.current_tab, Tab::Categories, t!("tab-categories"));
+                ui.selectable_value(&mut self.current_tab, Tab::Rules, t!("tab-rules"));
+                ui.selectable_value(&mut self.current_tab, Tab::Budgets, t!("tab-budgets"));
+                ui.selectable_value(&mut self.current_tab, Tab::Goals, t!("tab-goals"));
+                ui.selectable_value(&mut self.current_tab, Tab::NetWorth, t!("tab-net-worth"));
+                ui.selectable_value(&mut self.current_tab, Tab::Rates, t!("tab-currency"));
+                ui.selectable_value(&mut self.current_tab, Tab::Data, t!("tab-data"));
+                ui.selectable_value(&mut self.current_tab, Tab::Audit, t!("tab-audit"));
+                ui.selectable_value(&mut self.current_tab, Tab::Settings, t!("tab-settings"));
+                ui.separator();
+                let filter_label = if self.filter.is_active() { t!("filters-toggle-active") } else { t!("filters-toggle") };
+                ui.toggle_value(&mut self.show_filters, filter_label);
+                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                    self.show_save_indicator(ui);
+                    self.show_theme_toggle(ui);
+                    self.show_undo_buttons(ui);
+                    self.show_profile_switcher(ui);
+                });
+            });
+            if let Some(error) = &self.save_error {
+                ui.colored_label(Color32::RED, t!("save-failed", path = self.data_path.display().to_string(), error = error.clone()));
+            }
+            self.show_error_banner(ui);
+            self.show_changed_on_disk_banner(ui);
+            self.show_read_only_banner(ui);
+            ui.separator();
+
+            match self.current_tab {
+                Tab::Dashboard => self.show_dashboard_ui(ui),
+                Tab::Transactions => self.show_transactions_ui(ui),
+                Tab::Graph => self.show_analytics_ui(ui),
+                Tab::Categories => self.show_categories_ui(ui),
+                Tab::Rules => self.show_rules_ui(ui),
+                Tab::Rates => self.show_rates_ui(ui),
+                Tab::Budgets => self.show_budgets_ui(ui),
+                Tab::Goals => self.show_goals_ui(ui),
+                Tab::NetWorth => self.show_net_worth_ui(ui),
+                Tab::Data => self.show_data_ui(ui),
+                Tab::Audit => self.show_audit_ui(ui),
+                Tab::Settings => self.show_settings_ui(ui),
+            }
+        });
+    }
+}
+
+impl FinanceApp {
+    fn load_demo_data(&mut self) {
+        let mut samples = demo::sample_transactions(Local::now().date_naive(), &t!("demo-note"));
+        // In case the seeded categories were renamed or removed
+        for t in &mut samples {
+            if self.find_category(&t.category).is_none() {
+                t.category = OTHER_CATEGORY.to_string();
+            }
+        }
+        self.transactions.extend(samples);
+        self.save_data();
+    }
+
+    // Balance as of now, or of what the bank has cleared so far
+    fn balance(&self, cleared_only: bool) -> f64 {
+        self.opening_balance + self.transactions.iter()
+            .filter(|t| !t.is_scheduled() && (!cleared_only || t.status.is_cleared()))
+            .map(|t| self.signed_amount(t))
+            .sum::<f64>()
+    }
+
+    // Moves to the trash without saving, so bulk deletes write the file once
+    fn remove_transaction(&mut self, id: Uuid) -> bool {
+        let Some(index) = self.transaction_index(id) else { return false };
+        // If we delete the item being edited, exit edit mode
+        if self.editing_id == Some(id) {
+            self.editing_id = None;
+            self.clear_inputs();
+        }
+
+        let removed = self.transactions.remove(index);
+        let now = Local::now();
+        self.deleted.push(Tombstone { id: removed.id, deleted: now });
+        self.selected.remove(&id);
+        // Refunds of a deleted expense become plain income
+        for t in self.transactions.iter_mut().filter(|t| t.refund_of == Some(removed.id)) {
+            t.refund_of = None;
+            t.touch();
+        }
+        // Receipts stay on disk until the trash is purged
+        self.trash.push(Trashed { transaction: removed, deleted: now });
+        true
+    }
+
+    fn restore_from_trash(&mut self, ids: &[Uuid]) {
+        for id in ids {
+            let Some(index) = self.trash.iter().position(|t| t.transaction.id == *id) else { continue };
+            let mut t = self.trash.remove(index).transaction;
+            // Newer than the tombstone, so syncing keeps it
+            t.touch();
+            self.deleted.retain(|d| d.id != *id);
+            self.transactions.push(t);
+        }
+        self.transactions.sort_by_key(|t| t.date);
+        self.save_data();
+    }
+
+    // Deletes trashed entries for good along with their receipts. None purges everything.
+    fn purge_from_trash(&mut self, id: Option<Uuid>) {
+        let (gone, kept): (Vec<Trashed>, Vec<Trashed>) = std::mem::take(&mut self.trash)
+            .into_iter()
+            .partition(|t| id.is_none_or(|id| t.transaction.id == id));
+        self.trash = kept;
+        for name in gone.iter().flat_map(|t| &t.transaction.attachments) {
+            attachments::remove_attachment(name);
+        }
+    }
+
+    // Drops anything older than the retention period; true if something went
+    fn purge_trash(&mut self) -> bool {
+        let now = Local::now();
+        let expired: Vec<Uuid> = self.trash.iter()
+            .filter(|t| t.is_expired(now))
+            .map(|t| t.transaction.id)
+            .collect();
+        for id in &expired {
+            self.purge_from_trash(Some(*id));
+        }
+        !expired.is_empty()
+    }
+
+    // Copies the last payment of a recurring bill to `day`, where it waits as scheduled
+    fn schedule_bill(&mut self, description: &str, day: NaiveDate) {
+        let key = description.to_lowercase();
+        let Some(last) = self.transactions.iter()
+            .filter(|t| !t.is_scheduled() && t.description.trim().to_lowercase() == key)
+            .max_by_key(|t| t.date)
+        else {
+            return;
+        };
+        let copy = Transaction {
+            id: Uuid::new_v4(),
+            attachments: Vec::new(),
+            status: TransactionStatus::default(),
+            refund_of: None,
+            date: local_from_naive(day.and_time(last.date.time())),
+            modified: Some(Local::now()),
+            ..last.clone()
+        };
+        self.toasts.info(t!("bill-scheduled", description = copy.description.clone(), date = i18n::date(day)));
+        self.transactions.push(copy);
+        self.save_data();
+    }
+
+    // Home-currency total of every transaction allocated to the goal
+    fn goal_saved(&self, goal_id: Uuid) -> f64 {
+        self.transactions.iter()
+            .filter(|t| t.goal == Some(goal_id))
+            .map(|t| self.home_amount(t))
+            .sum()
+    }
+
+    fn rename_category(&mut self, index: usize, new_name: String) {
+        let old_name = self.categories[index].name.clone();
+        if new_name.is_empty() || new_name == old_name || self.find_category(&new_name).is_some() {
+            return;
+        }
+
+        for t in self.transactions.iter_mut() {
+            if t.category == old_name || t.splits.iter().any(|s| s.category == old_name) {
+                t.touch();
+            }
+            if t.category == old_name {
+                t.category = new_name.clone();
+            }
+            for split in t.splits.iter_mut().filter(|s| s.category == old_name) {
+                split.category = new_name.clone();
+            }
+        }
+        if self.input_category == old_name {
+            self.input_category = new_name.clone();
+        }
+        for budget in self.budgets.iter_mut().filter(|b| b.category == old_name) {
+            budget.category = new_name.clone();
+        }
+        for template in self.templates.iter_mut() {
+            if template.category == old_name {
+                template.category = new_name.clone();
+            }
+            for split in template.splits.iter_mut().filter(|s| s.category == old_name) {
+                split.category = new_name.clone();
+            }
+        }
+        for rule in self.rules.iter_mut().filter(|r| r.category == old_name) {
+            rule.category = new_name.clone();
+        }
+        self.categories[index].name = new_name;
+    }
+
+    // Transactions in a deleted category fall back to "Other"
+    fn delete_category(&mut self, index: usize) {
+        let removed = self.categories.remove(index);
+
+        for t in self.transactions.iter_mut() {
+            if t.category == removed.name || t.splits.iter().any(|s| s.category == removed.name) {
+                t.touch();
+            }
+            if t.category == removed.name {
+                t.category = default_category_name();
+                t.subcategory = None;
+            }
+            for split in t.splits.iter_mut().filter(|s| s.category == removed.name) {
+                split.category = default_category_name();
+                split.subcategory = None;
+            }
+        }
+        if self.input_category == removed.name {
+            self.input_category = self.default_category_for(self.input_type);
+            self.input_subcategory = None;
+        }
+        self.budgets.retain(|b| b.category != removed.name);
+        self.rules.retain(|r| r.category != removed.name);
+        for template in self.templates.iter_mut() {
+            if template.category == removed.name {
+                template.category = default_category_name();
+                template.subcategory = None;
+            }
+            for split in template.splits.iter_mut().filter(|s| s.category == removed.name) {
+                split.category = default_category_name();
+                split.subcategory = None;
+            }
+        }
+        if let Some((idx, _)) = self.renaming_category {
+            if idx >= index {
+                self.renaming_category = None;
+            }
+        }
+        if let Some((idx, _)) = self.new_subcat {
+            if idx >= index {
+                self.new_subcat = None;
+            }
+        }
+    }
+
+    // Transactions in a deleted subcategory keep their parent category
+    fn delete_subcategory(&mut self, index: usize, sub: &str) {
+        let parent = &mut self.categories[index];
+        parent.subcategories.retain(|s| s != sub);
+
+        for t in self.transactions.iter_mut() {
+            let mut changed = false;
+            if t.category == parent.name && t.subcategory.as_deref() == Some(sub) {
+                t.subcategory = None;
+                changed = true;
+            }
+            for split in t.splits.iter_mut() {
+                if split.category == parent.name && split.subcategory.as_deref() == Some(sub) {
+                    split.subcategory = None;
+                    changed = true;
+                }
+            }
+            if changed {
+                t.touch();
+            }
+        }
+        if self.input_category == parent.name && self.input_subcategory.as_deref() == Some(sub) {
+            self.input_subcategory = None;
+        }
+        for rule in self.rules.iter_mut() {
+            if rule.category == parent.name && rule.subcategory.as_deref() == Some(sub) {
+                rule.subcategory = None;
+            }
+        }
+    }
+}
+
+// Brought back from minimized, e.g. from the tray
+fn show_window(ctx: &egui::Context) {
+    ctx.send_viewport_cmd(egui::ViewportCommand::Minimized(false));
+    ctx.send_viewport_cmd(egui::ViewportCommand::Focus);
+}
+
+// `--wsl-compat` turns it on, `--no-wsl-compat` off, otherwise it is on only under WSL
+fn wsl_compat() -> bool {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if args.iter().any(|a| a == "--no-wsl-compat") {
+        return false;
+    }
+    args.iter().any(|a| a == "--wsl-compat")
+        || std::env::var_os("WSL_DISTRO_NAME").is_some()
+        || std::fs::read_to_string("/proc/sys/kernel/osrelease").is_ok_and(|r| r.to_lowercase().contains("microsoft"))
+}
+
+// Opens the window; main.rs only calls this
+pub fn run() -> eframe::Result<()> {
+    logging::init();
+    if let Some(command) = cli::command_from_args() {
+        let result = command.and_then(cli::run);
+        // Lets the webhooks for what it saved go out
+        webhooks::wait();
+        if let Err(e) = result {
+            eprintln!("{}", e);
+            std::process::exit(1);
+        }
+        return Ok(());
+    }
+
+    if wsl_compat() {
+        // X11 and software rendering, which work on WSL where Wayland and the GPU often
+        // don't. winit only takes Wayland when WAYLAND_DISPLAY is set.
+        std::env::remove_var("WAYLAND_DISPLAY");
+        std::env::set_var("WINIT_UNIX_BACKEND", "x11");
+        std::env::set_var("LIBGL_ALWAYS_SOFTWARE", "1");
+        tracing::info!("WSL compatibility mode: X11 and software rendering");
+    }
+
+    let mut app = FinanceApp::load_data(config::resolve_data_file());
+    let theme = app.settings.theme;
+    let ui_scale = app.settings.ui_scale;
+    let window = app.settings.window;
+    let start_in_tray = app.settings.start_in_tray;
+
+    let mut viewport = egui::ViewportBuilder::default()
+        .with_inner_size(window.size)
+        .with_maximized(window.maximized);
+    if let Some(position) = window.position {
+        viewport = viewport.with_position(position);
+    }
+    
+    let native_options = eframe::NativeOptions {
+        viewport: viewport
+            .with_transparent(false) 
+            .with_icon(eframe::icon_data::from_png_bytes(&[]).unwrap_or_default()), 
+        vsync: false, 
+        multisampling: 0, 
+        depth_buffer: 0,
+        stencil_buffer: 0,
+        ..Default::default()
+    };
+    
+    eframe::run_native(
+        "Rust Finance Tracker v6", // Bumped version
+        native_options,
+        Box::new(|cc| {
+            // Needed for receipt thumbnails
+            egui_extras::install_image_loaders(&cc.egui_ctx);
+            cc.egui_ctx.set_theme(theme.preference());
+            cc.egui_ctx.set_zoom_factor(ui_scale);
+            app.tray = tray::Tray::start(&cc.egui_ctx);
+            app.saver = Some(Saver::start(&cc.egui_ctx));
+            // Only when the tray icon is there to bring it back
+            if start_in_tray && app.tray.is_some() {
+                cc.egui_ctx.send_viewport_cmd(egui::ViewportCommand::Minimized(true));
+            }
+            Ok(Box::new(app))
+        }),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn switching_ledgers_keeps_the_tray() {
+        let mut app = FinanceApp { tray: Some(tray::Tray::detached()), current_tab: Tab::Data, ..FinanceApp::default() };
+        app.replace_ledger(FinanceApp::default(), false);
+        assert!(app.tray.is_some());
+        assert!(app.current_tab == Tab::Data);
+    }
+
+    #[test]
+    fn only_the_lock_holder_replays_the_log() {
+        let dir = std::env::temp_dir().join(format!("replay-lock-test-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let data_path = dir.join("finance_data.json");
+        let added = model::test_transaction("coffee", 4.5);
+        let entries = audit::changes(&audit::index(&[]), &[added], audit::Cause::Edit);
+        wal::append(&wal::wal_path(&data_path), &entries, None).unwrap();
+
+        // Another window's changes, still on their way to disk
+        for read_only in [true, false] {
+            let mut app = FinanceApp { data_path: data_path.clone(), read_only, ..FinanceApp::default() };
+            app.recover_unsaved();
+            assert!(app.transactions.is_empty());
+        }
+        assert!(wal::wal_path(&data_path).exists());
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+    #[test]
+    fn changing_home_currency_keeps_old_amounts() {
+        let mut euros = model::test_transaction("hotel", 80.0);
+        euros.currency = Some("EUR".to_string());
+        let mut app = FinanceApp {
+            transactions: vec![model::test_transaction("coffee", 4.5), euros],
+            read_only: true,
+            ..FinanceApp::default()
+        };
+        app.change_home_currency(" brl ");
+        assert_eq!(app.rates.home_currency, "BRL");
+        assert_eq!(app.transactions[0].currency.as_deref(), Some("USD"));
+        assert_eq!(app.transactions[1].currency.as_deref(), Some("EUR"));
+    }
+
+    #[test]
+    fn only_a_missing_file_is_a_new_ledger() {
+        let dir = std::env::temp_dir().join(format!("read-file-test-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        assert!(matches!(FinanceApp::read_file(&dir.join("nothing.json"), None), Err(LoadError::Missing)));
+        // A directory where the ledger should be can't be read
+        assert!(matches!(FinanceApp::read_file(&dir, None), Err(LoadError::Unreadable(_))));
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn changes_are_announced_once_written() {
+        let dir = std::env::temp_dir().join(format!("announce-test-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let mut app = FinanceApp { data_path: dir.join("finance_data.json"), ..FinanceApp::default() };
+        app.transactions.push(model::test_transaction("coffee", 4.5));
+
+        // Held back while another window's changes are on disk
+        app.changed_on_disk = true;
+        app.save_data();
+        assert_eq!(app.unannounced.len(), 1);
+
+        app.changed_on_disk = false;
+        app.save_data();
+        assert!(app.unannounced.is_empty());
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
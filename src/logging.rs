@@ -0,0 +1,148 @@
+use chrono::Local;
+use std::collections::VecDeque;
+use std::fmt::Write as _;
+use std::fs::{self, File};
+use std::io::Write as _;
+use std::path::PathBuf;
+use std::sync::{Mutex, OnceLock};
+use tracing::field::{Field, Visit};
+use tracing::{Event, Level, Metadata, Subscriber, span};
+
+// Log of what the app did with files, imports and sync, for when a user reports a
+// problem. Goes to finance_tracker.log in the config folder, which is started over once
+// it gets big with the previous ones kept as .1, .2, ..., and the latest lines stay in
+// memory for the diagnostics window (Ctrl+Shift+D).
+
+const MAX_LOG_BYTES: u64 = 1_000_000;
+const KEPT_LOGS: usize = 3;
+const RECENT_LINES: usize = 500;
+
+struct Log {
+    path: Option<PathBuf>,
+    file: Option<File>,
+    written: u64,
+    recent: VecDeque<String>,
+}
+
+static LOG: OnceLock<Mutex<Log>> = OnceLock::new();
+
+pub fn log_path() -> Option<PathBuf> {
+    dirs::config_dir().map(|dir| dir.join("finance_tracker").join("finance_tracker.log"))
+}
+
+fn numbered(path: &std::path::Path, n: usize) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(format!(".{}", n));
+    PathBuf::from(name)
+}
+
+// Moves finance_tracker.log to .1, .1 to .2 and so on, dropping the oldest
+fn rotate(path: &std::path::Path) {
+    for n in (1..KEPT_LOGS).rev() {
+        let _ = fs::rename(numbered(path, n), numbered(path, n + 1));
+    }
+    let _ = fs::rename(path, numbered(path, 1));
+}
+
+fn open(path: &std::path::Path) -> Option<(File, u64)> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).ok()?;
+    }
+    let file = fs::OpenOptions::new().create(true).append(true).open(path).ok()?;
+    let written = file.metadata().map(|m| m.len()).unwrap_or_default();
+    Some((file, written))
+}
+
+impl Log {
+    fn write(&mut self, line: String) {
+        if let (Some(path), true) = (&self.path, self.written >= MAX_LOG_BYTES) {
+            self.file = None;
+            rotate(path);
+            (self.file, self.written) = open(path).map_or((None, 0), |(file, written)| (Some(file), written));
+        }
+        if let Some(file) = &mut self.file {
+            // Not being able to log is no reason to stop
+            if writeln!(file, "{}", line).is_ok() {
+                self.written += line.len() as u64 + 1;
+            }
+        }
+        if self.recent.len() == RECENT_LINES {
+            self.recent.pop_front();
+        }
+        self.recent.push_back(line);
+    }
+}
+
+// Collects an event's message and fields into one line
+struct Line(String);
+
+impl Visit for Line {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        if field.name() == "message" {
+            let _ = write!(self.0, " {}", value);
+        } else {
+            let _ = write!(self.0, " {}={:?}", field.name(), value);
+        }
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        if field.name() == "message" {
+            let _ = write!(self.0, " {:?}", value);
+        } else {
+            let _ = write!(self.0, " {}={:?}", field.name(), value);
+        }
+    }
+}
+
+// Events only: nothing here uses spans, so they all get the same id and are ignored
+struct Logger;
+
+impl Subscriber for Logger {
+    fn enabled(&self, metadata: &Metadata<'_>) -> bool {
+        // Ours from info up, libraries only when something is wrong
+        let level = if metadata.target().starts_with("finance_tracker") { Level::INFO } else { Level::WARN };
+        *metadata.level() <= level
+    }
+
+    fn new_span(&self, _span: &span::Attributes<'_>) -> span::Id {
+        span::Id::from_u64(1)
+    }
+
+    fn record(&self, _span: &span::Id, _values: &span::Record<'_>) {}
+
+    fn record_follows_from(&self, _span: &span::Id, _follows: &span::Id) {}
+
+    fn event(&self, event: &Event<'_>) {
+        let metadata = event.metadata();
+        let target = metadata.target().strip_prefix("finance_tracker::").unwrap_or(metadata.target());
+        let mut line = Line(format!("{} {:<5} {}:", Local::now().format("%Y-%m-%d %H:%M:%S"), metadata.level(), target));
+        event.record(&mut line);
+        if let Some(log) = LOG.get() {
+            if let Ok(mut log) = log.lock() {
+                log.write(line.0);
+            }
+        }
+    }
+
+    fn enter(&self, _span: &span::Id) {}
+
+    fn exit(&self, _span: &span::Id) {}
+}
+
+// Starts logging for the rest of the run. Without a config folder the lines are only
+// kept in memory.
+pub fn init() {
+    let path = log_path();
+    let (file, written) = path.as_deref().and_then(open).map_or((None, 0), |(file, written)| (Some(file), written));
+    let log = Log { path, file, written, recent: VecDeque::with_capacity(RECENT_LINES) };
+    if LOG.set(Mutex::new(log)).is_ok() {
+        let _ = tracing::subscriber::set_global_default(Logger);
+    }
+}
+
+// Oldest first
+pub fn recent_lines() -> Vec<String> {
+    LOG.get()
+        .and_then(|log| log.lock().ok().map(|log| log.recent.iter().cloned().collect()))
+        .unwrap_or_default()
+}
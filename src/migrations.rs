@@ -0,0 +1,66 @@
+use serde_json::Value;
+
+use crate::error::Problem;
+
+// Bump this and append a step to MIGRATIONS whenever the saved layout changes in a way
+// serde defaults can't cover (renamed fields, restructured data, ...)
+pub const CURRENT_VERSION: u32 = 1;
+
+type Migration = fn(&mut Value) -> Result<(), Problem>;
+
+// MIGRATIONS[n] upgrades a version n file to version n + 1
+const MIGRATIONS: [Migration; CURRENT_VERSION as usize] = [
+    v0_to_v1,
+];
+
+// Upgrades a saved ledger to the current layout before it is deserialized
+pub fn migrate(mut data: Value) -> Result<Value, Problem> {
+    let version = data.get("version").and_then(Value::as_u64).unwrap_or(0) as u32;
+    if version > CURRENT_VERSION {
+        return Err(Problem::NewerFormat { version, supported: CURRENT_VERSION });
+    }
+    if !data.is_object() {
+        return Err(Problem::NotALedger);
+    }
+
+    for (from, step) in MIGRATIONS.iter().enumerate().skip(version as usize) {
+        step(&mut data)?;
+        data["version"] = Value::from(from as u32 + 1);
+    }
+    Ok(data)
+}
+
+// Files from before versioning. Missing IDs, categories, currencies, splits etc. are
+// filled in by serde defaults; the only thing that can't be is a null transaction list.
+fn v0_to_v1(data: &mut Value) -> Result<(), Problem> {
+    match data.get("transactions") {
+        None | Some(Value::Null) => data["transactions"] = Value::Array(Vec::new()),
+        Some(Value::Array(_)) => {}
+        Some(_) => return Err(Problem::NotAList("transactions")),
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn unversioned_file_with_null_transactions() {
+        let data = migrate(json!({ "transactions": null, "categories": [] })).unwrap();
+        assert_eq!(data["transactions"], json!([]));
+        assert_eq!(data["version"], json!(CURRENT_VERSION));
+        assert_eq!(migrate(json!({ "transactions": 3 })), Err(Problem::NotAList("transactions")));
+    }
+
+    #[test]
+    fn file_from_a_newer_version_is_refused() {
+        let version = CURRENT_VERSION + 1;
+        assert_eq!(
+            migrate(json!({ "version": version, "transactions": [] })),
+            Err(Problem::NewerFormat { version, supported: CURRENT_VERSION }),
+        );
+        assert_eq!(migrate(json!([])), Err(Problem::NotALedger));
+    }
+}
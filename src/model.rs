@@ -0,0 +1,382 @@
+use chrono::{DateTime, Datelike, Local, NaiveDate, NaiveDateTime, TimeZone};
+use eframe::egui::Color32;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::i18n::t;
+
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize, Default, Debug)]
+pub enum TransactionType {
+    Income,
+    #[default]
+    Expense,
+}
+
+// Whether the bank has seen the transaction yet
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize, Default, Debug)]
+pub enum TransactionStatus {
+    #[default]
+    Pending,
+    Cleared,
+    Reconciled,
+}
+
+impl TransactionStatus {
+    pub fn icon(&self) -> &'static str {
+        match self {
+            TransactionStatus::Pending => "○",
+            TransactionStatus::Cleared => "✔",
+            TransactionStatus::Reconciled => "🔒",
+        }
+    }
+
+    // Stable English name, used in exports
+    pub fn name(&self) -> &'static str {
+        match self {
+            TransactionStatus::Pending => "Pending",
+            TransactionStatus::Cleared => "Cleared",
+            TransactionStatus::Reconciled => "Reconciled",
+        }
+    }
+
+    pub fn label(&self) -> String {
+        match self {
+            TransactionStatus::Pending => t!("status-pending"),
+            TransactionStatus::Cleared => t!("status-cleared"),
+            TransactionStatus::Reconciled => t!("status-reconciled"),
+        }
+    }
+
+    // Click order in the list: Pending -> Cleared -> Reconciled -> Pending
+    pub fn next(&self) -> Self {
+        match self {
+            TransactionStatus::Pending => TransactionStatus::Cleared,
+            TransactionStatus::Cleared => TransactionStatus::Reconciled,
+            TransactionStatus::Reconciled => TransactionStatus::Pending,
+        }
+    }
+
+    pub fn is_cleared(&self) -> bool {
+        *self != TransactionStatus::Pending
+    }
+}
+
+// Category Registry
+// Categories are user-defined at runtime. Transactions reference them by name.
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize, Default, Debug)]
+pub enum CategoryKind {
+    Income,
+    #[default]
+    Expense,
+    Both,
+}
+
+impl CategoryKind {
+    pub fn matches(&self, t: TransactionType) -> bool {
+        match self {
+            CategoryKind::Both => true,
+            CategoryKind::Income => t == TransactionType::Income,
+            CategoryKind::Expense => t == TransactionType::Expense,
+        }
+    }
+
+    pub fn label(&self) -> String {
+        match self {
+            CategoryKind::Income => t!("income"),
+            CategoryKind::Expense => t!("expense"),
+            CategoryKind::Both => t!("both"),
+        }
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct CategoryDef {
+    pub name: String,
+    pub color: [u8; 3],
+    pub kind: CategoryKind,
+    // One level of nesting (Food → Groceries). Subcategories share the parent's color.
+    #[serde(default)]
+    pub subcategories: Vec<String>,
+    // Emoji shown next to the color chip. Empty means no icon.
+    #[serde(default)]
+    pub icon: String,
+}
+
+impl CategoryDef {
+    pub fn new(name: &str, color: [u8; 3], kind: CategoryKind) -> Self {
+        Self { name: name.to_string(), color, kind, subcategories: Vec::new(), icon: String::new() }
+    }
+
+    pub fn with_icon(mut self, icon: &str) -> Self {
+        self.icon = icon.to_string();
+        self
+    }
+
+    pub fn color32(&self) -> Color32 {
+        Color32::from_rgb(self.color[0], self.color[1], self.color[2])
+    }
+}
+
+// The catch-all category. It always exists and absorbs transactions from deleted categories.
+pub const OTHER_CATEGORY: &str = "Other";
+
+// Seeded on first run and when migrating files written before the registry existed
+// (the names match the old hard-coded enum variants)
+pub fn default_categories() -> Vec<CategoryDef> {
+    vec![
+        // Income Categories
+        CategoryDef::new("Salary", [100, 200, 100], CategoryKind::Income).with_icon("💼"),
+        CategoryDef::new("Business", [100, 255, 100], CategoryKind::Income).with_icon("🏢"),
+        CategoryDef::new("Investments", [50, 150, 50], CategoryKind::Income).with_icon("📈"),
+        CategoryDef::new("Gifts", [150, 255, 150], CategoryKind::Income).with_icon("🎁"),
+
+        // Expense Categories
+        CategoryDef::new("Food", [255, 100, 100], CategoryKind::Expense).with_icon("🍔"),
+        CategoryDef::new("Housing", [200, 50, 50], CategoryKind::Expense).with_icon("🏠"),
+        CategoryDef::new("Transport", [100, 100, 255], CategoryKind::Expense).with_icon("🚗"),
+        CategoryDef::new("Utilities", [100, 200, 255], CategoryKind::Expense).with_icon("💡"),
+        CategoryDef::new("Entertainment", [255, 165, 0], CategoryKind::Expense).with_icon("🎬"),
+        CategoryDef::new("Shopping", [255, 105, 180], CategoryKind::Expense).with_icon("🛍"),
+        CategoryDef::new("Health", [255, 50, 50], CategoryKind::Expense).with_icon("💊"),
+        CategoryDef::new("Education", [150, 100, 255], CategoryKind::Expense).with_icon("🎓"),
+
+        // Universal
+        CategoryDef::new(OTHER_CATEGORY, [160, 160, 160], CategoryKind::Both).with_icon("❓"),
+    ]
+}
+
+pub fn default_category_name() -> String {
+    OTHER_CATEGORY.to_string()
+}
+
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+pub struct Transaction {
+    // Stable identity for edit/delete, import dedup and sync. Older files get fresh IDs on load.
+    #[serde(default = "Uuid::new_v4")]
+    pub id: Uuid,
+    pub description: String,
+    pub amount: f64,
+    pub trans_type: TransactionType,
+    #[serde(default = "default_category_name")]
+    pub category: String,
+    #[serde(default)]
+    pub subcategory: Option<String>,
+    // None means the home currency
+    #[serde(default)]
+    pub currency: Option<String>,
+    // When non-empty, the amount is divided across these instead of `category`
+    #[serde(default)]
+    pub splits: Vec<Split>,
+    #[serde(default)]
+    pub note: String,
+    // File names inside the attachments folder
+    #[serde(default)]
+    pub attachments: Vec<String>,
+    // Savings goal this transaction contributes to
+    #[serde(default)]
+    pub goal: Option<Uuid>,
+    #[serde(default)]
+    pub status: TransactionStatus,
+    // For income: the expense this refunds. Analytics net it against that expense's categories.
+    #[serde(default)]
+    pub refund_of: Option<Uuid>,
+    // Stored with its UTC offset. Files from before this change have naive timestamps,
+    // which are read as local time.
+    #[serde(deserialize_with = "deserialize_local_datetime")]
+    pub date: DateTime<Local>,
+    // Last change, used to pick a side when syncing. None for entries from older files.
+    #[serde(default)]
+    pub modified: Option<DateTime<Local>>,
+}
+
+pub fn deserialize_local_datetime<'de, D>(deserializer: D) -> Result<DateTime<Local>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let raw = String::deserialize(deserializer)?;
+    if let Ok(dt) = DateTime::parse_from_rfc3339(&raw) {
+        return Ok(dt.with_timezone(&Local));
+    }
+    raw.parse::<NaiveDateTime>()
+        .map(local_from_naive)
+        .map_err(serde::de::Error::custom)
+}
+
+// Wall-clock time to a local timestamp. Times skipped by a DST change are read as UTC.
+pub fn local_from_naive(naive: NaiveDateTime) -> DateTime<Local> {
+    Local.from_local_datetime(&naive)
+        .earliest()
+        .unwrap_or_else(|| Local.from_utc_datetime(&naive))
+}
+
+// Months counted from year 0, so consecutive months are consecutive numbers
+pub fn month_index(day: NaiveDate) -> i32 {
+    day.year() * 12 + day.month0() as i32
+}
+
+// Plot x values are UTC timestamps; axis labels and tooltips show them in local time
+pub fn local_from_timestamp(secs: f64) -> Option<DateTime<Local>> {
+    DateTime::from_timestamp(secs as i64, 0).map(|dt| dt.with_timezone(&Local))
+}
+
+// A portion of a transaction, in the transaction's own currency
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+pub struct Split {
+    pub category: String,
+    #[serde(default)]
+    pub subcategory: Option<String>,
+    pub amount: f64,
+}
+
+impl Transaction {
+    pub fn touch(&mut self) {
+        self.modified = Some(Local::now());
+    }
+
+    pub fn category_path(&self) -> String {
+        if self.splits.is_empty() {
+            category_path(&self.category, self.subcategory.as_deref())
+        } else {
+            let names: Vec<String> = self.splits.iter()
+                .map(|s| category_path(&s.category, s.subcategory.as_deref()))
+                .collect();
+            names.join(" + ")
+        }
+    }
+
+    // Dated in the future: not part of the current balance yet
+    pub fn is_scheduled(&self) -> bool {
+        self.date > Local::now()
+    }
+
+    // Every word of the query has to match the description (the payee), note, category
+    // or amount. Words match as substrings or loosely, see fuzzy_match.
+    pub fn matches_search(&self, query: &str) -> bool {
+        let fields = [
+            self.description.to_lowercase(),
+            self.note.to_lowercase(),
+            self.category_path().to_lowercase(),
+        ];
+        let amount = format!("{:.2}", self.amount);
+
+        query.split_whitespace().all(|word| {
+            let word = word.to_lowercase();
+            // "12.5" or "12,50" finds 12.50
+            let as_number = word.replace(',', ".");
+            if as_number.parse::<f64>().is_ok() && amount.contains(&as_number) {
+                return true;
+            }
+            fields.iter().any(|field| fuzzy_match(field, &word))
+        })
+    }
+
+    // (category, subcategory, fraction of the amount) for analytics
+    pub fn category_shares(&self) -> Vec<(&str, Option<&str>, f64)> {
+        if self.splits.is_empty() || self.amount == 0.0 {
+            return vec![(self.category.as_str(), self.subcategory.as_deref(), 1.0)];
+        }
+        self.splits.iter()
+            .map(|s| (s.category.as_str(), s.subcategory.as_deref(), s.amount / self.amount))
+            .collect()
+    }
+}
+
+// A saved transaction that pre-fills the add form (everything but the date)
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Template {
+    pub name: String,
+    pub description: String,
+    pub amount: f64,
+    pub trans_type: TransactionType,
+    pub category: String,
+    #[serde(default)]
+    pub subcategory: Option<String>,
+    #[serde(default)]
+    pub currency: Option<String>,
+    #[serde(default)]
+    pub splits: Vec<Split>,
+    #[serde(default)]
+    pub note: String,
+}
+
+// Substring match, or for words of 3+ letters, all letters in order with small gaps
+// ("grcry" finds "grocery", "amzn" finds "amazon")
+pub fn fuzzy_match(text: &str, word: &str) -> bool {
+    if text.contains(word) {
+        return true;
+    }
+    if word.chars().count() < 3 {
+        return false;
+    }
+
+    let mut chars = word.chars().peekable();
+    let mut gap = 0;
+    let mut started = false;
+    for c in text.chars() {
+        let Some(&wanted) = chars.peek() else { break };
+        if c == wanted {
+            chars.next();
+            started = true;
+            gap = 0;
+        } else if started {
+            gap += 1;
+            // Letters spread over the whole text don't count as a match
+            if gap > 2 {
+                chars = word.chars().peekable();
+                started = false;
+                gap = 0;
+                if c == word.chars().next().unwrap_or_default() {
+                    chars.next();
+                    started = true;
+                }
+            }
+        }
+    }
+    chars.peek().is_none()
+}
+
+pub fn category_path(category: &str, subcategory: Option<&str>) -> String {
+    match subcategory {
+        Some(sub) => format!("{} → {}", category, sub),
+        None => category.to_string(),
+    }
+}
+
+// A plain expense for tests to start from
+#[cfg(test)]
+pub fn test_transaction(description: &str, amount: f64) -> Transaction {
+    Transaction {
+        id: Uuid::new_v4(),
+        description: description.to_string(),
+        amount,
+        trans_type: TransactionType::Expense,
+        category: "Food".to_string(),
+        subcategory: None,
+        currency: None,
+        splits: Vec::new(),
+        note: String::new(),
+        attachments: Vec::new(),
+        goal: None,
+        status: TransactionStatus::default(),
+        refund_of: None,
+        date: Local.with_ymd_and_hms(2025, 3, 1, 12, 0, 0).unwrap(),
+        modified: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fuzzy_matches() {
+        assert!(fuzzy_match("grocery store", "grcry"));
+        assert!(fuzzy_match("amazon mktplace", "amzn"));
+        assert!(fuzzy_match("coffee", "ff"));
+        // Too short to match loosely
+        assert!(!fuzzy_match("coffee", "cf"));
+        // Letters spread over the whole text
+        assert!(!fuzzy_match("a long monday at the zoo", "amz"));
+        assert!(!fuzzy_match("rent", "rents"));
+    }
+}
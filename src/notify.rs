@@ -0,0 +1,81 @@
+use chrono::{Duration, Local};
+
+use crate::audit::Entry;
+use crate::budgets::WARN_THRESHOLD;
+use crate::error::Problem;
+use crate::i18n::{self, t};
+use crate::FinanceApp;
+
+// Desktop notifications for budgets and bills. Sent over D-Bus to the desktop's
+// notification server, like the tray icon, so only Linux desktops get them so far.
+
+// On its own thread, since D-Bus can take a moment to answer
+pub fn show(summary: String, body: String) {
+    let app_name = t!("tray-title");
+    std::thread::spawn(move || {
+        if let Err(e) = send(&app_name, &summary, &body) {
+            tracing::warn!("notification not shown: {}", e);
+        }
+    });
+}
+
+#[cfg(target_os = "linux")]
+fn send(app_name: &str, summary: &str, body: &str) -> Result<(), Problem> {
+    let connection = zbus::blocking::Connection::session().map_err(|e| e.to_string())?;
+    let hints: std::collections::HashMap<&str, zbus::zvariant::Value> = std::collections::HashMap::new();
+    // Notify(app name, replaces id, icon, summary, body, actions, hints, timeout)
+    connection.call_method(
+        Some("org.freedesktop.Notifications"),
+        "/org/freedesktop/Notifications",
+        Some("org.freedesktop.Notifications"),
+        "Notify",
+        &(app_name, 0u32, "", summary, body, Vec::<&str>::new(), hints, -1i32),
+    ).map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+#[cfg(not(target_os = "linux"))]
+fn send(_app_name: &str, _summary: &str, _body: &str) -> Result<(), Problem> {
+    Err(Problem::NotSupported)
+}
+
+impl FinanceApp {
+    // Called with every save's changes once they're on disk: budgets that went past 80%
+    // or over their limit.
+    // Only the window (the one with a saver) shows them; the command line and the bot
+    // may well have no desktop.
+    pub fn notify_for_changes(&self, entries: &[Entry]) {
+        let wanted = &self.settings.notifications;
+        if self.saver.is_none() || (!wanted.budget_warning && !wanted.budget_exceeded) {
+            return;
+        }
+        for (category, limit, before, after) in self.budget_changes(entries) {
+            let spent = t!("notify-budget-spent", spent = self.money(after), limit = self.money(limit));
+            if wanted.budget_exceeded && before <= limit && after > limit {
+                show(t!("notify-budget-exceeded", category = category), spent);
+            } else if wanted.budget_warning && before < limit * WARN_THRESHOLD && after >= limit * WARN_THRESHOLD && after <= limit {
+                let percent = (after / limit * 100.0).floor() as i64;
+                show(t!("notify-budget-warning", category = category, percent = percent), spent);
+            }
+        }
+    }
+
+    // Bills due tomorrow, looked for once a day while the window is open
+    pub fn notify_bills_due(&mut self) {
+        let today = Local::now().date_naive();
+        // The window that has the ledger open does it
+        if self.read_only || !self.settings.notifications.bills_due || self.settings.notifications.bills_checked == Some(today) {
+            return;
+        }
+        let tomorrow = today + Duration::days(1);
+        let due: Vec<String> = self.upcoming_bills(tomorrow).into_iter()
+            .filter(|(day, ..)| *day == tomorrow)
+            .map(|(_, description, amount, _)| format!("{} ({})", description, self.money(amount)))
+            .collect();
+        if !due.is_empty() {
+            show(t!("notify-bills-due", count = due.len(), date = i18n::date(tomorrow)), due.join("\n"));
+        }
+        self.settings.notifications.bills_checked = Some(today);
+        self.save_settings();
+    }
+}
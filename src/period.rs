@@ -0,0 +1,170 @@
+use chrono::{Datelike, Duration, Local, NaiveDate};
+
+use crate::i18n::{self, t};
+use crate::settings::Settings;
+
+// The stretch of time the transaction list is scoped to, stepped through with the
+// "< December 2024 >" navigator. Calendar months, unlike budgets.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Period {
+    Month { year: i32, month: u32 },
+    Year(i32),
+    All,
+}
+
+impl Default for Period {
+    fn default() -> Self {
+        Self::month_of(Local::now().date_naive())
+    }
+}
+
+impl Period {
+    pub fn month_of(day: NaiveDate) -> Self {
+        Period::Month { year: day.year(), month: day.month() }
+    }
+
+    // First and last day, None for all time
+    pub fn range(&self) -> Option<(NaiveDate, NaiveDate)> {
+        let (first, next) = match *self {
+            Period::Month { year, month } => {
+                let next = if month == 12 { (year + 1, 1) } else { (year, month + 1) };
+                (NaiveDate::from_ymd_opt(year, month, 1)?, NaiveDate::from_ymd_opt(next.0, next.1, 1)?)
+            }
+            Period::Year(year) => (NaiveDate::from_ymd_opt(year, 1, 1)?, NaiveDate::from_ymd_opt(year + 1, 1, 1)?),
+            Period::All => return None,
+        };
+        Some((first, next.pred_opt()?))
+    }
+
+    pub fn contains(&self, day: NaiveDate) -> bool {
+        self.range().is_none_or(|(first, last)| first <= day && day <= last)
+    }
+
+    // One month or year back (-1) or forward (1)
+    pub fn step(&self, by: i32) -> Self {
+        match *self {
+            Period::Month { year, month } => {
+                let index = year * 12 + month as i32 - 1 + by;
+                Period::Month { year: index.div_euclid(12), month: index.rem_euclid(12) as u32 + 1 }
+            }
+            Period::Year(year) => Period::Year(year + by),
+            Period::All => Period::All,
+        }
+    }
+
+    // The year the month is in
+    pub fn to_year(self) -> Self {
+        match self {
+            Period::Month { year, .. } => Period::Year(year),
+            Period::Year(_) => self,
+            Period::All => Period::Year(Local::now().year()),
+        }
+    }
+
+    // Today's month if it is in the year shown, else the year's first
+    pub fn to_month(self) -> Self {
+        let today = Local::now().date_naive();
+        match self {
+            Period::Year(year) if year != today.year() => Period::Month { year, month: 1 },
+            Period::Month { .. } => self,
+            _ => Period::month_of(today),
+        }
+    }
+
+    pub fn label(&self) -> String {
+        match *self {
+            Period::Month { year, month } => NaiveDate::from_ymd_opt(year, month, 1)
+                .map(i18n::month_year)
+                .unwrap_or_default(),
+            Period::Year(year) => year.to_string(),
+            Period::All => t!("all-time"),
+        }
+    }
+}
+
+// Section headers in the transaction list
+#[derive(Clone, Copy, PartialEq, Debug, Default)]
+pub enum Grouping {
+    #[default]
+    None,
+    Day,
+    Month,
+}
+
+impl Grouping {
+    pub const ALL: [Grouping; 3] = [Grouping::None, Grouping::Day, Grouping::Month];
+
+    pub fn label(&self) -> String {
+        match self {
+            Grouping::None => t!("group-none"),
+            Grouping::Day => t!("group-day"),
+            Grouping::Month => t!("group-month"),
+        }
+    }
+
+    // The day that names the group `day` falls in
+    pub fn key(&self, day: NaiveDate) -> Option<NaiveDate> {
+        match self {
+            Grouping::None => None,
+            Grouping::Day => Some(day),
+            Grouping::Month => day.with_day(1),
+        }
+    }
+
+    pub fn title(&self, key: NaiveDate) -> String {
+        match self {
+            Grouping::Month => i18n::month_year(key),
+            _ => format!("{} {}", t!(&format!("weekday-{}", key.weekday().number_from_monday())), i18n::date(key)),
+        }
+    }
+}
+
+// Presets for how much history the Analytics tab covers
+#[derive(Clone, Copy, PartialEq, Debug, Default)]
+pub enum AnalyticsRange {
+    ThisMonth,
+    LastMonth,
+    Last90Days,
+    YearToDate,
+    LastYear,
+    #[default]
+    All,
+}
+
+impl AnalyticsRange {
+    pub const ALL: [AnalyticsRange; 6] = [
+        AnalyticsRange::ThisMonth,
+        AnalyticsRange::LastMonth,
+        AnalyticsRange::Last90Days,
+        AnalyticsRange::YearToDate,
+        AnalyticsRange::LastYear,
+        AnalyticsRange::All,
+    ];
+
+    pub fn label(&self) -> String {
+        match self {
+            AnalyticsRange::ThisMonth => t!("this-month"),
+            AnalyticsRange::LastMonth => t!("last-month"),
+            AnalyticsRange::Last90Days => t!("last-90-days"),
+            AnalyticsRange::YearToDate => t!("year-to-date"),
+            AnalyticsRange::LastYear => t!("last-year"),
+            AnalyticsRange::All => t!("all-time"),
+        }
+    }
+
+    // First and last day, None for all time. Months follow the budget month setting,
+    // like the filter's "This month".
+    pub fn range(&self, today: NaiveDate, settings: &Settings) -> Option<(NaiveDate, NaiveDate)> {
+        match self {
+            AnalyticsRange::ThisMonth => Some(settings.month_containing(today)),
+            AnalyticsRange::LastMonth => Some(settings.previous_month(today)),
+            AnalyticsRange::Last90Days => Some((today - Duration::days(89), today)),
+            AnalyticsRange::YearToDate => Some((today.with_ordinal(1)?, today)),
+            AnalyticsRange::LastYear => {
+                let year = today.year() - 1;
+                Some((NaiveDate::from_ymd_opt(year, 1, 1)?, NaiveDate::from_ymd_opt(year, 12, 31)?))
+            }
+            AnalyticsRange::All => None,
+        }
+    }
+}
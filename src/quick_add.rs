@@ -0,0 +1,174 @@
+use chrono::{Datelike, Duration, NaiveDate, Weekday};
+
+use crate::calc;
+use crate::model::TransactionType;
+
+// What the quick-entry box understood from a line like "coffee 4.50 yesterday" or
+// "salary +3000 jan 31". The category is guessed afterwards from the ledger.
+#[derive(Clone, PartialEq, Debug)]
+pub struct QuickEntry {
+    pub description: String,
+    pub amount: f64,
+    // Set by an explicit + or - on the amount
+    pub trans_type: Option<TransactionType>,
+    pub date: NaiveDate,
+}
+
+// Date words are understood in every UI language
+const MONTHS: [[&str; 12]; 2] = [
+    [
+        "january", "february", "march", "april", "may", "june",
+        "july", "august", "september", "october", "november", "december",
+    ],
+    [
+        "janeiro", "fevereiro", "março", "abril", "maio", "junho",
+        "julho", "agosto", "setembro", "outubro", "novembro", "dezembro",
+    ],
+];
+
+// "jan", "January" and "sept" all give the month. Needs at least three letters so
+// words like "ma" stay in the description.
+fn month_number(word: &str) -> Option<u32> {
+    if word.chars().count() < 3 {
+        return None;
+    }
+    MONTHS.iter()
+        .find_map(|names| names.iter().position(|m| m.starts_with(word)))
+        .map(|i| i as u32 + 1)
+}
+
+fn weekday(word: &str) -> Option<Weekday> {
+    let days = [
+        ("monday", Weekday::Mon), ("tuesday", Weekday::Tue), ("wednesday", Weekday::Wed),
+        ("thursday", Weekday::Thu), ("friday", Weekday::Fri), ("saturday", Weekday::Sat),
+        ("sunday", Weekday::Sun),
+    ];
+    if word.len() < 3 {
+        return None;
+    }
+    days.iter().find(|(name, _)| name.starts_with(word)).map(|(_, day)| *day)
+}
+
+fn day_number(word: &str) -> Option<u32> {
+    let digits = word.trim_end_matches(|c: char| c.is_ascii_alphabetic());
+    let suffix = &word[digits.len()..];
+    if digits.is_empty() || !digits.chars().all(|c| c.is_ascii_digit()) || !matches!(suffix, "" | "st" | "nd" | "rd" | "th") {
+        return None;
+    }
+    digits.parse().ok().filter(|d| (1..=31).contains(d))
+}
+
+fn year_number(word: &str) -> Option<i32> {
+    if word.len() != 4 || !word.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+    word.parse().ok()
+}
+
+// "4.50", "+3000", "-12", "$4.50", "1,234.56", "1.234,56" and "4,50", the digits read
+// as the amount fields read them (calc::parse_number)
+fn parse_amount(word: &str) -> Option<(f64, Option<TransactionType>)> {
+    let (sign, rest) = match word.chars().next()? {
+        '+' => (Some(TransactionType::Income), &word[1..]),
+        '-' => (Some(TransactionType::Expense), &word[1..]),
+        _ => (None, word),
+    };
+    // A currency symbol in front is fine, letters are not
+    let rest = rest.trim_start_matches(|c: char| !c.is_alphanumeric());
+    if !rest.starts_with(|c: char| c.is_ascii_digit()) || !rest.chars().all(|c| c.is_ascii_digit() || c == '.' || c == ',') {
+        return None;
+    }
+    let amount = calc::parse_number(rest)?;
+    (amount > 0.0).then_some((amount, sign))
+}
+
+// Reads the date words out of `words`, removing them. Today when there are none.
+fn take_date(words: &mut Vec<String>, today: NaiveDate) -> NaiveDate {
+    for i in 0..words.len() {
+        let word = words[i].to_lowercase();
+        let relative = match word.as_str() {
+            "today" | "hoje" => Some(0),
+            "yesterday" | "ontem" => Some(-1),
+            "tomorrow" | "amanhã" => Some(1),
+            _ => None,
+        };
+        if let Some(days) = relative {
+            words.remove(i);
+            return today + Duration::days(days);
+        }
+
+        // 2024-01-31
+        if let Ok(date) = NaiveDate::parse_from_str(&word, "%Y-%m-%d") {
+            words.remove(i);
+            return date;
+        }
+
+        // "jan 31", "31 jan", "january 31st 2024"
+        if let Some(month) = month_number(&word) {
+            let after = words.get(i + 1).and_then(|w| day_number(&w.to_lowercase()));
+            let before = i.checked_sub(1).and_then(|j| day_number(&words[j].to_lowercase()));
+            let (day, start) = match (after, before) {
+                (Some(day), _) => (day, i),
+                (None, Some(day)) => (day, i - 1),
+                (None, None) => continue,
+            };
+            let mut end = start + 2;
+            let year = words.get(end).and_then(|w| year_number(w));
+            if year.is_some() {
+                end += 1;
+            }
+            if let Some(date) = NaiveDate::from_ymd_opt(year.unwrap_or(today.year()), month, day) {
+                words.drain(start..end);
+                return date;
+            }
+        }
+
+        // "friday" is the last one, today included
+        if let Some(day) = weekday(&word) {
+            let back = (today.weekday().num_days_from_monday() + 7 - day.num_days_from_monday()) % 7;
+            words.remove(i);
+            return today - Duration::days(back as i64);
+        }
+    }
+    today
+}
+
+pub fn parse(input: &str, today: NaiveDate) -> Option<QuickEntry> {
+    let mut words: Vec<String> = input.split_whitespace().map(str::to_string).collect();
+    let date = take_date(&mut words, today);
+    let index = words.iter().position(|w| parse_amount(w).is_some())?;
+    let (amount, trans_type) = parse_amount(&words.remove(index))?;
+    let description = words.join(" ");
+    if description.is_empty() {
+        return None;
+    }
+    Some(QuickEntry { description, amount, trans_type, date })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn amounts_in_either_format() {
+        assert_eq!(parse_amount("4,50"), Some((4.5, None)));
+        assert_eq!(parse_amount("1.234,56"), Some((1234.56, None)));
+        assert_eq!(parse_amount("1,234.56"), Some((1234.56, None)));
+        assert_eq!(parse_amount("+3000"), Some((3000.0, Some(TransactionType::Income))));
+        assert_eq!(parse_amount("-12"), Some((12.0, Some(TransactionType::Expense))));
+        assert_eq!(parse_amount("$4,50"), Some((4.5, None)));
+        assert_eq!(parse_amount("4..50"), None);
+        assert_eq!(parse_amount("0"), None);
+        assert_eq!(parse_amount("abc"), None);
+    }
+
+    #[test]
+    fn line_with_date() {
+        let today = NaiveDate::from_ymd_opt(2025, 3, 12).unwrap();
+        let entry = parse("almoço 1.234,56 ontem", today).unwrap();
+        assert_eq!(entry.description, "almoço");
+        assert_eq!(entry.amount, 1234.56);
+        assert_eq!(entry.date, NaiveDate::from_ymd_opt(2025, 3, 11).unwrap());
+        assert_eq!(parse("4.50", today), None);
+    }
+}
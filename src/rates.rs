@@ -0,0 +1,163 @@
+use eframe::egui;
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::mpsc::{channel, Receiver};
+
+// Public API used for the optional daily rate fetch (ECB reference rates, no key needed)
+const RATES_API_URL: &str = "https://api.frankfurter.app/latest";
+
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize, Debug)]
+pub enum RateSource {
+    Manual,
+    Fetched,
+}
+
+// 1 unit of `from` is worth `rate` units of `to` on `date`
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Rate {
+    pub from: String,
+    pub to: String,
+    pub date: NaiveDate,
+    pub rate: f64,
+    pub source: RateSource,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct RateTable {
+    pub home_currency: String,
+    pub rates: Vec<Rate>,
+    // Fetch the latest rates the first time the ledger is open on a day
+    #[serde(default)]
+    pub daily_fetch: bool,
+    #[serde(default)]
+    pub fetched_on: Option<NaiveDate>,
+}
+
+impl Default for RateTable {
+    fn default() -> Self {
+        Self {
+            home_currency: "USD".to_string(),
+            rates: Vec::new(),
+            daily_fetch: false,
+            fetched_on: None,
+        }
+    }
+}
+
+impl RateTable {
+    // One rate per pair and day. A manual entry is never overwritten by a fetched one.
+    pub fn set_rate(&mut self, from: &str, to: &str, date: NaiveDate, rate: f64, source: RateSource) {
+        let from = from.trim().to_uppercase();
+        let to = to.trim().to_uppercase();
+        if from.is_empty() || to.is_empty() || from == to || rate <= 0.0 {
+            return;
+        }
+
+        if let Some(existing) = self.rates.iter_mut().find(|r| r.from == from && r.to == to && r.date == date) {
+            if existing.source == RateSource::Manual && source == RateSource::Fetched {
+                return;
+            }
+            existing.rate = rate;
+            existing.source = source;
+        } else {
+            self.rates.push(Rate { from, to, date, rate, source });
+            self.rates.sort_by(|a, b| (&a.from, &a.to, a.date).cmp(&(&b.from, &b.to, b.date)));
+        }
+    }
+
+    // Most recent rate on or before `date`, falling back to the earliest known rate.
+    // Inverse pairs are used when only the opposite direction was recorded.
+    pub fn rate_on(&self, from: &str, to: &str, date: NaiveDate) -> Option<f64> {
+        if from == to {
+            return Some(1.0);
+        }
+        Self::closest(self.rates.iter().filter(|r| r.from == from && r.to == to), date)
+            .map(|r| r.rate)
+            .or_else(|| {
+                Self::closest(self.rates.iter().filter(|r| r.from == to && r.to == from), date)
+                    .map(|r| 1.0 / r.rate)
+            })
+    }
+
+    fn closest<'a>(rates: impl Iterator<Item = &'a Rate>, date: NaiveDate) -> Option<&'a Rate> {
+        let candidates: Vec<&Rate> = rates.collect();
+        candidates.iter()
+            .filter(|r| r.date <= date)
+            .max_by_key(|r| r.date)
+            .or_else(|| candidates.iter().min_by_key(|r| r.date))
+            .copied()
+    }
+
+    pub fn convert(&self, amount: f64, from: &str, to: &str, date: NaiveDate) -> Option<f64> {
+        self.rate_on(from, to, date).map(|rate| amount * rate)
+    }
+
+    // Amount in the home currency. Without a known rate the raw amount is used as-is.
+    pub fn to_home(&self, amount: f64, currency: &str, date: NaiveDate) -> f64 {
+        self.convert(amount, currency, &self.home_currency, date).unwrap_or(amount)
+    }
+
+    pub fn has_rate(&self, currency: &str) -> bool {
+        currency == self.home_currency
+            || self.rates.iter().any(|r| (r.from == currency && r.to == self.home_currency)
+                || (r.to == currency && r.from == self.home_currency))
+    }
+
+    // Home currency first, then every other currency mentioned in the table
+    pub fn currencies(&self) -> Vec<String> {
+        let mut list: Vec<String> = self.rates.iter()
+            .flat_map(|r| [r.from.clone(), r.to.clone()])
+            .filter(|c| *c != self.home_currency)
+            .collect();
+        list.sort();
+        list.dedup();
+        list.insert(0, self.home_currency.clone());
+        list
+    }
+
+    pub fn apply_fetched(&mut self, fetched: &FetchedRates) {
+        for (code, rate) in &fetched.rates {
+            self.set_rate(&fetched.base, code, fetched.date, *rate, RateSource::Fetched);
+        }
+    }
+}
+
+// Response shape of the rates API
+#[derive(Deserialize)]
+pub struct FetchedRates {
+    pub base: String,
+    pub date: NaiveDate,
+    pub rates: HashMap<String, f64>,
+}
+
+// A fetch running in the background. Poll it once per frame.
+pub struct RateFetch {
+    rx: Receiver<Result<FetchedRates, String>>,
+}
+
+impl RateFetch {
+    pub fn poll(&self) -> Option<Result<FetchedRates, String>> {
+        self.rx.try_recv().ok()
+    }
+}
+
+pub fn fetch_latest(base: &str, ctx: &egui::Context) -> RateFetch {
+    let (tx, rx) = channel();
+    let ctx = ctx.clone();
+    let request = ehttp::Request::get(format!("{}?from={}", RATES_API_URL, base));
+
+    ehttp::fetch(request, move |result| {
+        let parsed = result.and_then(|response| {
+            if !response.ok {
+                return Err(format!("{} {}", response.status, response.status_text));
+            }
+            let text = response.text().unwrap_or_default();
+            serde_json::from_str::<FetchedRates>(text).map_err(|e| e.to_string())
+        });
+        let _ = tx.send(parsed);
+        ctx.request_repaint();
+    });
+
+    RateFetch { rx }
+}
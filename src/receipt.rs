@@ -0,0 +1,113 @@
+use chrono::{Local, NaiveDate};
+use eframe::egui;
+use regex::Regex;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::mpsc::{Receiver, TryRecvError, channel};
+
+use crate::error::Problem;
+use crate::import;
+
+// Reads a photographed receipt with the tesseract OCR program, when it's installed, and
+// picks out what the add form needs. OCR text is messy, so everything found is only a
+// suggestion the user checks before adding.
+
+// What could be made out, each part None when it couldn't
+pub struct Receipt {
+    pub merchant: Option<String>,
+    pub total: Option<f64>,
+    pub date: Option<NaiveDate>,
+}
+
+// Lines with the amount paid, in the UI languages. The largest amount on them wins,
+// which skips subtotals and tax lines.
+const TOTAL_WORDS: [&str; 6] = ["total", "amount due", "balance due", "to pay", "a pagar", "valor pago"];
+
+fn amounts(line: &str) -> Vec<f64> {
+    let number = Regex::new(r"\d{1,3}(?:[.,\s]\d{3})*[.,]\d{2}\b|\d+[.,]\d{2}\b").expect("valid pattern");
+    number.find_iter(line)
+        .filter_map(|m| import::parse_amount(&m.as_str().replace(' ', "")))
+        .filter(|a| *a > 0.0)
+        .collect()
+}
+
+fn date_in(line: &str, day_first: bool) -> Option<NaiveDate> {
+    let date = Regex::new(r"\b(\d{4})[./-](\d{1,2})[./-](\d{1,2})\b|\b(\d{1,2})[./-](\d{1,2})[./-](\d{4}|\d{2})\b").expect("valid pattern");
+    let caps = date.captures(line)?;
+    let number = |i: usize| caps.get(i).and_then(|m| m.as_str().parse::<u32>().ok());
+    let (year, month, day) = match number(1) {
+        Some(year) => (year as i32, number(2)?, number(3)?),
+        None => {
+            let (a, b) = (number(4)?, number(5)?);
+            let year = number(6)? as i32;
+            let year = if year < 100 { 2000 + year } else { year };
+            // Only one way round can be a date when either is over 12
+            let (month, day) = if (day_first && b <= 12) || a > 12 { (b, a) } else { (a, b) };
+            (year, month, day)
+        }
+    };
+    NaiveDate::from_ymd_opt(year, month, day)
+}
+
+// `day_first` says how to take dates like 03/04/2025, as in the UI language
+pub fn read(text: &str, day_first: bool) -> Receipt {
+    let lines: Vec<&str> = text.lines().map(str::trim).filter(|l| !l.is_empty()).collect();
+    // The shop's name is usually printed first
+    let merchant = lines.iter()
+        .find(|l| l.chars().filter(|c| c.is_alphabetic()).count() >= 3)
+        .map(|l| l.split_whitespace().collect::<Vec<_>>().join(" "));
+    let total = lines.iter()
+        .filter(|l| {
+            let lower = l.to_lowercase();
+            TOTAL_WORDS.iter().any(|w| lower.contains(w)) && !lower.contains("subtotal")
+        })
+        .flat_map(|l| amounts(l))
+        .max_by(f64::total_cmp);
+    let today = Local::now().date_naive();
+    let date = lines.iter()
+        .filter_map(|l| date_in(l, day_first))
+        .find(|d| *d <= today);
+    Receipt { merchant, total, date }
+}
+
+fn recognize(path: &Path) -> Result<String, Problem> {
+    let output = Command::new("tesseract")
+        .arg(path)
+        .arg("stdout")
+        .output()
+        .map_err(|e| match e.kind() {
+            std::io::ErrorKind::NotFound => Problem::TesseractMissing,
+            _ => Problem::Other(e.to_string()),
+        })?;
+    if !output.status.success() {
+        return Err(Problem::Other(String::from_utf8_lossy(&output.stderr).trim().to_string()));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+// OCR running in the background, which takes a few seconds. Poll it once per frame.
+pub struct ReceiptScan {
+    rx: Receiver<Result<Receipt, Problem>>,
+}
+
+impl ReceiptScan {
+    // None while it's still running
+    pub fn poll(&self) -> Option<Result<Receipt, Problem>> {
+        match self.rx.try_recv() {
+            Ok(result) => Some(result),
+            Err(TryRecvError::Empty) => None,
+            Err(TryRecvError::Disconnected) => Some(Err(Problem::Stopped)),
+        }
+    }
+}
+
+pub fn scan(ctx: &egui::Context, path: PathBuf, day_first: bool) -> ReceiptScan {
+    let (tx, rx) = channel();
+    let ctx = ctx.clone();
+    std::thread::spawn(move || {
+        let result = recognize(&path).map(|text| read(&text, day_first));
+        let _ = tx.send(result);
+        ctx.request_repaint();
+    });
+    ReceiptScan { rx }
+}
@@ -0,0 +1,190 @@
+use chrono::{Datelike, NaiveDate, NaiveDateTime, Timelike};
+use std::collections::HashMap;
+
+// Lists and figures for the reports under the Analytics charts. Amounts come in already
+// converted to the home currency.
+
+pub struct PayeeTotal {
+    pub description: String,
+    pub total: f64,
+    pub count: usize,
+}
+
+// Spending per description (the payee, mostly), ignoring case, biggest first
+pub fn top_payees<'a>(expenses: impl IntoIterator<Item = (&'a str, f64)>, limit: usize) -> Vec<PayeeTotal> {
+    let mut totals: HashMap<String, PayeeTotal> = HashMap::new();
+    for (description, amount) in expenses {
+        let description = description.trim();
+        let entry = totals.entry(description.to_lowercase()).or_insert_with(|| PayeeTotal {
+            description: description.to_string(),
+            total: 0.0,
+            count: 0,
+        });
+        entry.total += amount;
+        entry.count += 1;
+    }
+    let mut payees: Vec<PayeeTotal> = totals.into_values().collect();
+    payees.sort_by(|a, b| b.total.total_cmp(&a.total));
+    payees.truncate(limit);
+    payees
+}
+
+pub struct SpendStats {
+    pub total: f64,
+    pub average_daily: f64,
+    pub average_expense: f64,
+    pub median_expense: f64,
+    // The day with the most spent, and how much
+    pub busiest_day: Option<(NaiveDate, f64)>,
+    pub no_spend_days: i64,
+}
+
+// Figures for the expenses between `from` and `to`, both included. Days without any
+// count towards the daily average.
+pub fn spend_stats(expenses: &[(NaiveDate, f64)], from: NaiveDate, to: NaiveDate) -> SpendStats {
+    let days = (to - from).num_days() + 1;
+    let mut amounts: Vec<f64> = expenses.iter().map(|(_, amount)| *amount).collect();
+    amounts.sort_by(f64::total_cmp);
+    let total: f64 = amounts.iter().sum();
+    let median_expense = match amounts.len() {
+        0 => 0.0,
+        n if n % 2 == 1 => amounts[n / 2],
+        n => (amounts[n / 2 - 1] + amounts[n / 2]) / 2.0,
+    };
+
+    let mut per_day: HashMap<NaiveDate, f64> = HashMap::new();
+    for (day, amount) in expenses {
+        *per_day.entry(*day).or_default() += amount;
+    }
+    // Earliest of equally busy days, so it doesn't change from frame to frame
+    let busiest_day = per_day.iter()
+        .map(|(day, amount)| (*day, *amount))
+        .max_by(|a, b| a.1.total_cmp(&b.1).then(b.0.cmp(&a.0)));
+
+    SpendStats {
+        total,
+        average_daily: if days > 0 { total / days as f64 } else { 0.0 },
+        average_expense: if amounts.is_empty() { 0.0 } else { total / amounts.len() as f64 },
+        median_expense,
+        busiest_day,
+        no_spend_days: (days - per_day.len() as i64).max(0),
+    }
+}
+
+pub struct AmountBucket {
+    pub from: f64,
+    // Not included
+    pub to: f64,
+    pub count: usize,
+    pub total: f64,
+}
+
+// How many amounts fall in each bucket. Buckets grow 1, 2, 5, 10, 20, 50... so a
+// coffee and the rent both land somewhere readable. Empty buckets at either end are
+// left out.
+pub fn amount_histogram(amounts: &[f64]) -> Vec<AmountBucket> {
+    let largest = amounts.iter().copied().fold(0.0, f64::max);
+    let mut edges = vec![0.0];
+    let mut scale = 1.0;
+    while edges[edges.len() - 1] <= largest {
+        edges.extend([1.0, 2.0, 5.0].map(|step| step * scale));
+        scale *= 10.0;
+    }
+
+    let mut buckets: Vec<AmountBucket> = edges.windows(2)
+        .map(|w| AmountBucket { from: w[0], to: w[1], count: 0, total: 0.0 })
+        .collect();
+    for &amount in amounts {
+        if let Some(bucket) = buckets.iter_mut().find(|b| amount < b.to) {
+            bucket.count += 1;
+            bucket.total += amount;
+        }
+    }
+    let first = buckets.iter().position(|b| b.count > 0).unwrap_or(buckets.len());
+    let last = buckets.iter().rposition(|b| b.count > 0).map_or(0, |i| i + 1);
+    if first >= last {
+        return Vec::new();
+    }
+    buckets.truncate(last);
+    buckets.drain(..first);
+    buckets
+}
+
+pub struct SpendPattern {
+    // Monday first
+    pub by_weekday: [f64; 7],
+    pub by_hour: [f64; 24],
+}
+
+// Average spent on each day of the week, and in each hour of an average day, from
+// `from` to `to`. A Tuesday without expenses still counts as a Tuesday.
+pub fn spend_pattern(expenses: &[(NaiveDateTime, f64)], from: NaiveDate, to: NaiveDate) -> SpendPattern {
+    let mut weekdays = [0u32; 7];
+    for day in from.iter_days().take_while(|day| *day <= to) {
+        weekdays[day.weekday().num_days_from_monday() as usize] += 1;
+    }
+    let days: u32 = weekdays.iter().sum();
+
+    let mut by_weekday = [0.0; 7];
+    let mut by_hour = [0.0; 24];
+    for (time, amount) in expenses {
+        by_weekday[time.weekday().num_days_from_monday() as usize] += amount;
+        by_hour[time.hour() as usize] += amount;
+    }
+    for (total, count) in by_weekday.iter_mut().zip(weekdays) {
+        *total = if count > 0 { *total / count as f64 } else { 0.0 };
+    }
+    for total in &mut by_hour {
+        *total = if days > 0 { *total / days as f64 } else { 0.0 };
+    }
+    SpendPattern { by_weekday, by_hour }
+}
+
+pub struct CategoryChange {
+    pub category: String,
+    pub before: f64,
+    pub after: f64,
+}
+
+impl CategoryChange {
+    pub fn delta(&self) -> f64 {
+        self.after - self.before
+    }
+
+    // None when there was nothing to compare with
+    pub fn percent(&self) -> Option<f64> {
+        (self.before.abs() > 0.005).then(|| self.delta() / self.before * 100.0)
+    }
+}
+
+// Every category in either period, biggest increase first
+pub fn compare_categories(before: &HashMap<String, f64>, after: &HashMap<String, f64>) -> Vec<CategoryChange> {
+    let mut changes: Vec<CategoryChange> = before.keys()
+        .chain(after.keys().filter(|category| !before.contains_key(*category)))
+        .map(|category| CategoryChange {
+            category: category.clone(),
+            before: before.get(category).copied().unwrap_or(0.0),
+            after: after.get(category).copied().unwrap_or(0.0),
+        })
+        .collect();
+    changes.sort_by(|a, b| b.delta().total_cmp(&a.delta()).then_with(|| a.category.cmp(&b.category)));
+    changes
+}
+
+// Spending over the `window` days up to and including each day from `from` to `to`.
+// `expenses` should reach back `window` days before `from` for the first values to be whole.
+pub fn rolling_spend(expenses: &[(NaiveDate, f64)], from: NaiveDate, to: NaiveDate, window: i64) -> Vec<(NaiveDate, f64)> {
+    let mut per_day: HashMap<NaiveDate, f64> = HashMap::new();
+    for (day, amount) in expenses {
+        *per_day.entry(*day).or_default() += amount;
+    }
+    let day_total = |day: NaiveDate| per_day.get(&day).copied().unwrap_or(0.0);
+    let mut running: f64 = (1..window).map(|back| day_total(from - chrono::Duration::days(back))).sum();
+    let mut points = Vec::new();
+    for day in from.iter_days().take_while(|day| *day <= to) {
+        running += day_total(day);
+        points.push((day, running));
+        running -= day_total(day - chrono::Duration::days(window - 1));
+    }
+    points
+}
@@ -0,0 +1,164 @@
+use regex::{Regex, RegexBuilder};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::model::{Transaction, TransactionType};
+
+// User rules that file transactions by their description (the payee) and amount, used
+// when adding, on import and on request for the whole ledger. The first matching rule
+// wins, so their order matters.
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct Rule {
+    pub id: Uuid,
+    // Looked for anywhere in the description, in any case
+    pub pattern: String,
+    // `pattern` is a regular expression instead of plain text
+    #[serde(default)]
+    pub regex: bool,
+    // Only income or only expenses; None matches both
+    #[serde(default)]
+    pub trans_type: Option<TransactionType>,
+    // In the home currency, both ends included
+    #[serde(default)]
+    pub min_amount: Option<f64>,
+    #[serde(default)]
+    pub max_amount: Option<f64>,
+    pub category: String,
+    #[serde(default)]
+    pub subcategory: Option<String>,
+}
+
+// Why a regular expression can't be used, for the editor
+pub fn regex_error(pattern: &str) -> Option<String> {
+    compile(pattern).err().map(|e| e.to_string())
+}
+
+fn compile(pattern: &str) -> Result<Regex, regex::Error> {
+    RegexBuilder::new(pattern).case_insensitive(true).build()
+}
+
+enum Pattern {
+    Text(String),
+    Regex(Regex),
+    // A regular expression that doesn't compile matches nothing
+    Invalid,
+}
+
+// The rules with their patterns prepared, to run over many transactions
+pub struct Matcher<'a> {
+    rules: Vec<(&'a Rule, Pattern)>,
+}
+
+impl<'a> Matcher<'a> {
+    pub fn new(rules: &'a [Rule]) -> Self {
+        let rules = rules.iter()
+            .map(|rule| {
+                let pattern = if rule.regex {
+                    compile(&rule.pattern).map(Pattern::Regex).unwrap_or(Pattern::Invalid)
+                } else {
+                    Pattern::Text(rule.pattern.trim().to_lowercase())
+                };
+                (rule, pattern)
+            })
+            .collect();
+        Self { rules }
+    }
+
+    // `trans_type` None when it isn't known yet, e.g. a quick entry without a sign
+    pub fn find(&self, description: &str, amount: Option<f64>, trans_type: Option<TransactionType>) -> Option<&'a Rule> {
+        let lower = description.to_lowercase();
+        self.rules.iter()
+            .find(|(rule, pattern)| {
+                let text = match pattern {
+                    Pattern::Text(text) => !text.is_empty() && lower.contains(text.as_str()),
+                    Pattern::Regex(regex) => regex.is_match(description),
+                    Pattern::Invalid => false,
+                };
+                let kind = rule.trans_type.is_none_or(|wanted| trans_type.is_none_or(|t| t == wanted));
+                let bounded = rule.min_amount.is_some() || rule.max_amount.is_some();
+                let in_range = match amount {
+                    Some(amount) => rule.min_amount.is_none_or(|min| amount >= min) && rule.max_amount.is_none_or(|max| amount <= max),
+                    None => !bounded,
+                };
+                text && kind && in_range
+            })
+            .map(|(rule, _)| *rule)
+    }
+
+    // Files the transaction by the first matching rule. `amount` is its home amount.
+    // Split transactions are left alone. True when the category changed.
+    pub fn apply(&self, t: &mut Transaction, amount: f64) -> bool {
+        if !t.splits.is_empty() {
+            return false;
+        }
+        let Some(rule) = self.find(&t.description, Some(amount), Some(t.trans_type)) else { return false };
+        if t.category == rule.category && t.subcategory == rule.subcategory {
+            return false;
+        }
+        t.category = rule.category.clone();
+        t.subcategory = rule.subcategory.clone();
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::{Split, test_transaction};
+
+    fn rule(pattern: &str, category: &str) -> Rule {
+        Rule {
+            id: Uuid::new_v4(),
+            pattern: pattern.to_string(),
+            regex: false,
+            trans_type: None,
+            min_amount: None,
+            max_amount: None,
+            category: category.to_string(),
+            subcategory: None,
+        }
+    }
+
+    #[test]
+    fn first_matching_rule_wins() {
+        let mut big = rule("uber", "Travel");
+        big.min_amount = Some(50.0);
+        let rules = vec![big, rule("UBER", "Transport"), rule("uber eats", "Food")];
+        let matcher = Matcher::new(&rules);
+        assert_eq!(matcher.find("Uber Eats order", Some(12.0), None).unwrap().category, "Transport");
+        assert_eq!(matcher.find("uber trip", Some(80.0), None).unwrap().category, "Travel");
+        // Without an amount, rules with bounds are skipped
+        assert_eq!(matcher.find("uber trip", None, None).unwrap().category, "Transport");
+        assert!(matcher.find("bus", Some(2.0), None).is_none());
+    }
+
+    #[test]
+    fn regex_and_type_filters() {
+        let mut salary = rule(r"^acme\s+payroll", "Salary");
+        salary.regex = true;
+        salary.trans_type = Some(TransactionType::Income);
+        let mut broken = rule("(", "Other");
+        broken.regex = true;
+        let rules = vec![broken, salary];
+        let matcher = Matcher::new(&rules);
+        assert!(matcher.find("ACME  Payroll March", Some(3000.0), Some(TransactionType::Income)).is_some());
+        assert!(matcher.find("ACME Payroll March", Some(3000.0), Some(TransactionType::Expense)).is_none());
+        assert!(matcher.find("refund from acme payroll", Some(3000.0), Some(TransactionType::Income)).is_none());
+        assert!(regex_error("(").is_some());
+    }
+
+    #[test]
+    fn apply_leaves_split_transactions_alone() {
+        let rules = vec![rule("coffee", "Drinks")];
+        let matcher = Matcher::new(&rules);
+        let mut t = test_transaction("Coffee shop", 4.5);
+        assert!(matcher.apply(&mut t, 4.5));
+        assert_eq!(t.category, "Drinks");
+        assert!(!matcher.apply(&mut t, 4.5));
+
+        let mut split = test_transaction("Coffee and cake", 9.0);
+        split.splits.push(Split { category: "Food".to_string(), subcategory: None, amount: 9.0 });
+        assert!(!matcher.apply(&mut split, 9.0));
+        assert_eq!(split.category, "Food");
+    }
+}
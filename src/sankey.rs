@@ -0,0 +1,168 @@
+use eframe::egui::{self, epaint::Mesh, Color32, Pos2, Rect, Sense, Stroke, Vec2};
+
+// Sources on the left flowing into one total in the middle, which flows out to the
+// targets on the right. Band thickness is the amount.
+
+pub struct Node {
+    pub label: String,
+    pub value: f64,
+    pub color: Color32,
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Side {
+    Left,
+    Right,
+}
+
+const NODE_WIDTH: f32 = 14.0;
+const GAP: f32 = 8.0;
+// Room for the labels beside the outer columns
+const LABEL_WIDTH: f32 = 160.0;
+const CURVE_STEPS: usize = 24;
+
+// One node in a column, and where its band meets the middle
+struct Placed {
+    rect: Rect,
+    middle_top: f32,
+}
+
+fn place(nodes: &[Node], x: f32, top: f32, scale: f32, middle_top: f32) -> Vec<Placed> {
+    let mut y = top;
+    let mut middle_y = middle_top;
+    nodes.iter()
+        .map(|node| {
+            let height = (node.value as f32 * scale).max(1.0);
+            let placed = Placed {
+                rect: Rect::from_min_size(Pos2::new(x, y), Vec2::new(NODE_WIDTH, height)),
+                middle_top: middle_y,
+            };
+            y += height + GAP;
+            middle_y += height;
+            placed
+        })
+        .collect()
+}
+
+// Top and bottom edge of a band from x1 to x2, eased so it leaves and arrives flat
+fn band(x1: f32, (top1, bottom1): (f32, f32), x2: f32, (top2, bottom2): (f32, f32)) -> Vec<(f32, f32, f32)> {
+    (0..=CURVE_STEPS)
+        .map(|i| {
+            let t = i as f32 / CURVE_STEPS as f32;
+            let ease = t * t * (3.0 - 2.0 * t);
+            (x1 + (x2 - x1) * t, top1 + (top2 - top1) * ease, bottom1 + (bottom2 - bottom1) * ease)
+        })
+        .collect()
+}
+
+fn band_mesh(edges: &[(f32, f32, f32)], color: Color32) -> Mesh {
+    let mut mesh = Mesh::default();
+    for (i, &(x, top, bottom)) in edges.iter().enumerate() {
+        mesh.colored_vertex(Pos2::new(x, top), color);
+        mesh.colored_vertex(Pos2::new(x, bottom), color);
+        if i > 0 {
+            let n = (i * 2) as u32;
+            mesh.add_triangle(n - 2, n - 1, n);
+            mesh.add_triangle(n - 1, n, n + 1);
+        }
+    }
+    mesh
+}
+
+fn band_contains(edges: &[(f32, f32, f32)], pos: Pos2) -> bool {
+    edges.windows(2).any(|w| {
+        let ((x1, top1, bottom1), (x2, top2, bottom2)) = (w[0], w[1]);
+        if pos.x < x1 || pos.x > x2 {
+            return false;
+        }
+        let t = if x2 > x1 { (pos.x - x1) / (x2 - x1) } else { 0.0 };
+        let top = top1 + (top2 - top1) * t;
+        let bottom = bottom1 + (bottom2 - bottom1) * t;
+        pos.y >= top && pos.y <= bottom
+    })
+}
+
+// Draws the diagram. `money` formats amounts for labels and tooltips. Returns the
+// node whose node or band was clicked.
+pub fn show(
+    ui: &mut egui::Ui,
+    left: &[Node],
+    middle: &str,
+    right: &[Node],
+    height: f32,
+    money: &dyn Fn(f64) -> String,
+) -> Option<(Side, usize)> {
+    let width = ui.available_width().max(LABEL_WIDTH * 2.0 + 200.0);
+    let (rect, response) = ui.allocate_exact_size(Vec2::new(width, height), Sense::click());
+    let left_total: f64 = left.iter().map(|n| n.value).sum();
+    let right_total: f64 = right.iter().map(|n| n.value).sum();
+    let total = left_total.max(right_total);
+    if total <= 0.0 {
+        return None;
+    }
+
+    let most_gaps = left.len().max(right.len()).saturating_sub(1) as f32 * GAP;
+    let scale = ((height - most_gaps).max(10.0) as f64 / total) as f32;
+    let middle_height = total as f32 * scale;
+    let middle_rect = Rect::from_center_size(rect.center(), Vec2::new(NODE_WIDTH, middle_height));
+    // Each column is centred on the middle node
+    let column_top = |nodes: &[Node]| {
+        let used = nodes.iter().map(|n| (n.value as f32 * scale).max(1.0)).sum::<f32>() + nodes.len().saturating_sub(1) as f32 * GAP;
+        rect.center().y - used / 2.0
+    };
+    let left_x = rect.left() + LABEL_WIDTH;
+    let right_x = rect.right() - LABEL_WIDTH - NODE_WIDTH;
+    let lefts = place(left, left_x, column_top(left), scale, middle_rect.top());
+    let rights = place(right, right_x, column_top(right), scale, middle_rect.top());
+
+    let pointer = response.hover_pos();
+    let mut hovered = None;
+    let painter = ui.painter_at(rect);
+    let text_color = ui.visuals().text_color();
+    let font = egui::FontId::proportional(13.0);
+
+    for (side, nodes, placed) in [(Side::Left, left, &lefts), (Side::Right, right, &rights)] {
+        for (index, (node, p)) in nodes.iter().zip(placed.iter()).enumerate() {
+            let meets = (p.middle_top, p.middle_top + p.rect.height());
+            let edges = match side {
+                Side::Left => band(p.rect.right(), (p.rect.top(), p.rect.bottom()), middle_rect.left(), meets),
+                Side::Right => band(middle_rect.right(), meets, p.rect.left(), (p.rect.top(), p.rect.bottom())),
+            };
+            let is_hovered = pointer.is_some_and(|pos| p.rect.expand(2.0).contains(pos) || band_contains(&edges, pos));
+            if is_hovered {
+                hovered = Some((side, index));
+            }
+            let alpha = if is_hovered { 0.6 } else { 0.3 };
+            painter.add(band_mesh(&edges, node.color.linear_multiply(alpha)));
+            painter.rect_filled(p.rect, 2.0, node.color);
+
+            let text = format!("{}  {}", node.label, money(node.value));
+            let (anchor, align) = match side {
+                Side::Left => (p.rect.left_center() - Vec2::new(6.0, 0.0), egui::Align2::RIGHT_CENTER),
+                Side::Right => (p.rect.right_center() + Vec2::new(6.0, 0.0), egui::Align2::LEFT_CENTER),
+            };
+            painter.text(anchor, align, text, font.clone(), text_color);
+        }
+    }
+    painter.rect_filled(middle_rect, 2.0, ui.visuals().strong_text_color());
+    painter.rect_stroke(middle_rect, 2.0, Stroke::new(1.0, ui.visuals().panel_fill));
+    painter.text(
+        middle_rect.center_top() - Vec2::new(0.0, 4.0),
+        egui::Align2::CENTER_BOTTOM,
+        format!("{}  {}", middle, money(total)),
+        font,
+        text_color,
+    );
+
+    let (side, index) = hovered?;
+    let node = match side {
+        Side::Left => &left[index],
+        Side::Right => &right[index],
+    };
+    let clicked = response.clicked();
+    response.on_hover_cursor(egui::CursorIcon::PointingHand).on_hover_ui_at_pointer(|ui| {
+        ui.strong(&node.label);
+        ui.label(format!("{} ({:.1}%)", money(node.value), node.value / total * 100.0));
+    });
+    clicked.then_some((side, index))
+}
@@ -0,0 +1,147 @@
+use eframe::egui;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{Receiver, Sender, channel};
+
+use crate::{crypto, storage};
+
+// A serialized ledger on its way to disk
+pub struct Job {
+    pub path: PathBuf,
+    pub bytes: Vec<u8>,
+    pub passphrase: Option<String>,
+    pub backup_count: usize,
+    pub fingerprint: u64,
+}
+
+// Kept apart so the window can word them in its own language, the worker has none
+pub enum Failure {
+    Backup(String),
+    Write(String),
+}
+
+// What became of one or more queued jobs. Jobs that a newer one for the same file
+// overtook share its result.
+pub struct Saved {
+    pub jobs: usize,
+    pub fingerprint: u64,
+    pub result: Result<(), Failure>,
+}
+
+// Backs up, encrypts and writes ledgers on a thread of its own, so a big one doesn't
+// hold up the frame it was saved in. Jobs are written in order.
+pub struct Saver {
+    jobs: Sender<Job>,
+    done: Receiver<Saved>,
+    pending: usize,
+}
+
+impl Saver {
+    pub fn start(ctx: &egui::Context) -> Self {
+        let (jobs, queued) = channel();
+        let (finished, done) = channel();
+        let ctx = ctx.clone();
+        std::thread::spawn(move || work(queued, finished, ctx));
+        Self { jobs, done, pending: 0 }
+    }
+
+    pub fn queue(&mut self, job: Job) {
+        if self.jobs.send(job).is_ok() {
+            self.pending += 1;
+        }
+    }
+
+    pub fn is_busy(&self) -> bool {
+        self.pending > 0
+    }
+
+    // Whatever finished since the last frame
+    pub fn poll(&mut self) -> Vec<Saved> {
+        let finished: Vec<Saved> = self.done.try_iter().collect();
+        self.pending -= finished.iter().map(|saved| saved.jobs).sum::<usize>();
+        finished
+    }
+
+    // Blocks until everything queued is on disk
+    pub fn wait(&mut self) -> Vec<Saved> {
+        let mut finished = Vec::new();
+        while self.pending > 0 {
+            let Ok(saved) = self.done.recv() else { break };
+            self.pending -= saved.jobs;
+            finished.push(saved);
+        }
+        finished
+    }
+}
+
+fn work(queued: Receiver<Job>, finished: Sender<Saved>, ctx: egui::Context) {
+    let mut next = None;
+    loop {
+        let mut job = match next.take() {
+            Some(job) => job,
+            None => match queued.recv() {
+                Ok(job) => job,
+                Err(_) => return,
+            },
+        };
+        // Anything queued behind it for the same file is newer, only that one is written
+        let mut jobs = 1;
+        while let Ok(newer) = queued.try_recv() {
+            if newer.path != job.path {
+                next = Some(newer);
+                break;
+            }
+            job = newer;
+            jobs += 1;
+        }
+        let result = storage::backup(&job.path, job.backup_count)
+            .map_err(|e| Failure::Backup(e.to_string()))
+            .and_then(|_| write_ledger(&job.path, job.bytes, job.passphrase.as_deref()).map_err(Failure::Write));
+        if finished.send(Saved { jobs, fingerprint: job.fingerprint, result }).is_err() {
+            return;
+        }
+        ctx.request_repaint();
+    }
+}
+
+// Writes serialized ledger data, encrypted if a passphrase is set
+pub fn write_ledger(path: &Path, bytes: Vec<u8>, passphrase: Option<&str>) -> Result<(), String> {
+    let bytes = match passphrase {
+        Some(passphrase) => crypto::encrypt(&bytes, passphrase)?,
+        None => bytes,
+    };
+    storage::write_atomic(path, &bytes).map_err(|e| e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    fn job(path: &Path, text: &str, fingerprint: u64) -> Job {
+        Job { path: path.to_path_buf(), bytes: text.as_bytes().to_vec(), passphrase: None, backup_count: 0, fingerprint }
+    }
+
+    #[test]
+    fn queued_saves_of_one_file_are_written_once() {
+        let dir = std::env::temp_dir().join(format!("finance-saver-test-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let (a, b) = (dir.join("a.json"), dir.join("b.json"));
+
+        let (jobs, queued) = channel();
+        let (finished, done) = channel();
+        for (i, text) in ["1", "2", "3"].into_iter().enumerate() {
+            jobs.send(job(&a, text, i as u64)).unwrap();
+        }
+        jobs.send(job(&b, "other", 9)).unwrap();
+        jobs.send(job(&a, "4", 4)).unwrap();
+        drop(jobs);
+        work(queued, finished, egui::Context::default());
+
+        let saved: Vec<(usize, u64)> = done.try_iter().map(|s| (s.jobs, s.fingerprint)).collect();
+        // Another file in between keeps the order: a, b, then a again
+        assert_eq!(saved, vec![(3, 2), (1, 9), (1, 4)]);
+        assert_eq!(fs::read_to_string(&a).unwrap(), "4");
+        assert_eq!(fs::read_to_string(&b).unwrap(), "other");
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,168 @@
+use rhai::{Array, Dynamic, Engine, EvalAltResult, Map, Scope, AST};
+use std::path::Path;
+
+use crate::model::Transaction;
+
+// Optional Rhai script with functions the app calls at set points, for categorization,
+// checks and reports the app doesn't have:
+//   fn on_add(t)            a transaction being added; returns it, changed or not, or
+//                           throws a message to refuse it
+//   fn on_import(t)         an imported row; returns it, or () to leave it out
+//   fn on_report(list)      the report's transactions; returns text to show
+// Transactions are object maps with the same fields as in the data file.
+pub struct Hooks {
+    engine: Engine,
+    ast: AST,
+}
+
+// Plenty for a loop over a few thousand transactions, but a script stuck in a loop
+// can't freeze the window
+const MAX_OPERATIONS: u64 = 5_000_000;
+
+fn message(error: EvalAltResult) -> String {
+    match error {
+        // `throw "..."` gives just the message
+        EvalAltResult::ErrorRuntime(value, _) => value.to_string(),
+        other => other.to_string(),
+    }
+}
+
+// `t.amount = 5` stores an integer, which wouldn't read back into an f64. Nothing in a
+// transaction is a whole number, so every one becomes a float.
+fn ints_to_floats(value: Dynamic) -> Dynamic {
+    if let Ok(n) = value.as_int() {
+        return Dynamic::from_float(n as f64);
+    }
+    if value.is_map() {
+        let map: Map = value.cast();
+        return map.into_iter().map(|(key, value)| (key, ints_to_floats(value))).collect::<Map>().into();
+    }
+    if value.is_array() {
+        let array: Array = value.cast();
+        return array.into_iter().map(ints_to_floats).collect::<Array>().into();
+    }
+    value
+}
+
+impl Hooks {
+    // None when there is no script
+    pub fn load(path: &Path) -> Result<Option<Self>, String> {
+        if !path.exists() {
+            return Ok(None);
+        }
+        let mut engine = Engine::new();
+        engine.set_max_operations(MAX_OPERATIONS);
+        let ast = engine.compile_file(path.to_path_buf()).map_err(|e| message(*e))?;
+        Ok(Some(Self { engine, ast }))
+    }
+
+    fn has(&self, name: &str) -> bool {
+        self.ast.iter_functions().any(|f| f.name == name && f.params.len() == 1)
+    }
+
+    // The hooks the script defines
+    pub fn names(&self) -> Vec<&'static str> {
+        ["on_add", "on_import", "on_report"].into_iter().filter(|name| self.has(name)).collect()
+    }
+
+    pub fn has_report(&self) -> bool {
+        self.has("on_report")
+    }
+
+    fn call(&self, name: &str, arg: Dynamic) -> Result<Dynamic, String> {
+        self.engine
+            .call_fn::<Dynamic>(&mut Scope::new(), &self.ast, name, (arg,))
+            .map_err(|e| format!("{}: {}", name, message(*e)))
+    }
+
+    fn to_map(t: &Transaction) -> Result<Dynamic, String> {
+        rhai::serde::to_dynamic(t).map_err(|e| message(*e))
+    }
+
+    fn from_map(name: &str, value: Dynamic) -> Result<Transaction, String> {
+        rhai::serde::from_dynamic(&ints_to_floats(value)).map_err(|e| format!("{}: {}", name, message(*e)))
+    }
+
+    pub fn on_add(&self, t: Transaction) -> Result<Transaction, String> {
+        if !self.has("on_add") {
+            return Ok(t);
+        }
+        let result = self.call("on_add", Self::to_map(&t)?)?;
+        Self::from_map("on_add", result)
+    }
+
+    pub fn on_import(&self, t: Transaction) -> Result<Option<Transaction>, String> {
+        if !self.has("on_import") {
+            return Ok(Some(t));
+        }
+        let result = self.call("on_import", Self::to_map(&t)?)?;
+        if result.is_unit() {
+            return Ok(None);
+        }
+        Self::from_map("on_import", result).map(Some)
+    }
+
+    // None when the script has no report
+    pub fn on_report(&self, transactions: &[&Transaction]) -> Option<Result<String, String>> {
+        if !self.has("on_report") {
+            return None;
+        }
+        let list: Result<Array, String> = transactions.iter().map(|t| Self::to_map(t)).collect();
+        Some(list.and_then(|list| self.call("on_report", list.into())).map(|text| text.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::test_transaction;
+
+    fn hooks(script: &str) -> Hooks {
+        let mut engine = Engine::new();
+        engine.set_max_operations(MAX_OPERATIONS);
+        let ast = engine.compile(script).unwrap();
+        Hooks { engine, ast }
+    }
+
+    #[test]
+    fn on_add_can_change_or_refuse() {
+        let hooks = hooks(r#"
+            fn on_add(t) {
+                if t.amount > 1000.0 { throw "too big"; }
+                t.amount = 5;
+                t.category = "Drinks";
+                t
+            }
+        "#);
+        assert_eq!(hooks.names(), vec!["on_add"]);
+        let t = hooks.on_add(test_transaction("coffee", 4.5)).unwrap();
+        assert_eq!((t.amount, t.category.as_str()), (5.0, "Drinks"));
+        assert_eq!(hooks.on_add(test_transaction("tv", 2000.0)).err().unwrap(), "on_add: too big");
+    }
+
+    #[test]
+    fn on_import_can_leave_rows_out() {
+        let hooks = hooks(r#"
+            fn on_import(t) {
+                if t.description.contains("transfer") { return (); }
+                t
+            }
+        "#);
+        assert!(hooks.on_import(test_transaction("transfer to savings", 100.0)).unwrap().is_none());
+        assert!(hooks.on_import(test_transaction("rent", 900.0)).unwrap().is_some());
+        // Hooks the script doesn't define pass everything through
+        assert!(hooks.on_add(test_transaction("rent", 900.0)).is_ok());
+        assert!(hooks.on_report(&[]).is_none());
+    }
+
+    #[test]
+    fn report_text_and_runaway_scripts() {
+        let hooks = hooks(r#"
+            fn on_report(list) { `${list.len()} transactions` }
+            fn on_add(t) { loop {} }
+        "#);
+        let t = test_transaction("coffee", 4.5);
+        assert_eq!(hooks.on_report(&[&t, &t]), Some(Ok("2 transactions".to_string())));
+        assert!(hooks.on_add(t).is_err());
+    }
+}
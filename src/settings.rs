@@ -0,0 +1,230 @@
+use chrono::{DateTime, Datelike, Duration, Local, NaiveDate, Weekday};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+use crate::dashboard::Layout;
+use crate::holdings::PriceProvider;
+use crate::i18n::{t, Language};
+use crate::theme::ThemeChoice;
+use crate::model::TransactionType;
+use crate::webhooks::Webhook;
+
+#[derive(Clone, Copy, PartialEq, Debug, Default, Serialize, Deserialize)]
+pub enum WeekStart {
+    #[default]
+    Monday,
+    Sunday,
+}
+
+impl WeekStart {
+    pub fn label(&self) -> String {
+        match self {
+            WeekStart::Monday => t!("monday"),
+            WeekStart::Sunday => t!("sunday"),
+        }
+    }
+
+    pub fn weekday(&self) -> Weekday {
+        match self {
+            WeekStart::Monday => Weekday::Mon,
+            WeekStart::Sunday => Weekday::Sun,
+        }
+    }
+}
+
+// Where the window was when the app last closed, in logical pixels. Size and position
+// are of the restored window, so un-maximizing goes back to them.
+#[derive(Clone, Copy, PartialEq, Debug, Serialize, Deserialize)]
+pub struct WindowGeometry {
+    pub size: [f32; 2],
+    pub position: Option<[f32; 2]>,
+    pub maximized: bool,
+}
+
+impl Default for WindowGeometry {
+    fn default() -> Self {
+        Self { size: [900.0, 700.0], position: None, maximized: false }
+    }
+}
+
+// GoCardless Bank Account Data (formerly Nordigen) credentials and the account to pull,
+// see bank.rs. Kept even in builds without the bank feature, so they survive a save.
+#[derive(Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct BankConnection {
+    #[serde(default)]
+    pub secret_id: String,
+    #[serde(default)]
+    pub secret_key: String,
+    #[serde(default)]
+    pub account_id: String,
+    // Newest day already pulled, the next pull starts there
+    #[serde(default)]
+    pub last_pulled: Option<NaiveDate>,
+}
+
+// Google Sheet to export to and the service account key to sign in with, see sheets.rs.
+// Kept even in builds without the sheets feature, so they survive a save.
+#[derive(Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SheetsConnection {
+    #[serde(default)]
+    pub key_file: Option<PathBuf>,
+    // The sheet's address or just its ID
+    #[serde(default)]
+    pub spreadsheet: String,
+    // Export again once a day while the window is open
+    #[serde(default)]
+    pub daily: bool,
+    #[serde(default)]
+    pub last_exported: Option<DateTime<Local>>,
+}
+
+// Telegram bot for adding transactions from a phone, see bot.rs. Only messages from
+// these chats are added.
+#[derive(Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct BotConnection {
+    #[serde(default)]
+    pub token: String,
+    #[serde(default)]
+    pub allowed_chats: Vec<i64>,
+}
+
+// Which desktop notifications to show, see notify.rs
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+pub struct Notifications {
+    // A budget past 80% of its limit
+    #[serde(default = "enabled")]
+    pub budget_warning: bool,
+    #[serde(default = "enabled")]
+    pub budget_exceeded: bool,
+    // Bills due the next day
+    #[serde(default = "enabled")]
+    pub bills_due: bool,
+    // Last day they were looked for
+    #[serde(default)]
+    pub bills_checked: Option<NaiveDate>,
+}
+
+fn enabled() -> bool {
+    true
+}
+
+impl Default for Notifications {
+    fn default() -> Self {
+        Self { budget_warning: true, budget_exceeded: true, bills_due: true, bills_checked: None }
+    }
+}
+
+// Preferences of this user on this machine, shared by every ledger and stored in the
+// config file. Home currency and number format stay in the ledger, since its amounts
+// depend on them.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Settings {
+    #[serde(default)]
+    pub theme: ThemeChoice,
+    // Type the add form starts with
+    #[serde(default)]
+    pub default_type: TransactionType,
+    #[serde(default)]
+    pub week_start: WeekStart,
+    #[serde(default)]
+    pub language: Language,
+    // Zoom on top of the display's own scaling, 1.0 is egui's default size
+    #[serde(default = "default_ui_scale")]
+    pub ui_scale: f32,
+    #[serde(default)]
+    pub window: WindowGeometry,
+    // Open minimized, with just the tray icon to log things from
+    #[serde(default)]
+    pub start_in_tray: bool,
+    // Day of the month budgets and "this month" start on, e.g. payday. At most 28 so
+    // every month has it.
+    #[serde(default = "default_month_start")]
+    pub month_start: u32,
+    #[serde(default)]
+    pub dashboard: Layout,
+    #[serde(default)]
+    pub price_provider: PriceProvider,
+    #[serde(default)]
+    pub bank: BankConnection,
+    #[serde(default)]
+    pub bot: BotConnection,
+    #[serde(default)]
+    pub webhooks: Vec<Webhook>,
+    // Added transactions from this amount up, in the home currency, are sent to webhooks
+    #[serde(default = "default_large_transaction")]
+    pub large_transaction: f64,
+    // First day of the week the last weekly summary went out in
+    #[serde(default)]
+    pub last_weekly_summary: Option<NaiveDate>,
+    #[serde(default)]
+    pub notifications: Notifications,
+    #[serde(default)]
+    pub sheets: SheetsConnection,
+}
+
+fn default_large_transaction() -> f64 {
+    500.0
+}
+
+fn default_month_start() -> u32 {
+    1
+}
+
+fn default_ui_scale() -> f32 {
+    1.0
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            theme: ThemeChoice::default(),
+            default_type: TransactionType::default(),
+            week_start: WeekStart::default(),
+            language: Language::default(),
+            ui_scale: default_ui_scale(),
+            window: WindowGeometry::default(),
+            start_in_tray: false,
+            month_start: default_month_start(),
+            dashboard: Layout::default(),
+            price_provider: PriceProvider::default(),
+            bank: BankConnection::default(),
+            bot: BotConnection::default(),
+            webhooks: Vec::new(),
+            large_transaction: default_large_transaction(),
+            last_weekly_summary: None,
+            notifications: Notifications::default(),
+            sheets: SheetsConnection::default(),
+        }
+    }
+}
+
+impl Settings {
+    // First and last day of the week `day` is in
+    pub fn week_containing(&self, day: NaiveDate) -> (NaiveDate, NaiveDate) {
+        let first = day.week(self.week_start.weekday()).first_day();
+        (first, first + Duration::days(6))
+    }
+
+    // First and last day of the budget month `day` is in
+    pub fn month_containing(&self, day: NaiveDate) -> (NaiveDate, NaiveDate) {
+        let start_day = self.month_start.clamp(1, 28);
+        let (mut year, mut month) = (day.year(), day.month());
+        if day.day() < start_day {
+            (year, month) = previous_month(year, month);
+        }
+        let first = NaiveDate::from_ymd_opt(year, month, start_day).unwrap_or(day);
+        let (next_year, next_month) = if month == 12 { (year + 1, 1) } else { (year, month + 1) };
+        let next = NaiveDate::from_ymd_opt(next_year, next_month, start_day).unwrap_or(day);
+        (first, next.pred_opt().unwrap_or(next))
+    }
+
+    // The budget month before the one `day` is in
+    pub fn previous_month(&self, day: NaiveDate) -> (NaiveDate, NaiveDate) {
+        let (first, _) = self.month_containing(day);
+        self.month_containing(first.pred_opt().unwrap_or(first))
+    }
+}
+
+fn previous_month(year: i32, month: u32) -> (i32, u32) {
+    if month == 1 { (year - 1, 12) } else { (year, month - 1) }
+}
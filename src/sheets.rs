@@ -0,0 +1,205 @@
+use base64::Engine;
+use base64::engine::general_purpose::{STANDARD, URL_SAFE_NO_PAD};
+use chrono::{NaiveDate, Utc};
+use eframe::egui;
+use serde::Deserialize;
+use serde_json::{Value, json};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{Receiver, TryRecvError, channel};
+
+use crate::error::Problem;
+use crate::export::{self, Report};
+
+// One-way export to a Google Sheet, for someone who'd rather look there: the
+// transactions and the monthly totals, each on its own tab, replaced on every export.
+// It signs in as a Google Cloud service account, whose JSON key the user downloads and
+// whose email address the sheet is shared with, so there's no browser sign-in.
+
+const SHEETS_URL: &str = "https://sheets.googleapis.com/v4/spreadsheets";
+const TOKEN_URL: &str = "https://oauth2.googleapis.com/token";
+const SCOPE: &str = "https://www.googleapis.com/auth/spreadsheets";
+
+// Tabs written, created when the sheet doesn't have them yet
+const TRANSACTIONS_TAB: &str = "Transactions";
+const MONTHLY_TAB: &str = "Monthly";
+
+#[derive(Deserialize)]
+struct ServiceAccount {
+    client_email: String,
+    private_key: String,
+}
+
+#[derive(Deserialize)]
+struct Token {
+    access_token: String,
+}
+
+#[derive(Deserialize)]
+struct Spreadsheet {
+    sheets: Vec<Sheet>,
+}
+
+#[derive(Deserialize)]
+struct Sheet {
+    properties: SheetProperties,
+}
+
+#[derive(Deserialize)]
+struct SheetProperties {
+    title: String,
+}
+
+// The ID from a sheet's address, or the ID itself. It goes into the request URL, so
+// anything Google wouldn't have put in an ID is turned away.
+pub fn spreadsheet_id(text: &str) -> Result<&str, Problem> {
+    let text = text.trim();
+    let id = match text.split_once("/d/") {
+        Some((_, rest)) => rest.split('/').next().unwrap_or(rest),
+        None => text,
+    };
+    if id.is_empty() || !id.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-') {
+        return Err(Problem::BadSpreadsheet);
+    }
+    Ok(id)
+}
+
+fn call(request: ehttp::Request) -> Result<Vec<u8>, String> {
+    let response = ehttp::fetch_blocking(&request)?;
+    if !response.ok {
+        return Err(format!("{} {} {}", response.status, response.status_text, response.text().unwrap_or_default()));
+    }
+    Ok(response.bytes)
+}
+
+fn with_token(mut request: ehttp::Request, token: &str) -> ehttp::Request {
+    let bearer = format!("Bearer {}", token);
+    request.headers = ehttp::Headers::new(&[("Content-Type", "application/json"), ("Authorization", &bearer)]);
+    request
+}
+
+// A JWT signed with the account's key, traded for an access token (OAuth for service
+// accounts)
+fn access_token(key_file: &Path) -> Result<String, Problem> {
+    let text = std::fs::read_to_string(key_file).map_err(|e| format!("{}: {}", key_file.display(), e))?;
+    let account: ServiceAccount = serde_json::from_str(&text).map_err(|e| format!("{}: {}", key_file.display(), e))?;
+    let der: String = account.private_key.lines().filter(|l| !l.starts_with("-----")).collect();
+    let der = STANDARD.decode(der.trim()).map_err(|e| e.to_string())?;
+    let key = ring::signature::RsaKeyPair::from_pkcs8(&der).map_err(|e| Problem::PrivateKey(e.to_string()))?;
+
+    let now = Utc::now().timestamp();
+    let header = URL_SAFE_NO_PAD.encode(json!({ "alg": "RS256", "typ": "JWT" }).to_string());
+    let claims = URL_SAFE_NO_PAD.encode(json!({
+        "iss": account.client_email,
+        "scope": SCOPE,
+        "aud": TOKEN_URL,
+        "iat": now,
+        "exp": now + 3600,
+    }).to_string());
+    let signed = format!("{}.{}", header, claims);
+    let mut signature = vec![0; key.public().modulus_len()];
+    key.sign(&ring::signature::RSA_PKCS1_SHA256, &ring::rand::SystemRandom::new(), signed.as_bytes(), &mut signature)
+        .map_err(|_| Problem::TokenNotSigned)?;
+    let assertion = format!("{}.{}", signed, URL_SAFE_NO_PAD.encode(signature));
+
+    let body = format!("grant_type=urn%3Aietf%3Aparams%3Aoauth%3Agrant-type%3Ajwt-bearer&assertion={}", assertion);
+    let mut request = ehttp::Request::post(TOKEN_URL, body.into_bytes());
+    request.headers = ehttp::Headers::new(&[("Content-Type", "application/x-www-form-urlencoded")]);
+    let token: Token = serde_json::from_slice(&call(request)?).map_err(|e| e.to_string())?;
+    Ok(token.access_token)
+}
+
+// Rows as the CSV export has them, numbers left as numbers so the sheet can add them up
+fn tables(report: &Report) -> Vec<(&'static str, Vec<Value>)> {
+    let round = |v: f64| (v * 100.0).round() / 100.0;
+    let mut transactions = vec![json!(export::transaction_headers(&report.currency))];
+    for row in &report.rows {
+        let original = row.original.as_ref()
+            .map(|(amount, code)| format!("{:.2} {}", amount, code))
+            .unwrap_or_default();
+        transactions.push(json!([
+            row.date.format("%Y-%m-%d").to_string(),
+            row.description,
+            row.category,
+            row.kind,
+            row.status,
+            round(row.amount),
+            original,
+            row.note,
+        ]));
+    }
+    let mut monthly = vec![json!(export::monthly_headers())];
+    for &(year, month, income, expenses) in &report.months {
+        let label = NaiveDate::from_ymd_opt(year, month, 1).map(|d| d.format("%Y-%m").to_string()).unwrap_or_default();
+        monthly.push(json!([label, round(income), round(expenses), round(income - expenses)]));
+    }
+    vec![(TRANSACTIONS_TAB, transactions), (MONTHLY_TAB, monthly)]
+}
+
+fn upload(key_file: &Path, spreadsheet: &str, tables: Vec<(&'static str, Vec<Value>)>) -> Result<(), Problem> {
+    let token = access_token(key_file)?;
+    let url = format!("{}/{}", SHEETS_URL, spreadsheet);
+
+    let request = with_token(ehttp::Request::get(format!("{}?fields=sheets.properties.title", url)), &token);
+    let existing: Spreadsheet = serde_json::from_slice(&call(request)?).map_err(|e| e.to_string())?;
+    let missing: Vec<Value> = tables.iter()
+        .filter(|(tab, _)| !existing.sheets.iter().any(|s| s.properties.title == *tab))
+        .map(|(tab, _)| json!({ "addSheet": { "properties": { "title": tab } } }))
+        .collect();
+    if !missing.is_empty() {
+        let body = json!({ "requests": missing }).to_string().into_bytes();
+        call(with_token(ehttp::Request::post(format!("{}:batchUpdate", url), body), &token))?;
+    }
+
+    for (tab, rows) in tables {
+        // Rows from a longer earlier export would otherwise stay at the bottom
+        call(with_token(ehttp::Request::post(format!("{}/values/{}:clear", url, tab), b"{}".to_vec()), &token))?;
+        // RAW, so a description starting with = isn't taken for a formula
+        let body = json!({ "values": rows }).to_string().into_bytes();
+        let mut request = with_token(ehttp::Request::post(format!("{}/values/{}!A1?valueInputOption=RAW", url, tab), body), &token);
+        request.method = "PUT".to_string();
+        call(request)?;
+    }
+    Ok(())
+}
+
+// An export running in the background. Poll it once per frame.
+pub struct SheetsExport {
+    rx: Receiver<Result<(), Problem>>,
+}
+
+impl SheetsExport {
+    // None while it's still running
+    pub fn poll(&self) -> Option<Result<(), Problem>> {
+        match self.rx.try_recv() {
+            Ok(result) => Some(result),
+            Err(TryRecvError::Empty) => None,
+            Err(TryRecvError::Disconnected) => Some(Err(Problem::Stopped)),
+        }
+    }
+}
+
+pub fn start(ctx: &egui::Context, key_file: PathBuf, spreadsheet: String, report: &Report) -> SheetsExport {
+    let (tx, rx) = channel();
+    let ctx = ctx.clone();
+    let tables = tables(report);
+    std::thread::spawn(move || {
+        let _ = tx.send(upload(&key_file, &spreadsheet, tables));
+        ctx.request_repaint();
+    });
+    SheetsExport { rx }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn spreadsheet_ids_from_addresses() {
+        let id = "1BxiMVs0XRA5nFMdKvBdBZjgmUUqptlbs74OgvE2upms";
+        assert_eq!(spreadsheet_id(id), Ok(id));
+        assert_eq!(spreadsheet_id(&format!(" https://docs.google.com/spreadsheets/d/{}/edit#gid=0 ", id)), Ok(id));
+        assert_eq!(spreadsheet_id("abc/../../drive/v3/files"), Err(Problem::BadSpreadsheet));
+        assert_eq!(spreadsheet_id("abc?key=x"), Err(Problem::BadSpreadsheet));
+        assert_eq!(spreadsheet_id(""), Err(Problem::BadSpreadsheet));
+    }
+}
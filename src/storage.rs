@@ -0,0 +1,198 @@
+use chrono::Local;
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+use crate::crypto;
+use crate::error::Problem;
+
+pub const DATA_FILE: &str = "finance_data.json";
+
+// Backups sit in this folder next to the data file
+pub const BACKUP_DIR: &str = "backups";
+
+pub fn backup_dir(data_path: &Path) -> PathBuf {
+    data_path.parent().unwrap_or(Path::new("")).join(BACKUP_DIR)
+}
+
+fn file_stem(data_path: &Path) -> String {
+    data_path.file_stem()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_else(|| "finance_data".to_string())
+}
+
+// Copies the current data file to a timestamped backup and drops the oldest ones
+// beyond `keep`. Nothing happens when there is no file yet or keep is 0.
+pub fn backup(data_path: &Path, keep: usize) -> io::Result<()> {
+    if keep == 0 || !data_path.exists() {
+        return Ok(());
+    }
+    let dir = backup_dir(data_path);
+    fs::create_dir_all(&dir)?;
+
+    let name = format!("{}-{}.json", file_stem(data_path), Local::now().format("%Y%m%d-%H%M%S-%3f"));
+    fs::copy(data_path, dir.join(name))?;
+
+    for old in list_backups(data_path).into_iter().skip(keep) {
+        fs::remove_file(old)?;
+    }
+    Ok(())
+}
+
+// Whether `name` is "{stem}-YYYYMMDD-HHMMSS-mmm.json", as `backup` names them. A prefix
+// match isn't enough: "my-ledger-….json" would count as a backup of "my.json", and
+// other ledgers can sit in the same folder.
+fn is_backup_name(name: &str, stem: &str) -> bool {
+    let Some(stamp) = name.strip_prefix(stem)
+        .and_then(|rest| rest.strip_prefix('-'))
+        .and_then(|rest| rest.strip_suffix(".json"))
+    else {
+        return false;
+    };
+    let parts: Vec<&str> = stamp.split('-').collect();
+    matches!(parts.as_slice(), [date, time, millis]
+        if date.len() == 8 && time.len() == 6 && millis.len() == 3
+            && parts.iter().all(|p| p.bytes().all(|b| b.is_ascii_digit())))
+}
+
+// Backups of this data file, newest first. The timestamp in the name sorts correctly.
+pub fn list_backups(data_path: &Path) -> Vec<PathBuf> {
+    let stem = file_stem(data_path);
+    let Ok(entries) = fs::read_dir(backup_dir(data_path)) else {
+        return Vec::new();
+    };
+    let mut backups: Vec<PathBuf> = entries
+        .filter_map(|e| e.ok())
+        .map(|e| e.path())
+        .filter(|p| {
+            let name = p.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+            is_backup_name(&name, &stem)
+        })
+        .collect();
+    backups.sort();
+    backups.reverse();
+    backups
+}
+
+// "2024-03-05 14:02:11" from a backup's file name, for display
+pub fn backup_label(path: &Path) -> String {
+    let name = path.file_stem().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+    let stamp: Vec<&str> = name.rsplitn(3, '-').collect();
+    match stamp.as_slice() {
+        [_millis, time, rest] if rest.len() >= 8 && time.len() == 6 => {
+            let date = &rest[rest.len() - 8..];
+            format!(
+                "{}-{}-{} {}:{}:{}",
+                &date[0..4], &date[4..6], &date[6..8],
+                &time[0..2], &time[2..4], &time[4..6]
+            )
+        }
+        _ => name,
+    }
+}
+
+// Writes to a temporary file next to the target, flushes it to disk and renames it
+// over the original, so a crash mid-write leaves the old file intact
+pub fn write_atomic(path: &Path, bytes: &[u8]) -> io::Result<()> {
+    write_file(path, bytes, false)
+}
+
+// write_atomic for a file holding secrets, like the config with the bank and bot
+// credentials: only the user can read it (mode 0600 on unix), from the moment it exists
+pub fn write_private(path: &Path, bytes: &[u8]) -> io::Result<()> {
+    write_file(path, bytes, true)
+}
+
+fn write_file(path: &Path, bytes: &[u8], private: bool) -> io::Result<()> {
+    let mut tmp_name = path.file_name().map(|n| n.to_os_string()).unwrap_or_default();
+    tmp_name.push(".tmp");
+    let tmp = path.with_file_name(tmp_name);
+
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    if private {
+        // One left behind by a crash would keep its mode
+        let _ = fs::remove_file(&tmp);
+        #[cfg(unix)]
+        std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+    }
+    {
+        let mut file = options.open(&tmp)?;
+        file.write_all(bytes)?;
+        file.sync_all()?;
+    }
+    if let Err(e) = fs::rename(&tmp, path) {
+        let _ = fs::remove_file(&tmp);
+        return Err(e);
+    }
+
+    // Make the rename itself durable
+    #[cfg(unix)]
+    if let Ok(dir) = fs::File::open(path.parent().filter(|p| !p.as_os_str().is_empty()).unwrap_or(Path::new("."))) {
+        let _ = dir.sync_all();
+    }
+    Ok(())
+}
+
+pub enum LoadError {
+    Missing,
+    // Encrypted and no passphrase was given
+    Locked,
+    Unreadable(Problem),
+}
+
+// Used after encrypting a ledger so no readable copy is left behind
+pub fn remove_plaintext_backups(data_path: &Path) -> io::Result<usize> {
+    let mut removed = 0;
+    for path in list_backups(data_path) {
+        let bytes = fs::read(&path)?;
+        if !crypto::is_encrypted(&bytes) {
+            fs::remove_file(&path)?;
+            removed += 1;
+        }
+    }
+    Ok(removed)
+}
+
+// When the file was last written, None if it isn't there
+pub fn modified(path: &Path) -> Option<std::time::SystemTime> {
+    fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+// Cheap fingerprint of the serialized ledger, to tell whether anything changed since the last save
+pub fn fingerprint(bytes: &[u8]) -> u64 {
+    use std::hash::{Hash, Hasher};
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    bytes.hash(&mut hasher);
+    hasher.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn backups_of_other_ledgers_are_not_listed() {
+        assert!(is_backup_name("my-20240101-120000-123.json", "my"));
+        assert!(!is_backup_name("my-ledger-20240101-120000-123.json", "my"));
+        assert!(!is_backup_name("my-20240101-120000.json", "my"));
+        assert!(!is_backup_name("my-2024010a-120000-123.json", "my"));
+        assert!(!is_backup_name("my-20240101-120000-123.json.tmp", "my"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn private_files_are_only_the_users() {
+        use std::os::unix::fs::PermissionsExt;
+        let dir = std::env::temp_dir().join(format!("private-write-test-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("config.toml");
+        // Written by an older version, readable by everyone
+        fs::write(&path, "old").unwrap();
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o644)).unwrap();
+        write_private(&path, b"secret").unwrap();
+        assert_eq!(fs::metadata(&path).unwrap().permissions().mode() & 0o777, 0o600);
+        assert_eq!(fs::read(&path).unwrap(), b"secret");
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,86 @@
+use chrono::{DateTime, Local};
+use std::collections::HashMap;
+
+use crate::{Transaction, TransactionType};
+
+// Past descriptions offered while typing a new one. Every time a description was used
+// counts, but for half as much every month, so both habits and last week's one-off
+// make it near the top.
+const HALF_LIFE_DAYS: f64 = 30.0;
+
+#[derive(Clone, Debug)]
+pub struct Suggestion {
+    pub description: String,
+    pub uses: usize,
+    // How it is usually filed and what it usually costs
+    pub trans_type: TransactionType,
+    pub category: String,
+    pub subcategory: Option<String>,
+    pub amount: f64,
+    pub currency: Option<String>,
+}
+
+// Best first. Descriptions starting with what was typed come before ones that only
+// contain it; what was typed exactly is left out, there is nothing to complete.
+pub fn suggestions(transactions: &[Transaction], typed: &str, now: DateTime<Local>, limit: usize) -> Vec<Suggestion> {
+    let typed = typed.trim().to_lowercase();
+    if typed.is_empty() {
+        return Vec::new();
+    }
+
+    // Same description in any case
+    let mut groups: HashMap<String, Vec<&Transaction>> = HashMap::new();
+    for t in transactions {
+        let key = t.description.trim().to_lowercase();
+        if key != typed && key.contains(&typed) {
+            groups.entry(key).or_default().push(t);
+        }
+    }
+
+    let mut ranked: Vec<(bool, f64, Suggestion)> = groups.into_iter()
+        .map(|(key, mut uses)| {
+            uses.sort_by_key(|t| std::cmp::Reverse(t.date));
+            let score = uses.iter()
+                .map(|t| 0.5f64.powf((now - t.date).num_days().max(0) as f64 / HALF_LIFE_DAYS))
+                .sum();
+            (key.starts_with(&typed), score, suggestion(&uses))
+        })
+        .collect();
+    ranked.sort_by(|a, b| b.0.cmp(&a.0).then(b.1.total_cmp(&a.1)));
+    ranked.into_iter().take(limit).map(|(_, _, s)| s).collect()
+}
+
+// `uses` newest first
+fn suggestion(uses: &[&Transaction]) -> Suggestion {
+    let latest = uses[0];
+    // Split ones say nothing about a single category
+    let whole: Vec<&Transaction> = uses.iter().copied().filter(|t| t.splits.is_empty()).collect();
+    let filed = if whole.is_empty() { uses } else { &whole };
+    let (trans_type, category, subcategory) =
+        usual(filed, |t| (t.trans_type, t.category.clone(), t.subcategory.clone()));
+    let (amount, currency) = usual(uses, |t| (t.amount, t.currency.clone()));
+    Suggestion {
+        description: latest.description.trim().to_string(),
+        uses: uses.len(),
+        trans_type,
+        category,
+        subcategory,
+        amount,
+        currency,
+    }
+}
+
+// The value that comes up most often, the most recent one on a tie. `uses` is newest
+// first and not empty.
+fn usual<T: PartialEq>(uses: &[&Transaction], value: impl Fn(&Transaction) -> T) -> T {
+    let mut counts: Vec<(T, usize)> = Vec::new();
+    for t in uses {
+        let v = value(t);
+        match counts.iter_mut().find(|(seen, _)| *seen == v) {
+            Some((_, count)) => *count += 1,
+            None => counts.push((v, 1)),
+        }
+    }
+    // max_by_key keeps the last of equals, which reversed is the newest
+    counts.into_iter().rev().max_by_key(|(_, count)| *count).map(|(v, _)| v).expect("at least one use")
+}
@@ -0,0 +1,218 @@
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap};
+use uuid::Uuid;
+
+use crate::holdings;
+use crate::i18n::t;
+use crate::rates::RateSource;
+use crate::{FinanceApp, Transaction};
+
+// Remembers a deleted transaction so a merge doesn't bring it back from the other copy
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Tombstone {
+    pub id: Uuid,
+    pub deleted: DateTime<Local>,
+}
+
+#[derive(Default)]
+pub struct MergeReport {
+    pub added: usize,
+    pub updated: usize,
+    pub deleted: usize,
+}
+
+impl MergeReport {
+    pub fn summary(&self) -> String {
+        if self.added + self.updated + self.deleted == 0 {
+            return t!("sync-up-to-date");
+        }
+        t!(
+            "sync-merged",
+            added = self.added,
+            updated = self.updated,
+            deleted = self.deleted,
+        )
+    }
+}
+
+// Picks between two versions of the same transaction. The later edit wins; on a tie
+// the larger serialized form does, so both machines end up with the same result.
+fn newer<'a>(a: &'a Transaction, b: &'a Transaction) -> &'a Transaction {
+    match a.modified.cmp(&b.modified) {
+        std::cmp::Ordering::Greater => a,
+        std::cmp::Ordering::Less => b,
+        std::cmp::Ordering::Equal => {
+            let a_json = serde_json::to_string(a).unwrap_or_default();
+            let b_json = serde_json::to_string(b).unwrap_or_default();
+            if a_json >= b_json { a } else { b }
+        }
+    }
+}
+
+fn same(a: &Transaction, b: &Transaction) -> bool {
+    serde_json::to_string(a).ok() == serde_json::to_string(b).ok()
+}
+
+// Merges `remote` into `local` by transaction ID. Categories, budgets, goals, holdings,
+// templates, rules and rates are unioned, with the local copy winning where both have an
+// entry. Of two prices for a symbol the newer is kept.
+pub fn merge(local: &mut FinanceApp, remote: FinanceApp) -> MergeReport {
+    let mut report = MergeReport::default();
+
+    // Deletions from both sides, keeping the latest time per ID
+    let mut tombstones: HashMap<Uuid, DateTime<Local>> = HashMap::new();
+    for t in local.deleted.iter().chain(remote.deleted.iter()) {
+        let entry = tombstones.entry(t.id).or_insert(t.deleted);
+        *entry = (*entry).max(t.deleted);
+    }
+    let deleted_after = |t: &Transaction| {
+        tombstones.get(&t.id).is_some_and(|at| t.modified.is_none_or(|m| m <= *at))
+    };
+
+    let remote_by_id: HashMap<Uuid, &Transaction> = remote.transactions.iter().map(|t| (t.id, t)).collect();
+    let local_ids: BTreeSet<Uuid> = local.transactions.iter().map(|t| t.id).collect();
+
+    let mut merged = Vec::with_capacity(local.transactions.len());
+    for t in &local.transactions {
+        match remote_by_id.get(&t.id) {
+            Some(theirs) => {
+                // Without the version both started from there's no telling an edit on
+                // both sides from one on a single side, so the newer simply wins
+                let winner = newer(t, theirs);
+                if !same(t, theirs) && !std::ptr::eq(winner, t) {
+                    report.updated += 1;
+                }
+                merged.push(winner.clone());
+            }
+            None if deleted_after(t) => report.deleted += 1,
+            None => merged.push(t.clone()),
+        }
+    }
+    for t in &remote.transactions {
+        if !local_ids.contains(&t.id) && !deleted_after(t) {
+            merged.push(t.clone());
+            report.added += 1;
+        }
+    }
+    merged.sort_by_key(|t| t.date);
+    local.transactions = merged;
+
+    local.deleted = tombstones.into_iter()
+        .map(|(id, deleted)| Tombstone { id, deleted })
+        .collect();
+    local.deleted.sort_by_key(|t| t.deleted);
+
+    for category in remote.categories {
+        if local.find_category(&category.name).is_none() {
+            local.categories.push(category);
+        }
+    }
+    for budget in remote.budgets {
+        if !local.budgets.iter().any(|b| b.category == budget.category) {
+            local.budgets.push(budget);
+        }
+    }
+    for goal in remote.goals {
+        if !local.goals.iter().any(|g| g.id == goal.id) {
+            local.goals.push(goal);
+        }
+    }
+    for holding in remote.holdings {
+        if !local.holdings.iter().any(|h| h.id == holding.id) {
+            local.holdings.push(holding);
+        }
+    }
+    for price in remote.prices {
+        holdings::set_price(&mut local.prices, price);
+    }
+    for template in remote.templates {
+        if !local.templates.iter().any(|t| t.name == template.name) {
+            local.templates.push(template);
+        }
+    }
+    for rule in remote.rules {
+        if !local.rules.iter().any(|r| r.id == rule.id) {
+            local.rules.push(rule);
+        }
+    }
+    for rate in remote.rates.rates {
+        let known = local.rates.rates.iter()
+            .any(|r| r.from == rate.from && r.to == rate.to && r.date == rate.date && r.source == RateSource::Manual);
+        if !known {
+            local.rates.set_rate(&rate.from, &rate.to, rate.date, rate.rate, rate.source);
+        }
+    }
+    local.migrate_categories();
+
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, TimeZone};
+    use crate::model::test_transaction;
+
+    fn at(hour: u32) -> DateTime<Local> {
+        Local.with_ymd_and_hms(2025, 3, 2, hour, 0, 0).unwrap()
+    }
+
+    fn ledger(transactions: Vec<Transaction>) -> FinanceApp {
+        FinanceApp { transactions, ..FinanceApp::default() }
+    }
+
+    #[test]
+    fn later_edit_wins_when_both_sides_changed() {
+        let mut mine = test_transaction("coffee", 4.5);
+        mine.modified = Some(at(9));
+        let mut theirs = mine.clone();
+        theirs.amount = 5.0;
+        theirs.modified = Some(at(10));
+
+        let mut local = ledger(vec![mine.clone()]);
+        let report = merge(&mut local, ledger(vec![theirs.clone()]));
+        assert!(local.transactions == vec![theirs.clone()]);
+        assert_eq!(report.updated, 1);
+
+        // Same answer from the other machine
+        let mut remote = ledger(vec![theirs.clone()]);
+        let report = merge(&mut remote, ledger(vec![mine]));
+        assert!(remote.transactions == vec![theirs]);
+        assert_eq!(report.updated, 0);
+    }
+
+    #[test]
+    fn edit_after_a_delete_brings_it_back() {
+        let mut edited = test_transaction("coffee", 4.5);
+        edited.modified = Some(at(10));
+        let mut local = ledger(Vec::new());
+        local.deleted.push(Tombstone { id: edited.id, deleted: at(9) });
+
+        let report = merge(&mut local, ledger(vec![edited.clone()]));
+        assert!(local.transactions == vec![edited.clone()]);
+        assert_eq!(report.added, 1);
+
+        // Deleted after the edit, it stays deleted
+        let mut local = ledger(Vec::new());
+        local.deleted.push(Tombstone { id: edited.id, deleted: at(10) + Duration::minutes(1) });
+        let report = merge(&mut local, ledger(vec![edited]));
+        assert!(local.transactions.is_empty());
+        assert_eq!(report.added, 0);
+    }
+
+    #[test]
+    fn tie_on_modified_picks_the_same_side_everywhere() {
+        let mut a = test_transaction("coffee", 4.5);
+        a.modified = Some(at(10));
+        let mut b = a.clone();
+        b.amount = 5.0;
+        assert!(newer(&a, &b) == newer(&b, &a));
+
+        let mut left = ledger(vec![a.clone()]);
+        let mut right = ledger(vec![b.clone()]);
+        merge(&mut left, ledger(vec![b]));
+        merge(&mut right, ledger(vec![a]));
+        assert!(left.transactions == right.transactions);
+    }
+}
@@ -0,0 +1,92 @@
+use eframe::egui::{self, Color32, Visuals};
+use serde::{Deserialize, Serialize};
+
+use crate::i18n::t;
+
+#[derive(Clone, Copy, PartialEq, Debug, Default, Serialize, Deserialize)]
+pub enum ThemeChoice {
+    #[default]
+    System,
+    Light,
+    Dark,
+}
+
+impl ThemeChoice {
+    pub const ALL: [ThemeChoice; 3] = [ThemeChoice::System, ThemeChoice::Light, ThemeChoice::Dark];
+
+    pub fn label(&self) -> String {
+        match self {
+            ThemeChoice::System => t!("theme-system"),
+            ThemeChoice::Light => t!("theme-light"),
+            ThemeChoice::Dark => t!("theme-dark"),
+        }
+    }
+
+    pub fn preference(&self) -> egui::ThemePreference {
+        match self {
+            ThemeChoice::System => egui::ThemePreference::System,
+            ThemeChoice::Light => egui::ThemePreference::Light,
+            ThemeChoice::Dark => egui::ThemePreference::Dark,
+        }
+    }
+
+    pub fn next(&self) -> Self {
+        match self {
+            ThemeChoice::System => ThemeChoice::Light,
+            ThemeChoice::Light => ThemeChoice::Dark,
+            ThemeChoice::Dark => ThemeChoice::System,
+        }
+    }
+}
+
+// Rough perceived brightness, 0 to 1
+fn brightness(color: Color32) -> f32 {
+    (0.299 * color.r() as f32 + 0.587 * color.g() as f32 + 0.114 * color.b() as f32) / 255.0
+}
+
+// Category colors are picked freely and the status colors were chosen for the dark
+// theme. Pale ones get darkened on a light background, very dark ones lightened on a
+// dark background, so text and lines in them stay readable.
+pub fn readable(color: Color32, visuals: &Visuals) -> Color32 {
+    let level = brightness(color);
+    if !visuals.dark_mode && level > 0.5 {
+        let scale = 0.5 / level;
+        let darken = |c: u8| (c as f32 * scale) as u8;
+        Color32::from_rgb(darken(color.r()), darken(color.g()), darken(color.b()))
+    } else if visuals.dark_mode && level < 0.3 {
+        let mix = (0.3 - level) / (1.0 - level);
+        let lighten = |c: u8| (c as f32 + (255.0 - c as f32) * mix) as u8;
+        Color32::from_rgb(lighten(color.r()), lighten(color.g()), lighten(color.b()))
+    } else {
+        color
+    }
+}
+
+// Black or white, whichever reads better on top of `fill`
+pub fn text_on(fill: Color32) -> Color32 {
+    if brightness(fill) > 0.6 { Color32::BLACK } else { Color32::WHITE }
+}
+
+pub fn income(visuals: &Visuals) -> Color32 {
+    readable(Color32::GREEN, visuals)
+}
+
+pub fn expense(visuals: &Visuals) -> Color32 {
+    readable(Color32::RED, visuals)
+}
+
+pub fn warning(visuals: &Visuals) -> Color32 {
+    readable(Color32::from_rgb(255, 165, 0), visuals)
+}
+
+pub fn balance_line(visuals: &Visuals) -> Color32 {
+    readable(Color32::LIGHT_BLUE, visuals)
+}
+
+pub fn projected_line(visuals: &Visuals) -> Color32 {
+    readable(Color32::GOLD, visuals)
+}
+
+pub fn forecast_line(visuals: &Visuals) -> Color32 {
+    readable(Color32::from_rgb(200, 120, 255), visuals)
+}
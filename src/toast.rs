@@ -0,0 +1,85 @@
+use eframe::egui::{self, Color32};
+use uuid::Uuid;
+
+use crate::i18n::t;
+
+// Short-lived messages in the bottom corner confirming what just happened
+const SECONDS: f64 = 4.0;
+// Errors stay longer, there is more to read and it matters more
+const ERROR_SECONDS: f64 = 8.0;
+
+struct Toast {
+    text: String,
+    error: bool,
+    // Transactions the Undo button brings back from the trash
+    restore: Vec<Uuid>,
+    // Set the first time it is drawn
+    shown_at: Option<f64>,
+}
+
+#[derive(Default)]
+pub struct Toasts {
+    toasts: Vec<Toast>,
+}
+
+impl Toasts {
+    pub fn info(&mut self, text: String) {
+        self.toasts.push(Toast { text, error: false, restore: Vec::new(), shown_at: None });
+    }
+
+    pub fn error(&mut self, text: String) {
+        self.toasts.push(Toast { text, error: true, restore: Vec::new(), shown_at: None });
+    }
+
+    // Offers Undo, which restores `ids` from the trash
+    pub fn deleted(&mut self, text: String, ids: Vec<Uuid>) {
+        self.toasts.push(Toast { text, error: false, restore: ids, shown_at: None });
+    }
+
+    // Draws the toasts and drops expired ones. Returns the transactions to restore if
+    // an Undo was clicked.
+    pub fn show(&mut self, ctx: &egui::Context) -> Option<Vec<Uuid>> {
+        let now = ctx.input(|i| i.time);
+        self.toasts.retain(|toast| {
+            let limit = if toast.error { ERROR_SECONDS } else { SECONDS };
+            toast.shown_at.is_none_or(|at| now - at < limit)
+        });
+        if self.toasts.is_empty() {
+            return None;
+        }
+
+        let mut undo = None;
+        let mut dismiss = None;
+        egui::Area::new(egui::Id::new("toasts"))
+            .anchor(egui::Align2::RIGHT_BOTTOM, egui::vec2(-12.0, -12.0))
+            .order(egui::Order::Foreground)
+            .show(ctx, |ui| {
+                for (index, toast) in self.toasts.iter_mut().enumerate() {
+                    toast.shown_at.get_or_insert(now);
+                    egui::Frame::popup(ui.style()).show(ui, |ui| {
+                        ui.horizontal(|ui| {
+                            if toast.error {
+                                ui.colored_label(Color32::RED, &toast.text);
+                            } else {
+                                ui.label(&toast.text);
+                            }
+                            if !toast.restore.is_empty() && ui.button(t!("undo")).clicked() {
+                                undo = Some(std::mem::take(&mut toast.restore));
+                                dismiss = Some(index);
+                            }
+                            if ui.small_button("✖").clicked() {
+                                dismiss = Some(index);
+                            }
+                        });
+                    });
+                }
+            });
+        if let Some(index) = dismiss {
+            self.toasts.remove(index);
+        }
+
+        // Wake up again to drop the expired ones
+        ctx.request_repaint_after(std::time::Duration::from_secs_f64(1.0));
+        undo
+    }
+}
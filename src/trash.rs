@@ -0,0 +1,27 @@
+use chrono::{DateTime, Duration, Local};
+use serde::{Deserialize, Serialize};
+
+use crate::model::Transaction;
+
+// Deleted transactions stay restorable this long, then they and their receipts are purged
+pub const TRASH_DAYS: i64 = 30;
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Trashed {
+    pub transaction: Transaction,
+    pub deleted: DateTime<Local>,
+}
+
+impl Trashed {
+    pub fn purge_at(&self) -> DateTime<Local> {
+        self.deleted + Duration::days(TRASH_DAYS)
+    }
+
+    pub fn is_expired(&self, now: DateTime<Local>) -> bool {
+        now >= self.purge_at()
+    }
+
+    pub fn days_left(&self, now: DateTime<Local>) -> i64 {
+        (self.purge_at() - now).num_days().max(0)
+    }
+}
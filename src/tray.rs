@@ -0,0 +1,154 @@
+use eframe::egui;
+use std::sync::mpsc::{self, Receiver};
+
+use crate::i18n::t;
+
+// Icon in the system tray for logging an expense without bringing up the whole
+// window. The menu runs on its own thread and sends what was picked to the UI.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum TrayCommand {
+    QuickAdd,
+    ToggleWindow,
+    Quit,
+}
+
+// Menu texts, translated up front since the tray thread has no UI language
+struct Labels {
+    title: String,
+    quick_add: String,
+    toggle: String,
+    quit: String,
+}
+
+impl Labels {
+    fn current() -> Self {
+        Self {
+            title: t!("tray-title"),
+            quick_add: t!("tray-quick-add"),
+            toggle: t!("tray-show-hide"),
+            quit: t!("tray-quit"),
+        }
+    }
+}
+
+pub struct Tray {
+    commands: Receiver<TrayCommand>,
+    // Always there outside of tests, which have no tray to put it in
+    #[cfg(target_os = "linux")]
+    handle: Option<ksni::blocking::Handle<linux::Icon>>,
+}
+
+impl Tray {
+    // None when there is no tray to put it in
+    #[cfg(target_os = "linux")]
+    pub fn start(ctx: &egui::Context) -> Option<Self> {
+        use ksni::blocking::TrayMethods;
+        let (sender, commands) = mpsc::channel();
+        let icon = linux::Icon { sender, ctx: ctx.clone(), labels: Labels::current() };
+        match icon.spawn() {
+            Ok(handle) => Some(Self { commands, handle: Some(handle) }),
+            Err(e) => {
+                tracing::warn!("system tray unavailable: {}", e);
+                None
+            }
+        }
+    }
+
+    // Only Linux desktops are supported so far
+    #[cfg(not(target_os = "linux"))]
+    pub fn start(_ctx: &egui::Context) -> Option<Self> {
+        None
+    }
+
+    // Stands in for the icon in tests, never showing anything
+    #[cfg(test)]
+    pub fn detached() -> Self {
+        let (_, commands) = mpsc::channel();
+        Self {
+            commands,
+            #[cfg(target_os = "linux")]
+            handle: None,
+        }
+    }
+
+    // Next thing picked in the menu, if any
+    pub fn poll(&self) -> Option<TrayCommand> {
+        self.commands.try_recv().ok()
+    }
+
+    // After the UI language changes
+    pub fn relabel(&self) {
+        #[cfg(target_os = "linux")]
+        if let Some(handle) = &self.handle {
+            let labels = Labels::current();
+            handle.update(|icon| icon.labels = labels);
+        }
+    }
+}
+
+#[cfg(target_os = "linux")]
+impl Drop for Tray {
+    fn drop(&mut self) {
+        if let Some(handle) = &self.handle {
+            handle.shutdown();
+        }
+    }
+}
+
+#[cfg(target_os = "linux")]
+mod linux {
+    use super::{Labels, TrayCommand};
+    use eframe::egui;
+    use std::sync::mpsc::Sender;
+
+    pub struct Icon {
+        pub sender: Sender<TrayCommand>,
+        pub ctx: egui::Context,
+        pub labels: Labels,
+    }
+
+    impl Icon {
+        fn send(&self, command: TrayCommand) {
+            let _ = self.sender.send(command);
+            // The window may be asleep waiting for input
+            self.ctx.request_repaint();
+        }
+    }
+
+    impl ksni::Tray for Icon {
+        fn id(&self) -> String {
+            env!("CARGO_PKG_NAME").into()
+        }
+
+        fn title(&self) -> String {
+            self.labels.title.clone()
+        }
+
+        fn icon_name(&self) -> String {
+            "accessories-calculator".into()
+        }
+
+        // Clicking the icon itself goes straight to quick add
+        fn activate(&mut self, _x: i32, _y: i32) {
+            self.send(TrayCommand::QuickAdd);
+        }
+
+        fn menu(&self) -> Vec<ksni::MenuItem<Self>> {
+            use ksni::menu::StandardItem;
+            let item = |label: &str, command: TrayCommand| {
+                StandardItem {
+                    label: label.to_string(),
+                    activate: Box::new(move |icon: &mut Self| icon.send(command)),
+                    ..Default::default()
+                }
+                .into()
+            };
+            vec![
+                item(&self.labels.quick_add, TrayCommand::QuickAdd),
+                item(&self.labels.toggle, TrayCommand::ToggleWindow),
+                ksni::MenuItem::Separator,
+                item(&self.labels.quit, TrayCommand::Quit),
+            ]
+        }
+    }
+}
@@ -0,0 +1,157 @@
+use eframe::egui::{self, Color32, Pos2, Rect, Sense, Stroke, Vec2};
+
+// Expense categories as rectangles sized by amount, their subcategories nested inside.
+// Easier to read than a pie once there are many categories.
+
+pub struct Tile {
+    pub label: String,
+    pub value: f64,
+    pub color: Color32,
+    // Subcategories, biggest first. Empty when there are none to show.
+    pub children: Vec<Tile>,
+}
+
+// Room for a category's name above its subcategories
+const HEADER: f32 = 18.0;
+// Smallest tile that still gets a label
+const LABEL_MIN: Vec2 = Vec2::new(48.0, 18.0);
+
+// Squarified layout: rows of tiles along the shorter side, each row kept as long as
+// that makes its tiles less elongated. `values` biggest first.
+fn squarify(values: &[f64], rect: Rect) -> Vec<Rect> {
+    let mut rects = Vec::with_capacity(values.len());
+    let mut remaining: f64 = values.iter().sum();
+    let mut rect = rect;
+    let mut start = 0;
+    while start < values.len() {
+        if remaining <= 0.0 || rect.width() <= 0.0 || rect.height() <= 0.0 {
+            rects.extend((start..values.len()).map(|_| Rect::from_min_size(rect.min, Vec2::ZERO)));
+            break;
+        }
+        let scale = rect.area() as f64 / remaining;
+        let short = rect.width().min(rect.height()) as f64;
+        let mut end = start + 1;
+        let mut best = worst(&values[start..end], short, scale);
+        while end < values.len() {
+            let next = worst(&values[start..=end], short, scale);
+            if next > best {
+                break;
+            }
+            best = next;
+            end += 1;
+        }
+
+        let row = &values[start..end];
+        let row_area = row.iter().sum::<f64>() * scale;
+        if rect.width() >= rect.height() {
+            // A column down the left
+            let width = (row_area / rect.height() as f64) as f32;
+            let mut y = rect.top();
+            for value in row {
+                let height = (value * scale / width as f64) as f32;
+                rects.push(Rect::from_min_size(Pos2::new(rect.left(), y), Vec2::new(width, height)));
+                y += height;
+            }
+            rect.min.x += width;
+        } else {
+            // A row along the top
+            let height = (row_area / rect.width() as f64) as f32;
+            let mut x = rect.left();
+            for value in row {
+                let width = (value * scale / height as f64) as f32;
+                rects.push(Rect::from_min_size(Pos2::new(x, rect.top()), Vec2::new(width, height)));
+                x += width;
+            }
+            rect.min.y += height;
+        }
+        remaining -= row.iter().sum::<f64>();
+        start = end;
+    }
+    rects
+}
+
+// Most elongated tile of a row laid along a side of length `side`
+fn worst(row: &[f64], side: f64, scale: f64) -> f64 {
+    let areas = row.iter().map(|value| value * scale);
+    let sum: f64 = areas.clone().sum();
+    let largest = areas.clone().fold(0.0, f64::max);
+    let smallest = areas.fold(f64::INFINITY, f64::min);
+    if smallest <= 0.0 || sum <= 0.0 {
+        return f64::INFINITY;
+    }
+    let side = side * side;
+    (side * largest / (sum * sum)).max(sum * sum / (side * smallest))
+}
+
+// Darker shades of the category color tell its subcategories apart
+fn shade(color: Color32, index: usize) -> Color32 {
+    let factor = 1.0 - 0.12 * (index % 4) as f32;
+    let darken = |c: u8| (c as f32 * factor) as u8;
+    Color32::from_rgb(darken(color.r()), darken(color.g()), darken(color.b()))
+}
+
+fn label(painter: &egui::Painter, rect: Rect, text: String, color: Color32) {
+    if rect.width() >= LABEL_MIN.x && rect.height() >= LABEL_MIN.y {
+        painter.with_clip_rect(rect.shrink(2.0)).text(
+            rect.left_top() + Vec2::new(4.0, 3.0),
+            egui::Align2::LEFT_TOP,
+            text,
+            egui::FontId::proportional(12.0),
+            crate::theme::text_on(color),
+        );
+    }
+}
+
+// Draws the tiles, `tiles` biggest first. `money` formats amounts for labels and
+// tooltips. Returns the category whose tile (or subcategory in it) was clicked.
+pub fn show(ui: &mut egui::Ui, tiles: &[Tile], height: f32, money: &dyn Fn(f64) -> String) -> Option<usize> {
+    let (rect, response) = ui.allocate_exact_size(Vec2::new(ui.available_width(), height), Sense::click());
+    let total: f64 = tiles.iter().map(|t| t.value).sum();
+    if total <= 0.0 {
+        return None;
+    }
+    let painter = ui.painter_at(rect);
+    let border = Stroke::new(1.0, ui.visuals().panel_fill);
+    let pointer = response.hover_pos();
+    // (category, subcategory) under the pointer
+    let mut hovered = None;
+
+    let values: Vec<f64> = tiles.iter().map(|t| t.value).collect();
+    let areas = squarify(&values, rect);
+    for (index, (tile, &area)) in tiles.iter().zip(&areas).enumerate() {
+        painter.rect_filled(area, 0.0, tile.color);
+        if pointer.is_some_and(|pos| area.contains(pos)) {
+            hovered = Some((index, None));
+        }
+        let inner = Rect::from_min_max(area.min + Vec2::new(0.0, HEADER), area.max).shrink(2.0);
+        if tile.children.is_empty() || inner.height() < LABEL_MIN.y {
+            label(&painter, area, format!("{}\n{}", tile.label, money(tile.value)), tile.color);
+        } else {
+            label(&painter, area, format!("{}  {}", tile.label, money(tile.value)), tile.color);
+            let child_values: Vec<f64> = tile.children.iter().map(|c| c.value).collect();
+            for (i, (child, child_area)) in tile.children.iter().zip(squarify(&child_values, inner)).enumerate() {
+                let color = shade(child.color, i + 1);
+                painter.rect_filled(child_area, 0.0, color);
+                painter.rect_stroke(child_area, 0.0, border);
+                label(&painter, child_area, format!("{}\n{}", child.label, money(child.value)), color);
+                if pointer.is_some_and(|pos| child_area.contains(pos)) {
+                    hovered = Some((index, Some(i)));
+                }
+            }
+        }
+        painter.rect_stroke(area, 0.0, Stroke::new(2.0, ui.visuals().panel_fill));
+    }
+
+    let (index, child) = hovered?;
+    let tile = &tiles[index];
+    painter.rect_stroke(areas[index].shrink(1.0), 0.0, Stroke::new(2.0, ui.visuals().strong_text_color()));
+    let clicked = response.clicked();
+    response.on_hover_cursor(egui::CursorIcon::PointingHand).on_hover_ui_at_pointer(|ui| {
+        ui.strong(&tile.label);
+        ui.label(format!("{} ({:.1}%)", money(tile.value), tile.value / total * 100.0));
+        if let Some(child) = child.map(|i| &tile.children[i]) {
+            ui.label(format!("→ {}: {} ({:.1}%)", child.label, money(child.value), child.value / total * 100.0));
+        }
+    });
+    clicked.then_some(index)
+}
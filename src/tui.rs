@@ -0,0 +1,243 @@
+use chrono::Local;
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use ratatui::layout::{Constraint, Layout};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::Line;
+use ratatui::widgets::{Block, Cell, Paragraph, Row, Table, TableState};
+use ratatui::{DefaultTerminal, Frame};
+use uuid::Uuid;
+
+use crate::i18n::t;
+use crate::model::Transaction;
+use crate::period::Period;
+use crate::{audit, FinanceApp};
+
+// A terminal front end for SSH sessions and machines without a display: one month of
+// transactions, a quick-add line and delete. Everything else stays in the window.
+
+enum Mode {
+    Browse,
+    // Typing a quick-add line like "coffee 4.50 yesterday" into the app's quick_input
+    Add,
+    ConfirmDelete(Uuid),
+    Unlock,
+}
+
+struct Tui {
+    app: FinanceApp,
+    period: Period,
+    table: TableState,
+    mode: Mode,
+    // Passphrase being typed
+    input: String,
+    // Last thing that happened, shown above the key help
+    status: Option<String>,
+}
+
+pub fn run(app: FinanceApp) -> Result<(), String> {
+    let mode = if app.locked { Mode::Unlock } else { Mode::Browse };
+    let mut tui = Tui {
+        app,
+        period: Period::default(),
+        table: TableState::default().with_selected(Some(0)),
+        mode,
+        input: String::new(),
+        status: None,
+    };
+    let mut terminal = ratatui::try_init().map_err(|e| e.to_string())?;
+    let result = tui.event_loop(&mut terminal);
+    ratatui::restore();
+    result
+}
+
+impl Tui {
+    fn event_loop(&mut self, terminal: &mut DefaultTerminal) -> Result<(), String> {
+        loop {
+            terminal.draw(|frame| self.draw(frame)).map_err(|e| e.to_string())?;
+            let Event::Key(key) = event::read().map_err(|e| e.to_string())? else { continue };
+            if key.kind != KeyEventKind::Press {
+                continue;
+            }
+            if !self.handle_key(key.code) {
+                return Ok(());
+            }
+        }
+    }
+
+    // Shown month, oldest first, like the window's list
+    fn shown(&self) -> Vec<&Transaction> {
+        let mut shown: Vec<&Transaction> = self.app.transactions.iter()
+            .filter(|t| self.period.contains(t.date.date_naive()))
+            .collect();
+        shown.sort_by_key(|t| t.date);
+        shown
+    }
+
+    fn save(&mut self, done: String) {
+        self.app.audit_cause = audit::Cause::Terminal;
+        self.app.save_data();
+        // No banner to ask with here, so another program's changes are merged like a sync
+        let merged = self.app.changed_on_disk;
+        if merged {
+            self.app.reload_changed_file(true);
+        }
+        self.status = Some(match &self.app.save_error {
+            Some(error) => t!("save-failed-toast", error = error.clone()),
+            None if merged => t!("tui-merged", done = done),
+            None => done,
+        });
+    }
+
+    // False when it's time to quit
+    fn handle_key(&mut self, code: KeyCode) -> bool {
+        match self.mode {
+            Mode::Browse => match code {
+                KeyCode::Char('q') | KeyCode::Esc => return false,
+                KeyCode::Left => self.step(-1),
+                KeyCode::Right => self.step(1),
+                KeyCode::Up => self.table.select_previous(),
+                KeyCode::Down => self.table.select_next(),
+                KeyCode::Char('a') => {
+                    self.app.quick_input.clear();
+                    self.mode = Mode::Add;
+                }
+                KeyCode::Char('d') | KeyCode::Delete => {
+                    let selected = self.table.selected().and_then(|i| self.shown().get(i).map(|t| t.id));
+                    if let Some(id) = selected {
+                        self.mode = Mode::ConfirmDelete(id);
+                    }
+                }
+                _ => {}
+            },
+            Mode::Add => match code {
+                KeyCode::Esc => self.mode = Mode::Browse,
+                KeyCode::Enter => {
+                    if let Some(transaction) = self.app.quick_transaction() {
+                        self.period = Period::month_of(transaction.date.date_naive());
+                        match self.app.add_transaction(transaction) {
+                            Ok(()) => self.save(t!("transaction-added")),
+                            Err(e) => self.status = Some(e),
+                        }
+                        self.mode = Mode::Browse;
+                    }
+                }
+                KeyCode::Backspace => {
+                    self.app.quick_input.pop();
+                }
+                KeyCode::Char(c) => self.app.quick_input.push(c),
+                _ => {}
+            },
+            Mode::ConfirmDelete(id) => {
+                if code == KeyCode::Char('y') && self.app.remove_transaction(id) {
+                    self.save(t!("tui-deleted"));
+                }
+                self.mode = Mode::Browse;
+            }
+            Mode::Unlock => match code {
+                KeyCode::Esc => return false,
+                KeyCode::Enter => {
+                    let passphrase = std::mem::take(&mut self.input);
+                    match self.app.unlock(&passphrase) {
+                        Ok(()) => {
+                            self.status = None;
+                            self.mode = Mode::Browse;
+                        }
+                        Err(e) => self.status = Some(e),
+                    }
+                }
+                KeyCode::Backspace => {
+                    self.input.pop();
+                }
+                KeyCode::Char(c) => self.input.push(c),
+                _ => {}
+            },
+        }
+        true
+    }
+
+    fn step(&mut self, by: i32) {
+        self.period = self.period.step(by);
+        self.table.select(Some(0));
+    }
+
+    fn draw(&mut self, frame: &mut Frame) {
+        let [header, body, footer] = Layout::vertical([
+            Constraint::Length(3),
+            Constraint::Min(5),
+            Constraint::Length(4),
+        ]).areas(frame.area());
+
+        if let Mode::Unlock = self.mode {
+            let lines = vec![
+                Line::from(t!("this-ledger-is-encrypted")),
+                Line::from(self.app.data_path.display().to_string()),
+                Line::from(""),
+                Line::from(format!("{}: {}", t!("passphrase"), "•".repeat(self.input.chars().count()))),
+                Line::from(self.status.clone().unwrap_or_default()).style(Style::new().fg(Color::Red)),
+            ];
+            frame.render_widget(Paragraph::new(lines).block(Block::bordered()), frame.area());
+            return;
+        }
+
+        let app = &self.app;
+        let shown = self.shown();
+        // Same totals as `finance_tracker report`, refunds netted against expenses
+        let (income, expenses) = app.build_report(&shown).months.iter()
+            .fold((0.0, 0.0), |(income, expenses), m| (income + m.2, expenses + m.3));
+        let title = format!(
+            "◀ {} ▶   {}: {}",
+            self.period.label(),
+            t!("widget-balance"),
+            app.money(app.balance(false)),
+        );
+        let totals = t!(
+            "cli-report-month",
+            month = self.period.label(),
+            income = app.money(income),
+            expenses = app.money(expenses),
+            net = app.money(income - expenses),
+        );
+        frame.render_widget(Paragraph::new(totals).block(Block::bordered().title(title)), header);
+
+        let today = Local::now();
+        let rows = shown.iter().map(|t| {
+            let amount = app.signed_amount(t);
+            let color = if amount < 0.0 { Color::Red } else { Color::Green };
+            let style = if t.date > today { Style::new().add_modifier(Modifier::DIM) } else { Style::new() };
+            Row::new(vec![
+                Cell::from(t.date.format("%Y-%m-%d").to_string()),
+                Cell::from(t.description.clone()),
+                Cell::from(t.category_path()),
+                Cell::from(Line::from(app.money(amount)).right_aligned()).style(Style::new().fg(color)),
+            ]).style(style)
+        });
+        let table = Table::new(rows, [
+            Constraint::Length(10),
+            Constraint::Fill(2),
+            Constraint::Fill(1),
+            Constraint::Length(14),
+        ])
+            .header(Row::new(vec![t!("column-date"), t!("column-description"), t!("column-category"), t!("column-amount")])
+                .style(Style::new().add_modifier(Modifier::BOLD)))
+            .row_highlight_style(Style::new().add_modifier(Modifier::REVERSED))
+            .block(Block::bordered().title(app.data_path.display().to_string()));
+        if shown.is_empty() {
+            let empty = Paragraph::new(t!("cli-no-transactions")).centered();
+            frame.render_widget(empty.block(Block::bordered().title(app.data_path.display().to_string())), body);
+        } else {
+            frame.render_stateful_widget(table, body, &mut self.table);
+        }
+
+        let (first, second) = match &self.mode {
+            Mode::Add => {
+                let preview = app.quick_transaction()
+                    .map(|t| format!("→ {}  {}  {}", t.description, t.category_path(), app.money(app.signed_amount(&t))))
+                    .unwrap_or_default();
+                (format!("{} {}▏", t!("quick-add-label"), app.quick_input), format!("{}   {}", preview, t!("tui-add-keys")))
+            }
+            Mode::ConfirmDelete(_) => (t!("tui-confirm-delete"), String::new()),
+            _ => (self.status.clone().unwrap_or_default(), t!("tui-keys")),
+        };
+        frame.render_widget(Paragraph::new(vec![Line::from(first), Line::from(second)]).block(Block::bordered()), footer);
+    }
+}
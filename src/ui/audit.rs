@@ -0,0 +1,93 @@
+use eframe::egui;
+use egui::Color32;
+
+use crate::{FinanceApp, audit, i18n, theme};
+use crate::audit::Cause;
+use crate::i18n::t;
+
+// Newest entries shown; the search reaches further back
+const AUDIT_SHOWN: usize = 500;
+
+fn cause_label(cause: Cause) -> String {
+    match cause {
+        Cause::Edit => t!("audit-cause-edit"),
+        Cause::Import => t!("audit-cause-import"),
+        Cause::Undo => t!("audit-cause-undo"),
+        Cause::Sync => t!("audit-cause-sync"),
+        Cause::Restore => t!("audit-cause-restore"),
+        Cause::Terminal => t!("audit-cause-terminal"),
+        Cause::Bot => t!("audit-cause-bot"),
+    }
+}
+
+impl FinanceApp {
+    pub fn show_audit_ui(&mut self, ui: &mut egui::Ui) {
+        let path = audit::journal_path(&self.data_path);
+        if self.audit_log.is_none() {
+            let read = self.journal_key().and_then(|key| audit::read(&path, key.as_ref()).map_err(|e| e.to_string()));
+            self.audit_log = Some(read.unwrap_or_else(|e| {
+                self.audit_error = Some(e);
+                Vec::new()
+            }));
+        }
+
+        ui.heading(t!("tab-audit"));
+        ui.label(t!("audit-explained", path = path.display().to_string()));
+        if self.passphrase.is_some() {
+            ui.colored_label(theme::warning(ui.visuals()), t!("audit-encrypted"));
+        }
+        if let Some(error) = &self.audit_error {
+            ui.colored_label(Color32::RED, t!("audit-failed", error = error.clone()));
+        }
+        ui.horizontal(|ui| {
+            ui.label("🔍");
+            ui.add(egui::TextEdit::singleline(&mut self.audit_search).hint_text(t!("audit-search-hint")));
+            if ui.button(t!("audit-reload")).clicked() {
+                self.audit_log = None;
+                self.audit_error = None;
+            }
+        });
+        ui.separator();
+
+        let query = self.audit_search.trim().to_lowercase();
+        let log = self.audit_log.as_deref().unwrap_or_default();
+        let shown: Vec<&audit::Entry> = log.iter()
+            .rev()
+            .filter(|entry| {
+                query.is_empty() || entry.before.iter().chain(&entry.after).any(|t| t.description.to_lowercase().contains(&query))
+            })
+            .take(AUDIT_SHOWN)
+            .collect();
+        if shown.is_empty() {
+            ui.label(t!("audit-empty"));
+            return;
+        }
+
+        egui::ScrollArea::vertical().show(ui, |ui| {
+            for entry in shown {
+                let Some(t) = entry.after.as_ref().or(entry.before.as_ref()) else { continue };
+                let action = match (&entry.before, &entry.after) {
+                    (None, _) => t!("audit-added"),
+                    (_, None) => t!("audit-deleted"),
+                    _ => t!("audit-changed"),
+                };
+                ui.horizontal(|ui| {
+                    ui.weak(i18n::date_time(&entry.time));
+                    ui.label(cause_label(entry.cause));
+                    ui.strong(action);
+                    ui.label(&t.description);
+                    ui.label(self.money(self.signed_amount(t)));
+                    ui.weak(i18n::date(t.date.date_naive()));
+                });
+                if let (Some(before), Some(after)) = (&entry.before, &entry.after) {
+                    for (field, old, new) in audit::changed_fields(before, after) {
+                        ui.horizontal(|ui| {
+                            ui.add_space(24.0);
+                            ui.weak(format!("{}: {} → {}", field, old, new));
+                        });
+                    }
+                }
+            }
+        });
+    }
+}
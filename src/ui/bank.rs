@@ -0,0 +1,148 @@
+use eframe::egui;
+use egui::Color32;
+
+use crate::{FinanceApp, audit, bank, i18n};
+use crate::i18n::t;
+use crate::model::{TransactionType, category_path};
+
+impl FinanceApp {
+    // Checks on a running bank pull, once per frame
+    pub fn poll_bank_pull(&mut self) {
+        let Some(result) = self.bank_pull.as_ref().and_then(|pull| pull.poll()) else { return };
+        self.bank_pull = None;
+        match result {
+            Ok(pulled) => {
+                tracing::info!(staged = pulled.staged.len(), skipped = pulled.skipped, "bank pull finished");
+                self.bank_status = Some(t!("bank-pulled", count = pulled.staged.len(), skipped = pulled.skipped));
+                if pulled.staged.is_empty() {
+                    self.remember_bank_pull(pulled.newest);
+                } else {
+                    self.bank_pulled = Some(pulled);
+                }
+            }
+            Err(e) => {
+                tracing::warn!("bank pull failed: {}", e);
+                self.bank_status = Some(t!("bank-pull-failed", error = e.to_string()));
+            }
+        }
+    }
+
+    fn remember_bank_pull(&mut self, newest: Option<chrono::NaiveDate>) {
+        if newest.is_some() {
+            self.settings.bank.last_pulled = newest;
+            self.save_settings();
+        }
+    }
+
+    pub fn show_bank_ui(&mut self, ui: &mut egui::Ui) {
+        ui.heading(t!("bank-connection"));
+        ui.label(t!("bank-explained"));
+        let mut changed = false;
+        egui::Grid::new("bank_connection").num_columns(2).show(ui, |ui| {
+            let bank = &mut self.settings.bank;
+            ui.label(t!("bank-secret-id"));
+            changed |= ui.add(egui::TextEdit::singleline(&mut bank.secret_id).desired_width(320.0)).lost_focus();
+            ui.end_row();
+            ui.label(t!("bank-secret-key"));
+            changed |= ui.add(egui::TextEdit::singleline(&mut bank.secret_key).password(true).desired_width(320.0)).lost_focus();
+            ui.end_row();
+            ui.label(t!("bank-account-id"));
+            changed |= ui.add(egui::TextEdit::singleline(&mut bank.account_id).desired_width(320.0)).lost_focus();
+            ui.end_row();
+        });
+        if changed {
+            self.save_settings();
+        }
+        ui.weak(t!("bank-secret-stored"));
+
+        ui.horizontal(|ui| {
+            let bank = &self.settings.bank;
+            let ready = !bank.secret_id.trim().is_empty() && !bank.secret_key.trim().is_empty() && !bank.account_id.trim().is_empty();
+            let busy = self.bank_pull.is_some() || self.bank_pulled.is_some();
+            let label = if self.bank_pull.is_some() { t!("fetching") } else { t!("bank-pull") };
+            if ui.add_enabled(ready && !busy && !self.read_only, egui::Button::new(label)).clicked() {
+                self.bank_status = None;
+                self.bank_pull = Some(bank::start(
+                    ui.ctx(),
+                    bank.clone(),
+                    self.rates.home_currency.clone(),
+                    self.rules.clone(),
+                    self.hooks.clone(),
+                    self.transactions.clone(),
+                ));
+            }
+            match bank.last_pulled {
+                Some(day) => ui.weak(t!("bank-last-pulled", date = i18n::date(day))),
+                None => ui.weak(t!("bank-never-pulled")),
+            };
+        });
+        if let Some(status) = &self.bank_status {
+            ui.label(status);
+        }
+    }
+
+    // Everything the pull brought in, to tick off before it goes in the ledger
+    pub fn show_bank_review(&mut self, ctx: &egui::Context) {
+        let Some(pulled) = &mut self.bank_pulled else { return };
+        let (mut add, mut discard) = (false, false);
+        egui::Window::new(t!("bank-review"))
+            .resizable(true)
+            .default_size([760.0, 420.0])
+            .show(ctx, |ui| {
+                ui.label(t!("bank-review-explained"));
+                ui.horizontal(|ui| {
+                    if ui.button(t!("select-all")).clicked() {
+                        pulled.staged.iter_mut().for_each(|s| s.include = true);
+                    }
+                    if ui.button(t!("select-none")).clicked() {
+                        pulled.staged.iter_mut().for_each(|s| s.include = false);
+                    }
+                });
+                ui.separator();
+                egui::ScrollArea::vertical().max_height(320.0).show(ui, |ui| {
+                    egui::Grid::new("bank_review").striped(true).num_columns(5).show(ui, |ui| {
+                        for staged in &mut pulled.staged {
+                            let t = &staged.transaction;
+                            ui.checkbox(&mut staged.include, "");
+                            ui.label(i18n::date(t.date.date_naive()));
+                            ui.label(&t.description);
+                            ui.label(category_path(&t.category, t.subcategory.as_deref()));
+                            let (sign, color) = match t.trans_type {
+                                TransactionType::Income => ("+", Color32::from_rgb(100, 200, 100)),
+                                TransactionType::Expense => ("-", Color32::from_rgb(220, 100, 100)),
+                            };
+                            let currency = t.currency.as_deref().unwrap_or(&self.rates.home_currency);
+                            ui.horizontal(|ui| {
+                                ui.colored_label(color, format!("{}{} {}", sign, self.money_format.number(t.amount), currency));
+                                if staged.duplicate_of.is_some() {
+                                    ui.weak(t!("bank-looks-like-existing"));
+                                }
+                            });
+                            ui.end_row();
+                        }
+                    });
+                });
+                ui.separator();
+                let count = pulled.staged.iter().filter(|s| s.include).count();
+                ui.horizontal(|ui| {
+                    add = ui.button(t!("bank-add-selected", count = count)).clicked();
+                    discard = ui.button(t!("bank-discard")).clicked();
+                });
+            });
+        if !(add || discard) {
+            return;
+        }
+        let Some(pulled) = self.bank_pulled.take() else { return };
+        if add {
+            let added: Vec<_> = pulled.staged.into_iter().filter(|s| s.include).map(|s| s.transaction).collect();
+            let count = added.len();
+            self.transactions.extend(added);
+            self.migrate_categories();
+            self.audit_cause = audit::Cause::Import;
+            self.save_data();
+            self.bank_status = Some(t!("bank-added", count = count));
+            // Discarded rows come back next time, so only an accepted pull moves on
+            self.remember_bank_pull(pulled.newest);
+        }
+    }
+}
@@ -0,0 +1,184 @@
+use chrono::Local;
+use eframe::egui;
+use egui::{Color32, Sense, Vec2};
+
+use crate::{FinanceApp, budgets, i18n, theme};
+use crate::budgets::BudgetStatus;
+use crate::error::AppError;
+use crate::goals::{Goal, GoalOutlook};
+use crate::i18n::t;
+use crate::model::TransactionType;
+
+impl FinanceApp {
+    pub fn show_budgets_ui(&mut self, ui: &mut egui::Ui) {
+        ui.heading(t!("set-monthly-budget"));
+
+        ui.horizontal(|ui| {
+            ui.label(t!("category-label"));
+            egui::ComboBox::from_id_salt("budget_cat")
+                .selected_text(self.budget_category.clone())
+                .show_ui(ui, |ui| {
+                    for cat in self.categories.iter().filter(|c| c.kind.matches(TransactionType::Expense)) {
+                        ui.selectable_value(&mut self.budget_category, cat.name.clone(), &cat.name);
+                    }
+                });
+
+            ui.label(t!("limit-label"));
+            ui.add(egui::TextEdit::singleline(&mut self.budget_limit).desired_width(80.0));
+
+            if ui.button(t!("set-limit")).clicked() {
+                match self.budget_limit.trim().parse::<f64>() {
+                    Ok(limit) if limit > 0.0 => {
+                        budgets::set_budget(&mut self.budgets, &self.budget_category, limit);
+                        self.budget_limit.clear();
+                        self.clear_input_error();
+                        self.save_data();
+                    }
+                    _ => self.error = Some(AppError::BadAmount { field: t!("budget-limit-field"), value: self.budget_limit.clone() }),
+                }
+            }
+        });
+        ui.separator();
+
+        let today = Local::now().date_naive();
+        // Budget months can start on payday instead of the 1st
+        let (from, to) = self.settings.month_containing(today);
+        ui.heading(t!("budgets-for", period = self.budget_month_label(from, to)));
+
+        if self.budgets.is_empty() {
+            ui.label(t!("no-budgets-yet-set-a-monthly"));
+            return;
+        }
+
+        let spending = self.category_spending(from, to);
+        let mut to_remove = None;
+
+        egui::ScrollArea::vertical().show(ui, |ui| {
+            for (index, budget) in self.budgets.iter().enumerate() {
+                let spent = spending.get(&budget.category).copied().unwrap_or(0.0);
+                let status = BudgetStatus::from_spend(spent, budget.monthly_limit);
+                let fraction = (spent / budget.monthly_limit).clamp(0.0, 1.0) as f32;
+
+                ui.horizontal(|ui| {
+                    let (rect, _resp) = ui.allocate_exact_size(Vec2::splat(16.0), Sense::hover());
+                    ui.painter().rect_filled(rect, 3.0, self.category_color(&budget.category));
+                    ui.add_sized([120.0, 16.0], egui::Label::new(&budget.category));
+
+                    ui.add(egui::ProgressBar::new(fraction)
+                        .desired_width(300.0)
+                        .fill(status.color())
+                        .text(format!("{} / {}", self.money(spent), self.money(budget.monthly_limit))));
+
+                    match status {
+                        BudgetStatus::Exceeded => {
+                            ui.colored_label(theme::readable(status.color(), ui.visuals()), t!("budget-over", amount = self.money(spent - budget.monthly_limit)));
+                        }
+                        _ => {
+                            ui.label(t!("budget-left", amount = self.money(budget.monthly_limit - spent)));
+                        }
+                    }
+
+                    if ui.button("🗑").clicked() {
+                        to_remove = Some(index);
+                    }
+                });
+            }
+        });
+
+        if let Some(index) = to_remove {
+            self.budgets.remove(index);
+            self.save_data();
+        }
+    }
+
+    pub fn show_goals_ui(&mut self, ui: &mut egui::Ui) {
+        ui.heading(t!("new-savings-goal"));
+
+        ui.horizontal(|ui| {
+            ui.label(t!("name-label"));
+            ui.text_edit_singleline(&mut self.goal_name);
+            ui.label(t!("target-label"));
+            ui.add(egui::TextEdit::singleline(&mut self.goal_amount).desired_width(80.0));
+            ui.label(t!("by-label"));
+            ui.add(egui_extras::DatePickerButton::new(&mut self.goal_date).id_salt("goal_date"));
+
+            if ui.button(t!("add")).clicked() {
+                match self.goal_amount.trim().parse::<f64>() {
+                    Ok(amount) if amount > 0.0 => {
+                        let name = self.goal_name.trim();
+                        if !name.is_empty() {
+                            self.goals.push(Goal::new(name, amount, self.goal_date, Local::now().date_naive()));
+                            self.goal_name.clear();
+                            self.goal_amount.clear();
+                            self.clear_input_error();
+                            self.save_data();
+                        }
+                    }
+                    _ => self.error = Some(AppError::BadAmount { field: t!("goal-target-field"), value: self.goal_amount.clone() }),
+                }
+            }
+        });
+        ui.label(t!("allocate-transactions-to-a-goal-with"));
+        ui.separator();
+
+        ui.heading(t!("goals"));
+
+        if self.goals.is_empty() {
+            ui.label(t!("no-goals-yet"));
+            return;
+        }
+
+        let today = Local::now().date_naive();
+        let mut to_remove = None;
+
+        egui::ScrollArea::vertical().show(ui, |ui| {
+            for (index, goal) in self.goals.iter().enumerate() {
+                let saved = self.goal_saved(goal.id);
+                let outlook = goal.outlook(saved, today);
+                let color = match outlook {
+                    GoalOutlook::Reached | GoalOutlook::OnTrack => Color32::from_rgb(100, 200, 100),
+                    GoalOutlook::Behind => Color32::from_rgb(255, 165, 0),
+                    GoalOutlook::Overdue => Color32::from_rgb(220, 50, 50),
+                };
+
+                ui.horizontal(|ui| {
+                    ui.strong(&goal.name);
+                    ui.weak(t!("goal-by", date = i18n::date(goal.target_date)));
+                    if ui.button("🗑").clicked() {
+                        to_remove = Some(index);
+                    }
+                });
+
+                ui.add(egui::ProgressBar::new(goal.fraction(saved) as f32)
+                    .desired_width(400.0)
+                    .fill(color)
+                    .text(format!("{} / {}", self.money(saved), self.money(goal.target_amount))));
+
+                ui.horizontal(|ui| {
+                    ui.colored_label(theme::readable(color, ui.visuals()), outlook.label());
+                    if outlook != GoalOutlook::Reached {
+                        match goal.projected_completion(saved, today) {
+                            Some(date) => ui.label(t!("goal-projected", date = i18n::date(date))),
+                            None => ui.label(t!("projected-no-contributions-yet")),
+                        };
+                        ui.label(t!("goal-needs", amount = self.money(goal.monthly_needed(saved, today))));
+                    }
+                });
+                ui.add_space(10.0);
+            }
+        });
+
+        // Allocated transactions are kept, just detached from the goal
+        if let Some(index) = to_remove {
+            let removed = self.goals.remove(index);
+            for t in self.transactions.iter_mut().filter(|t| t.goal == Some(removed.id)) {
+                t.goal = None;
+                t.touch();
+            }
+            if self.input_goal == Some(removed.id) {
+                self.input_goal = None;
+            }
+            self.save_data();
+        }
+    }
+}
@@ -0,0 +1,151 @@
+use eframe::egui;
+
+use crate::FinanceApp;
+use crate::i18n::t;
+use crate::model::{CategoryDef, CategoryKind, OTHER_CATEGORY, default_categories};
+use crate::ui::icon_picker;
+
+impl FinanceApp {
+    pub fn show_categories_ui(&mut self, ui: &mut egui::Ui) {
+        ui.heading(t!("add-category"));
+
+        ui.horizontal(|ui| {
+            ui.label(t!("name-label"));
+            ui.text_edit_singleline(&mut self.new_cat_name);
+            ui.color_edit_button_srgb(&mut self.new_cat_color);
+            icon_picker(ui, &mut self.new_cat_icon);
+
+            for kind in [CategoryKind::Income, CategoryKind::Expense, CategoryKind::Both] {
+                ui.radio_value(&mut self.new_cat_kind, kind, kind.label());
+            }
+
+            ui.add_space(20.0);
+
+            let name = self.new_cat_name.trim().to_string();
+            let valid = !name.is_empty() && self.find_category(&name).is_none();
+            if ui.add_enabled(valid, egui::Button::new(t!("add"))).clicked() {
+                self.categories.push(CategoryDef::new(&name, self.new_cat_color, self.new_cat_kind).with_icon(&self.new_cat_icon));
+                self.new_cat_name.clear();
+                self.new_cat_icon.clear();
+                self.save_data();
+            }
+        });
+        ui.separator();
+
+        ui.heading(t!("categories"));
+
+        let mut to_delete = None;
+        let mut to_rename = None;
+        let mut to_delete_sub = None;
+        let mut to_add_sub = None;
+        let mut changed = false;
+
+        // Seeded categories can go back to their original color
+        let defaults = default_categories();
+
+        egui::ScrollArea::vertical().show(ui, |ui| {
+            for (index, cat) in self.categories.iter_mut().enumerate() {
+                ui.horizontal(|ui| {
+                    if ui.color_edit_button_srgb(&mut cat.color).on_hover_text(t!("category-color")).changed() {
+                        changed = true;
+                    }
+                    let original = defaults.iter().find(|d| d.name == cat.name).map(|d| d.color);
+                    if let Some(color) = original.filter(|color| *color != cat.color) {
+                        if ui.small_button("↺").on_hover_text(t!("default-color")).clicked() {
+                            cat.color = color;
+                            changed = true;
+                        }
+                    }
+                    ui.push_id(index, |ui| {
+                        if icon_picker(ui, &mut cat.icon) {
+                            changed = true;
+                        }
+                    });
+
+                    match &mut self.renaming_category {
+                        Some((idx, buffer)) if *idx == index => {
+                            ui.text_edit_singleline(buffer);
+                            if ui.button(t!("save")).clicked() {
+                                to_rename = Some((index, buffer.trim().to_string()));
+                            }
+                            if ui.button(t!("cancel")).clicked() {
+                                to_rename = Some((index, cat.name.clone()));
+                            }
+                        }
+                        _ => {
+                            ui.label(&cat.name);
+                            ui.weak(format!("({})", cat.kind.label()));
+
+                            // The catch-all category can't be renamed or removed
+                            if cat.name != OTHER_CATEGORY {
+                                if ui.button("✏").clicked() {
+                                    self.renaming_category = Some((index, cat.name.clone()));
+                                }
+                                if ui.button("🗑").clicked() {
+                                    to_delete = Some(index);
+                                }
+                            }
+                            if ui.button(t!("add-subcategory")).clicked() {
+                                self.new_subcat = Some((index, String::new()));
+                            }
+                        }
+                    }
+                });
+
+                for sub in &cat.subcategories {
+                    ui.horizontal(|ui| {
+                        ui.add_space(32.0);
+                        ui.label(format!("↳ {}", sub));
+                        if ui.small_button("🗑").clicked() {
+                            to_delete_sub = Some((index, sub.clone()));
+                        }
+                    });
+                }
+
+                if let Some((idx, buffer)) = &mut self.new_subcat {
+                    if *idx == index {
+                        ui.horizontal(|ui| {
+                            ui.add_space(32.0);
+                            ui.text_edit_singleline(buffer);
+                            if ui.button(t!("add")).clicked() {
+                                to_add_sub = Some((index, buffer.trim().to_string()));
+                            }
+                            if ui.button(t!("cancel")).clicked() {
+                                to_add_sub = Some((index, String::new()));
+                            }
+                        });
+                    }
+                }
+            }
+        });
+
+        if let Some((index, name)) = to_add_sub {
+            self.new_subcat = None;
+            let subs = &mut self.categories[index].subcategories;
+            if !name.is_empty() && !subs.contains(&name) {
+                subs.push(name);
+                changed = true;
+            }
+        }
+
+        if let Some((index, sub)) = to_delete_sub {
+            self.delete_subcategory(index, &sub);
+            changed = true;
+        }
+
+        if let Some((index, new_name)) = to_rename {
+            self.renaming_category = None;
+            self.rename_category(index, new_name);
+            changed = true;
+        }
+
+        if let Some(index) = to_delete {
+            self.delete_category(index);
+            changed = true;
+        }
+
+        if changed {
+            self.save_data();
+        }
+    }
+}
@@ -0,0 +1,1466 @@
+use chrono::{Datelike, Local, NaiveDate, NaiveDateTime};
+use eframe::egui;
+use egui::{Color32, Pos2, Sense, Shape, Stroke, Vec2};
+use egui_plot::{Bar, BarChart, Legend, Line, Plot, PlotPoints, Points};
+use std::collections::BTreeSet;
+use std::f64::consts::TAU;
+
+use crate::{FinanceApp, Tab, export, forecast, i18n, reports, sankey, theme, treemap};
+use crate::budgets::BudgetStatus;
+use crate::i18n::t;
+use crate::model::{Transaction, TransactionType, local_from_naive, local_from_timestamp};
+use crate::period::{AnalyticsRange, Period};
+
+// "March 2025" for a month index, as plotted on chart axes
+pub fn month_label(index: f64) -> String {
+    let index = index.round() as i32;
+    NaiveDate::from_ymd_opt(index.div_euclid(12), index.rem_euclid(12) as u32 + 1, 1)
+        .map(i18n::month_year)
+        .unwrap_or_default()
+}
+
+// The big chart at the top of the Analytics tab
+#[derive(PartialEq, Default)]
+pub enum AnalyticsChart {
+    #[default]
+    Balance,
+    CashFlow,
+    Categories,
+    Flow,
+    Histogram,
+    Patterns,
+    Treemap,
+    Rolling,
+    Budget,
+}
+
+impl AnalyticsChart {
+    pub fn file_name(&self) -> &'static str {
+        match self {
+            AnalyticsChart::Balance => "balance-history.png",
+            AnalyticsChart::CashFlow => "cash-flow.png",
+            AnalyticsChart::Categories => "spending-by-category.png",
+            AnalyticsChart::Flow => "money-flow.png",
+            AnalyticsChart::Histogram => "amount-distribution.png",
+            AnalyticsChart::Patterns => "spending-patterns.png",
+            AnalyticsChart::Treemap => "expense-treemap.png",
+            AnalyticsChart::Rolling => "rolling-spending.png",
+            AnalyticsChart::Budget => "budget-vs-actual.png",
+        }
+    }
+}
+
+// What a click on a chart asks the transaction list to show
+#[derive(Default)]
+pub struct DrillDown {
+    pub category: Option<String>,
+    pub trans_type: Option<TransactionType>,
+    // None for no limit on that side
+    pub from: Option<NaiveDate>,
+    pub to: Option<NaiveDate>,
+    // Amounts in the home currency, the upper one not included
+    pub amounts: Option<(f64, f64)>,
+}
+
+// Days the rolling spending line adds up
+pub const ROLLING_DAYS: i64 = 30;
+
+// Expense pie: smaller slices are lumped together, bigger ones get their percentage
+pub const PIE_OTHER_SHARE: f64 = 0.03;
+
+pub const PIE_LABEL_SHARE: f64 = 0.08;
+
+impl FinanceApp {
+    // Asks for a screenshot; the part of it inside `rect` is saved when it arrives
+    pub fn export_image_button(&mut self, ui: &mut egui::Ui, rect: Option<egui::Rect>, file_name: &'static str) {
+        let rect = rect.filter(|rect| rect.is_positive() && self.pending_capture.is_none());
+        if ui.add_enabled(rect.is_some(), egui::Button::new(t!("export-image"))).clicked() {
+            self.pending_capture = rect.map(|rect| (rect, file_name));
+            ui.ctx().send_viewport_cmd(egui::ViewportCommand::Screenshot);
+        }
+    }
+
+    pub fn save_capture(&mut self, ctx: &egui::Context) {
+        let Some((rect, file_name)) = self.pending_capture else { return };
+        let screenshot = ctx.input(|i| i.events.iter().find_map(|event| match event {
+            egui::Event::Screenshot { image, .. } => Some(image.clone()),
+            _ => None,
+        }));
+        let Some(screenshot) = screenshot else { return };
+        self.pending_capture = None;
+
+        let pixels_per_point = ctx.pixels_per_point();
+        let size = egui::vec2(screenshot.size[0] as f32, screenshot.size[1] as f32) / pixels_per_point;
+        let rect = rect.intersect(egui::Rect::from_min_size(egui::Pos2::ZERO, size));
+        if !rect.is_positive() {
+            return;
+        }
+        let image = screenshot.region(&rect, Some(pixels_per_point));
+        if let Some(path) = export::pick_target("PNG image", "png", file_name) {
+            match export::write_png(&path, &image) {
+                Ok(()) => self.toasts.info(t!("image-saved", path = path.display().to_string())),
+                Err(e) => self.toasts.error(t!("image-save-failed", error = e)),
+            }
+        }
+    }
+
+    // What the script's on_report makes of the transactions in the range, on request
+    pub fn show_script_report(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            if ui.button(t!("run-report-script")).on_hover_text(t!("run-report-script-hint")).clicked() {
+                let shown: Vec<&Transaction> = self.analytics_transactions().collect();
+                let report = self.hooks.as_ref().and_then(|hooks| hooks.on_report(&shown));
+                self.script_report = report;
+            }
+            if self.script_report.is_some() && ui.small_button("✖").clicked() {
+                self.script_report = None;
+            }
+        });
+        match &self.script_report {
+            Some(Ok(text)) => {
+                ui.monospace(text);
+            }
+            Some(Err(e)) => {
+                ui.colored_label(theme::warning(ui.visuals()), e);
+            }
+            None => {}
+        }
+    }
+
+    pub fn show_analytics_ui(&mut self, ui: &mut egui::Ui) {
+        self.refresh_aggregates();
+        ui.horizontal(|ui| {
+            ui.label(t!("analytics-range-label"));
+            for range in AnalyticsRange::ALL {
+                ui.selectable_value(&mut self.analytics_range, range, range.label());
+            }
+            if let Some((from, to)) = self.analytics_bounds() {
+                ui.weak(format!("{} – {}", i18n::date(from), i18n::date(to)));
+            }
+        });
+        if self.hooks.as_ref().is_some_and(|hooks| hooks.has_report()) {
+            self.show_script_report(ui);
+        }
+        // Wraps, there are more charts than fit on one line in a narrow window
+        ui.horizontal_wrapped(|ui| {
+            ui.selectable_value(&mut self.analytics_chart, AnalyticsChart::Balance, egui::RichText::new(t!("balance-history")).heading());
+            ui.selectable_value(&mut self.analytics_chart, AnalyticsChart::CashFlow, egui::RichText::new(t!("monthly-cash-flow")).heading());
+            ui.selectable_value(&mut self.analytics_chart, AnalyticsChart::Categories, egui::RichText::new(t!("spending-by-category")).heading());
+            ui.selectable_value(&mut self.analytics_chart, AnalyticsChart::Flow, egui::RichText::new(t!("money-flow")).heading());
+            ui.selectable_value(&mut self.analytics_chart, AnalyticsChart::Histogram, egui::RichText::new(t!("amount-distribution")).heading());
+            ui.selectable_value(&mut self.analytics_chart, AnalyticsChart::Patterns, egui::RichText::new(t!("spending-patterns")).heading());
+            ui.selectable_value(&mut self.analytics_chart, AnalyticsChart::Treemap, egui::RichText::new(t!("expense-treemap")).heading());
+            ui.selectable_value(&mut self.analytics_chart, AnalyticsChart::Rolling, egui::RichText::new(t!("rolling-spending")).heading());
+            if !self.budgets.is_empty() {
+                ui.selectable_value(&mut self.analytics_chart, AnalyticsChart::Budget, egui::RichText::new(t!("budget-vs-actual")).heading());
+            } else if self.analytics_chart == AnalyticsChart::Budget {
+                self.analytics_chart = AnalyticsChart::Balance;
+            }
+            let (chart_rect, file_name) = (self.chart_rect, self.analytics_chart.file_name());
+            self.export_image_button(ui, chart_rect, file_name);
+            if self.analytics_chart == AnalyticsChart::CashFlow {
+                ui.checkbox(&mut self.show_net_line, t!("show-net-line"));
+            }
+            if self.analytics_chart == AnalyticsChart::Histogram {
+                ui.selectable_value(&mut self.histogram_type, TransactionType::Expense, t!("expense"));
+                ui.selectable_value(&mut self.histogram_type, TransactionType::Income, t!("income"));
+                let kind = self.histogram_type;
+                egui::ComboBox::from_id_salt("histogram_category")
+                    .selected_text(self.histogram_category.clone().unwrap_or_else(|| t!("all-categories")))
+                    .show_ui(ui, |ui| {
+                        ui.selectable_value(&mut self.histogram_category, None, t!("all-categories"));
+                        for cat in self.categories.iter().filter(|c| c.kind.matches(kind)) {
+                            ui.selectable_value(&mut self.histogram_category, Some(cat.name.clone()), &cat.name);
+                        }
+                    });
+            }
+            if self.analytics_chart == AnalyticsChart::Balance {
+                ui.label(t!("forecast-label"));
+                let months_label = |months: u32| if months == 0 { t!("forecast-off") } else { t!("forecast-months", count = months) };
+                egui::ComboBox::from_id_salt("forecast_months")
+                    .selected_text(months_label(self.forecast_months))
+                    .show_ui(ui, |ui| {
+                        for months in 0..=3 {
+                            ui.selectable_value(&mut self.forecast_months, months, months_label(months));
+                        }
+                    });
+            }
+        });
+        let available_height = ui.available_height();
+        let plot_height = available_height * 0.5;
+        
+        let filtered = self.filter.is_active();
+        if filtered && self.analytics_chart == AnalyticsChart::Balance {
+            ui.label(t!("filters-are-on-the-line-shows"));
+        }
+
+        // Clicking a chart shows the transactions behind what was clicked
+        let mut drill = None;
+        let chart_top = ui.cursor().top();
+        if self.analytics_chart == AnalyticsChart::Budget {
+            drill = self.show_budget_chart(ui, plot_height);
+        } else if self.analytics_chart == AnalyticsChart::Categories {
+            if self.transactions.is_empty() {
+                self.show_onboarding(ui);
+            } else {
+                drill = self.show_category_bars(ui, plot_height);
+            }
+        } else if self.analytics_chart == AnalyticsChart::Flow {
+            if self.transactions.is_empty() {
+                self.show_onboarding(ui);
+            } else {
+                drill = self.show_money_flow(ui, plot_height);
+            }
+        } else if self.analytics_chart == AnalyticsChart::Histogram {
+            if self.transactions.is_empty() {
+                self.show_onboarding(ui);
+            } else {
+                drill = self.show_amount_histogram(ui, plot_height);
+            }
+        } else if self.analytics_chart == AnalyticsChart::Patterns {
+            if self.transactions.is_empty() {
+                self.show_onboarding(ui);
+            } else {
+                self.show_spending_patterns(ui, plot_height);
+            }
+        } else if self.analytics_chart == AnalyticsChart::Treemap {
+            if self.transactions.is_empty() {
+                self.show_onboarding(ui);
+            } else {
+                drill = self.show_expense_treemap(ui, plot_height);
+            }
+        } else if self.analytics_chart == AnalyticsChart::Rolling {
+            if self.transactions.is_empty() {
+                self.show_onboarding(ui);
+            } else {
+                drill = self.show_rolling_spending(ui, plot_height);
+            }
+        } else if self.analytics_chart == AnalyticsChart::CashFlow {
+            if self.transactions.is_empty() {
+                self.show_onboarding(ui);
+            } else {
+                drill = self.show_cash_flow_chart(ui, plot_height);
+            }
+        } else {
+            ui.push_id("line_graph", |ui| {
+                let mut points: Vec<[f64; 2]> = Vec::new();
+                // Scheduled transactions continue the line from the last real point
+                let mut projected: Vec<[f64; 2]> = Vec::new();
+                let mut tooltips: Vec<(f64, f64, &str, f64, TransactionType)> = Vec::new();
+
+                for &(index, running_balance) in &self.aggregates.line {
+                    let t = &self.transactions[index];
+                    let amount = self.home_amount(t);
+                    // Earlier ones still count towards the balance, they just aren't drawn
+                    if !self.in_analytics_range(t.date.date_naive()) {
+                        continue;
+                    }
+                    let x = t.date.timestamp() as f64; 
+                    if t.is_scheduled() {
+                        if projected.is_empty() {
+                            if let Some(last) = points.last() {
+                                projected.push(*last);
+                            }
+                        }
+                        projected.push([x, running_balance]);
+                    } else {
+                        points.push([x, running_balance]);
+                    }
+                    tooltips.push((x, running_balance, &t.description, amount, t.trans_type));
+                }
+
+                if self.transactions.is_empty() {
+                    self.show_onboarding(ui);
+                } else if points.is_empty() && projected.is_empty() {
+                    ui.vertical_centered(|ui| {
+                        ui.add_space(20.0);
+                        ui.label(t!("no-transactions-yet-add-some-data"));
+                        ui.add_space(20.0);
+                    });
+                } else {
+                    // A filtered line doesn't say where the balance is heading, nor does
+                    // one that ends before today
+                    let today = Local::now().date_naive();
+                    let reaches_today = self.analytics_bounds().is_none_or(|(_, to)| to >= today);
+                    let forecast = match points.last() {
+                        Some(&[_, balance]) if !filtered && reaches_today => self.balance_forecast(balance),
+                        _ => None,
+                    };
+                    let mut forecast_points: Vec<[f64; 2]> = Vec::new();
+                    if let Some(forecast) = &forecast {
+                        forecast_points.extend(points.last().copied());
+                        forecast_points.extend(forecast.points.iter().map(|(day, balance)| {
+                            [local_from_naive(day.and_hms_opt(12, 0, 0).unwrap_or_default()).timestamp() as f64, *balance]
+                        }));
+                        match (forecast.zero_on, forecast.points.last()) {
+                            (Some(day), _) => {
+                                ui.colored_label(theme::warning(ui.visuals()), t!("forecast-zero", date = i18n::date(day)));
+                            }
+                            (None, Some((day, balance))) => {
+                                ui.label(t!("forecast-end", date = i18n::date(*day), balance = self.money(*balance)));
+                            }
+                            (None, None) => {}
+                        }
+                    }
+
+                    let fmt = self.money_format.clone();
+                    let balance_color = theme::balance_line(ui.visuals());
+                    let balance_name = t!("chart-balance");
+                    let projected_name = t!("chart-projected");
+                    let forecast_name = t!("chart-forecast");
+                    let forecast_label = forecast_name.clone();
+                    let forecast_color = theme::forecast_line(ui.visuals());
+                    let (names, income, expense) = (
+                        (balance_name.clone(), projected_name.clone()),
+                        t!("income"),
+                        t!("expense"),
+                    );
+                    let projected_color = theme::projected_line(ui.visuals());
+                    // Transactions that can be clicked through to
+                    let dots: Vec<[f64; 2]> = points.iter().chain(&projected).copied().collect();
+                    let plot = Plot::new("balance_plot")
+                        .height(plot_height)
+                        .allow_zoom(true)
+                        .allow_drag(true)
+                        .legend(Legend::default())
+                        .auto_bounds(egui::Vec2b::TRUE)
+                        .x_axis_formatter(|x, _range| {
+                            let val = x.value; 
+                            if let Some(dt) = local_from_timestamp(val) {
+                                format!("{}\n{}", i18n::date(dt.date_naive()), dt.format("%H:%M"))
+                            } else {
+                                String::new()
+                            }
+                        })
+                        .label_formatter(move |name, value| {
+                             if name == forecast_label {
+                                 let day = local_from_timestamp(value.x).map(|dt| i18n::date(dt.date_naive())).unwrap_or_default();
+                                 return t!("chart-forecast-at", date = day, balance = fmt.money(value.y));
+                             }
+                             if name != names.0 && name != names.1 { return String::new(); }
+                         
+                             let closest = tooltips.iter().min_by(|a, b| {
+                                 let dist_a = (a.0 - value.x).abs();
+                                 let dist_b = (b.0 - value.x).abs();
+                                 dist_a.partial_cmp(&dist_b).unwrap_or(std::cmp::Ordering::Equal)
+                             });
+                         
+                             if let Some((x, y, desc, amt, t_type)) = closest {
+                                 if (x - value.x).abs() < 86400.0 { 
+                                     let date_str = local_from_timestamp(*x)
+                                         .map(|dt| i18n::date_time(&dt))
+                                         .unwrap_or_default();
+                                 
+                                     let (sign, color_name) = match t_type {
+                                         TransactionType::Income => ("+", &income),
+                                         TransactionType::Expense => ("-", &expense),
+                                     };
+
+                                     return t!(
+                                         "chart-tooltip",
+                                         date = date_str,
+                                         description = desc.to_string(),
+                                         amount = format!("{}{}", sign, fmt.money(*amt)),
+                                         kind = color_name.clone(),
+                                         balance = fmt.money(*y),
+                                     );
+                                 }
+                             }
+                             t!("chart-balance-at", balance = fmt.money(value.y))
+                        })
+                        .show(ui, |plot_ui| {
+                            plot_ui.line(Line::new(PlotPoints::from(points.clone())).name(balance_name).width(2.0).color(balance_color));
+                            plot_ui.points(Points::new(PlotPoints::from(points)).radius(4.0).color(balance_color));
+
+                            if !projected.is_empty() {
+                                plot_ui.line(Line::new(PlotPoints::from(projected.clone()))
+                                    .name(projected_name)
+                                    .width(2.0)
+                                    .style(egui_plot::LineStyle::dashed_loose())
+                                    .color(projected_color));
+                                plot_ui.points(Points::new(PlotPoints::from(projected)).radius(3.0).color(projected_color));
+                            }
+                            if !forecast_points.is_empty() {
+                                plot_ui.line(Line::new(PlotPoints::from(forecast_points))
+                                    .name(forecast_name)
+                                    .width(2.0)
+                                    .style(egui_plot::LineStyle::dotted_dense())
+                                    .color(forecast_color));
+                            }
+                        });
+
+                    // The day of the dot clicked, if the click was close enough to one
+                    if let Some(pos) = plot.response.interact_pointer_pos().filter(|_| plot.response.clicked()) {
+                        let nearest = dots.iter()
+                            .map(|&[x, y]| (x, plot.transform.position_from_point(&egui_plot::PlotPoint::new(x, y)).distance(pos)))
+                            .min_by(|a, b| a.1.total_cmp(&b.1));
+                        if let Some((x, distance)) = nearest {
+                            if distance < 12.0 {
+                                let day = local_from_timestamp(x).map(|dt| dt.date_naive());
+                                drill = Some(DrillDown { from: day, to: day, ..Default::default() });
+                            }
+                        }
+                    }
+                }
+            });
+        }
+
+        self.chart_rect = Some(egui::Rect::from_x_y_ranges(ui.max_rect().x_range(), chart_top..=ui.min_rect().bottom()));
+
+        ui.add_space(20.0);
+        ui.separator();
+        // The chart stays put, the reports below it scroll
+        egui::ScrollArea::vertical().show(ui, |ui| {
+            ui.add_space(10.0);
+            if let Some(clicked) = self.show_expense_breakdown(ui) {
+                drill = Some(clicked);
+            }
+            ui.add_space(10.0);
+            ui.separator();
+            self.show_top_expenses(ui);
+            ui.add_space(10.0);
+            self.show_spend_stats(ui);
+            ui.add_space(10.0);
+            ui.separator();
+            self.show_period_comparison(ui);
+            ui.add_space(10.0);
+            ui.separator();
+            self.show_recurring_bills(ui);
+        });
+
+        if let Some(drill) = drill {
+            self.drill_down(drill);
+        }
+    }
+
+    // Switches to the transaction list, filtered down to what made up a number on a
+    // chart. Whatever the filter already had still applies.
+    pub fn drill_down(&mut self, drill: DrillDown) {
+        // None sorts first, so max is the later start and ignores a missing one
+        self.filter.from = self.filter.from.max(drill.from);
+        self.filter.to = match (self.filter.to, drill.to) {
+            (Some(a), Some(b)) => Some(a.min(b)),
+            (a, b) => a.or(b),
+        };
+        if let Some(category) = drill.category {
+            self.filter.categories = BTreeSet::from([category]);
+        }
+        if drill.trans_type.is_some() {
+            self.filter.trans_type = drill.trans_type;
+        }
+        if let Some((min, max)) = drill.amounts {
+            self.filter.min_amount = format!("{:.2}", min);
+            self.filter.max_amount = format!("{:.2}", max - 0.01);
+        }
+        // The dates are in the filter now, the list shouldn't cut them down to a month
+        self.period = Period::All;
+        self.search_query.clear();
+        self.show_filters = true;
+        self.current_tab = Tab::Transactions;
+    }
+
+    // A whole calendar month (see month_index), as far as the Analytics range goes
+    pub fn month_drill(&self, index: i32, trans_type: Option<TransactionType>, category: Option<String>) -> DrillDown {
+        let first = NaiveDate::from_ymd_opt(index.div_euclid(12), index.rem_euclid(12) as u32 + 1, 1);
+        let last = first.and_then(|d| d.checked_add_months(chrono::Months::new(1))).and_then(|d| d.pred_opt());
+        let (mut from, mut to) = (first, last);
+        if let Some((start, end)) = self.analytics_bounds() {
+            from = from.map(|d| d.max(start));
+            to = to.map(|d| d.min(end));
+        }
+        DrillDown { category, trans_type, from, to, amounts: None }
+    }
+
+    pub fn show_expense_breakdown(&mut self, ui: &mut egui::Ui) -> Option<DrillDown> {
+        ui.horizontal(|ui| {
+            ui.heading(t!("expense-breakdown"));
+            self.export_image_button(ui, self.breakdown_rect, "expense-breakdown.png");
+        });
+        
+        // Subcategory amounts roll up into their parent for the pie, and are listed beneath it in the legend.
+        // A handful of entries, cloned so the closures below can borrow self.
+        let category_totals = self.aggregates.category_totals.clone();
+        let subcategory_totals = self.aggregates.subcategory_totals.clone();
+        let total_expenses: f64 = category_totals.values().sum();
+
+        let mut clicked = None;
+        if total_expenses > 0.0 {
+            let shown = ui.horizontal(|ui| {
+                clicked = self.draw_pie_chart(ui, &category_totals, total_expenses);
+                ui.add_space(40.0);
+
+                ui.vertical(|ui| {
+                    let mut sorted_cats: Vec<_> = category_totals.iter().collect();
+                    sorted_cats.sort_by(|a, b| b.1.partial_cmp(a.1).unwrap());
+
+                    for (cat, amount) in sorted_cats {
+                        let percentage = (amount / total_expenses) * 100.0;
+                        ui.horizontal(|ui| {
+                            self.category_chip(ui, cat);
+                            ui.label(format!("{} ({:.1}%)", cat, percentage));
+                            ui.label(self.money(*amount));
+                        });
+
+                        let mut subs: Vec<_> = subcategory_totals.iter()
+                            .filter(|((parent, _), _)| parent == cat)
+                            .collect();
+                        subs.sort_by(|a, b| b.1.partial_cmp(a.1).unwrap_or(std::cmp::Ordering::Equal));
+
+                        for ((_, sub), sub_amount) in subs {
+                            ui.horizontal(|ui| {
+                                ui.add_space(24.0);
+                                ui.weak(format!("{} ({:.1}%)", sub, (sub_amount / total_expenses) * 100.0));
+                                ui.weak(self.money(*sub_amount));
+                            });
+                        }
+                    }
+                });
+            });
+            // Only what is scrolled into view makes it into a screenshot
+            self.breakdown_rect = Some(shown.response.rect.intersect(ui.clip_rect()));
+        } else {
+            self.breakdown_rect = None;
+            ui.label(t!("no-expenses-to-show"));
+        }
+
+        let bounds = self.analytics_bounds();
+        clicked.map(|category| DrillDown {
+            category: Some(category),
+            from: bounds.map(|(from, _)| from),
+            to: bounds.map(|(_, to)| to),
+            ..Default::default()
+        })
+    }
+
+    // The biggest single expenses, and where the most money went in total
+    pub fn show_top_expenses(&self, ui: &mut egui::Ui) {
+        ui.heading(t!("top-expenses"));
+        ui.add_space(5.0);
+
+        let mut largest: Vec<&Transaction> = self.analytics_transactions()
+            .filter(|t| t.trans_type == TransactionType::Expense)
+            .collect();
+        if largest.is_empty() {
+            ui.label(t!("no-expenses-to-show"));
+            return;
+        }
+        let payees = reports::top_payees(largest.iter().map(|t| (t.description.as_str(), self.home_amount(t))), 10);
+        largest.sort_by(|a, b| self.home_amount(b).total_cmp(&self.home_amount(a)));
+        largest.truncate(10);
+
+        ui.columns(2, |columns| {
+            columns[0].strong(t!("largest-expenses"));
+            egui::Grid::new("largest_expenses").striped(true).show(&mut columns[0], |ui| {
+                for t in &largest {
+                    ui.label(i18n::date(t.date.date_naive()));
+                    ui.horizontal(|ui| {
+                        self.category_chip(ui, &t.category);
+                        ui.label(&t.description);
+                    });
+                    ui.colored_label(theme::expense(ui.visuals()), self.money(self.home_amount(t)));
+                    ui.end_row();
+                }
+            });
+
+            columns[1].strong(t!("top-payees"));
+            egui::Grid::new("top_payees").striped(true).show(&mut columns[1], |ui| {
+                for payee in &payees {
+                    ui.label(&payee.description);
+                    ui.weak(t!("times-count", count = payee.count));
+                    ui.colored_label(theme::expense(ui.visuals()), self.money(payee.total));
+                    ui.end_row();
+                }
+            });
+        });
+    }
+
+    // Averages and such for the Analytics range, up to today. All time starts on the
+    // first transaction.
+    pub fn show_spend_stats(&self, ui: &mut egui::Ui) {
+        let today = Local::now().date_naive();
+        let first = self.transactions.iter().map(|t| t.date.date_naive()).min().unwrap_or(today);
+        let (from, to) = self.analytics_bounds().unwrap_or((first, today));
+        let to = to.min(today);
+        ui.strong(t!("spending-stats"));
+        if from > to {
+            ui.label(t!("no-expenses-to-show"));
+            return;
+        }
+
+        let expenses: Vec<(NaiveDate, f64)> = self.analytics_transactions()
+            .filter(|t| t.trans_type == TransactionType::Expense)
+            .map(|t| (t.date.date_naive(), self.home_amount(t)))
+            .collect();
+        let stats = reports::spend_stats(&expenses, from, to);
+
+        egui::Grid::new("spend_stats").num_columns(2).striped(true).show(ui, |ui| {
+            ui.label(t!("stats-total-spent"));
+            ui.label(self.money(stats.total));
+            ui.end_row();
+            ui.label(t!("stats-average-daily"));
+            ui.label(self.money(stats.average_daily));
+            ui.end_row();
+            ui.label(t!("stats-average-expense"));
+            ui.label(self.money(stats.average_expense));
+            ui.end_row();
+            ui.label(t!("stats-median-expense"));
+            ui.label(self.money(stats.median_expense));
+            ui.end_row();
+            ui.label(t!("stats-busiest-day"));
+            match stats.busiest_day {
+                Some((day, amount)) => ui.label(format!("{} ({})", i18n::date(day), self.money(amount))),
+                None => ui.label("—"),
+            };
+            ui.end_row();
+            ui.label(t!("stats-no-spend-days"));
+            ui.label(t!("stats-days-of", count = stats.no_spend_days, total = (to - from).num_days() + 1));
+            ui.end_row();
+        });
+    }
+
+    // Two periods side by side, per category, with the biggest increases highlighted.
+    // Any dates can be picked, so this goes by neither the Analytics range nor the filter.
+    pub fn show_period_comparison(&mut self, ui: &mut egui::Ui) {
+        ui.heading(t!("compare-periods"));
+        let today = Local::now().date_naive();
+        let [mut before, mut after] = self.compare_periods
+            .unwrap_or_else(|| [self.settings.previous_month(today), self.settings.month_containing(today)]);
+        ui.horizontal(|ui| {
+            ui.label(t!("compare-period-a"));
+            ui.add(egui_extras::DatePickerButton::new(&mut before.0).id_salt("compare_a_from"));
+            ui.label("–");
+            ui.add(egui_extras::DatePickerButton::new(&mut before.1).id_salt("compare_a_to"));
+            ui.add_space(20.0);
+            ui.label(t!("compare-period-b"));
+            ui.add(egui_extras::DatePickerButton::new(&mut after.0).id_salt("compare_b_from"));
+            ui.label("–");
+            ui.add(egui_extras::DatePickerButton::new(&mut after.1).id_salt("compare_b_to"));
+        });
+        self.compare_periods = Some([before, after]);
+
+        let changes = reports::compare_categories(
+            &self.category_spending(before.0, before.1),
+            &self.category_spending(after.0, after.1),
+        );
+        if changes.is_empty() {
+            ui.label(t!("no-expenses-to-show"));
+            return;
+        }
+        // The three biggest increases stand out
+        let highlighted = changes.iter().take(3).filter(|c| c.delta() > 0.005).count();
+        let warning = theme::warning(ui.visuals());
+        let saved = theme::income(ui.visuals());
+
+        egui::Grid::new("period_comparison").num_columns(5).striped(true).show(ui, |ui| {
+            ui.strong(t!("column-category"));
+            ui.strong(format!("{} – {}", i18n::date(before.0), i18n::date(before.1)));
+            ui.strong(format!("{} – {}", i18n::date(after.0), i18n::date(after.1)));
+            ui.strong(t!("compare-change"));
+            ui.strong("%");
+            ui.end_row();
+            for (i, change) in changes.iter().enumerate() {
+                let delta = change.delta();
+                let color = if i < highlighted {
+                    Some(warning)
+                } else if delta < -0.005 {
+                    Some(saved)
+                } else {
+                    None
+                };
+                let text = |s: String| match color {
+                    Some(color) => egui::RichText::new(s).color(color),
+                    None => egui::RichText::new(s),
+                };
+                ui.horizontal(|ui| {
+                    self.category_chip(ui, &change.category);
+                    ui.label(text(change.category.clone()));
+                });
+                ui.label(self.money(change.before));
+                ui.label(self.money(change.after));
+                ui.label(text(format!("{}{}", if delta > 0.005 { "+" } else { "" }, self.money(delta))));
+                ui.label(text(change.percent().map(|p| format!("{:+.1}%", p)).unwrap_or_else(|| "—".to_string())));
+                ui.end_row();
+            }
+            let (total_before, total_after): (f64, f64) = changes.iter().map(|c| (c.before, c.after)).fold((0.0, 0.0), |(a, b), (x, y)| (a + x, b + y));
+            let total = reports::CategoryChange { category: String::new(), before: total_before, after: total_after };
+            ui.strong(t!("compare-total"));
+            ui.strong(self.money(total.before));
+            ui.strong(self.money(total.after));
+            ui.strong(format!("{}{}", if total.delta() > 0.005 { "+" } else { "" }, self.money(total.delta())));
+            ui.strong(total.percent().map(|p| format!("{:+.1}%", p)).unwrap_or_else(|| "—".to_string()));
+            ui.end_row();
+        });
+    }
+
+    // Subscriptions and bills found in the whole history, whatever the range, biggest
+    // monthly cost first. Each can be scheduled ahead as a copy of its last payment.
+    pub fn show_recurring_bills(&mut self, ui: &mut egui::Ui) {
+        ui.strong(t!("recurring-bills"));
+        let today = Local::now().date_naive();
+        let past: Vec<forecast::Entry> = self.transactions.iter()
+            .filter(|t| !t.is_scheduled())
+            .map(|t| forecast::Entry { day: t.date.date_naive(), description: &t.description, amount: self.signed_amount(t) })
+            .collect();
+        let mut bills: Vec<forecast::Recurring> = forecast::find_recurring(&past, today).into_iter()
+            .filter(|r| r.amount < 0.0)
+            .collect();
+        if bills.is_empty() {
+            ui.label(t!("no-recurring-bills"));
+            return;
+        }
+        bills.sort_by(|a, b| a.per_month().total_cmp(&b.per_month()));
+
+        let mut schedule = None;
+        egui::Grid::new("recurring_bills").num_columns(6).striped(true).show(ui, |ui| {
+            ui.strong(t!("column-description"));
+            ui.strong(t!("recurring-every"));
+            ui.strong(t!("amount"));
+            ui.strong(t!("recurring-per-month"));
+            ui.strong(t!("recurring-next"));
+            ui.end_row();
+            for bill in &bills {
+                let next = bill.next_after(today);
+                ui.label(&bill.description);
+                ui.label(if bill.monthly() {
+                    t!("recurring-monthly")
+                } else if bill.every == 7 {
+                    t!("recurring-weekly")
+                } else {
+                    t!("recurring-days", count = bill.every)
+                });
+                ui.label(self.money(-bill.amount));
+                ui.label(self.money(-bill.per_month()));
+                ui.label(next.map(i18n::date).unwrap_or_default());
+                // Already entered ahead, around the expected day
+                let scheduled = next.is_some_and(|next| self.transactions.iter().any(|t| {
+                    t.is_scheduled() && bill.matches(&t.description) && (t.date.date_naive() - next).num_days().abs() <= bill.every / 2
+                }));
+                if scheduled {
+                    ui.weak(t!("recurring-scheduled"));
+                } else if let Some(next) = next {
+                    if ui.small_button(t!("recurring-schedule")).on_hover_text(t!("recurring-schedule-hint")).clicked() {
+                        schedule = Some((bill.description.clone(), next));
+                    }
+                }
+                ui.end_row();
+            }
+            ui.strong(t!("recurring-total"));
+            ui.label("");
+            ui.label("");
+            ui.strong(self.money(-bills.iter().map(|b| b.per_month()).sum::<f64>()));
+            ui.end_row();
+        });
+
+        if let Some((description, day)) = schedule {
+            self.schedule_bill(&description, day);
+        }
+    }
+
+    // One bar per month made of each category's spending in its color, so the total and
+    // what it is made of can both be followed over time
+    pub fn show_category_bars(&self, ui: &mut egui::Ui, height: f32) -> Option<DrillDown> {
+        let (months, categories) = &self.aggregates.category_months;
+        if months.is_empty() {
+            ui.label(t!("no-expenses-to-show"));
+            return None;
+        }
+
+        let label = month_label;
+        let mut charts: Vec<BarChart> = Vec::new();
+        // Biggest at the bottom
+        for (category, amounts) in categories {
+            let color = self.category_color(category);
+            let bars = months.iter().zip(amounts)
+                .map(|(&month, &amount)| Bar::new(month as f64, amount)
+                    .width(0.7)
+                    .name(label(month as f64))
+                    .fill(color)
+                    .stroke(Stroke::new(1.0, ui.visuals().panel_fill)))
+                .collect();
+            let fmt = self.money_format.clone();
+            let name = category.clone();
+            let mut chart = BarChart::new(bars)
+                .name(category)
+                .color(color)
+                .element_formatter(Box::new(move |bar: &Bar, _: &BarChart| {
+                    format!("{}\n{}: {}", bar.name, name, fmt.money(bar.value))
+                }));
+            if let Some(below) = charts.last() {
+                chart = chart.stack_on(&[below]);
+            }
+            charts.push(chart);
+        }
+        let fmt = self.money_format.clone();
+
+        let plot = Plot::new("category_bars_plot")
+            .height(height)
+            .legend(Legend::default())
+            .x_grid_spacer(egui_plot::uniform_grid_spacer(|_| [1.0, 3.0, 12.0]))
+            .x_axis_formatter(move |mark, _range| {
+                if (mark.value - mark.value.round()).abs() < 0.01 { label(mark.value) } else { String::new() }
+            })
+            .y_axis_formatter(move |mark, _range| fmt.money(mark.value))
+            .label_formatter(|_, _| String::new())
+            .show(ui, |plot_ui| {
+                for chart in charts {
+                    plot_ui.bar_chart(chart);
+                }
+            });
+
+        // The month, and the category whose part of the stack was clicked
+        let pos = plot.response.interact_pointer_pos().filter(|_| plot.response.clicked())?;
+        let value = plot.transform.value_from_position(pos);
+        let month = value.x.round() as i32;
+        let column = months.iter().position(|m| *m == month)?;
+        let mut top = 0.0;
+        let category = categories.iter().find_map(|(category, amounts)| {
+            top += amounts[column];
+            (value.y >= 0.0 && value.y <= top).then(|| category.clone())
+        });
+        Some(self.month_drill(month, None, category))
+    }
+
+    // Income by category on the left, flowing through the total to expense categories on
+    // the right. What is left over flows to savings; spending more than came in is drawn
+    // as coming from savings.
+    pub fn show_money_flow(&self, ui: &mut egui::Ui, height: f32) -> Option<DrillDown> {
+        let mut income: std::collections::HashMap<String, f64> = std::collections::HashMap::new();
+        let mut expenses: std::collections::HashMap<String, f64> = std::collections::HashMap::new();
+        for t in self.analytics_transactions() {
+            if t.trans_type == TransactionType::Income && t.refund_of.is_none() {
+                *income.entry(t.category.clone()).or_default() += self.home_amount(t);
+            }
+            for (category, _, amount) in self.expense_contributions(t) {
+                // Other parts of a split don't belong in "Food only"
+                if self.filter.categories.is_empty() || self.filter.categories.contains(&category) {
+                    *expenses.entry(category).or_default() += amount;
+                }
+            }
+        }
+        let nodes = |totals: std::collections::HashMap<String, f64>| {
+            let mut nodes: Vec<sankey::Node> = totals.into_iter()
+                .filter(|(_, value)| *value > 0.005)
+                .map(|(label, value)| sankey::Node { color: self.category_color(&label), label, value })
+                .collect();
+            nodes.sort_by(|a, b| b.value.total_cmp(&a.value));
+            nodes
+        };
+        let mut left = nodes(income);
+        let mut right = nodes(expenses);
+        // Categories end where the savings nodes start
+        let (categories_left, categories_right) = (left.len(), right.len());
+        let difference = left.iter().map(|n| n.value).sum::<f64>() - right.iter().map(|n| n.value).sum::<f64>();
+        if difference > 0.005 {
+            right.push(sankey::Node { label: t!("flow-saved"), value: difference, color: theme::income(ui.visuals()) });
+        } else if difference < -0.005 {
+            left.push(sankey::Node { label: t!("flow-from-savings"), value: -difference, color: Color32::GRAY });
+        }
+        if left.is_empty() && right.is_empty() {
+            ui.label(t!("no-transactions-yet-add-some-data"));
+            return None;
+        }
+
+        let (side, index) = sankey::show(ui, &left, &t!("flow-total"), &right, height, &|value| self.money(value))?;
+        let (node, trans_type) = match side {
+            sankey::Side::Left if index < categories_left => (&left[index], TransactionType::Income),
+            sankey::Side::Right if index < categories_right => (&right[index], TransactionType::Expense),
+            _ => return None,
+        };
+        let bounds = self.analytics_bounds();
+        Some(DrillDown {
+            category: Some(node.label.clone()),
+            trans_type: Some(trans_type),
+            from: bounds.map(|(from, _)| from),
+            to: bounds.map(|(_, to)| to),
+            amounts: None,
+        })
+    }
+
+    // Spending over the last ROLLING_DAYS days, for every day of the range. Unlike the
+    // balance it goes back down once a spending spree is over.
+    pub fn show_rolling_spending(&self, ui: &mut egui::Ui, height: f32) -> Option<DrillDown> {
+        let today = Local::now().date_naive();
+        let first = self.transactions.iter().map(|t| t.date.date_naive()).min().unwrap_or(today);
+        let (from, to) = self.analytics_bounds().unwrap_or((first, today));
+        let to = to.min(today);
+        // The range only decides which days are drawn, the first ones still add up the
+        // days before it
+        let mut expenses = Vec::new();
+        for t in self.transactions.iter().filter(|t| !t.is_scheduled() && self.filter.matches(t, self.home_amount(t))) {
+            for (category, _, amount) in self.expense_contributions(t) {
+                if self.filter.categories.is_empty() || self.filter.categories.contains(&category) {
+                    expenses.push((t.date.date_naive(), amount));
+                }
+            }
+        }
+        if from > to || expenses.is_empty() {
+            ui.label(t!("no-expenses-to-show"));
+            return None;
+        }
+
+        let rolling = reports::rolling_spend(&expenses, from, to, ROLLING_DAYS);
+        let points: Vec<[f64; 2]> = rolling.iter().map(|(day, amount)| [day.num_days_from_ce() as f64, *amount]).collect();
+        let average = rolling.iter().map(|(_, amount)| amount).sum::<f64>() / rolling.len() as f64;
+        ui.label(t!("rolling-spending-now", days = ROLLING_DAYS, amount = self.money(rolling.last().map_or(0.0, |(_, amount)| *amount)), average = self.money(average)));
+
+        let day_of = |x: f64| NaiveDate::from_num_days_from_ce_opt(x.round() as i32);
+        let fmt = self.money_format.clone();
+        let label_fmt = self.money_format.clone();
+        let line_name = t!("rolling-spending-line", days = ROLLING_DAYS);
+        let average_name = t!("rolling-spending-average");
+        let ends = [points[0][0], points[points.len() - 1][0]];
+        let color = theme::expense(ui.visuals());
+        let plot = Plot::new("rolling_spending_plot")
+            .height(height)
+            .legend(Legend::default())
+            .x_axis_formatter(move |mark, _range| day_of(mark.value).map(i18n::date).unwrap_or_default())
+            .y_axis_formatter(move |mark, _range| fmt.money(mark.value))
+            .label_formatter(move |_, value| {
+                let day = day_of(value.x).map(i18n::date).unwrap_or_default();
+                format!("{}\n{}", day, label_fmt.money(value.y))
+            })
+            .show(ui, |plot_ui| {
+                plot_ui.line(Line::new(PlotPoints::from(points))
+                    .name(&line_name)
+                    .width(2.0)
+                    .color(color));
+                plot_ui.line(Line::new(PlotPoints::from(ends.map(|x| [x, average]).to_vec()))
+                    .name(&average_name)
+                    .style(egui_plot::LineStyle::dashed_loose())
+                    .color(Color32::GRAY));
+            });
+
+        // The days that add up to the clicked one
+        let pos = plot.response.interact_pointer_pos().filter(|_| plot.response.clicked())?;
+        let day = day_of(plot.transform.value_from_position(pos).x)?.clamp(from, to);
+        Some(DrillDown {
+            trans_type: Some(TransactionType::Expense),
+            from: Some(day - chrono::Duration::days(ROLLING_DAYS - 1)),
+            to: Some(day),
+            ..Default::default()
+        })
+    }
+
+    // Expense categories sized by amount, with their subcategories inside
+    pub fn show_expense_treemap(&self, ui: &mut egui::Ui, height: f32) -> Option<DrillDown> {
+        // Category -> (total, subcategory totals)
+        let mut totals: std::collections::HashMap<String, (f64, std::collections::HashMap<String, f64>)> = std::collections::HashMap::new();
+        for t in self.analytics_transactions() {
+            for (category, subcategory, amount) in self.expense_contributions(t) {
+                // Other parts of a split don't belong in "Food only"
+                if !self.filter.categories.is_empty() && !self.filter.categories.contains(&category) {
+                    continue;
+                }
+                let entry = totals.entry(category).or_default();
+                entry.0 += amount;
+                *entry.1.entry(subcategory.unwrap_or_default()).or_default() += amount;
+            }
+        }
+
+        let mut tiles: Vec<treemap::Tile> = totals.into_iter()
+            .filter(|(_, (total, _))| *total > 0.005)
+            .map(|(category, (total, subs))| {
+                let color = self.category_color(&category);
+                let mut children: Vec<treemap::Tile> = subs.into_iter()
+                    .filter(|(_, amount)| *amount > 0.005)
+                    .map(|(sub, amount)| treemap::Tile {
+                        // The part filed under the category itself
+                        label: if sub.is_empty() { category.clone() } else { sub },
+                        value: amount,
+                        color,
+                        children: Vec::new(),
+                    })
+                    .collect();
+                // Nothing to nest when it is all filed under the category itself
+                if children.len() == 1 && children[0].label == category {
+                    children.clear();
+                }
+                children.sort_by(|a, b| b.value.total_cmp(&a.value));
+                treemap::Tile { label: category, value: total, color, children }
+            })
+            .collect();
+        if tiles.is_empty() {
+            ui.label(t!("no-expenses-to-show"));
+            return None;
+        }
+        tiles.sort_by(|a, b| b.value.total_cmp(&a.value));
+
+        let index = treemap::show(ui, &tiles, height, &|value| self.money(value))?;
+        let bounds = self.analytics_bounds();
+        Some(DrillDown {
+            category: Some(tiles[index].label.clone()),
+            trans_type: Some(TransactionType::Expense),
+            from: bounds.map(|(from, _)| from),
+            to: bounds.map(|(_, to)| to),
+            amounts: None,
+        })
+    }
+
+    // Average spending by day of the week and by hour of the day, weekends picked out
+    pub fn show_spending_patterns(&self, ui: &mut egui::Ui, height: f32) {
+        let today = Local::now().date_naive();
+        let first = self.transactions.iter().map(|t| t.date.date_naive()).min().unwrap_or(today);
+        let (from, to) = self.analytics_bounds().unwrap_or((first, today));
+        let to = to.min(today);
+        let expenses: Vec<(NaiveDateTime, f64)> = self.analytics_transactions()
+            .filter(|t| t.trans_type == TransactionType::Expense)
+            .map(|t| (t.date.naive_local(), self.home_amount(t)))
+            .collect();
+        if from > to || expenses.is_empty() {
+            ui.label(t!("no-expenses-to-show"));
+            return;
+        }
+        let pattern = reports::spend_pattern(&expenses, from, to);
+
+        let weekday_color = theme::expense(ui.visuals());
+        let weekend_color = theme::warning(ui.visuals());
+        // In the order the week starts
+        let mut day = self.settings.week_start.weekday();
+        let mut days = Vec::new();
+        for i in 0..7 {
+            let number = day.number_from_monday();
+            let name = t!(&format!("weekday-{}", number));
+            let color = if number >= 6 { weekend_color } else { weekday_color };
+            days.push((i as f64, name, pattern.by_weekday[day.num_days_from_monday() as usize], color));
+            day = day.succ();
+        }
+        let hours: Vec<Bar> = pattern.by_hour.iter().enumerate()
+            .map(|(hour, &amount)| Bar::new(hour as f64, amount).width(0.8).name(format!("{:02}:00", hour)).fill(weekday_color))
+            .collect();
+
+        let fmt = self.money_format.clone();
+        let tooltip = move |bar: &Bar, _: &BarChart| t!("pattern-tooltip", when = bar.name.clone(), amount = fmt.money(bar.value));
+        let names: Vec<String> = days.iter().map(|(_, name, _, _)| name.clone()).collect();
+        let weekday_bars = days.into_iter()
+            .map(|(x, name, amount, color)| Bar::new(x, amount).width(0.7).name(name).fill(color))
+            .collect();
+        let weekday_chart = BarChart::new(weekday_bars).element_formatter(Box::new(tooltip.clone()));
+        let hour_chart = BarChart::new(hours).element_formatter(Box::new(tooltip));
+
+        ui.columns(2, |columns| {
+            columns[0].strong(t!("average-by-weekday"));
+            let fmt = self.money_format.clone();
+            Plot::new("weekday_pattern_plot")
+                .height(height)
+                .allow_drag(false)
+                .allow_zoom(false)
+                .x_grid_spacer(egui_plot::uniform_grid_spacer(|_| [1.0, 7.0, 7.0]))
+                .x_axis_formatter(move |mark, _range| {
+                    let index = mark.value.round();
+                    if (mark.value - index).abs() < 0.01 && index >= 0.0 {
+                        names.get(index as usize).cloned().unwrap_or_default()
+                    } else {
+                        String::new()
+                    }
+                })
+                .y_axis_formatter(move |mark, _range| fmt.money(mark.value))
+                .label_formatter(|_, _| String::new())
+                .show(&mut columns[0], |plot_ui| plot_ui.bar_chart(weekday_chart));
+
+            columns[1].strong(t!("average-by-hour"));
+            let fmt = self.money_format.clone();
+            Plot::new("hour_pattern_plot")
+                .height(height)
+                .allow_drag(false)
+                .allow_zoom(false)
+                .x_grid_spacer(egui_plot::uniform_grid_spacer(|_| [1.0, 3.0, 6.0]))
+                .x_axis_formatter(|mark, _range| {
+                    let hour = mark.value.round();
+                    if (mark.value - hour).abs() < 0.01 && (0.0..24.0).contains(&hour) { format!("{:02}h", hour) } else { String::new() }
+                })
+                .y_axis_formatter(move |mark, _range| fmt.money(mark.value))
+                .label_formatter(|_, _| String::new())
+                .show(&mut columns[1], |plot_ui| plot_ui.bar_chart(hour_chart));
+        });
+    }
+
+    // How many transactions of each size there were: many small purchases, or a few big ones
+    pub fn show_amount_histogram(&self, ui: &mut egui::Ui, height: f32) -> Option<DrillDown> {
+        let amounts: Vec<f64> = self.analytics_transactions()
+            .filter(|t| t.trans_type == self.histogram_type)
+            .filter(|t| self.histogram_category.as_ref()
+                .is_none_or(|category| t.category_shares().iter().any(|(c, _, _)| c == category)))
+            .map(|t| self.home_amount(t))
+            .collect();
+        let buckets = reports::amount_histogram(&amounts);
+        if buckets.is_empty() {
+            ui.label(t!("histogram-empty"));
+            return None;
+        }
+
+        let fmt = self.money_format.clone();
+        let labels: Vec<String> = buckets.iter().map(|b| format!("{} – {}", fmt.money(b.from), fmt.money(b.to))).collect();
+        let color = match self.histogram_type {
+            TransactionType::Income => theme::income(ui.visuals()),
+            TransactionType::Expense => theme::expense(ui.visuals()),
+        };
+        let bars = buckets.iter().zip(&labels).enumerate()
+            .map(|(i, (bucket, label))| Bar::new(i as f64, bucket.count as f64)
+                .width(0.9)
+                .name(t!("histogram-bar", range = label.clone(), count = bucket.count, total = fmt.money(bucket.total)))
+                .fill(color))
+            .collect();
+        let chart = BarChart::new(bars)
+            .color(color)
+            .element_formatter(Box::new(|bar: &Bar, _: &BarChart| bar.name.clone()));
+
+        let plot = Plot::new("amount_histogram_plot")
+            .height(height)
+            .x_grid_spacer(egui_plot::uniform_grid_spacer(|_| [1.0, 2.0, 5.0]))
+            .x_axis_formatter(move |mark, _range| {
+                let index = mark.value.round();
+                if (mark.value - index).abs() < 0.01 && index >= 0.0 {
+                    labels.get(index as usize).cloned().unwrap_or_default()
+                } else {
+                    String::new()
+                }
+            })
+            .y_axis_formatter(|mark, _range| {
+                if mark.value.fract() == 0.0 { format!("{}", mark.value) } else { String::new() }
+            })
+            .label_formatter(|_, _| String::new())
+            .show(ui, |plot_ui| plot_ui.bar_chart(chart));
+
+        // The transactions in the clicked bucket
+        let pos = plot.response.interact_pointer_pos().filter(|_| plot.response.clicked())?;
+        let index = plot.transform.value_from_position(pos).x.round();
+        let bucket = (index >= 0.0).then(|| buckets.get(index as usize)).flatten()?;
+        let bounds = self.analytics_bounds();
+        Some(DrillDown {
+            category: self.histogram_category.clone(),
+            trans_type: Some(self.histogram_type),
+            from: bounds.map(|(from, _)| from),
+            to: bounds.map(|(_, to)| to),
+            amounts: Some((bucket.from, bucket.to)),
+        })
+    }
+
+    // Income and expense bars side by side for each month, with the net as a line
+    pub fn show_cash_flow_chart(&self, ui: &mut egui::Ui, height: f32) -> Option<DrillDown> {
+        let months = &self.aggregates.cash_flow;
+        if months.is_empty() {
+            ui.vertical_centered(|ui| {
+                ui.add_space(20.0);
+                ui.label(t!("no-transactions-yet-add-some-data"));
+                ui.add_space(20.0);
+            });
+            return None;
+        }
+
+        let label = month_label;
+        let mut income_bars = Vec::new();
+        let mut expense_bars = Vec::new();
+        let mut net = Vec::new();
+        for &(year, month, income, expenses) in months {
+            let x = (year * 12 + month as i32 - 1) as f64;
+            income_bars.push(Bar::new(x - 0.2, income).width(0.4).name(label(x)));
+            expense_bars.push(Bar::new(x + 0.2, expenses).width(0.4).name(label(x)));
+            net.push([x, income - expenses]);
+        }
+
+        let (income_name, expense_name, net_name) = (t!("income"), t!("expense"), t!("chart-net"));
+        let tooltip = |kind: String| {
+            let fmt = self.money_format.clone();
+            Box::new(move |bar: &Bar, _: &BarChart| format!("{}\n{}: {}", bar.name, kind, fmt.money(bar.value)))
+        };
+        let income_chart = BarChart::new(income_bars)
+            .name(&income_name)
+            .color(theme::income(ui.visuals()))
+            .element_formatter(tooltip(income_name.clone()));
+        let expense_chart = BarChart::new(expense_bars)
+            .name(&expense_name)
+            .color(theme::expense(ui.visuals()))
+            .element_formatter(tooltip(expense_name.clone()));
+        let net_color = theme::balance_line(ui.visuals());
+        let fmt = self.money_format.clone();
+
+        let plot = Plot::new("cash_flow_plot")
+            .height(height)
+            .legend(Legend::default())
+            .x_grid_spacer(egui_plot::uniform_grid_spacer(|_| [1.0, 3.0, 12.0]))
+            .x_axis_formatter(move |mark, _range| {
+                if (mark.value - mark.value.round()).abs() < 0.01 { label(mark.value) } else { String::new() }
+            })
+            .y_axis_formatter(move |mark, _range| fmt.money(mark.value))
+            .label_formatter(|name, value| {
+                if name == net_name {
+                    format!("{}\n{}: {}", label(value.x), net_name, self.money(value.y))
+                } else {
+                    String::new()
+                }
+            })
+            .show(ui, |plot_ui| {
+                plot_ui.bar_chart(income_chart);
+                plot_ui.bar_chart(expense_chart);
+                if self.show_net_line {
+                    plot_ui.line(Line::new(PlotPoints::from(net.clone())).name(&net_name).width(2.0).color(net_color));
+                    plot_ui.points(Points::new(PlotPoints::from(net)).radius(3.0).color(net_color));
+                }
+            });
+
+        let pos = plot.response.interact_pointer_pos().filter(|_| plot.response.clicked())?;
+        let value = plot.transform.value_from_position(pos);
+        let month = value.x.round() as i32;
+        if !months.iter().any(|&(year, m, _, _)| year * 12 + m as i32 - 1 == month) {
+            return None;
+        }
+        // Income bars are on the left, expenses on the right; between them is both
+        let offset = value.x - month as f64;
+        let trans_type = if offset < -0.05 {
+            Some(TransactionType::Income)
+        } else if offset > 0.05 {
+            Some(TransactionType::Expense)
+        } else {
+            None
+        };
+        Some(self.month_drill(month, trans_type, None))
+    }
+
+    // Budgeted against spent for each budgeted category in one budget month, as pairs of
+    // horizontal bars. Whatever went over the limit is drawn in red on the end.
+    pub fn show_budget_chart(&mut self, ui: &mut egui::Ui, height: f32) -> Option<DrillDown> {
+        let (from, to) = self.settings.month_containing(self.budget_chart_day);
+        ui.horizontal(|ui| {
+            if ui.button("<").clicked() {
+                self.budget_chart_day = self.settings.previous_month(from).0;
+            }
+            ui.add_sized([170.0, 20.0], egui::Label::new(egui::RichText::new(self.budget_month_label(from, to)).strong()));
+            if ui.button(">").clicked() {
+                self.budget_chart_day = to.succ_opt().unwrap_or(to);
+            }
+            if ui.button(t!("period-today")).clicked() {
+                self.budget_chart_day = Local::now().date_naive();
+            }
+        });
+
+        let spending = self.category_spending(from, to);
+        let names: Vec<String> = self.budgets.iter().map(|b| b.category.clone()).collect();
+        let count = names.len();
+        // First budget at the top
+        let row = |index: usize| (count - 1 - index) as f64;
+
+        let (budgeted_name, spent_name, over_name) = (t!("chart-budgeted"), t!("chart-spent"), t!("chart-over-budget"));
+        let mut budgeted = Vec::new();
+        let mut spent_bars = Vec::new();
+        let mut over_bars = Vec::new();
+        for (index, budget) in self.budgets.iter().enumerate() {
+            let spent = spending.get(&budget.category).copied().unwrap_or(0.0);
+            let limit = budget.monthly_limit;
+            let status = BudgetStatus::from_spend(spent, limit);
+            let y = row(index);
+            budgeted.push(Bar::new(y + 0.2, limit).width(0.35).name(&budget.category));
+            // Green while on track, amber once close; a full bar is amber with red past it
+            let within = if status == BudgetStatus::OnTrack { status.color() } else { BudgetStatus::Approaching.color() };
+            spent_bars.push(Bar::new(y - 0.2, spent.min(limit)).width(0.35).name(&budget.category)
+                .fill(within.linear_multiply(0.6))
+                .stroke(Stroke::new(1.0, within)));
+            if spent > limit {
+                over_bars.push(Bar::new(y - 0.2, spent - limit).base_offset(limit).width(0.35).name(&budget.category));
+            }
+        }
+
+        // Tooltips show what was spent in full, not only the part within the limit
+        let totals: std::collections::HashMap<String, (f64, f64)> = self.budgets.iter()
+            .map(|b| (b.category.clone(), (spending.get(&b.category).copied().unwrap_or(0.0), b.monthly_limit)))
+            .collect();
+        let tooltip = |kind: String, amount: fn(f64, f64) -> f64| {
+            let fmt = self.money_format.clone();
+            let totals = totals.clone();
+            Box::new(move |bar: &Bar, _: &BarChart| {
+                let (spent, limit) = totals.get(&bar.name).copied().unwrap_or_default();
+                format!("{}\n{}: {}", bar.name, kind, fmt.money(amount(spent, limit)))
+            })
+        };
+        let budgeted_chart = BarChart::new(budgeted)
+            .name(&budgeted_name)
+            .color(Color32::GRAY)
+            .horizontal()
+            .element_formatter(tooltip(budgeted_name.clone(), |_, limit| limit));
+        let spent_chart = BarChart::new(spent_bars)
+            .name(&spent_name)
+            .color(BudgetStatus::OnTrack.color())
+            .horizontal()
+            .element_formatter(tooltip(spent_name.clone(), |spent, _| spent));
+        let over_chart = BarChart::new(over_bars)
+            .name(&over_name)
+            .color(BudgetStatus::Exceeded.color())
+            .horizontal()
+            .element_formatter(tooltip(over_name.clone(), |spent, limit| spent - limit));
+        let fmt = self.money_format.clone();
+
+        let plot = Plot::new("budget_plot")
+            .height(height)
+            .legend(Legend::default())
+            .allow_drag(false)
+            .allow_zoom(false)
+            .allow_scroll(false)
+            .include_x(0.0)
+            .y_grid_spacer(egui_plot::uniform_grid_spacer(|_| [1.0, 1.0, 1.0]))
+            .y_axis_formatter(move |mark, _range| {
+                let index = count as f64 - 1.0 - mark.value;
+                if (index - index.round()).abs() < 0.01 && index >= 0.0 {
+                    names.get(index.round() as usize).cloned().unwrap_or_default()
+                } else {
+                    String::new()
+                }
+            })
+            .x_axis_formatter(move |mark, _range| fmt.money(mark.value))
+            .label_formatter(|_, _| String::new())
+            .show(ui, |plot_ui| {
+                plot_ui.bar_chart(budgeted_chart);
+                plot_ui.bar_chart(spent_chart);
+                plot_ui.bar_chart(over_chart);
+            });
+
+        // The budget month of the category whose bars were clicked
+        let pos = plot.response.interact_pointer_pos().filter(|_| plot.response.clicked())?;
+        let value = plot.transform.value_from_position(pos);
+        let index = count as f64 - 1.0 - value.y.round();
+        let budget = (index >= 0.0).then(|| self.budgets.get(index as usize)).flatten()?;
+        Some(DrillDown {
+            category: Some(budget.category.clone()),
+            from: Some(from),
+            to: Some(to),
+            ..Default::default()
+        })
+    }
+
+    // Returns the category of a clicked slice
+    // Donut of the expense categories with the total in the hole. Slices under
+    // PIE_OTHER_SHARE are lumped into one "Other (n)" slice so they don't end up as slivers.
+    pub fn draw_pie_chart(&self, ui: &mut egui::Ui, data: &std::collections::HashMap<String, f64>, total: f64) -> Option<String> {
+        let size = 200.0;
+        // Room for the hovered slice to move out
+        let pop_out = 8.0;
+        let (rect, response) = ui.allocate_exact_size(Vec2::splat(size + 2.0 * pop_out), Sense::click());
+        
+        let center = rect.center();
+        let radius = size / 2.0;
+        let hole = radius * 0.55;
+        let start_angle = -TAU / 4.0;
+        
+        let mut sorted_data: Vec<_> = data.iter().collect();
+        sorted_data.sort_by(|a, b| b.1.partial_cmp(a.1).unwrap_or(std::cmp::Ordering::Equal));
+
+        // (label, amount, color, category to drill into). A single small slice stays as it
+        // is, lumping it would only rename it.
+        let small = sorted_data.iter().filter(|(_, amount)| **amount / total < PIE_OTHER_SHARE).count();
+        let mut slices: Vec<(String, f64, Color32, Option<&String>)> = Vec::new();
+        let mut others: Vec<&String> = Vec::new();
+        let mut other_amount = 0.0;
+        for (cat, amount) in &sorted_data {
+            if small > 1 && **amount / total < PIE_OTHER_SHARE {
+                others.push(cat);
+                other_amount += **amount;
+            } else {
+                slices.push(((*cat).clone(), **amount, self.category_color(cat), Some(cat)));
+            }
+        }
+        if !others.is_empty() {
+            slices.push((t!("pie-other", count = others.len()), other_amount, Color32::GRAY, None));
+        }
+
+        // The slice under the pointer, by how far round from the top it is
+        let hovered = response.hover_pos().and_then(|pos| {
+            let offset = pos - center;
+            if offset.length() > radius + pop_out || offset.length() < hole {
+                return None;
+            }
+            let angle = (offset.y as f64).atan2(offset.x as f64);
+            let around = (angle - start_angle).rem_euclid(TAU);
+            let mut end = 0.0;
+            slices.iter().position(|(_, amount, _, _)| {
+                end += *amount / total * TAU;
+                around < end
+            })
+        });
+
+        let mut current_angle = start_angle;
+        // Drawn last so its neighbours don't cover its outline
+        let mut hovered_outline = None;
+        let mut percentages = Vec::new();
+
+        for (index, (_, amount, color, _)) in slices.iter().enumerate() {
+            let slice_angle = (*amount / total) * TAU;
+            let is_hovered = hovered == Some(index);
+            let middle = current_angle + slice_angle / 2.0;
+            let direction = Vec2::new(middle.cos() as f32, middle.sin() as f32);
+            let slice_center = if is_hovered { center + direction * pop_out } else { center };
+
+            let points_on_arc = 30;
+            let arc = |r: f32| -> Vec<Pos2> {
+                (0..=points_on_arc)
+                    .map(|i| {
+                        let angle = current_angle + i as f64 / points_on_arc as f64 * slice_angle;
+                        slice_center + Vec2::new(angle.cos() as f32, angle.sin() as f32) * r
+                    })
+                    .collect()
+            };
+            let (outer, inner) = (arc(radius), arc(hole));
+
+            let mut mesh = egui::epaint::Mesh::default();
+            for (i, (o, n)) in outer.iter().zip(&inner).enumerate() {
+                mesh.colored_vertex(*o, *color);
+                mesh.colored_vertex(*n, *color);
+                if i > 0 {
+                    let v = (i * 2) as u32;
+                    mesh.add_triangle(v - 2, v - 1, v);
+                    mesh.add_triangle(v - 1, v, v + 1);
+                }
+            }
+            ui.painter().add(mesh);
+
+            // Slices are separated by a line in the background color, whichever theme is on
+            let separator = Stroke::new(1.0, ui.visuals().panel_fill);
+            ui.painter().line_segment([inner[0], outer[0]], separator);
+            if is_hovered {
+                let mut outline = outer;
+                outline.extend(inner.into_iter().rev());
+                hovered_outline = Some(Shape::closed_line(outline, Stroke::new(2.0, ui.visuals().strong_text_color())));
+            }
+            if *amount / total >= PIE_LABEL_SHARE {
+                percentages.push((slice_center + direction * (hole + radius) / 2.0, *amount / total * 100.0, *color));
+            }
+
+            current_angle += slice_angle;
+        }
+        if let Some(shape) = hovered_outline {
+            ui.painter().add(shape);
+        }
+        for (pos, percent, color) in percentages {
+            ui.painter().text(
+                pos,
+                egui::Align2::CENTER_CENTER,
+                format!("{:.0}%", percent),
+                egui::FontId::proportional(12.0),
+                theme::text_on(color),
+            );
+        }
+        ui.painter().text(center - Vec2::new(0.0, 8.0), egui::Align2::CENTER_CENTER, t!("pie-total"), egui::FontId::proportional(12.0), ui.visuals().weak_text_color());
+        ui.painter().text(center + Vec2::new(0.0, 8.0), egui::Align2::CENTER_CENTER, self.money(total), egui::FontId::proportional(15.0), ui.visuals().strong_text_color());
+
+        let (label, amount, color, category) = hovered.map(|index| &slices[index])?;
+        let clicked = response.clicked();
+        response.on_hover_cursor(egui::CursorIcon::PointingHand).on_hover_ui_at_pointer(|ui| {
+            ui.horizontal(|ui| {
+                match category {
+                    Some(cat) => self.category_chip(ui, cat),
+                    None => {
+                        let (rect, _) = ui.allocate_exact_size(Vec2::splat(16.0), Sense::hover());
+                        ui.painter().rect_filled(rect, 3.0, *color);
+                    }
+                }
+                ui.strong(label.as_str());
+            });
+            ui.label(format!("{} ({:.1}%)", self.money(*amount), amount / total * 100.0));
+            if category.is_some() {
+                ui.weak(t!("click-to-see-transactions"));
+            } else {
+                for cat in &others {
+                    ui.weak(format!("{}: {}", cat, self.money(data[*cat])));
+                }
+            }
+        });
+        category.filter(|_| clicked).map(|cat| (*cat).clone())
+    }
+}
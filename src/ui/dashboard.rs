@@ -0,0 +1,247 @@
+use chrono::{Datelike, Local, NaiveDate};
+use eframe::egui;
+use egui_plot::{Line, Plot, PlotPoints};
+
+use crate::{FinanceApp, dashboard, forecast, i18n, theme};
+use crate::budgets::BudgetStatus;
+use crate::i18n::t;
+use crate::model::{Transaction, TransactionType};
+
+impl FinanceApp {
+    // The landing tab: a few boxes with the state of things, see dashboard.rs
+    pub fn show_dashboard_ui(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            ui.heading(t!("tab-dashboard"));
+            ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                ui.toggle_value(&mut self.customizing_dashboard, t!("customize-dashboard"));
+            });
+        });
+        if self.customizing_dashboard {
+            self.show_dashboard_layout(ui);
+        }
+        ui.separator();
+
+        let widgets = self.settings.dashboard.shown();
+        if widgets.is_empty() {
+            ui.label(t!("dashboard-empty"));
+            return;
+        }
+        egui::ScrollArea::vertical().show(ui, |ui| {
+            for widget in widgets {
+                egui::Frame::group(ui.style()).show(ui, |ui| {
+                    ui.set_width(ui.available_width());
+                    ui.strong(widget.label());
+                    ui.add_space(4.0);
+                    match widget {
+                        dashboard::Widget::Balance => self.show_balance_widget(ui),
+                        dashboard::Widget::ThisMonth => self.show_this_month_widget(ui),
+                        dashboard::Widget::Budgets => self.show_budgets_widget(ui),
+                        dashboard::Widget::UpcomingBills => self.show_upcoming_widget(ui),
+                        dashboard::Widget::BalanceChart => self.show_balance_chart_widget(ui),
+                        dashboard::Widget::Recent => self.show_recent_widget(ui),
+                    }
+                });
+                ui.add_space(6.0);
+            }
+        });
+    }
+
+    // Show or hide each widget and move it up or down
+    pub fn show_dashboard_layout(&mut self, ui: &mut egui::Ui) {
+        self.settings.dashboard.complete();
+        let mut changed = false;
+        let mut move_up = None;
+        let count = self.settings.dashboard.widgets.len();
+        egui::Grid::new("dashboard_layout").num_columns(3).show(ui, |ui| {
+            for (index, (widget, shown)) in self.settings.dashboard.widgets.iter_mut().enumerate() {
+                changed |= ui.checkbox(shown, widget.label()).changed();
+                if ui.add_enabled(index > 0, egui::Button::new("⬆").small()).clicked() {
+                    move_up = Some(index);
+                }
+                if ui.add_enabled(index + 1 < count, egui::Button::new("⬇").small()).clicked() {
+                    move_up = Some(index + 1);
+                }
+                ui.end_row();
+            }
+        });
+        if let Some(index) = move_up {
+            self.settings.dashboard.move_up(index);
+            changed = true;
+        }
+        if ui.button(t!("reset-dashboard")).clicked() {
+            self.settings.dashboard = dashboard::Layout::default();
+            changed = true;
+        }
+        if changed {
+            self.save_settings();
+        }
+    }
+
+    pub fn show_balance_widget(&self, ui: &mut egui::Ui) {
+        let balance = self.balance(false);
+        let color = if balance < 0.0 { theme::expense(ui.visuals()) } else { ui.visuals().strong_text_color() };
+        ui.label(egui::RichText::new(self.money(balance)).size(28.0).color(color));
+        ui.label(t!("cleared-balance", amount = self.money(self.balance(true))));
+    }
+
+    // Budget month, so it starts on payday if that's set
+    pub fn show_this_month_widget(&self, ui: &mut egui::Ui) {
+        let today = Local::now().date_naive();
+        let (from, to) = self.settings.month_containing(today);
+        let (mut income, mut expenses) = (0.0, 0.0);
+        for t in self.transactions.iter().filter(|t| !t.is_scheduled()) {
+            let day = t.date.date_naive();
+            if day < from || day > to {
+                continue;
+            }
+            match t.trans_type {
+                TransactionType::Income => income += self.home_amount(t),
+                TransactionType::Expense => expenses += self.home_amount(t),
+            }
+        }
+        let net = income - expenses;
+        ui.weak(self.budget_month_label(from, to));
+        ui.horizontal(|ui| {
+            ui.colored_label(theme::income(ui.visuals()), t!("period-income", amount = self.money(income)));
+            ui.separator();
+            ui.colored_label(theme::expense(ui.visuals()), t!("period-expenses", amount = self.money(expenses)));
+            ui.separator();
+            let color = if net < 0.0 { theme::expense(ui.visuals()) } else { theme::income(ui.visuals()) };
+            ui.label(egui::RichText::new(t!("period-net", amount = self.money(net))).color(color).strong());
+        });
+    }
+
+    pub fn show_budgets_widget(&self, ui: &mut egui::Ui) {
+        if self.budgets.is_empty() {
+            ui.label(t!("no-budgets-yet-set-a-monthly"));
+            return;
+        }
+        let (from, to) = self.settings.month_containing(Local::now().date_naive());
+        let spending = self.category_spending(from, to);
+        for budget in &self.budgets {
+            let spent = spending.get(&budget.category).copied().unwrap_or(0.0);
+            let status = BudgetStatus::from_spend(spent, budget.monthly_limit);
+            ui.horizontal(|ui| {
+                self.category_chip(ui, &budget.category);
+                ui.add_sized([120.0, 16.0], egui::Label::new(&budget.category));
+                ui.add(egui::ProgressBar::new((spent / budget.monthly_limit).clamp(0.0, 1.0) as f32)
+                    .desired_width(250.0)
+                    .fill(status.color())
+                    .text(format!("{} / {}", self.money(spent), self.money(budget.monthly_limit))));
+            });
+        }
+    }
+
+    // Scheduled expenses up to `until` and the recurring bills expected by then, as
+    // (day, description, amount, scheduled), soonest first
+    pub fn upcoming_bills(&self, until: NaiveDate) -> Vec<(NaiveDate, String, f64, bool)> {
+        let today = Local::now().date_naive();
+        let mut upcoming: Vec<(NaiveDate, String, f64, bool)> = self.transactions.iter()
+            .filter(|t| t.is_scheduled() && t.trans_type == TransactionType::Expense && t.date.date_naive() <= until)
+            .map(|t| (t.date.date_naive(), t.description.clone(), self.home_amount(t), true))
+            .collect();
+        let past: Vec<forecast::Entry> = self.transactions.iter()
+            .filter(|t| !t.is_scheduled())
+            .map(|t| forecast::Entry { day: t.date.date_naive(), description: &t.description, amount: self.signed_amount(t) })
+            .collect();
+        for bill in forecast::find_recurring(&past, today).into_iter().filter(|r| r.amount < 0.0) {
+            let Some(next) = bill.next_after(today).filter(|next| *next <= until) else { continue };
+            // Already in the list as a scheduled transaction
+            let entered = upcoming.iter()
+                .any(|(day, description, _, _)| bill.matches(description) && (*day - next).num_days().abs() <= bill.every / 2);
+            if !entered {
+                upcoming.push((next, bill.description.clone(), -bill.amount, false));
+            }
+        }
+        upcoming.sort_by_key(|(day, _, _, _)| *day);
+        upcoming
+    }
+
+    // Scheduled expenses and the recurring bills expected in the next month
+    pub fn show_upcoming_widget(&self, ui: &mut egui::Ui) {
+        let upcoming = self.upcoming_bills(Local::now().date_naive() + chrono::Duration::days(30));
+        if upcoming.is_empty() {
+            ui.label(t!("nothing-upcoming"));
+            return;
+        }
+
+        egui::Grid::new("upcoming_widget").num_columns(3).striped(true).show(ui, |ui| {
+            for (day, description, amount, scheduled) in upcoming.iter().take(8) {
+                ui.label(i18n::date(*day));
+                if *scheduled {
+                    ui.label(description);
+                } else {
+                    ui.label(description).on_hover_text(t!("expected-from-history"));
+                }
+                ui.label(self.money(*amount));
+                ui.end_row();
+            }
+        });
+    }
+
+    // The balance over the last three months, without the controls of the big one
+    pub fn show_balance_chart_widget(&self, ui: &mut egui::Ui) {
+        let today = Local::now().date_naive();
+        let since = today - chrono::Duration::days(90);
+        let mut sorted: Vec<&Transaction> = self.transactions.iter().filter(|t| !t.is_scheduled()).collect();
+        sorted.sort_by_key(|t| t.date);
+        let mut running = self.opening_balance;
+        let mut points: Vec<[f64; 2]> = Vec::new();
+        for t in sorted {
+            running += self.signed_amount(t);
+            let day = t.date.date_naive();
+            if day >= since {
+                // Start the line where the balance stood on the first day shown
+                if points.is_empty() {
+                    points.push([since.num_days_from_ce() as f64, running - self.signed_amount(t)]);
+                }
+                points.push([day.num_days_from_ce() as f64, running]);
+            }
+        }
+        if points.is_empty() {
+            points.push([since.num_days_from_ce() as f64, running]);
+        }
+        points.push([today.num_days_from_ce() as f64, running]);
+
+        let color = theme::balance_line(ui.visuals());
+        let fmt = self.money_format.clone();
+        Plot::new("dashboard_balance_plot")
+            .height(150.0)
+            .allow_drag(false)
+            .allow_zoom(false)
+            .allow_scroll(false)
+            .show_x(false)
+            .x_axis_formatter(|mark, _range| {
+                NaiveDate::from_num_days_from_ce_opt(mark.value.round() as i32).map(i18n::date).unwrap_or_default()
+            })
+            .y_axis_formatter(move |mark, _range| fmt.money(mark.value))
+            .show(ui, |plot_ui| {
+                plot_ui.line(Line::new(PlotPoints::from(points)).width(2.0).color(color));
+            });
+    }
+
+    pub fn show_recent_widget(&self, ui: &mut egui::Ui) {
+        let mut recent: Vec<&Transaction> = self.transactions.iter().filter(|t| !t.is_scheduled()).collect();
+        if recent.is_empty() {
+            ui.label(t!("no-transactions-yet-add-some-data"));
+            return;
+        }
+        recent.sort_by_key(|t| std::cmp::Reverse(t.date));
+        egui::Grid::new("recent_widget").num_columns(4).striped(true).show(ui, |ui| {
+            for t in recent.iter().take(8) {
+                ui.label(i18n::date(t.date.date_naive()));
+                ui.horizontal(|ui| {
+                    self.category_chip(ui, &t.category);
+                    ui.label(&t.description);
+                });
+                ui.label(t.category_path());
+                let (sign, color) = match t.trans_type {
+                    TransactionType::Income => ("+", theme::income(ui.visuals())),
+                    TransactionType::Expense => ("-", theme::expense(ui.visuals())),
+                };
+                ui.colored_label(color, format!("{}{}", sign, self.money(self.home_amount(t))));
+                ui.end_row();
+            }
+        });
+    }
+}
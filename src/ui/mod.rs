@@ -0,0 +1,324 @@
+use chrono::Local;
+use eframe::egui;
+use egui::{Color32, Sense, Vec2};
+use std::path::Path;
+
+pub mod audit;
+#[cfg(feature = "bank")]
+pub mod bank;
+pub mod budgets;
+pub mod categories;
+pub mod charts;
+pub mod dashboard;
+pub mod net_worth;
+pub mod rules;
+pub mod settings;
+#[cfg(feature = "sheets")]
+pub mod sheets;
+pub mod transactions;
+
+use crate::{DESC_FIELD, FinanceApp, Tab, import, logging, theme};
+use crate::error::Problem;
+use crate::i18n::t;
+use crate::import::ImportFormat;
+use crate::model::{CategoryDef, TransactionType, category_path};
+use crate::period::Period;
+
+// Choices offered by the icon picker in the category editor
+pub const CATEGORY_ICONS: [&str; 40] = [
+    "💼", "🏢", "📈", "🎁", "💰", "🏦", "💳", "🧾",
+    "🍔", "🍕", "☕", "🍺", "🛒", "🏠", "🔧", "💡",
+    "🚗", "⛽", "🚌", "✈", "📱", "🌐", "🎬", "🎮",
+    "🎵", "📚", "🎓", "🛍", "👕", "💇", "💊", "❤",
+    "🏋", "🐶", "🐱", "👶", "🎉", "🌴", "⭐", "❓",
+];
+
+// Small menu with a grid of icons. Returns true when the icon changed.
+pub fn icon_picker(ui: &mut egui::Ui, icon: &mut String) -> bool {
+    let mut changed = false;
+    let label = if icon.is_empty() { "☐".to_string() } else { icon.clone() };
+    ui.menu_button(label, |ui| {
+        egui::Grid::new("icon_grid").show(ui, |ui| {
+            for (i, choice) in CATEGORY_ICONS.iter().enumerate() {
+                if ui.button(*choice).clicked() {
+                    *icon = choice.to_string();
+                    changed = true;
+                    ui.close_menu();
+                }
+                if i % 8 == 7 {
+                    ui.end_row();
+                }
+            }
+        });
+        if ui.button(t!("no-icon")).clicked() {
+            icon.clear();
+            changed = true;
+            ui.close_menu();
+        }
+    });
+    changed
+}
+
+// Hierarchical category dropdown: parents first, with their subcategories indented underneath
+pub fn category_picker(
+    ui: &mut egui::Ui,
+    id_salt: impl std::hash::Hash,
+    categories: &[CategoryDef],
+    trans_type: TransactionType,
+    category: &mut String,
+    subcategory: &mut Option<String>,
+) {
+    egui::ComboBox::from_id_salt(id_salt)
+        .selected_text(category_path(category, subcategory.as_deref()))
+        .show_ui(ui, |ui| {
+            for cat in categories.iter().filter(|c| c.kind.matches(trans_type)) {
+                let selected = *category == cat.name && subcategory.is_none();
+                if ui.selectable_label(selected, &cat.name).clicked() {
+                    *category = cat.name.clone();
+                    *subcategory = None;
+                }
+                for sub in &cat.subcategories {
+                    let selected = *category == cat.name && subcategory.as_ref() == Some(sub);
+                    if ui.selectable_label(selected, format!("    {}", sub)).clicked() {
+                        *category = cat.name.clone();
+                        *subcategory = Some(sub.clone());
+                    }
+                }
+            }
+        });
+}
+
+// "< December 2024 > Today | Month Year All time"
+pub fn period_picker(ui: &mut egui::Ui, period: &mut Period) {
+    let stepping = *period != Period::All;
+    if ui.add_enabled(stepping, egui::Button::new("<")).clicked() {
+        *period = period.step(-1);
+    }
+    ui.add_sized([130.0, 20.0], egui::Label::new(egui::RichText::new(period.label()).strong()));
+    if ui.add_enabled(stepping, egui::Button::new(">")).clicked() {
+        *period = period.step(1);
+    }
+    if ui.button(t!("period-today")).clicked() {
+        *period = Period::month_of(Local::now().date_naive());
+    }
+
+    ui.separator();
+    let current = *period;
+    if ui.selectable_label(matches!(current, Period::Month { .. }), t!("period-month")).clicked() {
+        *period = current.to_month();
+    }
+    if ui.selectable_label(matches!(current, Period::Year(_)), t!("period-year")).clicked() {
+        *period = current.to_year();
+    }
+    if ui.selectable_label(current == Period::All, t!("all-time")).clicked() {
+        *period = Period::All;
+    }
+}
+
+impl FinanceApp {
+    pub fn show_undo_buttons(&mut self, ui: &mut egui::Ui) {
+        // Right-to-left layout, so redo goes first
+        if ui.add_enabled(self.history.can_redo(), egui::Button::new("↷")).on_hover_text(t!("redo-ctrl-y")).clicked() {
+            self.redo();
+        }
+        if ui.add_enabled(self.history.can_undo(), egui::Button::new("↶")).on_hover_text(t!("undo-ctrl-z")).clicked() {
+            self.undo();
+        }
+    }
+
+    pub fn show_save_indicator(&self, ui: &mut egui::Ui) {
+        if self.save_error.is_some() {
+            ui.colored_label(Color32::RED, t!("unsaved-changes"));
+        } else if self.saver.as_ref().is_some_and(|saver| saver.is_busy()) {
+            ui.weak(t!("saving"));
+        } else if self.dirty {
+            ui.colored_label(theme::warning(ui.visuals()), t!("unsaved-pending"));
+        } else if let Some(time) = self.last_saved {
+            ui.weak(t!("saved-at", time = time.format("%H:%M:%S").to_string()));
+        } else {
+            ui.weak(t!("saved"));
+        }
+    }
+
+    // Recent log lines, for working out what went wrong with a save, import or sync
+    pub fn show_diagnostics_window(&mut self, ctx: &egui::Context) {
+        let mut open = self.show_diagnostics;
+        egui::Window::new(t!("diagnostics"))
+            .open(&mut open)
+            .default_size([640.0, 360.0])
+            .show(ctx, |ui| {
+                let lines = logging::recent_lines();
+                ui.horizontal(|ui| {
+                    ui.label(t!("log-file"));
+                    match logging::log_path() {
+                        Some(path) => ui.monospace(path.display().to_string()),
+                        None => ui.weak(t!("log-memory-only")),
+                    };
+                    if ui.button(t!("copy-log")).clicked() {
+                        ctx.copy_text(lines.join("\n"));
+                    }
+                });
+                ui.separator();
+                egui::ScrollArea::both().stick_to_bottom(true).show(ui, |ui| {
+                    for line in &lines {
+                        ui.monospace(line);
+                    }
+                });
+            });
+        self.show_diagnostics = open;
+    }
+
+    pub fn show_error_banner(&mut self, ui: &mut egui::Ui) {
+        let Some(error) = &self.error else { return };
+        let mut dismissed = false;
+        egui::Frame::group(ui.style()).stroke(egui::Stroke::new(1.0, Color32::RED)).show(ui, |ui| {
+            ui.horizontal(|ui| {
+                ui.colored_label(Color32::RED, format!("⚠ {}", error.message()));
+                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                    dismissed = ui.small_button("✖").clicked();
+                });
+            });
+            ui.label(error.advice());
+        });
+        if dismissed {
+            self.error = None;
+        }
+    }
+
+    pub fn show_changed_on_disk_banner(&mut self, ui: &mut egui::Ui) {
+        if !self.changed_on_disk {
+            return;
+        }
+        let warning = theme::warning(ui.visuals());
+        egui::Frame::group(ui.style()).stroke(egui::Stroke::new(1.0, warning)).show(ui, |ui| {
+            ui.colored_label(warning, format!("⚠ {}", t!("changed-on-disk", path = self.data_path.display().to_string())));
+            ui.label(t!("changed-on-disk-advice"));
+            ui.horizontal(|ui| {
+                if ui.button(t!("merge-changes")).clicked() {
+                    self.reload_changed_file(true);
+                }
+                if ui.button(t!("reload-file")).on_hover_text(t!("reload-file-hint")).clicked() {
+                    self.reload_changed_file(false);
+                }
+                if ui.button(t!("keep-mine")).on_hover_text(t!("keep-mine-hint")).clicked() {
+                    self.overwrite_changed_file();
+                }
+            });
+        });
+    }
+
+    pub fn show_read_only_banner(&mut self, ui: &mut egui::Ui) {
+        if !self.read_only {
+            return;
+        }
+        let warning = theme::warning(ui.visuals());
+        egui::Frame::group(ui.style()).stroke(egui::Stroke::new(1.0, warning)).show(ui, |ui| {
+            ui.horizontal(|ui| {
+                ui.colored_label(warning, format!("🔒 {}", t!("read-only", path = self.data_path.display().to_string())));
+                if ui.button(t!("try-again")).on_hover_text(t!("try-again-hint")).clicked() {
+                    self.retry_instance_lock();
+                }
+            });
+            ui.label(t!("read-only-advice"));
+        });
+    }
+
+    pub fn show_load_error_ui(&mut self, ui: &mut egui::Ui) {
+        let error = self.load_error.as_ref().map(|e| e.to_string()).unwrap_or_default();
+        ui.vertical_centered(|ui| {
+            ui.add_space(60.0);
+            ui.heading(t!("the-ledger-could-not-be-read"));
+            ui.label(self.data_path.display().to_string());
+            ui.colored_label(Color32::RED, &error);
+            ui.add_space(10.0);
+            ui.label(t!("nothing-has-been-changed-fix-the"));
+            if ui.button(t!("start-a-new-ledger-the-old")).clicked() {
+                let mut aside = self.data_path.clone().into_os_string();
+                aside.push(format!(".unreadable-{}", Local::now().format("%Y%m%d-%H%M%S")));
+                match std::fs::rename(&self.data_path, &aside) {
+                    Ok(()) => {
+                        self.load_error = None;
+                        self.data_file_status = Some(t!("moved-aside", path = Path::new(&aside).display().to_string()));
+                        self.save_data();
+                    }
+                    Err(e) => self.load_error = Some(Problem::Other(t!("move-aside-failed", error = error.clone(), reason = e.to_string()))),
+                }
+            }
+        });
+    }
+
+    pub fn show_unlock_ui(&mut self, ui: &mut egui::Ui) {
+        ui.vertical_centered(|ui| {
+            ui.add_space(60.0);
+            ui.heading(t!("this-ledger-is-encrypted"));
+            ui.label(self.data_path.display().to_string());
+            ui.add_space(10.0);
+            let response = ui.add(egui::TextEdit::singleline(&mut self.unlock_input)
+                .password(true)
+                .hint_text(t!("passphrase")));
+            let submitted = response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter));
+            if ui.button(t!("unlock")).clicked() || submitted {
+                let passphrase = self.unlock_input.clone();
+                if let Err(e) = self.unlock(&passphrase) {
+                    self.unlock_error = Some(e);
+                }
+            }
+            if let Some(error) = &self.unlock_error {
+                ui.colored_label(Color32::RED, error);
+            }
+            if self.profiles.len() > 1 {
+                ui.add_space(20.0);
+                ui.label(t!("or-open-another-profile"));
+                self.show_profile_switcher(ui);
+            }
+        });
+    }
+
+    // Cycles system → light → dark
+    pub fn show_theme_toggle(&mut self, ui: &mut egui::Ui) {
+        let current = self.settings.theme;
+        if ui.button(current.label()).on_hover_text(t!("theme")).clicked() {
+            self.set_theme(ui.ctx(), current.next());
+        }
+    }
+
+    // Color chip followed by the category icon, as used in the list and legend
+    pub fn category_chip(&self, ui: &mut egui::Ui, name: &str) {
+        let (rect, _resp) = ui.allocate_exact_size(Vec2::splat(16.0), Sense::hover());
+        ui.painter().rect_filled(rect, 3.0, self.category_color(name));
+        let icon = self.category_icon(name);
+        if !icon.is_empty() {
+            ui.label(icon);
+        }
+    }
+
+    // Shown instead of the list and the graph while the ledger is empty
+    pub fn show_onboarding(&mut self, ui: &mut egui::Ui) {
+        ui.vertical_centered(|ui| {
+            ui.add_space(30.0);
+            ui.heading(t!("welcome"));
+            ui.label(t!("welcome-text"));
+            ui.add_space(15.0);
+
+            if ui.button(t!("add-first-transaction")).clicked() {
+                self.current_tab = Tab::Transactions;
+                ui.memory_mut(|m| m.request_focus(egui::Id::new(DESC_FIELD)));
+            }
+            ui.add_space(5.0);
+            for format in [ImportFormat::Ynab, ImportFormat::Mint] {
+                if ui.button(t!("import-from", format = format.label())).clicked() {
+                    if let Some(path) = import::pick_csv() {
+                        // The progress shows there
+                        self.current_tab = Tab::Transactions;
+                        self.import_csv(ui.ctx(), &path, format);
+                    }
+                }
+            }
+            ui.add_space(5.0);
+            if ui.button(t!("load-demo-data")).clicked() {
+                self.load_demo_data();
+            }
+            ui.weak(t!("demo-data-hint"));
+        });
+    }
+}
@@ -0,0 +1,182 @@
+use chrono::Local;
+use eframe::egui;
+use egui::Color32;
+
+use crate::{AppError, FinanceApp, holdings, i18n, theme};
+use crate::holdings::{Holding, PriceProvider};
+use crate::i18n::t;
+
+impl FinanceApp {
+    // In the home currency at the latest price. None before a price was fetched, or
+    // when there is no rate from the price's currency.
+    fn holding_value(&self, holding: &Holding) -> Option<f64> {
+        let price = holdings::latest(&self.prices, &holding.symbol)?;
+        let today = Local::now().date_naive();
+        self.rates.convert(price.price * holding.quantity, &price.currency, &self.rates.home_currency, today)
+    }
+
+    fn poll_price_fetch(&mut self) {
+        let Some(fetch) = &mut self.price_fetch else { return };
+        let prices = fetch.poll();
+        for price in prices {
+            holdings::set_price(&mut self.prices, price);
+        }
+        if !fetch.is_done() {
+            return;
+        }
+        for (symbol, error) in &fetch.failures {
+            tracing::warn!(symbol = %symbol, "price fetch failed: {}", error);
+        }
+        self.price_status = Some(match fetch.failures.first() {
+            None => t!("prices-fetched", count = fetch.fetched),
+            Some((symbol, error)) => t!(
+                "prices-fetch-failed",
+                count = fetch.fetched,
+                failed = fetch.failures.len(),
+                symbol = symbol.clone(),
+                error = error.to_string(),
+            ),
+        });
+        let fetched = fetch.fetched;
+        self.price_fetch = None;
+        if fetched > 0 {
+            self.save_data();
+        }
+    }
+
+    pub fn show_net_worth_ui(&mut self, ui: &mut egui::Ui) {
+        self.poll_price_fetch();
+
+        let cash = self.balance(false);
+        let values: Vec<Option<f64>> = self.holdings.iter().map(|h| self.holding_value(h)).collect();
+        let invested: f64 = values.iter().flatten().sum();
+        ui.heading(t!("tab-net-worth"));
+        egui::Grid::new("net_worth_totals").num_columns(2).show(ui, |ui| {
+            ui.label(t!("net-worth-cash"));
+            ui.label(self.money(cash));
+            ui.end_row();
+            ui.label(t!("net-worth-holdings"));
+            ui.label(self.money(invested));
+            ui.end_row();
+            ui.strong(t!("net-worth-total"));
+            ui.strong(self.money(cash + invested));
+            ui.end_row();
+        });
+        if values.iter().any(Option::is_none) {
+            ui.colored_label(theme::warning(ui.visuals()), t!("holdings-unpriced"));
+        }
+        ui.separator();
+
+        ui.heading(t!("new-holding"));
+        ui.horizontal(|ui| {
+            ui.label(t!("name-label"));
+            ui.add(egui::TextEdit::singleline(&mut self.holding_name).desired_width(140.0));
+            ui.label(t!("symbol-label"));
+            ui.add(egui::TextEdit::singleline(&mut self.holding_symbol).desired_width(100.0).hint_text("bitcoin"));
+            ui.label(t!("quantity-label"));
+            ui.add(egui::TextEdit::singleline(&mut self.holding_quantity).desired_width(80.0));
+
+            if ui.button(t!("add")).clicked() {
+                match self.holding_quantity.trim().replace(',', ".").parse::<f64>() {
+                    Ok(quantity) if quantity > 0.0 => {
+                        let symbol = self.holding_symbol.trim();
+                        if !symbol.is_empty() {
+                            let name = if self.holding_name.trim().is_empty() { symbol } else { self.holding_name.trim() };
+                            self.holdings.push(Holding::new(name, symbol, quantity));
+                            self.holding_name.clear();
+                            self.holding_symbol.clear();
+                            self.holding_quantity.clear();
+                            self.clear_input_error();
+                            self.save_data();
+                        }
+                    }
+                    _ => self.error = Some(AppError::BadAmount { field: t!("quantity-field"), value: self.holding_quantity.clone() }),
+                }
+            }
+        });
+        ui.separator();
+
+        ui.horizontal(|ui| {
+            ui.heading(t!("holdings"));
+            let fetching = self.price_fetch.is_some();
+            let label = if fetching { t!("fetching") } else { t!("fetch-prices") };
+            if ui.add_enabled(!fetching && !self.holdings.is_empty(), egui::Button::new(label)).clicked() {
+                let mut symbols: Vec<String> = self.holdings.iter().map(|h| h.symbol.clone()).collect();
+                symbols.sort();
+                symbols.dedup();
+                self.price_fetch = Some(holdings::fetch_prices(
+                    &self.settings.price_provider,
+                    symbols,
+                    &self.rates.home_currency,
+                    ui.ctx(),
+                ));
+                self.price_status = None;
+            }
+            if let Some(status) = &self.price_status {
+                ui.label(status);
+            }
+        });
+
+        if self.holdings.is_empty() {
+            ui.label(t!("no-holdings-yet"));
+        } else {
+            let mut to_remove = None;
+            egui::Grid::new("holdings").striped(true).num_columns(7).show(ui, |ui| {
+                for heading in [t!("name-label"), t!("symbol-label"), t!("quantity-label"), t!("price-label"), t!("value-label"), t!("price-updated")] {
+                    ui.strong(heading);
+                }
+                ui.end_row();
+                for (index, (holding, value)) in self.holdings.iter().zip(&values).enumerate() {
+                    ui.label(&holding.name);
+                    ui.monospace(&holding.symbol);
+                    ui.label(holding.quantity.to_string());
+                    match holdings::latest(&self.prices, &holding.symbol) {
+                        Some(price) => {
+                            ui.label(format!("{} {}", self.money_format.number(price.price), price.currency));
+                            match value {
+                                Some(value) => ui.label(self.money(*value)),
+                                None => ui.colored_label(Color32::RED, t!("no-rate-for", currency = price.currency.clone())),
+                            };
+                            ui.weak(i18n::date_time(&price.fetched));
+                        }
+                        None => {
+                            ui.weak("–");
+                            ui.weak("–");
+                            ui.weak(t!("never"));
+                        }
+                    }
+                    if ui.button("🗑").clicked() {
+                        to_remove = Some(index);
+                    }
+                    ui.end_row();
+                }
+            });
+            if let Some(index) = to_remove {
+                self.holdings.remove(index);
+                self.save_data();
+            }
+        }
+        ui.separator();
+
+        egui::CollapsingHeader::new(t!("price-source")).show(ui, |ui| {
+            ui.label(t!("price-source-explained"));
+            let provider = &mut self.settings.price_provider;
+            let mut changed = false;
+            egui::Grid::new("price_source").num_columns(2).show(ui, |ui| {
+                ui.label(t!("price-url"));
+                changed |= ui.add(egui::TextEdit::singleline(&mut provider.url).desired_width(480.0)).lost_focus();
+                ui.end_row();
+                ui.label(t!("price-pointer"));
+                changed |= ui.add(egui::TextEdit::singleline(&mut provider.pointer).desired_width(480.0)).lost_focus();
+                ui.end_row();
+            });
+            if ui.button(t!("use-coingecko")).clicked() {
+                *provider = PriceProvider::default();
+                changed = true;
+            }
+            if changed {
+                self.save_settings();
+            }
+        });
+    }
+}
@@ -0,0 +1,164 @@
+use eframe::egui;
+use uuid::Uuid;
+
+use crate::{FinanceApp, rules, theme};
+use crate::i18n::t;
+use crate::model::{TransactionType, category_path};
+use crate::rules::Rule;
+use crate::ui::category_picker;
+
+fn type_label(trans_type: Option<TransactionType>) -> String {
+    match trans_type {
+        None => t!("all"),
+        Some(TransactionType::Income) => t!("income"),
+        Some(TransactionType::Expense) => t!("expense"),
+    }
+}
+
+// Empty is no limit; anything else has to be a number
+fn parse_limit(input: &str) -> Result<Option<f64>, ()> {
+    let input = input.trim();
+    if input.is_empty() {
+        return Ok(None);
+    }
+    input.replace(',', ".").parse().map(Some).map_err(|_| ())
+}
+
+impl FinanceApp {
+    pub fn show_rules_ui(&mut self, ui: &mut egui::Ui) {
+        ui.heading(t!("new-rule"));
+        ui.label(t!("rules-explained"));
+
+        ui.horizontal(|ui| {
+            ui.label(t!("rule-contains"));
+            ui.add(egui::TextEdit::singleline(&mut self.rule_pattern).desired_width(160.0));
+            ui.checkbox(&mut self.rule_regex, t!("rule-regex"));
+            egui::ComboBox::from_id_salt("rule_type")
+                .selected_text(type_label(self.rule_type))
+                .show_ui(ui, |ui| {
+                    for kind in [None, Some(TransactionType::Expense), Some(TransactionType::Income)] {
+                        ui.selectable_value(&mut self.rule_type, kind, type_label(kind));
+                    }
+                });
+            ui.label(t!("amount"));
+            ui.add(egui::TextEdit::singleline(&mut self.rule_min).hint_text(t!("min")).desired_width(60.0));
+            ui.label("–");
+            ui.add(egui::TextEdit::singleline(&mut self.rule_max).hint_text(t!("max")).desired_width(60.0));
+        });
+
+        ui.horizontal(|ui| {
+            ui.label(t!("rule-file-under"));
+            let kind = self.rule_type.unwrap_or(TransactionType::Expense);
+            category_picker(ui, "rule_category", &self.categories, kind, &mut self.rule_category, &mut self.rule_subcategory);
+
+            let pattern = self.rule_pattern.trim().to_string();
+            let regex_error = if self.rule_regex { rules::regex_error(&pattern) } else { None };
+            let limits = parse_limit(&self.rule_min).and_then(|min| parse_limit(&self.rule_max).map(|max| (min, max)));
+            let valid = !pattern.is_empty() && regex_error.is_none() && limits.is_ok();
+            if ui.add_enabled(valid, egui::Button::new(t!("add"))).clicked() {
+                if let Ok((min_amount, max_amount)) = limits {
+                    self.rules.push(Rule {
+                        id: Uuid::new_v4(),
+                        pattern,
+                        regex: self.rule_regex,
+                        trans_type: self.rule_type,
+                        min_amount,
+                        max_amount,
+                        category: self.rule_category.clone(),
+                        subcategory: self.rule_subcategory.clone(),
+                    });
+                    self.rule_pattern.clear();
+                    self.rule_min.clear();
+                    self.rule_max.clear();
+                    self.save_data();
+                }
+            }
+            if let Some(error) = regex_error {
+                ui.colored_label(theme::warning(ui.visuals()), t!("rule-bad-regex", error = error));
+            } else if limits.is_err() {
+                ui.colored_label(theme::warning(ui.visuals()), t!("rule-bad-amount"));
+            }
+        });
+        ui.separator();
+
+        ui.horizontal(|ui| {
+            ui.heading(t!("tab-rules"));
+            if ui.add_enabled(!self.rules.is_empty(), egui::Button::new(t!("apply-rules")))
+                .on_hover_text(t!("apply-rules-hint"))
+                .clicked()
+            {
+                self.apply_rules_to_existing();
+            }
+            if let Some(status) = &self.rules_status {
+                ui.label(status);
+            }
+        });
+
+        if self.rules.is_empty() {
+            ui.label(t!("no-rules-yet"));
+            return;
+        }
+
+        let mut to_remove = None;
+        let mut to_raise = None;
+        egui::ScrollArea::vertical().show(ui, |ui| {
+            for (index, rule) in self.rules.iter().enumerate() {
+                ui.horizontal(|ui| {
+                    ui.add_enabled_ui(index > 0, |ui| {
+                        if ui.small_button("⬆").on_hover_text(t!("rule-earlier")).clicked() {
+                            to_raise = Some(index);
+                        }
+                    });
+                    ui.label(format!("{}.", index + 1));
+
+                    let pattern = if rule.regex { format!("/{}/", rule.pattern) } else { format!("“{}”", rule.pattern) };
+                    ui.monospace(pattern);
+                    if rule.trans_type.is_some() {
+                        ui.weak(type_label(rule.trans_type));
+                    }
+                    match (rule.min_amount, rule.max_amount) {
+                        (None, None) => {}
+                        (min, max) => {
+                            let bound = |b: Option<f64>| b.map(|b| self.money(b)).unwrap_or_default();
+                            ui.weak(format!("{} – {}", bound(min), bound(max)));
+                        }
+                    }
+                    ui.label("→");
+                    self.category_chip(ui, &rule.category);
+                    ui.label(category_path(&rule.category, rule.subcategory.as_deref()));
+
+                    if ui.button("🗑").clicked() {
+                        to_remove = Some(index);
+                    }
+                });
+            }
+        });
+
+        if let Some(index) = to_raise {
+            self.rules.swap(index - 1, index);
+            self.save_data();
+        }
+        if let Some(index) = to_remove {
+            self.rules.remove(index);
+            self.save_data();
+        }
+    }
+
+    // Refiles every transaction a rule matches. One save, so one undo takes it back.
+    pub fn apply_rules_to_existing(&mut self) {
+        let matcher = rules::Matcher::new(&self.rules);
+        let mut changed = 0;
+        for index in 0..self.transactions.len() {
+            let amount = self.home_amount(&self.transactions[index]);
+            let t = &mut self.transactions[index];
+            if matcher.apply(t, amount) {
+                t.touch();
+                changed += 1;
+            }
+        }
+        self.rules_status = Some(t!("rules-applied", count = changed));
+        if changed > 0 {
+            self.save_data();
+        }
+    }
+}
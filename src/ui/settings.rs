@@ -0,0 +1,726 @@
+use chrono::Local;
+use eframe::egui;
+use egui::Color32;
+
+use crate::{FinanceApp, config, export, format, i18n, rates, storage, theme, webhooks};
+use crate::error::AppError;
+use crate::format::{MoneyFormat, SymbolPosition};
+use crate::i18n::{Language, t};
+use crate::model::{Transaction, TransactionType};
+use crate::rates::RateSource;
+use crate::settings::WeekStart;
+use crate::theme::ThemeChoice;
+use crate::webhooks::Webhook;
+
+pub fn separator_label(sep: Option<char>) -> String {
+    match sep {
+        Some(',') => t!("comma"),
+        Some('.') => t!("point"),
+        Some('\u{202F}') => t!("space"),
+        Some('\'') => t!("apostrophe"),
+        Some(_) => t!("other"),
+        None => t!("none"),
+    }
+}
+
+impl FinanceApp {
+    pub fn show_settings_ui(&mut self, ui: &mut egui::Ui) {
+        egui::ScrollArea::vertical().show(ui, |ui| {
+            ui.heading(t!("appearance"));
+            ui.horizontal(|ui| {
+                ui.label(t!("theme-label"));
+                for theme in ThemeChoice::ALL {
+                    if ui.radio(self.settings.theme == theme, theme.label()).clicked() {
+                        self.set_theme(ui.ctx(), theme);
+                    }
+                }
+            });
+            ui.horizontal(|ui| {
+                ui.label(t!("ui-scale-label"));
+                // Resizing everything mid-drag moves the slider away from the pointer, so
+                // the new size is only applied once it is let go
+                let dragging = ui.id().with("ui_scale");
+                let mut scale = ui.data(|d| d.get_temp(dragging)).unwrap_or(self.settings.ui_scale);
+                let response = ui.add(egui::Slider::new(&mut scale, 0.5..=3.0)
+                    .step_by(0.1)
+                    .custom_formatter(|value, _| format!("{:.0}%", value * 100.0)));
+                if response.drag_stopped() || (response.changed() && !response.dragged()) {
+                    ui.data_mut(|d| d.remove::<f32>(dragging));
+                    self.set_ui_scale(ui.ctx(), scale);
+                } else if response.changed() {
+                    ui.data_mut(|d| d.insert_temp(dragging, scale));
+                }
+                if ui.button(t!("reset")).clicked() {
+                    self.set_ui_scale(ui.ctx(), 1.0);
+                }
+            });
+            ui.weak(t!("ui-scale-hint"));
+            ui.separator();
+
+            ui.heading(t!("language"));
+            ui.horizontal(|ui| {
+                ui.label(t!("language-label"));
+                let before = self.settings.language;
+                egui::ComboBox::from_id_salt("language")
+                    .selected_text(before.label())
+                    .show_ui(ui, |ui| {
+                        for language in Language::ALL {
+                            ui.selectable_value(&mut self.settings.language, language, language.label());
+                        }
+                    });
+                if self.settings.language != before {
+                    i18n::set_language(self.settings.language);
+                    if let Some(tray) = &self.tray {
+                        tray.relabel();
+                    }
+                    self.save_settings();
+                }
+                if ui.button(t!("use-language-format")).clicked() {
+                    if let Some(preset) = MoneyFormat::preset(self.settings.language.number_preset()) {
+                        self.money_format = preset;
+                        self.save_data();
+                    }
+                }
+            });
+            ui.label(t!("language-format-hint"));
+            ui.separator();
+
+            ui.heading(t!("system-tray"));
+            if ui.checkbox(&mut self.settings.start_in_tray, t!("start-in-tray")).changed() {
+                self.save_settings();
+            }
+            if self.tray.is_none() {
+                ui.weak(t!("tray-unavailable"));
+            }
+            ui.separator();
+
+            ui.heading(t!("notifications"));
+            let notifications = &mut self.settings.notifications;
+            let mut changed = ui.checkbox(&mut notifications.budget_warning, t!("notify-on-budget-warning")).changed();
+            changed |= ui.checkbox(&mut notifications.budget_exceeded, t!("notify-on-budget-exceeded")).changed();
+            changed |= ui.checkbox(&mut notifications.bills_due, t!("notify-on-bills-due")).changed();
+            if changed {
+                self.save_settings();
+            }
+            ui.separator();
+
+            ui.heading(t!("new-transactions"));
+            ui.horizontal(|ui| {
+                ui.label(t!("start-the-form-as"));
+                let before = self.settings.default_type;
+                ui.radio_value(&mut self.settings.default_type, TransactionType::Expense, t!("expense"));
+                ui.radio_value(&mut self.settings.default_type, TransactionType::Income, t!("income"));
+                if self.settings.default_type != before {
+                    self.save_settings();
+                }
+            });
+            ui.separator();
+
+            ui.heading(t!("calendar"));
+            ui.horizontal(|ui| {
+                ui.label(t!("weeks-start-on"));
+                let before = self.settings.week_start;
+                for start in [WeekStart::Monday, WeekStart::Sunday] {
+                    ui.radio_value(&mut self.settings.week_start, start, start.label());
+                }
+                if self.settings.week_start != before {
+                    self.save_settings();
+                }
+            });
+            ui.horizontal(|ui| {
+                ui.label(t!("months-start-on-day"));
+                let response = ui.add(egui::DragValue::new(&mut self.settings.month_start).range(1..=28));
+                if response.changed() {
+                    self.save_settings();
+                }
+            });
+            ui.label(t!("budgets-and-the-this-month-filter"));
+            if let Some(path) = config::config_file() {
+                ui.weak(t!("settings-file", path = path.display().to_string()));
+            }
+            ui.separator();
+
+            ui.heading(t!("currency-format"));
+            ui.horizontal(|ui| {
+                ui.label(t!("balances-are-shown-in"));
+                let response = ui.add(egui::TextEdit::singleline(&mut self.home_currency_input).desired_width(60.0));
+                if response.lost_focus() {
+                    self.change_home_currency(&self.home_currency_input.clone());
+                }
+            });
+            self.show_money_format_settings(ui);
+            ui.label(t!("the-currency-and-number-format-are"));
+            ui.separator();
+
+            self.show_data_file_settings(ui);
+            ui.separator();
+
+            ui.heading(t!("backups"));
+            ui.horizontal(|ui| {
+                ui.label(t!("keep-the-last"));
+                let response = ui.add(egui::DragValue::new(&mut self.backup_count).range(0..=100));
+                ui.label(t!("copies-0-turns-backups-off"));
+                // Saving also writes a backup, so wait until dragging is done
+                if response.drag_stopped() || (response.changed() && !response.dragged()) {
+                    self.save_data();
+                }
+            });
+            ui.label(t!("backup-location", path = storage::backup_dir(&self.data_path).display().to_string()));
+            ui.separator();
+
+            self.show_hooks_settings(ui);
+            ui.separator();
+
+            self.show_webhook_settings(ui);
+
+            if let Some(status) = &self.settings_status {
+                ui.colored_label(Color32::RED, status);
+            }
+        });
+    }
+
+    pub fn show_profile_switcher(&mut self, ui: &mut egui::Ui) {
+        let current = self.profile_name.clone().unwrap_or_else(|| {
+            self.data_path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default()
+        });
+        let mut switch_to = None;
+        egui::ComboBox::from_id_salt("profile_switcher")
+            .selected_text(format!("👤 {}", current))
+            .show_ui(ui, |ui| {
+                for profile in &self.profiles {
+                    let active = self.profile_name.as_deref() == Some(profile.name.as_str());
+                    if ui.selectable_label(active, &profile.name).clicked() && !active {
+                        switch_to = Some(profile.data_file.clone());
+                    }
+                }
+            });
+        if let Some(path) = switch_to {
+            self.open_data_file(path);
+        }
+    }
+
+    pub fn show_profiles_ui(&mut self, ui: &mut egui::Ui) {
+        ui.heading(t!("profiles"));
+        ui.label(t!("each-profile-is-a-separate-ledger"));
+
+        let mut remove = None;
+        let mut open = None;
+        for profile in &self.profiles {
+            ui.horizontal(|ui| {
+                let active = self.profile_name.as_deref() == Some(profile.name.as_str());
+                if active {
+                    ui.strong(format!("● {}", profile.name));
+                } else {
+                    ui.label(&profile.name);
+                }
+                ui.weak(profile.data_file.display().to_string());
+                if !active {
+                    if ui.button(t!("open")).clicked() {
+                        open = Some(profile.data_file.clone());
+                    }
+                    if ui.button("🗑").on_hover_text(t!("forget-this-profile-the-file-is")).clicked() {
+                        remove = Some(profile.data_file.clone());
+                    }
+                }
+            });
+        }
+        if let Some(path) = open {
+            self.open_data_file(path);
+        }
+        if let Some(path) = remove {
+            self.profile_status = config::remove_profile(&path).err().map(|e| e.to_string());
+            self.refresh_profiles();
+        }
+
+        ui.horizontal(|ui| {
+            ui.add(egui::TextEdit::singleline(&mut self.new_profile_name).hint_text(t!("profile-name")).desired_width(150.0));
+            let name = self.new_profile_name.trim().to_string();
+            if ui.add_enabled(!name.is_empty(), egui::Button::new(t!("new-profile"))).clicked() {
+                let picked = rfd::FileDialog::new()
+                    .add_filter("Ledger", &["json"])
+                    .set_file_name(format!("{}.json", name))
+                    .save_file();
+                if let Some(path) = picked {
+                    self.create_profile(&name, path);
+                }
+            }
+            if self.profile_name.is_some() && ui.add_enabled(!name.is_empty(), egui::Button::new(t!("rename-current"))).clicked() {
+                self.profile_status = config::rename_profile(&self.data_path, &name).err().map(|e| e.to_string());
+                if self.profile_status.is_none() {
+                    self.new_profile_name.clear();
+                }
+                self.refresh_profiles();
+            }
+        });
+        if let Some(status) = &self.profile_status {
+            ui.colored_label(Color32::RED, status);
+        }
+    }
+
+    pub fn show_data_ui(&mut self, ui: &mut egui::Ui) {
+        self.show_profiles_ui(ui);
+        ui.separator();
+
+        self.show_sync_ui(ui);
+        ui.separator();
+
+        #[cfg(feature = "bank")]
+        {
+            self.show_bank_ui(ui);
+            ui.separator();
+        }
+
+        #[cfg(feature = "sheets")]
+        {
+            self.show_sheets_ui(ui);
+            ui.separator();
+        }
+
+        self.show_bot_ui(ui);
+        ui.separator();
+
+        self.show_export_ui(ui);
+        ui.separator();
+
+        self.show_encryption_ui(ui);
+        ui.separator();
+
+        self.show_restore_ui(ui);
+    }
+
+    // Runs as `finance_tracker bot`, see bot.rs; this only sets it up
+    fn show_bot_ui(&mut self, ui: &mut egui::Ui) {
+        ui.heading(t!("bot-heading"));
+        ui.label(t!("bot-explained"));
+        let mut changed = false;
+        egui::Grid::new("bot_connection").num_columns(2).show(ui, |ui| {
+            ui.label(t!("bot-token"));
+            changed |= ui.add(egui::TextEdit::singleline(&mut self.settings.bot.token).password(true).desired_width(320.0)).lost_focus();
+            ui.end_row();
+            // Typed as text and read back into IDs once the field is left
+            ui.label(t!("bot-allowed-chats"));
+            let chats_id = ui.id().with("bot_chats");
+            let mut chats: String = ui.data(|d| d.get_temp(chats_id)).unwrap_or_else(|| {
+                self.settings.bot.allowed_chats.iter().map(|c| c.to_string()).collect::<Vec<_>>().join(", ")
+            });
+            let response = ui.add(egui::TextEdit::singleline(&mut chats).hint_text("123456789, 987654321").desired_width(320.0));
+            if response.lost_focus() {
+                self.settings.bot.allowed_chats = chats.split([',', ' ']).filter_map(|c| c.trim().parse().ok()).collect();
+                ui.data_mut(|d| d.remove::<String>(chats_id));
+                changed = true;
+            } else if response.has_focus() {
+                ui.data_mut(|d| d.insert_temp(chats_id, chats));
+            }
+            ui.end_row();
+        });
+        if changed {
+            self.save_settings();
+        }
+        ui.weak(t!("bot-token-stored"));
+    }
+
+    pub fn show_hooks_settings(&mut self, ui: &mut egui::Ui) {
+        ui.heading(t!("script-hooks"));
+        ui.label(t!("script-hooks-explained"));
+        if let Some(path) = config::hooks_file() {
+            ui.horizontal(|ui| {
+                ui.label(t!("script-label"));
+                ui.monospace(path.display().to_string());
+            });
+        }
+        ui.horizontal(|ui| {
+            if ui.button(t!("reload-script")).clicked() {
+                self.load_hooks();
+            }
+            match (&self.hooks, &self.hooks_error) {
+                (_, Some(error)) => ui.colored_label(Color32::RED, error),
+                (Some(hooks), None) => ui.label(t!("script-loaded", hooks = hooks.names().join(", "))),
+                (None, None) => ui.weak(t!("no-script")),
+            };
+        });
+    }
+
+    pub fn show_webhook_settings(&mut self, ui: &mut egui::Ui) {
+        ui.heading(t!("webhooks"));
+        ui.label(t!("webhooks-explained"));
+        let mut changed = false;
+        let mut remove = None;
+        for (index, hook) in self.settings.webhooks.iter_mut().enumerate() {
+            ui.horizontal(|ui| {
+                changed |= ui.add(egui::TextEdit::singleline(&mut hook.url).desired_width(280.0)).lost_focus();
+                changed |= ui.checkbox(&mut hook.budget_exceeded, t!("webhook-on-budget")).changed();
+                changed |= ui.checkbox(&mut hook.large_transaction, t!("webhook-on-large")).changed();
+                changed |= ui.checkbox(&mut hook.weekly_summary, t!("webhook-on-weekly")).changed();
+                if ui.button(t!("webhook-send-test")).clicked() {
+                    webhooks::send_test(hook);
+                }
+                if ui.button("🗑").clicked() {
+                    remove = Some(index);
+                }
+            });
+        }
+        if let Some(index) = remove {
+            self.settings.webhooks.remove(index);
+            changed = true;
+        }
+        ui.horizontal(|ui| {
+            ui.add(egui::TextEdit::singleline(&mut self.webhook_url).hint_text("https://ntfy.sh/my-topic").desired_width(280.0));
+            let url = self.webhook_url.trim();
+            if ui.add_enabled(url.starts_with("http://") || url.starts_with("https://"), egui::Button::new(t!("webhook-add"))).clicked() {
+                self.settings.webhooks.push(Webhook::new(url));
+                self.webhook_url.clear();
+                changed = true;
+            }
+        });
+        ui.horizontal(|ui| {
+            ui.label(t!("webhook-large-from"));
+            changed |= ui.add(egui::DragValue::new(&mut self.settings.large_transaction).range(0.0..=f64::MAX).speed(10.0)).changed();
+        });
+        if changed {
+            self.save_settings();
+        }
+    }
+
+    pub fn show_data_file_settings(&mut self, ui: &mut egui::Ui) {
+        ui.heading(t!("data-file"));
+        ui.horizontal(|ui| {
+            ui.label(t!("ledger-label"));
+            ui.monospace(self.data_path.display().to_string());
+        });
+        ui.horizontal(|ui| {
+            if ui.button(t!("open-file")).clicked() {
+                if let Some(path) = rfd::FileDialog::new().add_filter("Ledger", &["json"]).pick_file() {
+                    self.open_data_file(path);
+                }
+            }
+            if ui.button(t!("save-as")).clicked() {
+                let picked = rfd::FileDialog::new()
+                    .add_filter("Ledger", &["json"])
+                    .set_file_name(storage::DATA_FILE)
+                    .save_file();
+                if let Some(path) = picked {
+                    self.move_data_file(path);
+                }
+            }
+        });
+        ui.label(t!("the-last-file-used-is-opened"));
+        if let Some(status) = &self.data_file_status {
+            ui.label(status);
+        }
+    }
+
+    pub fn show_sync_ui(&mut self, ui: &mut egui::Ui) {
+        ui.heading(t!("sync"));
+        ui.label(t!("point-this-at-a-ledger-in"));
+        ui.horizontal(|ui| {
+            match &self.sync_file {
+                Some(path) => ui.monospace(path.display().to_string()),
+                None => ui.label(t!("no-shared-file-chosen")),
+            };
+            if ui.button(t!("choose")).clicked() {
+                let picked = rfd::FileDialog::new()
+                    .add_filter("Ledger", &["json"])
+                    .set_file_name("shared_ledger.json")
+                    .save_file();
+                if let Some(path) = picked {
+                    self.sync_status = config::remember_sync_file(&self.data_path, &path).err()
+                        .map(|e| t!("sync-not-remembered", error = e.to_string()));
+                    self.sync_file = Some(path);
+                }
+            }
+            if ui.add_enabled(self.sync_file.is_some(), egui::Button::new(t!("sync-now"))).clicked() {
+                self.sync_now();
+            }
+        });
+        if let Some(status) = &self.sync_status {
+            ui.label(status);
+        }
+    }
+
+    pub fn show_export_ui(&mut self, ui: &mut egui::Ui) {
+        ui.heading(t!("export"));
+        ui.horizontal(|ui| {
+            if ui.button(t!("export-to-excel")).clicked() {
+                if let Some(path) = export::pick_xlsx_target() {
+                    let all: Vec<&Transaction> = self.transactions.iter().collect();
+                    let report = self.build_report(&all);
+                    self.export_status = Some(match export::write_xlsx(&path, &report) {
+                        Ok(()) => t!("exported", count = report.rows.len(), path = path.display().to_string()),
+                        Err(e) => t!("export-failed", error = e.to_string()),
+                    });
+                }
+            }
+            if let Some(status) = &self.export_status {
+                ui.label(status);
+            }
+        });
+    }
+
+    pub fn show_encryption_ui(&mut self, ui: &mut egui::Ui) {
+        ui.heading(t!("encryption"));
+        if self.passphrase.is_some() {
+            ui.label(t!("the-ledger-and-its-backups-are"));
+        } else {
+            ui.label(t!("the-ledger-is-stored-as-plain"));
+        }
+        ui.horizontal(|ui| {
+            ui.add(egui::TextEdit::singleline(&mut self.new_passphrase).password(true).hint_text(t!("passphrase")).desired_width(150.0));
+            ui.add(egui::TextEdit::singleline(&mut self.confirm_passphrase).password(true).hint_text(t!("repeat")).desired_width(150.0));
+
+            let valid = !self.new_passphrase.is_empty() && self.new_passphrase == self.confirm_passphrase;
+            let label = if self.passphrase.is_some() { t!("change-passphrase") } else { t!("encrypt-ledger") };
+            if ui.add_enabled(valid, egui::Button::new(label)).clicked() {
+                self.set_passphrase(Some(self.new_passphrase.clone()));
+            }
+            if self.passphrase.is_some() && ui.button(t!("remove-encryption")).clicked() {
+                self.set_passphrase(None);
+            }
+        });
+        ui.label(t!("there-is-no-way-to-recover"));
+        if let Some(status) = &self.encryption_status {
+            ui.label(status);
+        }
+    }
+
+    pub fn show_restore_ui(&mut self, ui: &mut egui::Ui) {
+        ui.heading(t!("restore-from-backup"));
+        if let Some(status) = &self.backup_status {
+            ui.label(status);
+        }
+        let backups = storage::list_backups(&self.data_path);
+        if backups.is_empty() {
+            ui.label(t!("no-backups-yet"));
+        }
+        let mut restore = None;
+        egui::ScrollArea::vertical().show(ui, |ui| {
+            for path in &backups {
+                ui.horizontal(|ui| {
+                    ui.label(storage::backup_label(path));
+                    if ui.button(t!("restore")).clicked() {
+                        restore = Some(path.clone());
+                    }
+                });
+            }
+        });
+        if let Some(path) = restore {
+            self.restore_backup(&path);
+        }
+    }
+
+    // Transactions and templates without a currency were in the old home currency, so
+    // they're given it before the new one takes over; otherwise their amounts would
+    // silently turn into the new currency
+    pub fn change_home_currency(&mut self, code: &str) {
+        let code = code.trim().to_uppercase();
+        if code.is_empty() || code == self.rates.home_currency {
+            self.home_currency_input = self.rates.home_currency.clone();
+            return;
+        }
+        let old = std::mem::replace(&mut self.rates.home_currency, code.clone());
+        self.home_currency_input = code.clone();
+        let mut kept = 0;
+        for t in &mut self.transactions {
+            if t.currency.is_none() {
+                t.currency = Some(old.clone());
+                t.touch();
+                kept += 1;
+            }
+        }
+        for trashed in &mut self.trash {
+            trashed.transaction.currency.get_or_insert_with(|| old.clone());
+        }
+        for template in &mut self.templates {
+            template.currency.get_or_insert_with(|| old.clone());
+        }
+        self.input_currency = code;
+        if kept > 0 {
+            self.toasts.info(t!("home-currency-changed", count = kept, currency = old));
+        }
+        self.save_data();
+    }
+
+    // Picks up the result of a background fetch, whichever tab is open
+    pub fn poll_rate_fetch(&mut self) {
+        let Some(result) = self.rate_fetch.as_ref().and_then(|f| f.poll()) else { return };
+        self.rate_fetch = None;
+        match result {
+            Ok(fetched) => {
+                self.rates.apply_fetched(&fetched);
+                self.rate_status = Some(t!("rates-fetched", count = fetched.rates.len(), date = i18n::date(fetched.date)));
+                self.save_data();
+            }
+            Err(e) => {
+                tracing::warn!("rate fetch failed: {}", e);
+                self.rate_status = Some(t!("rates-fetch-failed", error = e));
+            }
+        }
+    }
+
+    // The daily fetch, once per day the ledger is open. Not retried the same day when it
+    // fails; the button is there for that.
+    pub fn fetch_rates_if_due(&mut self, ctx: &egui::Context) {
+        let today = Local::now().date_naive();
+        if self.read_only || !self.rates.daily_fetch || self.rates.fetched_on == Some(today) || self.rate_fetch.is_some() {
+            return;
+        }
+        self.rate_fetch = Some(rates::fetch_latest(&self.rates.home_currency, ctx));
+        self.rates.fetched_on = Some(today);
+    }
+
+    pub fn show_rates_ui(&mut self, ui: &mut egui::Ui) {
+        ui.heading(t!("opening-balance-heading"));
+        ui.horizontal(|ui| {
+            ui.label(t!("opening-balance-label"));
+            let response = ui.add(egui::TextEdit::singleline(&mut self.opening_input).desired_width(100.0));
+            if response.lost_focus() {
+                let cleaned: String = self.opening_input.chars()
+                    .filter(|c| c.is_ascii_digit() || *c == '-' || *c == self.money_format.decimal_separator)
+                    .map(|c| if c == self.money_format.decimal_separator { '.' } else { c })
+                    .collect();
+                match cleaned.parse::<f64>() {
+                    Ok(value) => {
+                        self.opening_balance = value;
+                        self.save_data();
+                    }
+                    Err(_) if cleaned.is_empty() => {
+                        self.opening_balance = 0.0;
+                        self.save_data();
+                    }
+                    Err(_) => self.error = Some(AppError::BadAmount { field: t!("opening-balance-field"), value: self.opening_input.clone() }),
+                }
+                self.opening_input = self.money_format.number(self.opening_balance);
+            }
+            ui.label(&self.rates.home_currency);
+        });
+        ui.label(t!("the-home-currency-and-number-format"));
+        ui.separator();
+
+        ui.heading(t!("add-rate"));
+        ui.horizontal(|ui| {
+            ui.label(t!("date-label"));
+            ui.add(egui_extras::DatePickerButton::new(&mut self.rate_date).id_salt("rate_date"));
+            ui.label("1");
+            ui.add(egui::TextEdit::singleline(&mut self.rate_from).desired_width(50.0).hint_text("EUR"));
+            ui.label("=");
+            ui.add(egui::TextEdit::singleline(&mut self.rate_value).desired_width(80.0));
+            ui.add(egui::TextEdit::singleline(&mut self.rate_to).desired_width(50.0));
+
+            if ui.button(t!("add")).clicked() {
+                match self.rate_value.trim().replace(',', ".").parse::<f64>() {
+                    Ok(rate) if rate > 0.0 => {
+                        self.rates.set_rate(&self.rate_from, &self.rate_to, self.rate_date, rate, RateSource::Manual);
+                        self.rate_value.clear();
+                        self.clear_input_error();
+                        self.save_data();
+                    }
+                    _ => self.error = Some(AppError::BadAmount { field: t!("rate-field"), value: self.rate_value.clone() }),
+                }
+            }
+        });
+
+        ui.horizontal(|ui| {
+            let fetching = self.rate_fetch.is_some();
+            let label = if fetching { t!("fetching") } else { t!("fetch-rates") };
+            if ui.add_enabled(!fetching, egui::Button::new(label)).clicked() {
+                self.rate_fetch = Some(rates::fetch_latest(&self.rates.home_currency, ui.ctx()));
+                self.rate_status = None;
+            }
+            if let Some(status) = &self.rate_status {
+                ui.label(status);
+            }
+        });
+        if ui.checkbox(&mut self.rates.daily_fetch, t!("fetch-rates-daily")).changed() {
+            self.save_data();
+        }
+
+        // Currencies used by transactions that can't be converted yet
+        let mut missing: Vec<&String> = self.transactions.iter()
+            .filter_map(|t| t.currency.as_ref())
+            .filter(|c| !self.rates.has_rate(c))
+            .collect();
+        missing.sort();
+        missing.dedup();
+        if !missing.is_empty() {
+            let list: Vec<&str> = missing.iter().map(|c| c.as_str()).collect();
+            ui.colored_label(theme::warning(ui.visuals()), t!("missing-rates", currencies = list.join(", ")));
+        }
+        ui.separator();
+
+        ui.heading(t!("rate-history"));
+        let mut to_remove = None;
+        egui::ScrollArea::vertical().show(ui, |ui| {
+            for (index, r) in self.rates.rates.iter().enumerate().rev() {
+                ui.horizontal(|ui| {
+                    ui.label(i18n::date(r.date));
+                    ui.label(format!("1 {} = {:.4} {}", r.from, r.rate, r.to));
+                    if r.source == RateSource::Fetched {
+                        ui.weak(t!("fetched"));
+                    }
+                    if ui.button("🗑").clicked() {
+                        to_remove = Some(index);
+                    }
+                });
+            }
+        });
+
+        if let Some(index) = to_remove {
+            self.rates.rates.remove(index);
+            self.save_data();
+        }
+    }
+
+    pub fn show_money_format_settings(&mut self, ui: &mut egui::Ui) {
+        let before = self.money_format.clone();
+        let fmt = &mut self.money_format;
+
+        ui.horizontal(|ui| {
+            ui.label(t!("format-preset-label"));
+            egui::ComboBox::from_id_salt("format_preset")
+                .selected_text(t!("choose"))
+                .show_ui(ui, |ui| {
+                    for (id, example) in format::PRESETS {
+                        if ui.selectable_label(false, format!("{} ({})", id, example)).clicked() {
+                            if let Some(preset) = MoneyFormat::preset(id) {
+                                *fmt = preset;
+                            }
+                        }
+                    }
+                });
+        });
+
+        ui.horizontal(|ui| {
+            ui.label(t!("symbol-label"));
+            ui.add(egui::TextEdit::singleline(&mut fmt.symbol).desired_width(50.0));
+            if ui.button(t!("use-code")).on_hover_text(t!("show-the-home-currency-code-instead")).clicked() {
+                fmt.symbol = self.rates.home_currency.clone();
+            }
+            ui.radio_value(&mut fmt.position, SymbolPosition::Before, t!("before"));
+            ui.radio_value(&mut fmt.position, SymbolPosition::After, t!("after"));
+            ui.add_enabled(fmt.position == SymbolPosition::Before, egui::Checkbox::new(&mut fmt.space_after_symbol, t!("space-after-symbol")));
+
+            ui.add_space(20.0);
+            ui.label(t!("thousands-label"));
+            egui::ComboBox::from_id_salt("thousands_sep")
+                .width(70.0)
+                .selected_text(separator_label(fmt.thousands_separator))
+                .show_ui(ui, |ui| {
+                    for sep in [Some(','), Some('.'), Some('\u{202F}'), Some('\''), None] {
+                        ui.selectable_value(&mut fmt.thousands_separator, sep, separator_label(sep));
+                    }
+                });
+
+            ui.label(t!("decimal-label"));
+            ui.radio_value(&mut fmt.decimal_separator, '.', t!("point"));
+            ui.radio_value(&mut fmt.decimal_separator, ',', t!("comma"));
+
+            ui.label(t!("decimals-label"));
+            ui.add(egui::DragValue::new(&mut fmt.decimals).range(0..=4));
+        });
+
+        ui.label(t!("format-example", positive = fmt.money(1234567.891), negative = fmt.money(-42.5)));
+
+        if self.money_format != before {
+            self.save_data();
+        }
+    }
+}
@@ -0,0 +1,107 @@
+use chrono::{Duration, Local};
+use eframe::egui;
+use egui::Color32;
+
+use crate::{FinanceApp, i18n, sheets};
+use crate::i18n::t;
+use crate::model::Transaction;
+use crate::settings::SheetsConnection;
+
+fn is_set_up(sheets: &SheetsConnection) -> bool {
+    sheets.key_file.is_some() && !sheets.spreadsheet.trim().is_empty()
+}
+
+impl FinanceApp {
+    // Checks on a running export, once per frame
+    pub fn poll_sheets_export(&mut self) {
+        let Some(result) = self.sheets_export.as_ref().and_then(|export| export.poll()) else { return };
+        self.sheets_export = None;
+        match result {
+            Ok(()) => {
+                tracing::info!("exported to Google Sheets");
+                self.settings.sheets.last_exported = Some(Local::now());
+                self.save_settings();
+                self.sheets_status = None;
+            }
+            Err(e) => {
+                tracing::warn!("Google Sheets export failed: {}", e);
+                self.sheets_status = Some(t!("sheets-export-failed", error = e.to_string()));
+            }
+        }
+    }
+
+    fn start_sheets_export(&mut self, ctx: &egui::Context) {
+        let Some(key_file) = self.settings.sheets.key_file.clone() else { return };
+        let all: Vec<&Transaction> = self.transactions.iter().collect();
+        let report = self.build_report(&all);
+        self.sheets_tried = Some(Local::now());
+        let spreadsheet = match sheets::spreadsheet_id(&self.settings.sheets.spreadsheet) {
+            Ok(id) => id.to_string(),
+            Err(e) => {
+                self.sheets_status = Some(t!("sheets-export-failed", error = e.to_string()));
+                return;
+            }
+        };
+        self.sheets_status = None;
+        self.sheets_export = Some(sheets::start(ctx, key_file, spreadsheet, &report));
+    }
+
+    // The daily export, when it's turned on and a day has passed since the last one
+    pub fn export_to_sheets_if_due(&mut self, ctx: &egui::Context) {
+        let sheets = &self.settings.sheets;
+        if !sheets.daily || !is_set_up(sheets) || self.read_only || self.sheets_export.is_some() {
+            return;
+        }
+        let day_ago = Local::now() - Duration::days(1);
+        let last = sheets.last_exported.max(self.sheets_tried);
+        if last.is_none_or(|last| last < day_ago) {
+            self.start_sheets_export(ctx);
+        }
+    }
+
+    pub fn show_sheets_ui(&mut self, ui: &mut egui::Ui) {
+        ui.heading(t!("sheets-heading"));
+        ui.label(t!("sheets-explained"));
+        let mut changed = false;
+        egui::Grid::new("sheets_connection").num_columns(2).show(ui, |ui| {
+            ui.label(t!("sheets-key-file"));
+            ui.horizontal(|ui| {
+                match &self.settings.sheets.key_file {
+                    Some(path) => ui.monospace(path.display().to_string()),
+                    None => ui.weak(t!("none")),
+                };
+                if ui.button(t!("choose")).clicked() {
+                    if let Some(path) = rfd::FileDialog::new().add_filter("JSON", &["json"]).pick_file() {
+                        self.settings.sheets.key_file = Some(path);
+                        changed = true;
+                    }
+                }
+            });
+            ui.end_row();
+            ui.label(t!("sheets-spreadsheet"));
+            changed |= ui.add(egui::TextEdit::singleline(&mut self.settings.sheets.spreadsheet)
+                .hint_text("https://docs.google.com/spreadsheets/d/…")
+                .desired_width(320.0))
+                .lost_focus();
+            ui.end_row();
+        });
+        changed |= ui.checkbox(&mut self.settings.sheets.daily, t!("sheets-daily")).changed();
+        if changed {
+            self.save_settings();
+        }
+
+        ui.horizontal(|ui| {
+            let label = if self.sheets_export.is_some() { t!("sheets-exporting") } else { t!("sheets-export") };
+            let enabled = is_set_up(&self.settings.sheets) && self.sheets_export.is_none();
+            if ui.add_enabled(enabled, egui::Button::new(label)).clicked() {
+                self.start_sheets_export(ui.ctx());
+            }
+            if let Some(when) = self.settings.sheets.last_exported {
+                ui.weak(t!("sheets-last-exported", date = i18n::date(when.date_naive()), time = when.format("%H:%M").to_string()));
+            }
+        });
+        if let Some(status) = &self.sheets_status {
+            ui.colored_label(Color32::RED, status);
+        }
+    }
+}
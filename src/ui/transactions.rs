@@ -0,0 +1,1963 @@
+use chrono::Local;
+use eframe::egui;
+use egui::{Color32, Sense, Vec2};
+use egui_extras::{Column, TableBuilder};
+use std::collections::BTreeSet;
+use std::path::Path;
+use uuid::Uuid;
+
+use crate::{DESC_FIELD, FinanceApp, QUICK_FIELD, SEARCH_FIELD, Tab, attachments, audit, calc, export, i18n, import, quick_add, receipt, rules, suggest, theme, trash};
+use crate::error::AppError;
+use crate::filter::Filter;
+use crate::i18n::t;
+use crate::import::{ImportFormat, RowProblem};
+use crate::model::{CategoryKind, Split, Template, Transaction, TransactionStatus, TransactionType, category_path, local_from_naive};
+use crate::period::Grouping;
+use crate::quick_add::QuickEntry;
+use crate::ui::{category_picker, period_picker};
+
+// Clicks collected while drawing the transaction list, applied after the loop
+#[derive(Default)]
+pub struct RowActions {
+    pub sort: Option<SortColumn>,
+    pub select: Option<(Uuid, bool)>,
+    // Header checkbox: every row of that table at once
+    pub select_all: Option<(Vec<Uuid>, bool)>,
+    pub toggle_status: Option<Uuid>,
+    pub preview: Option<String>,
+    pub toggle_note: Option<Uuid>,
+    pub edit: Option<Uuid>,
+    pub remove: Option<Uuid>,
+    // Cell being edited in place. Taken out of the app while the list is drawn so the
+    // cells can write to it, and put back afterwards.
+    pub inline: Option<InlineEdit>,
+    // Edit finished with Enter or by clicking away, to be saved
+    pub commit: Option<InlineEdit>,
+}
+
+// A line of the transaction list: a section header when grouping, or a transaction
+pub enum ListRow<'a> {
+    Group { title: String, net: f64 },
+    Item(&'a Transaction),
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum InlineField {
+    Description,
+    Category,
+    Amount,
+}
+
+// A list cell being edited in place after a double-click
+pub struct InlineEdit {
+    pub id: Uuid,
+    pub field: InlineField,
+    pub text: String,
+    pub category: String,
+    pub subcategory: Option<String>,
+    // Focus is moved to the text box once, when editing starts
+    pub focused: bool,
+}
+
+impl InlineEdit {
+    pub fn new(t: &Transaction, field: InlineField) -> Self {
+        let text = match field {
+            InlineField::Description => t.description.clone(),
+            InlineField::Amount => t.amount.to_string(),
+            InlineField::Category => String::new(),
+        };
+        Self {
+            id: t.id,
+            field,
+            text,
+            category: t.category.clone(),
+            subcategory: t.subcategory.clone(),
+            focused: false,
+        }
+    }
+}
+
+// Operations on the selected transactions, waiting for confirmation
+#[derive(Clone, Copy, PartialEq)]
+pub enum BulkAction {
+    Delete,
+    Recategorize,
+}
+
+#[derive(Clone, Copy, PartialEq, Default)]
+pub enum SortColumn {
+    #[default]
+    Date,
+    Category,
+    Description,
+    Amount,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum ExportKind {
+    Csv,
+    Json,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum ReviewChoice {
+    Skip,
+    Merge,
+    KeepBoth,
+}
+
+impl ReviewChoice {
+    pub fn label(&self) -> String {
+        match self {
+            ReviewChoice::Skip => t!("review-skip"),
+            ReviewChoice::Merge => t!("review-merge"),
+            ReviewChoice::KeepBoth => t!("review-keep-both"),
+        }
+    }
+}
+
+pub struct ImportReview {
+    pub incoming: Transaction,
+    pub existing: Uuid,
+    pub choice: ReviewChoice,
+}
+
+// Split editor row in the add/edit form
+#[derive(Clone, Default)]
+pub struct SplitRow {
+    pub category: String,
+    pub subcategory: Option<String>,
+    pub amount: String,
+}
+
+// The amount typed in, None unless it's a positive number. Signs come from the type,
+// so zero or a negative amount is always a mistake. A comma works as the decimal point,
+// see calc.rs.
+fn positive_amount(input: &str) -> Option<f64> {
+    calc::evaluate(input).filter(|a| *a > 0.0)
+}
+
+// Why an amount field can't be used, None while it's empty or fine
+fn amount_problem(input: &str) -> Option<String> {
+    if input.trim().is_empty() || positive_amount(input).is_some() {
+        return None;
+    }
+    Some(t!("amount-must-be-positive"))
+}
+
+// Red outline and the reason next to an amount field amount_problem objects to
+fn show_amount_problem(ui: &mut egui::Ui, field: &egui::Response, problem: String) {
+    let invalid = ui.visuals().error_fg_color;
+    ui.painter().rect_stroke(field.rect, ui.visuals().widgets.inactive.rounding, egui::Stroke::new(1.5, invalid));
+    ui.colored_label(invalid, problem);
+}
+
+impl FinanceApp {
+    // Adds the transaction in the form, or saves the one being edited
+    pub fn submit_form(&mut self) {
+        self.save_form(false);
+    }
+
+    // What the form's currency picker means for the saved transaction
+    fn form_currency(&self) -> Option<String> {
+        if self.input_currency == self.rates.home_currency {
+            None
+        } else {
+            Some(self.input_currency.clone())
+        }
+    }
+
+    // One already in the ledger with the form's day, type, amount, currency and
+    // description, most likely Add clicked twice
+    fn identical_transaction(&self) -> Option<Uuid> {
+        let amount = positive_amount(&self.input_amount)?;
+        let currency = self.form_currency();
+        self.transactions.iter()
+            .find(|existing| {
+                existing.date.date_naive() == self.input_date
+                    && existing.trans_type == self.input_type
+                    && (existing.amount - amount).abs() < 0.005
+                    && existing.currency == currency
+                    && existing.description.trim().eq_ignore_ascii_case(self.input_desc.trim())
+            })
+            .map(|existing| existing.id)
+    }
+
+    // `allow_duplicate` is the warning's Add anyway
+    fn save_form(&mut self, allow_duplicate: bool) {
+        let Some(amount) = positive_amount(&self.input_amount) else {
+            self.error = Some(AppError::BadAmount { field: t!("amount"), value: self.input_amount.clone() });
+            return;
+        };
+        if self.input_desc.trim().is_empty() {
+            self.error = Some(AppError::MissingDescription);
+            return;
+        }
+        let Some(splits) = self.parsed_splits(amount) else {
+            self.error = Some(AppError::SplitsDontAddUp);
+            return;
+        };
+        self.clear_input_error();
+
+        // Handle Time Logic
+        let editing = self.editing_id.and_then(|id| self.transaction_index(id));
+        let time_part = if let Some(idx) = editing {
+            // If editing, preserve the original time of the transaction
+            self.transactions[idx].date.time()
+        } else {
+            // If adding new, use current time
+            Local::now().time()
+        };
+        let full_date_time = local_from_naive(self.input_date.and_time(time_part));
+
+        let new_trans = Transaction {
+            id: self.editing_id.unwrap_or(self.draft_id),
+            description: self.input_desc.clone(),
+            amount,
+            trans_type: self.input_type,
+            category: self.input_category.clone(),
+            subcategory: self.input_subcategory.clone(),
+            currency: self.form_currency(),
+            splits,
+            note: self.input_note.trim().to_string(),
+            attachments: self.input_attachments.clone(),
+            goal: self.input_goal,
+            refund_of: match self.input_type {
+                TransactionType::Income => self.input_refund_of,
+                TransactionType::Expense => None,
+            },
+            // Editing keeps the status set from the list
+            status: editing.map(|idx| self.transactions[idx].status).unwrap_or_default(),
+            date: full_date_time,
+            modified: Some(Local::now()),
+        };
+
+        if let Some(idx) = editing {
+            // UPDATE existing, dropping files for attachments that were removed
+            for name in &self.transactions[idx].attachments {
+                if !new_trans.attachments.contains(name) {
+                    attachments::remove_attachment(name);
+                }
+            }
+            self.transactions[idx] = new_trans;
+            self.editing_id = None; // Exit edit mode
+            self.toasts.info(t!("transaction-updated"));
+        } else {
+            // Held back with a warning the first time; nothing is lost, the form stays filled in
+            if !allow_duplicate {
+                self.duplicate_of = self.identical_transaction();
+                if self.duplicate_of.is_some() {
+                    return;
+                }
+            }
+            // ADD new, unless the script refuses it; the form stays filled in then
+            if let Err(e) = self.add_transaction(new_trans) {
+                self.toasts.error(e);
+                return;
+            }
+            self.toasts.info(t!("transaction-added"));
+        }
+
+        self.clear_inputs();
+        self.save_data();
+
+    }
+
+    // Type and category for a quick entry: a matching rule, else a category named in it,
+    // else whatever the most similar past transaction used, else the default for the type
+    pub fn guess_category(&self, entry: &QuickEntry) -> (TransactionType, String, Option<String>) {
+        if let Some(rule) = rules::Matcher::new(&self.rules).find(&entry.description, Some(entry.amount), entry.trans_type) {
+            let kind = entry.trans_type.or(rule.trans_type).unwrap_or_else(|| {
+                match self.find_category(&rule.category).map(|c| c.kind) {
+                    Some(CategoryKind::Income) => TransactionType::Income,
+                    _ => TransactionType::Expense,
+                }
+            });
+            return (kind, rule.category.clone(), rule.subcategory.clone());
+        }
+        let words: Vec<String> = entry.description.split_whitespace().map(|w| w.to_lowercase()).collect();
+        let fits = |t: TransactionType| entry.trans_type.is_none_or(|wanted| wanted == t);
+
+        for category in &self.categories {
+            let kind = match category.kind {
+                CategoryKind::Income => TransactionType::Income,
+                CategoryKind::Expense | CategoryKind::Both => entry.trans_type.unwrap_or(TransactionType::Expense),
+            };
+            if !fits(kind) {
+                continue;
+            }
+            if let Some(sub) = category.subcategories.iter().find(|s| words.contains(&s.to_lowercase())) {
+                return (kind, category.name.clone(), Some(sub.clone()));
+            }
+            if words.contains(&category.name.to_lowercase()) {
+                return (kind, category.name.clone(), None);
+            }
+        }
+
+        let shared = |t: &Transaction| {
+            let description = t.description.to_lowercase();
+            let theirs: Vec<&str> = description.split_whitespace().collect();
+            words.iter().filter(|w| theirs.contains(&w.as_str())).count()
+        };
+        let similar = self.transactions.iter()
+            .filter(|t| t.splits.is_empty() && fits(t.trans_type))
+            .map(|t| (shared(t), t))
+            .filter(|(count, _)| *count > 0)
+            .max_by(|(a, x), (b, y)| a.cmp(b).then(x.date.cmp(&y.date)));
+        if let Some((_, t)) = similar {
+            return (t.trans_type, t.category.clone(), t.subcategory.clone());
+        }
+
+        let kind = entry.trans_type.unwrap_or(TransactionType::Expense);
+        (kind, self.default_category_for(kind), None)
+    }
+
+    pub fn quick_transaction(&self) -> Option<Transaction> {
+        self.parse_quick(&self.quick_input)
+    }
+
+    // Also what the bot adds from a message
+    pub fn parse_quick(&self, input: &str) -> Option<Transaction> {
+        let entry = quick_add::parse(input, Local::now().date_naive())?;
+        let (trans_type, category, subcategory) = self.guess_category(&entry);
+        Some(Transaction {
+            id: Uuid::new_v4(),
+            description: entry.description,
+            amount: entry.amount,
+            trans_type,
+            category,
+            subcategory,
+            currency: None,
+            splits: Vec::new(),
+            note: String::new(),
+            attachments: Vec::new(),
+            goal: None,
+            status: TransactionStatus::default(),
+            refund_of: None,
+            date: local_from_naive(entry.date.and_time(Local::now().time())),
+            modified: Some(Local::now()),
+        })
+    }
+
+    // One line like "coffee 4.50 yesterday", previewed as it is typed and added on Enter
+    pub fn show_quick_add(&mut self, ui: &mut egui::Ui) {
+        let preview = self.quick_transaction();
+        let mut add = false;
+        ui.horizontal(|ui| {
+            ui.label(t!("quick-add-label"));
+            let response = ui.add(egui::TextEdit::singleline(&mut self.quick_input)
+                .id(egui::Id::new(QUICK_FIELD))
+                .hint_text(t!("quick-add-hint"))
+                .desired_width(250.0));
+            if response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter)) {
+                add = true;
+            }
+
+            match &preview {
+                Some(t) => {
+                    ui.label("→");
+                    ui.label(i18n::date(t.date.date_naive()));
+                    ui.strong(&t.description);
+                    let (symbol, color) = match t.trans_type {
+                        TransactionType::Income => ("+", theme::income(ui.visuals())),
+                        TransactionType::Expense => ("-", theme::expense(ui.visuals())),
+                    };
+                    ui.colored_label(color, format!("{}{}", symbol, self.money(t.amount)));
+                    self.category_chip(ui, &t.category);
+                    ui.label(t.category_path());
+                    if ui.button(t!("add")).on_hover_text("Enter").clicked() {
+                        add = true;
+                    }
+                }
+                None if !self.quick_input.trim().is_empty() => {
+                    ui.weak(t!("quick-add-incomplete"));
+                }
+                None => {}
+            }
+        });
+
+        if let (true, Some(transaction)) = (add, preview) {
+            match self.add_transaction(transaction) {
+                Ok(()) => {
+                    self.toasts.info(t!("transaction-added"));
+                    self.quick_input.clear();
+                    self.save_data();
+                }
+                Err(e) => self.toasts.error(e),
+            }
+            ui.memory_mut(|m| m.request_focus(egui::Id::new(QUICK_FIELD)));
+        }
+    }
+
+    // Past descriptions like the one being typed, in a list under the field. Up/Down and
+    // Enter or a click pick one. Returns true when Enter picked one rather than submitting.
+    pub fn show_description_suggestions(&mut self, ui: &mut egui::Ui, desc: &egui::Response) -> bool {
+        let popup = egui::Id::new("desc_suggestions");
+        let open = ui.memory(|m| m.is_popup_open(popup));
+        // Only for new ones, an edit shouldn't have its category swapped under it
+        if self.editing_id.is_some() || !(desc.has_focus() || desc.lost_focus() || open) {
+            return false;
+        }
+        let suggestions = suggest::suggestions(&self.transactions, &self.input_desc, Local::now(), 8);
+        let highlight_id = popup.with("highlight");
+        let mut highlight: Option<usize> = ui.data(|d| d.get_temp(highlight_id)).flatten();
+        if desc.changed() {
+            highlight = None;
+            ui.memory_mut(|m| if suggestions.is_empty() { m.close_popup() } else { m.open_popup(popup) });
+        }
+        if suggestions.is_empty() {
+            return false;
+        }
+        if desc.has_focus() {
+            if ui.input(|i| i.key_pressed(egui::Key::ArrowDown)) {
+                highlight = Some(highlight.map_or(0, |h| (h + 1).min(suggestions.len() - 1)));
+                ui.memory_mut(|m| m.open_popup(popup));
+            }
+            if ui.input(|i| i.key_pressed(egui::Key::ArrowUp)) {
+                highlight = highlight.and_then(|h| h.checked_sub(1));
+            }
+        }
+
+        let mut chosen = None;
+        if desc.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter)) && open {
+            chosen = highlight;
+        }
+        egui::popup_below_widget(ui, popup, desc, egui::PopupCloseBehavior::CloseOnClickOutside, |ui| {
+            for (index, s) in suggestions.iter().enumerate() {
+                let text = format!("{}   ·   {} · {} · ×{}", s.description, category_path(&s.category, s.subcategory.as_deref()),
+                    self.money(s.amount), s.uses);
+                if ui.selectable_label(highlight == Some(index), text).clicked() {
+                    chosen = Some(index);
+                }
+            }
+        });
+        ui.data_mut(|d| d.insert_temp(highlight_id, highlight));
+
+        let Some(index) = chosen else {
+            return false;
+        };
+        self.apply_suggestion(suggestions[index].clone());
+        ui.memory_mut(|m| {
+            m.close_popup();
+            m.request_focus(egui::Id::new(DESC_FIELD));
+        });
+        ui.data_mut(|d| d.remove::<Option<usize>>(highlight_id));
+        true
+    }
+
+    // A matching rule files a new transaction once its description and amount are typed
+    pub fn apply_rules_to_form(&mut self) {
+        if self.editing_id.is_some() || !self.input_splits.is_empty() {
+            return;
+        }
+        let amount = calc::evaluate(&self.input_amount);
+        if let Some(rule) = rules::Matcher::new(&self.rules).find(&self.input_desc, amount, Some(self.input_type)) {
+            self.input_category = rule.category.clone();
+            self.input_subcategory = rule.subcategory.clone();
+        }
+    }
+
+    // Fills the form the way this description is usually entered
+    pub fn apply_suggestion(&mut self, suggestion: suggest::Suggestion) {
+        self.input_desc = suggestion.description;
+        self.input_amount = suggestion.amount.to_string();
+        self.input_type = suggestion.trans_type;
+        self.input_category = suggestion.category;
+        self.input_subcategory = suggestion.subcategory;
+        self.input_currency = suggestion.currency.unwrap_or_else(|| self.rates.home_currency.clone());
+        self.input_splits.clear();
+    }
+
+    // Under the form after Add, when the transaction is already in the ledger
+    fn show_duplicate_warning(&mut self, ui: &mut egui::Ui) {
+        let Some(id) = self.duplicate_of else { return };
+        // Gone once the form no longer matches it, by the same test Add used
+        let Some(existing) = self.identical_transaction()
+            .filter(|found| *found == id)
+            .and_then(|found| self.transaction_index(found))
+            .map(|i| &self.transactions[i])
+        else {
+            self.duplicate_of = None;
+            return;
+        };
+        let warning = t!(
+            "duplicate-warning",
+            description = existing.description.clone(),
+            amount = self.money(existing.amount),
+            date = i18n::date(existing.date.date_naive()),
+        );
+        let mut add_anyway = false;
+        ui.horizontal(|ui| {
+            ui.colored_label(theme::warning(ui.visuals()), format!("⚠ {}", warning));
+            add_anyway = ui.button(t!("add-anyway")).clicked();
+            if ui.small_button("✖").clicked() {
+                self.duplicate_of = None;
+            }
+        });
+        if add_anyway {
+            self.save_form(true);
+        }
+    }
+
+    pub fn cancel_edit(&mut self) {
+        self.editing_id = None;
+        self.clear_inputs();
+    }
+
+    pub fn show_transactions_ui(&mut self, ui: &mut egui::Ui) {
+        // Change header based on mode
+        if self.editing_id.is_some() {
+            ui.heading(t!("edit-transaction"));
+        } else {
+            self.show_quick_add(ui);
+            ui.separator();
+            ui.heading(t!("add-new-transaction"));
+            self.show_template_bar(ui);
+        }
+        let mut submit = false;
+        
+        ui.horizontal(|ui| {
+            ui.label(t!("date-label"));
+            ui.add(egui_extras::DatePickerButton::new(&mut self.input_date));
+            
+            ui.add_space(10.0);
+            
+            ui.label(t!("description-label"));
+            let desc = ui.add(egui::TextEdit::singleline(&mut self.input_desc).id(egui::Id::new(DESC_FIELD)));
+            let picked = self.show_description_suggestions(ui, &desc);
+            ui.label(t!("amount-label"));
+            // Checked as it's typed, rather than only when Add is clicked
+            let problem = amount_problem(&self.input_amount);
+            let invalid = ui.visuals().error_fg_color;
+            let amount = ui.add(egui::TextEdit::singleline(&mut self.input_amount)
+                .hint_text("12.99+4.50*2")
+                .text_color_opt(problem.as_ref().map(|_| invalid)));
+            match problem {
+                Some(problem) => show_amount_problem(ui, &amount, problem),
+                None if calc::is_expression(&self.input_amount) => {
+                    if let Some(value) = calc::evaluate(&self.input_amount) {
+                        ui.weak(format!("= {}", self.money_format.number(value)));
+                    }
+                }
+                None => {}
+            }
+            if !picked && (desc.lost_focus() || amount.lost_focus()) {
+                self.apply_rules_to_form();
+                // Enter in either field submits, like clicking Add/Update
+                if ui.input(|i| i.key_pressed(egui::Key::Enter)) {
+                    submit = true;
+                }
+            }
+
+            egui::ComboBox::from_id_salt("currency_dropdown")
+                .width(60.0)
+                .selected_text(self.input_currency.clone())
+                .show_ui(ui, |ui| {
+                    for code in self.rates.currencies() {
+                        let label = code.clone();
+                        ui.selectable_value(&mut self.input_currency, code, label);
+                    }
+                });
+        });
+
+        ui.horizontal(|ui| {
+            if ui.radio_value(&mut self.input_type, TransactionType::Income, t!("income")).clicked() {
+                 self.input_category = self.default_category_for(TransactionType::Income);
+                 self.input_subcategory = None;
+                 self.input_splits.clear();
+            }
+            if ui.radio_value(&mut self.input_type, TransactionType::Expense, t!("expense")).clicked() {
+                 self.input_category = self.default_category_for(TransactionType::Expense);
+                 self.input_subcategory = None;
+                 self.input_splits.clear();
+            }
+
+            ui.add_space(20.0);
+            ui.label(t!("category-label"));
+            
+            category_picker(ui, "cat_dropdown", &self.categories, self.input_type,
+                &mut self.input_category, &mut self.input_subcategory);
+
+            if ui.button(t!("split")).clicked() {
+                // Start with the selected category so the first row covers the whole amount
+                if self.input_splits.is_empty() {
+                    self.input_splits.push(SplitRow {
+                        category: self.input_category.clone(),
+                        subcategory: self.input_subcategory.clone(),
+                        amount: self.input_amount.trim().to_string(),
+                    });
+                }
+                self.input_splits.push(SplitRow {
+                    category: self.input_category.clone(),
+                    ..Default::default()
+                });
+            }
+
+            ui.add_space(20.0);
+            
+            // Dynamic Button Text (Add vs Update)
+            let btn_text = if self.editing_id.is_some() { t!("update") } else { t!("add") };
+
+            if ui.button(btn_text).on_hover_text("Enter").clicked() {
+                submit = true;
+            }
+
+            // Cancel Button (only visible when editing)
+            if self.editing_id.is_some() && ui.button(t!("cancel")).on_hover_text("Esc").clicked() {
+                self.cancel_edit();
+            }
+        });
+        if submit {
+            self.submit_form();
+        }
+        self.show_duplicate_warning(ui);
+
+        ui.horizontal(|ui| {
+            ui.label(t!("note-label"));
+            ui.add(egui::TextEdit::multiline(&mut self.input_note)
+                .desired_rows(2)
+                .desired_width(f32::INFINITY)
+                .hint_text(t!("optional-details")));
+        });
+
+        ui.horizontal(|ui| {
+            if ui.button(t!("attach-receipt")).clicked() {
+                for path in attachments::pick_images() {
+                    let owner = self.editing_id.unwrap_or(self.draft_id);
+                    match attachments::import_attachment(&path, owner) {
+                        Ok(name) => self.input_attachments.push(name),
+                        Err(e) => self.attachment_error = Some(t!("attach-failed", path = path.display().to_string(), error = e.to_string())),
+                    }
+                }
+            }
+
+            let label = if self.receipt_scan.is_some() { t!("receipt-reading") } else { t!("scan-receipt") };
+            let scan = ui.add_enabled(self.receipt_scan.is_none(), egui::Button::new(label))
+                .on_hover_text(t!("scan-receipt-hint"));
+            if scan.clicked() {
+                self.scan_receipt(ui.ctx());
+            }
+
+            let mut to_detach = None;
+            for (i, name) in self.input_attachments.iter().enumerate() {
+                ui.add(egui::Image::new(attachments::attachment_uri(name)).max_size(Vec2::splat(24.0)));
+                if ui.small_button("✖").on_hover_text(name).clicked() {
+                    to_detach = Some(i);
+                }
+            }
+            if let Some(i) = to_detach {
+                self.input_attachments.remove(i);
+            }
+
+            if !self.goals.is_empty() {
+                ui.add_space(20.0);
+                ui.label(t!("goal-label"));
+                let selected = self.input_goal
+                    .and_then(|id| self.goals.iter().find(|g| g.id == id))
+                    .map(|g| g.name.clone())
+                    .unwrap_or_else(|| t!("none"));
+                egui::ComboBox::from_id_salt("goal_dropdown")
+                    .selected_text(selected)
+                    .show_ui(ui, |ui| {
+                        ui.selectable_value(&mut self.input_goal, None, t!("none"));
+                        for goal in &self.goals {
+                            ui.selectable_value(&mut self.input_goal, Some(goal.id), &goal.name);
+                        }
+                    });
+            }
+
+            if self.input_type == TransactionType::Income {
+                ui.add_space(20.0);
+                ui.label(t!("refund-of-label"));
+                self.refund_picker(ui);
+            }
+
+            if let Some(error) = &self.attachment_error {
+                ui.colored_label(Color32::RED, error);
+            }
+            if let Some(status) = &self.receipt_status {
+                ui.label(status);
+            }
+        });
+
+        if !self.input_splits.is_empty() {
+            self.show_split_editor(ui);
+        }
+        ui.separator();
+
+        let (book_balance, cleared_balance) = (self.balance(false), self.balance(true));
+
+        ui.horizontal(|ui| {
+            ui.heading(t!("book-balance", amount = self.money(book_balance)));
+            ui.add_space(20.0);
+            ui.heading(t!("cleared-balance", amount = self.money(cleared_balance)));
+        });
+        self.show_period_navigator(ui);
+
+        ui.horizontal(|ui| {
+            ui.label(t!("import-label"));
+            if let Some(task) = &self.import_task {
+                ui.label(t!("importing", format = task.format.label()));
+                ui.add(egui::ProgressBar::new(task.progress).show_percentage().desired_width(200.0));
+                if ui.add_enabled(!task.is_cancelled(), egui::Button::new(t!("cancel"))).clicked() {
+                    task.cancel();
+                }
+            } else {
+                for format in [ImportFormat::Ynab, ImportFormat::Mint] {
+                    if ui.button(t!("import-csv", format = format.label())).clicked() {
+                        if let Some(path) = import::pick_csv() {
+                            self.import_csv(ui.ctx(), &path, format);
+                        }
+                    }
+                }
+            }
+            if let Some(status) = &self.import_status {
+                ui.label(status);
+            }
+        });
+        if !self.import_errors.is_empty() {
+            egui::CollapsingHeader::new(t!("import-errors", count = self.import_errors.len()))
+                .id_salt("import_errors")
+                .show(ui, |ui| {
+                    egui::ScrollArea::vertical().max_height(120.0).show(ui, |ui| {
+                        for error in &self.import_errors {
+                            ui.weak(error);
+                        }
+                    });
+                });
+        }
+
+        ui.horizontal(|ui| {
+            ui.label("🔍");
+            ui.add(egui::TextEdit::singleline(&mut self.search_query)
+                .id(egui::Id::new(SEARCH_FIELD))
+                .hint_text(t!("search-payee-note-category-or-amount")));
+
+            let shown = self.transactions.iter().filter(|t| self.is_shown(t)).count();
+            if !self.search_query.trim().is_empty() {
+                ui.label(t!("search-matches", shown = shown, total = self.transactions.len()));
+                if ui.small_button("✖").on_hover_text(t!("clear-search")).clicked() {
+                    self.search_query.clear();
+                }
+            }
+            ui.menu_button(t!("export-shown", count = shown), |ui| {
+                if ui.button(t!("csv")).clicked() {
+                    ui.close_menu();
+                    self.export_shown(ExportKind::Csv);
+                }
+                if ui.button(t!("json")).clicked() {
+                    ui.close_menu();
+                    self.export_shown(ExportKind::Json);
+                }
+            });
+            if ui.button(t!("copy-shown", count = shown)).on_hover_text(t!("copy-hint")).clicked() {
+                let ids: BTreeSet<Uuid> = self.transactions.iter().filter(|t| self.is_shown(t)).map(|t| t.id).collect();
+                self.copy_transactions(ui.ctx(), &ids);
+            }
+            if let Some(status) = &self.export_status {
+                ui.label(status);
+            }
+            ui.toggle_value(&mut self.show_trash, t!("trash-toggle", count = self.trash.len()));
+        });
+        
+        self.show_bulk_bar(ui);
+        if let Some(status) = &self.bulk_status {
+            ui.label(status);
+        }
+        if self.transactions.is_empty() {
+            self.show_onboarding(ui);
+        } else {
+            self.show_transaction_list(ui);
+        }
+        self.show_bulk_confirm(ui.ctx());
+        self.show_trash_window(ui.ctx());
+        self.show_attachment_viewer(ui.ctx());
+        self.show_import_review(ui.ctx());
+    }
+
+    pub fn show_transaction_list(&mut self, ui: &mut egui::Ui) {
+        self.show_summary_strip(ui);
+
+        let mut actions = RowActions { inline: self.inline_edit.take(), ..Default::default() };
+
+        // Future-dated transactions don't count yet, list them separately, soonest first
+        let mut upcoming: Vec<&Transaction> = self.transactions.iter()
+            .filter(|t| t.is_scheduled() && self.is_shown(t))
+            .collect();
+        upcoming.sort_by_key(|t| t.date);
+
+        if !upcoming.is_empty() {
+            egui::CollapsingHeader::new(t!("upcoming", count = upcoming.len()))
+                .default_open(true)
+                .show(ui, |ui| {
+                    self.transaction_table(ui, "upcoming_table", &upcoming, false, Some(150.0), &mut actions);
+                });
+            ui.separator();
+        }
+
+        let mut rows: Vec<&Transaction> = self.transactions.iter()
+            .filter(|t| !t.is_scheduled() && self.is_shown(t))
+            .collect();
+        self.sort_rows(&mut rows);
+        // Groups follow the date order, newest first unless sorted oldest first. Within a
+        // group the chosen sort still applies, since the sort is stable.
+        if self.grouping != Grouping::None {
+            let oldest_first = self.sort_column == SortColumn::Date && self.sort_ascending;
+            rows.sort_by(|a, b| {
+                let (a, b) = (self.grouping.key(a.date.date_naive()), self.grouping.key(b.date.date_naive()));
+                if oldest_first { a.cmp(&b) } else { b.cmp(&a) }
+            });
+        }
+        self.transaction_table(ui, "transaction_table", &rows, true, None, &mut actions);
+
+        self.apply_row_actions(actions);
+    }
+
+    // Whether the transaction passes the current search and filter
+    pub fn is_shown(&self, t: &Transaction) -> bool {
+        self.period.contains(t.date.date_naive())
+            && t.matches_search(&self.search_query)
+            && self.filter.matches(t, self.home_amount(t))
+    }
+
+    // "< December 2024 >", the stretch of time the list shows
+    pub fn show_period_navigator(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            period_picker(ui, &mut self.period);
+
+            ui.separator();
+            ui.label(t!("group-by-label"));
+            egui::ComboBox::from_id_salt("grouping")
+                .selected_text(self.grouping.label())
+                .show_ui(ui, |ui| {
+                    for grouping in Grouping::ALL {
+                        ui.selectable_value(&mut self.grouping, grouping, grouping.label());
+                    }
+                });
+        });
+    }
+
+    // Totals of exactly what the list shows: period, filters and search all apply
+    pub fn show_summary_strip(&self, ui: &mut egui::Ui) {
+        let (mut count, mut income, mut expenses) = (0, 0.0, 0.0);
+        for t in self.transactions.iter().filter(|t| self.is_shown(t)) {
+            count += 1;
+            match t.trans_type {
+                TransactionType::Income => income += self.home_amount(t),
+                TransactionType::Expense => expenses += self.home_amount(t),
+            }
+        }
+        let net = income - expenses;
+        egui::Frame::group(ui.style()).show(ui, |ui| {
+            ui.horizontal(|ui| {
+                ui.label(t!("summary-count", count = count));
+                ui.separator();
+                ui.colored_label(theme::income(ui.visuals()), t!("period-income", amount = self.money(income)));
+                ui.separator();
+                ui.colored_label(theme::expense(ui.visuals()), t!("period-expenses", amount = self.money(expenses)));
+                ui.separator();
+                let color = if net < 0.0 { theme::expense(ui.visuals()) } else { theme::income(ui.visuals()) };
+                ui.label(egui::RichText::new(t!("period-net", amount = self.money(net))).color(color).strong());
+            });
+        });
+    }
+
+    pub fn show_filter_panel(&mut self, ctx: &egui::Context) {
+        let visible = self.show_filters && matches!(self.current_tab, Tab::Transactions | Tab::Graph);
+        egui::SidePanel::left("filter_panel").resizable(false).show_animated(ctx, visible, |ui| {
+            ui.heading(t!("filters"));
+            let today = Local::now().date_naive();
+
+            ui.label(t!("date-range"));
+            for (label, bound) in [("from", &mut self.filter.from), ("to", &mut self.filter.to)] {
+                ui.horizontal(|ui| {
+                    let mut enabled = bound.is_some();
+                    if ui.checkbox(&mut enabled, t!(&format!("filter-{}", label))).changed() {
+                        *bound = if enabled { Some(today) } else { None };
+                    }
+                    if let Some(date) = bound {
+                        ui.add(egui_extras::DatePickerButton::new(date).id_salt(label));
+                    }
+                });
+            }
+            ui.horizontal_wrapped(|ui| {
+                if ui.small_button(t!("this-week")).clicked() {
+                    let (first, _) = self.settings.week_containing(today);
+                    self.filter.set_range(first, today);
+                }
+                if ui.small_button(t!("this-month")).clicked() {
+                    let (first, _) = self.settings.month_containing(today);
+                    self.filter.set_range(first, today);
+                }
+                if ui.small_button(t!("last-month")).clicked() {
+                    let (first, last) = self.settings.previous_month(today);
+                    self.filter.set_range(first, last);
+                }
+                if ui.small_button(t!("this-year")).clicked() {
+                    self.filter.this_year(today);
+                }
+            });
+            ui.separator();
+
+            ui.label(t!("type"));
+            ui.horizontal(|ui| {
+                ui.radio_value(&mut self.filter.trans_type, None, t!("all"));
+                ui.radio_value(&mut self.filter.trans_type, Some(TransactionType::Income), t!("income"));
+                ui.radio_value(&mut self.filter.trans_type, Some(TransactionType::Expense), t!("expense"));
+            });
+            ui.separator();
+
+            ui.label(t!("amount"));
+            ui.horizontal(|ui| {
+                ui.add(egui::TextEdit::singleline(&mut self.filter.min_amount).hint_text(t!("min")).desired_width(60.0));
+                ui.label("–");
+                ui.add(egui::TextEdit::singleline(&mut self.filter.max_amount).hint_text(t!("max")).desired_width(60.0));
+            });
+            ui.separator();
+
+            ui.label(t!("categories"));
+            ui.horizontal_wrapped(|ui| {
+                for cat in &self.categories {
+                    let selected = self.filter.categories.contains(&cat.name);
+                    let text = egui::RichText::new(format!("{} {}", cat.icon, cat.name).trim().to_string())
+                        .color(cat.color32());
+                    if ui.selectable_label(selected, text).clicked() {
+                        if selected {
+                            self.filter.categories.remove(&cat.name);
+                        } else {
+                            self.filter.categories.insert(cat.name.clone());
+                        }
+                    }
+                }
+            });
+            ui.separator();
+
+            if ui.add_enabled(self.filter.is_active(), egui::Button::new(t!("clear-filters"))).clicked() {
+                self.filter = Filter::default();
+            }
+        });
+    }
+
+    // Writes the transactions currently listed (search applied) to CSV or JSON
+    pub fn export_shown(&mut self, kind: ExportKind) {
+        let shown: Vec<&Transaction> = self.transactions.iter().filter(|t| self.is_shown(t)).collect();
+        let (filter, extension) = match kind {
+            ExportKind::Csv => ("CSV", "csv"),
+            ExportKind::Json => ("JSON", "json"),
+        };
+        let Some(path) = export::pick_target(filter, extension, &format!("transactions.{}", extension)) else { return };
+
+        let result = match kind {
+            ExportKind::Csv => export::write_csv(&path, &self.build_report(&shown)),
+            ExportKind::Json => export::write_json(&path, &shown),
+        };
+        self.export_status = Some(match result {
+            Ok(()) => t!("exported", count = shown.len(), path = path.display().to_string()),
+            Err(e) => t!("export-failed", error = e),
+        });
+    }
+
+    pub fn copy_transactions(&mut self, ctx: &egui::Context, ids: &BTreeSet<Uuid>) {
+        let rows: Vec<&Transaction> = self.transactions.iter().filter(|t| ids.contains(&t.id)).collect();
+        let text = export::to_tsv(&self.build_report(&rows), self.money_format.decimal_separator);
+        ctx.copy_text(text);
+        self.toasts.info(t!("copied", count = rows.len()));
+    }
+
+    // Sorts by the column picked in the table header
+    pub fn sort_rows(&self, rows: &mut [&Transaction]) {
+        match self.sort_column {
+            SortColumn::Date => rows.sort_by_key(|t| t.date),
+            SortColumn::Category => rows.sort_by_cached_key(|t| t.category_path().to_lowercase()),
+            SortColumn::Description => rows.sort_by_cached_key(|t| t.description.to_lowercase()),
+            SortColumn::Amount => rows.sort_by(|a, b| self.signed_amount(a).total_cmp(&self.signed_amount(b))),
+        }
+        if !self.sort_ascending {
+            rows.reverse();
+        }
+    }
+
+    pub fn transaction_table(
+        &self,
+        ui: &mut egui::Ui,
+        id_salt: &str,
+        rows: &[&Transaction],
+        sortable: bool,
+        max_height: Option<f32>,
+        actions: &mut RowActions,
+    ) {
+        // The main list gets section headers when grouping; the upcoming one stays flat
+        let grouping = if sortable { self.grouping } else { Grouping::None };
+        let mut entries = Vec::new();
+        let mut current = None;
+        let mut header = None;
+        for t in rows {
+            let key = grouping.key(t.date.date_naive());
+            if let Some(day) = key.filter(|_| key != current) {
+                header = Some(entries.len());
+                entries.push(ListRow::Group { title: grouping.title(day), net: 0.0 });
+                current = key;
+            }
+            if let Some(ListRow::Group { net, .. }) = header.and_then(|i| entries.get_mut(i)) {
+                *net += self.signed_amount(t);
+            }
+            entries.push(ListRow::Item(t));
+        }
+
+        // Header of the group at the top of the scrolled list, as of the last frame,
+        // so it stays in view while scrolling through a long day or month
+        let first_row_id = egui::Id::new((id_salt, "first_row"));
+        if grouping != Grouping::None {
+            let first_row: usize = ui.data(|d| d.get_temp(first_row_id)).unwrap_or(0);
+            let top = entries[..entries.len().min(first_row + 1)].iter().rev().find_map(|entry| match entry {
+                ListRow::Group { title, net } => Some((title, *net)),
+                ListRow::Item(_) => None,
+            });
+            ui.horizontal(|ui| {
+                if let Some((title, net)) = top {
+                    ui.strong(title);
+                    ui.weak(self.money(net));
+                }
+            });
+        }
+        let row_height = 24.0;
+        let mut table = TableBuilder::new(ui)
+            .id_salt(id_salt)
+            .striped(true)
+            .resizable(true)
+            .cell_layout(egui::Layout::left_to_right(egui::Align::Center))
+            .column(Column::exact(24.0))
+            .column(Column::exact(24.0))
+            .column(Column::initial(120.0).at_least(80.0))
+            .column(Column::initial(150.0).at_least(60.0).clip(true))
+            .column(Column::remainder().at_least(120.0).clip(true))
+            .column(Column::initial(110.0).at_least(70.0))
+            .column(Column::exact(64.0));
+        if let Some(height) = max_height {
+            table = table.max_scroll_height(height);
+        }
+
+        let drawn_first = std::cell::Cell::new(None);
+
+        let columns = [
+            (SortColumn::Date, t!("column-date")),
+            (SortColumn::Category, t!("column-category")),
+            (SortColumn::Description, t!("column-description")),
+            (SortColumn::Amount, t!("column-amount")),
+        ];
+        table
+            .header(22.0, |mut header| {
+                header.col(|ui| {
+                    let mut all = !rows.is_empty() && rows.iter().all(|t| self.selected.contains(&t.id));
+                    if ui.checkbox(&mut all, "").on_hover_text(t!("select-all")).changed() {
+                        actions.select_all = Some((rows.iter().map(|t| t.id).collect(), all));
+                    }
+                });
+                header.col(|_| {});
+                for (column, title) in columns {
+                    header.col(|ui| {
+                        let arrow = match (sortable && self.sort_column == column, self.sort_ascending) {
+                            (true, true) => " ▲",
+                            (true, false) => " ▼",
+                            (false, _) => "",
+                        };
+                        let text = egui::RichText::new(format!("{}{}", title, arrow)).strong();
+                        let layout = if column == SortColumn::Amount {
+                            egui::Layout::right_to_left(egui::Align::Center)
+                        } else {
+                            egui::Layout::left_to_right(egui::Align::Center)
+                        };
+                        ui.with_layout(layout, |ui| {
+                            if sortable {
+                                if ui.add(egui::Label::new(text).sense(Sense::click())).clicked() {
+                                    actions.sort = Some(column);
+                                }
+                            } else {
+                                ui.label(text);
+                            }
+                        });
+                    });
+                }
+                header.col(|_| {});
+            })
+            .body(|body| {
+                // Only the rows scrolled into view are laid out, so long ledgers stay smooth
+                let heights = entries.iter().map(|entry| match entry {
+                    ListRow::Item(t) if self.expanded_note == Some(t.id) => row_height * 2.0,
+                    _ => row_height,
+                });
+                body.heterogeneous_rows(heights, |mut row| {
+                    if drawn_first.get().is_none() {
+                        drawn_first.set(Some(row.index()));
+                    }
+                    let t = match &entries[row.index()] {
+                        ListRow::Item(t) => *t,
+                        ListRow::Group { title, net } => {
+                            self.group_header_row(&mut row, title, *net);
+                            return;
+                        }
+                    };
+                    let expanded = self.expanded_note == Some(t.id);
+                    row.col(|ui| {
+                        let mut checked = self.selected.contains(&t.id);
+                        if ui.checkbox(&mut checked, "").changed() {
+                            actions.select = Some((t.id, checked));
+                        }
+                    });
+                    row.col(|ui| {
+                        if ui.small_button(t.status.icon()).on_hover_text(t.status.label()).clicked() {
+                            actions.toggle_status = Some(t.id);
+                        }
+                    });
+                    row.col(|ui| {
+                        ui.label(i18n::date_time(&t.date));
+                    });
+                    // Double-clicking the category, description or amount edits it in
+                    // place. Split transactions keep using the form for those two.
+                    row.col(|ui| {
+                        if self.inline_cell(ui, t, InlineField::Category, actions) {
+                            return;
+                        }
+                        self.category_chip(ui, &t.category);
+                        let color = theme::readable(self.category_color(&t.category), ui.visuals());
+                        let label = egui::Label::new(egui::RichText::new(t.category_path()).color(color)).sense(Sense::click());
+                        if ui.add(label).double_clicked() && t.splits.is_empty() {
+                            actions.inline = Some(InlineEdit::new(t, InlineField::Category));
+                        }
+                    });
+                    row.col(|ui| {
+                        if self.inline_cell(ui, t, InlineField::Description, actions) {
+                            return;
+                        }
+                        ui.vertical(|ui| {
+                            ui.horizontal(|ui| self.description_cell(ui, t, actions));
+                            if expanded {
+                                ui.label(egui::RichText::new(&t.note).italics());
+                            }
+                        });
+                    });
+                    row.col(|ui| {
+                        ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                            if self.inline_cell(ui, t, InlineField::Amount, actions) {
+                                return;
+                            }
+                            let (symbol, color) = match t.trans_type {
+                                TransactionType::Income => ("+", theme::income(ui.visuals())),
+                                TransactionType::Expense => ("-", theme::expense(ui.visuals())),
+                            };
+                            let amount = match &t.currency {
+                                Some(code) => format!("{}{} {}", symbol, self.money_format.number(t.amount), code),
+                                None => format!("{}{}", symbol, self.money(t.amount)),
+                            };
+                            let label = egui::Label::new(egui::RichText::new(amount).color(color)).sense(Sense::click());
+                            if ui.add(label).double_clicked() && t.splits.is_empty() {
+                                actions.inline = Some(InlineEdit::new(t, InlineField::Amount));
+                            }
+                        });
+                    });
+                    row.col(|ui| {
+                        if ui.button("✏").clicked() {
+                            actions.edit = Some(t.id);
+                        }
+                        if ui.button("🗑").clicked() {
+                            actions.remove = Some(t.id);
+                        }
+                    });
+                });
+            });
+        ui.data_mut(|d| d.insert_temp(first_row_id, drawn_first.get().unwrap_or(0)));
+    }
+
+    pub fn group_header_row(&self, row: &mut egui_extras::TableRow, title: &str, net: f64) {
+        row.col(|_| {});
+        row.col(|_| {});
+        row.col(|ui| {
+            ui.strong(title);
+        });
+        row.col(|_| {});
+        row.col(|_| {});
+        row.col(|ui| {
+            ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                let color = if net < 0.0 { theme::expense(ui.visuals()) } else { theme::income(ui.visuals()) };
+                ui.label(egui::RichText::new(self.money(net)).color(color).strong());
+            });
+        });
+        row.col(|_| {});
+    }
+
+    // Description followed by receipt thumbnails, refund marker and note indicator
+    pub fn description_cell(&self, ui: &mut egui::Ui, t: &Transaction, actions: &mut RowActions) {
+        if ui.add(egui::Label::new(&t.description).sense(Sense::click())).double_clicked() {
+            actions.inline = Some(InlineEdit::new(t, InlineField::Description));
+        }
+
+        // Receipt thumbnails, click for the full-size viewer
+        for name in &t.attachments {
+            let thumb = egui::Image::new(attachments::attachment_uri(name)).max_size(Vec2::splat(20.0));
+            if ui.add(egui::ImageButton::new(thumb)).on_hover_text(name).clicked() {
+                actions.preview = Some(name.clone());
+            }
+        }
+
+        if let Some(original) = t.refund_of.and_then(|id| self.transaction_index(id)) {
+            ui.label("↩").on_hover_text(t!("refund-of", description = self.transactions[original].description.clone()));
+        }
+
+        // Note indicator: hover to peek, click to expand
+        if !t.note.is_empty() && ui.small_button("📝").on_hover_text(&t.note).clicked() {
+            actions.toggle_note = Some(t.id);
+        }
+    }
+
+    // Draws the editor if this cell is the one being edited. Enter or clicking away
+    // commits, Esc cancels.
+    pub fn inline_cell(&self, ui: &mut egui::Ui, t: &Transaction, field: InlineField, actions: &mut RowActions) -> bool {
+        let Some(edit) = actions.inline.as_mut().filter(|e| e.id == t.id && e.field == field) else {
+            return false;
+        };
+        if field == InlineField::Category {
+            category_picker(ui, ("inline_category", t.id), &self.categories, t.trans_type,
+                &mut edit.category, &mut edit.subcategory);
+            if edit.category != t.category || edit.subcategory != t.subcategory {
+                actions.commit = actions.inline.take();
+            } else if ui.small_button("✖").on_hover_text("Esc").clicked()
+                || ui.input(|i| i.key_pressed(egui::Key::Escape))
+            {
+                actions.inline = None;
+            }
+            return true;
+        }
+
+        let problem = (field == InlineField::Amount).then(|| amount_problem(&edit.text)).flatten();
+        let invalid = ui.visuals().error_fg_color;
+        let response = ui.add(egui::TextEdit::singleline(&mut edit.text)
+            .desired_width(if problem.is_some() { 80.0 } else { f32::INFINITY })
+            .text_color_opt(problem.as_ref().map(|_| invalid)));
+        if !edit.focused {
+            response.request_focus();
+            edit.focused = true;
+        }
+        let has_problem = problem.is_some();
+        if let Some(problem) = problem {
+            show_amount_problem(ui, &response, problem);
+        }
+        if response.lost_focus() {
+            if ui.input(|i| i.key_pressed(egui::Key::Escape)) {
+                actions.inline = None;
+            } else if has_problem {
+                // Stays open until it's fixed or Esc'd, like the form won't add it
+                edit.focused = false;
+            } else {
+                actions.commit = actions.inline.take();
+            }
+        }
+        true
+    }
+
+    pub fn commit_inline(&mut self, edit: InlineEdit) {
+        let Some(index) = self.transaction_index(edit.id) else { return };
+        let t = &mut self.transactions[index];
+        match edit.field {
+            InlineField::Description => {
+                let text = edit.text.trim();
+                if text.is_empty() || text == t.description {
+                    return;
+                }
+                t.description = text.to_string();
+            }
+            InlineField::Amount => {
+                let Some(amount) = positive_amount(&edit.text) else { return };
+                if amount == t.amount {
+                    return;
+                }
+                t.amount = amount;
+            }
+            InlineField::Category => {
+                t.category = edit.category;
+                t.subcategory = edit.subcategory;
+            }
+        }
+        t.touch();
+        self.save_data();
+    }
+
+    pub fn apply_row_actions(&mut self, actions: RowActions) {
+        // Clicking the sorted column again flips the direction
+        if let Some(column) = actions.sort {
+            if self.sort_column == column {
+                self.sort_ascending = !self.sort_ascending;
+            } else {
+                self.sort_column = column;
+                self.sort_ascending = column != SortColumn::Date && column != SortColumn::Amount;
+            }
+        }
+
+        if let Some((id, checked)) = actions.select {
+            if checked {
+                self.selected.insert(id);
+            } else {
+                self.selected.remove(&id);
+            }
+        }
+        if let Some((ids, checked)) = actions.select_all {
+            if checked {
+                self.selected.extend(ids);
+            } else {
+                for id in ids {
+                    self.selected.remove(&id);
+                }
+            }
+        }
+
+        if let Some(index) = actions.toggle_status.and_then(|id| self.transaction_index(id)) {
+            let t = &mut self.transactions[index];
+            t.status = t.status.next();
+            t.touch();
+            self.save_data();
+        }
+
+        if actions.preview.is_some() {
+            self.preview_attachment = actions.preview;
+        }
+
+        if let Some(id) = actions.toggle_note {
+            self.expanded_note = if self.expanded_note == Some(id) { None } else { Some(id) };
+        }
+
+        if let Some(index) = actions.edit.and_then(|id| self.transaction_index(id)) {
+            self.editing_id = actions.edit;
+            // Populate fields with data from the transaction we want to edit
+            let t = &self.transactions[index];
+            self.input_desc = t.description.clone();
+            self.input_amount = t.amount.to_string();
+            self.input_currency = t.currency.clone().unwrap_or_else(|| self.rates.home_currency.clone());
+            self.input_type = t.trans_type;
+            self.input_category = t.category.clone();
+            self.input_subcategory = t.subcategory.clone();
+            self.input_splits = t.splits.iter().map(|s| SplitRow {
+                category: s.category.clone(),
+                subcategory: s.subcategory.clone(),
+                amount: s.amount.to_string(),
+            }).collect();
+            self.input_note = t.note.clone();
+            self.input_attachments = t.attachments.clone();
+            self.input_goal = t.goal;
+            self.input_refund_of = t.refund_of;
+            self.input_date = t.date.date_naive();
+        }
+
+        self.inline_edit = actions.inline;
+        if let Some(edit) = actions.commit {
+            self.commit_inline(edit);
+        }
+
+        if let Some(id) = actions.remove {
+            if self.remove_transaction(id) {
+                self.save_data();
+                self.toasts.deleted(t!("moved-to-trash", count = 1), vec![id]);
+            }
+        }
+    }
+
+    pub fn show_trash_window(&mut self, ctx: &egui::Context) {
+        if !self.show_trash {
+            return;
+        }
+
+        let now = Local::now();
+        let mut open = true;
+        let mut restore = None;
+        let mut purge = None;
+        let mut empty = false;
+        egui::Window::new(t!("recently-deleted"))
+            .open(&mut open)
+            .resizable(true)
+            .default_size([600.0, 400.0])
+            .show(ctx, |ui| {
+                if self.trash.is_empty() {
+                    ui.label(t!("the-trash-is-empty"));
+                    return;
+                }
+                ui.horizontal(|ui| {
+                    ui.label(t!("trash-explained", days = trash::TRASH_DAYS));
+                    if ui.button(t!("empty-trash")).clicked() {
+                        empty = true;
+                    }
+                });
+                ui.separator();
+
+                egui::ScrollArea::vertical().show(ui, |ui| {
+                    for item in self.trash.iter().rev() {
+                        let t = &item.transaction;
+                        ui.horizontal(|ui| {
+                            ui.label(i18n::date(t.date.date_naive()));
+                            ui.label(&t.description);
+                            ui.label(t.category_path());
+                            ui.label(self.money(self.signed_amount(t)));
+                            ui.weak(t!("trash-item", deleted = i18n::date_time(&item.deleted), days = item.days_left(now)));
+                            if ui.button(t!("restore-deleted")).clicked() {
+                                restore = Some(t.id);
+                            }
+                            if ui.button(t!("delete-forever")).clicked() {
+                                purge = Some(t.id);
+                            }
+                        });
+                    }
+                });
+            });
+
+        if let Some(id) = restore {
+            self.restore_from_trash(&[id]);
+        }
+        if purge.is_some() || empty {
+            self.purge_from_trash(purge);
+            self.save_data();
+        }
+        if !open {
+            self.show_trash = false;
+        }
+    }
+
+    pub fn show_bulk_bar(&mut self, ui: &mut egui::Ui) {
+        // Forget rows that no longer exist (deleted, or replaced by a sync)
+        let existing: BTreeSet<Uuid> = self.transactions.iter().map(|t| t.id).collect();
+        self.selected.retain(|id| existing.contains(id));
+        if self.selected.is_empty() {
+            return;
+        }
+
+        // Offer the categories that fit the selection; mixed selections get expense ones
+        let all_income = self.transactions.iter()
+            .filter(|t| self.selected.contains(&t.id))
+            .all(|t| t.trans_type == TransactionType::Income);
+        let kind = if all_income { TransactionType::Income } else { TransactionType::Expense };
+
+        ui.horizontal(|ui| {
+            ui.strong(t!("selected-count", count = self.selected.len()));
+            ui.add_space(10.0);
+            ui.label(t!("category-label"));
+            category_picker(ui, "bulk_cat", &self.categories, kind,
+                &mut self.bulk_category, &mut self.bulk_subcategory);
+            if ui.button(t!("set-category")).clicked() {
+                self.bulk_confirm = Some(BulkAction::Recategorize);
+            }
+            if ui.button(t!("delete-selected")).clicked() {
+                self.bulk_confirm = Some(BulkAction::Delete);
+            }
+            if ui.button(t!("copy-selected")).on_hover_text(t!("copy-hint")).clicked() {
+                let ids = self.selected.clone();
+                self.copy_transactions(ui.ctx(), &ids);
+            }
+            if ui.button(t!("clear-selection")).clicked() {
+                self.selected.clear();
+            }
+        });
+    }
+
+    pub fn show_bulk_confirm(&mut self, ctx: &egui::Context) {
+        let Some(action) = self.bulk_confirm else { return };
+        let count = self.selected.len();
+        let question = match action {
+            BulkAction::Delete => t!("confirm-trash", count = count),
+            BulkAction::Recategorize => t!(
+                "confirm-recategorize",
+                count = count,
+                category = category_path(&self.bulk_category, self.bulk_subcategory.as_deref()),
+            ),
+        };
+
+        let mut answer = None;
+        egui::Window::new(t!("confirm"))
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, Vec2::ZERO)
+            .show(ctx, |ui| {
+                ui.label(question);
+                ui.horizontal(|ui| {
+                    if ui.button(t!("yes")).clicked() {
+                        answer = Some(true);
+                    }
+                    if ui.button(t!("cancel")).clicked() {
+                        answer = Some(false);
+                    }
+                });
+            });
+
+        match answer {
+            Some(true) => {
+                self.bulk_confirm = None;
+                self.apply_bulk(action);
+            }
+            Some(false) => self.bulk_confirm = None,
+            None => {}
+        }
+    }
+
+    pub fn apply_bulk(&mut self, action: BulkAction) {
+        let ids = self.selected.clone();
+        match action {
+            BulkAction::Delete => {
+                let removed: Vec<Uuid> = ids.into_iter().filter(|id| self.remove_transaction(*id)).collect();
+                self.toasts.deleted(t!("moved-to-trash", count = removed.len()), removed);
+            }
+            BulkAction::Recategorize => {
+                let kind = self.find_category(&self.bulk_category).map(|c| c.kind).unwrap_or_default();
+                let (mut moved, mut skipped) = (0, 0);
+                for t in self.transactions.iter_mut().filter(|t| ids.contains(&t.id)) {
+                    // Splits keep their parts, and income can't go into an expense-only category
+                    if !t.splits.is_empty() || !kind.matches(t.trans_type) {
+                        skipped += 1;
+                        continue;
+                    }
+                    t.category = self.bulk_category.clone();
+                    t.subcategory = self.bulk_subcategory.clone();
+                    t.touch();
+                    moved += 1;
+                }
+                self.bulk_status = Some(t!("recategorized", count = moved, skipped = skipped));
+                self.selected.clear();
+            }
+        }
+        self.save_data();
+    }
+
+    pub fn show_template_bar(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            ui.label(t!("template-label"));
+
+            let selected = self.selected_template
+                .and_then(|i| self.templates.get(i))
+                .map(|t| t.name.clone())
+                .unwrap_or_else(|| t!("choose"));
+
+            let mut picked = None;
+            egui::ComboBox::from_id_salt("template_dropdown")
+                .selected_text(selected)
+                .show_ui(ui, |ui| {
+                    for (i, template) in self.templates.iter().enumerate() {
+                        let label = format!("{} ({}, {})", template.name, self.money(template.amount), template.category);
+                        if ui.selectable_label(self.selected_template == Some(i), label).clicked() {
+                            picked = Some(i);
+                        }
+                    }
+                });
+            if let Some(i) = picked {
+                self.selected_template = Some(i);
+                self.apply_template(i);
+            }
+
+            if let Some(i) = self.selected_template {
+                if ui.button("🗑").on_hover_text(t!("delete-template")).clicked() {
+                    self.templates.remove(i);
+                    self.selected_template = None;
+                    self.save_data();
+                }
+            }
+
+            ui.add_space(20.0);
+            ui.add(egui::TextEdit::singleline(&mut self.template_name).desired_width(120.0).hint_text(t!("template-name")));
+            if ui.button(t!("save-as-template")).clicked() {
+                self.save_template();
+            }
+        });
+    }
+
+    // Starts reading a YNAB/Mint export in the background; finish_import adds it. Rows
+    // matching an existing transaction are held back for review, so re-importing is harmless.
+    pub fn import_csv(&mut self, ctx: &egui::Context, path: &Path, format: ImportFormat) {
+        if self.import_task.is_some() {
+            return;
+        }
+        tracing::info!(path = %path.display(), format = format.label(), "import started");
+        self.import_status = None;
+        self.import_errors.clear();
+        self.import_task = Some(import::start(
+            ctx,
+            path.to_path_buf(),
+            format,
+            self.rules.clone(),
+            self.hooks.clone(),
+            self.transactions.clone(),
+        ));
+    }
+
+    // Checks on a running import, once per frame
+    pub fn poll_import(&mut self) {
+        let Some(task) = &mut self.import_task else { return };
+        let Some(result) = task.poll() else { return };
+        let cancelled = task.is_cancelled();
+        self.import_task = None;
+        if cancelled {
+            tracing::info!("import cancelled");
+            self.import_status = Some(t!("import-cancelled"));
+            return;
+        }
+        match result {
+            Ok(prepared) => self.finish_import(prepared),
+            Err(e) => {
+                tracing::warn!("import failed: {}", e);
+                self.import_status = Some(t!("import-failed", error = e.to_string()));
+            }
+        }
+    }
+
+    // Adds a whole import at once, with a single save so one undo takes it back
+    pub fn finish_import(&mut self, prepared: import::Prepared) {
+        let added = prepared.new.len();
+        self.transactions.extend(prepared.new);
+        self.import_review.extend(prepared.duplicates.into_iter().map(|(incoming, existing)| ImportReview {
+            incoming,
+            existing,
+            choice: ReviewChoice::Skip,
+        }));
+        self.import_errors = prepared.errors.iter()
+            .map(|error| match &error.problem {
+                RowProblem::Unreadable(e) => t!("import-row-unreadable", line = error.line, error = e.clone()),
+                RowProblem::Date(value) => t!("import-row-bad-date", line = error.line, value = value.clone()),
+                RowProblem::Amount(value) => t!("import-row-bad-amount", line = error.line, value = value.clone()),
+            })
+            .collect();
+
+        tracing::info!(
+            added = added,
+            review = self.import_review.len(),
+            skipped = prepared.skipped,
+            unreadable = prepared.errors.len(),
+            "import finished",
+        );
+        // Registers the categories the export introduced
+        self.migrate_categories();
+        self.audit_cause = audit::Cause::Import;
+        self.save_data();
+        self.import_status = Some(t!(
+            "imported",
+            count = added,
+            format = prepared.format.label(),
+            review = self.import_review.len(),
+            skipped = prepared.skipped,
+            errors = prepared.errors.len(),
+        ));
+    }
+
+    pub fn show_import_review(&mut self, ctx: &egui::Context) {
+        if self.import_review.is_empty() {
+            return;
+        }
+
+        let mut apply = false;
+        egui::Window::new(t!("possible-duplicates"))
+            .resizable(true)
+            .default_size([700.0, 400.0])
+            .show(ctx, |ui| {
+                ui.label(t!("these-imported-rows-look-like-transactions"));
+                ui.horizontal(|ui| {
+                    ui.label(t!("set-all-to"));
+                    for choice in [ReviewChoice::Skip, ReviewChoice::Merge, ReviewChoice::KeepBoth] {
+                        if ui.button(choice.label()).clicked() {
+                            for review in self.import_review.iter_mut() {
+                                review.choice = choice;
+                            }
+                        }
+                    }
+                });
+                ui.separator();
+
+                egui::ScrollArea::vertical().max_height(300.0).show(ui, |ui| {
+                    for (i, review) in self.import_review.iter_mut().enumerate() {
+                        let Some(existing) = self.transactions.iter().find(|t| t.id == review.existing) else { continue };
+                        ui.push_id(i, |ui| {
+                            ui.horizontal(|ui| {
+                                ui.label(t!("imported-label"));
+                                ui.strong(format!(
+                                    "{} {} {}",
+                                    i18n::date(review.incoming.date.date_naive()),
+                                    review.incoming.description,
+                                    self.money_format.number(review.incoming.amount)
+                                ));
+                            });
+                            ui.horizontal(|ui| {
+                                ui.label(t!("existing-label"));
+                                ui.label(format!(
+                                    "{} {} {}",
+                                    i18n::date(existing.date.date_naive()),
+                                    existing.description,
+                                    self.money_format.number(existing.amount)
+                                ));
+                            });
+                            ui.horizontal(|ui| {
+                                for choice in [ReviewChoice::Skip, ReviewChoice::Merge, ReviewChoice::KeepBoth] {
+                                    ui.radio_value(&mut review.choice, choice, choice.label());
+                                }
+                            });
+                        });
+                        ui.separator();
+                    }
+                });
+
+                if ui.button(t!("apply")).clicked() {
+                    apply = true;
+                }
+            });
+
+        if apply {
+            self.apply_import_review();
+        }
+    }
+
+    pub fn apply_import_review(&mut self) {
+        let (mut kept, mut merged, mut skipped) = (0, 0, 0);
+        for review in std::mem::take(&mut self.import_review) {
+            match review.choice {
+                ReviewChoice::Skip => skipped += 1,
+                ReviewChoice::KeepBoth => {
+                    self.transactions.push(review.incoming);
+                    kept += 1;
+                }
+                // The existing entry keeps its description and category; the statement
+                // can only confirm it cleared and fill in a missing note
+                ReviewChoice::Merge => {
+                    let Some(index) = self.transaction_index(review.existing) else { continue };
+                    let t = &mut self.transactions[index];
+                    if review.incoming.status.is_cleared() && !t.status.is_cleared() {
+                        t.status = TransactionStatus::Cleared;
+                    }
+                    if t.note.is_empty() {
+                        t.note = review.incoming.note;
+                    }
+                    t.touch();
+                    merged += 1;
+                }
+            }
+        }
+        // Same as the rows that went straight in: kept ones may bring new categories
+        self.migrate_categories();
+        self.audit_cause = audit::Cause::Import;
+        self.save_data();
+        self.import_status = Some(t!("review-done", added = kept, merged = merged, skipped = skipped));
+    }
+
+    pub fn apply_template(&mut self, index: usize) {
+        let template = self.templates[index].clone();
+        self.input_desc = template.description;
+        self.input_amount = template.amount.to_string();
+        self.input_type = template.trans_type;
+        self.input_category = template.category;
+        self.input_subcategory = template.subcategory;
+        self.input_currency = template.currency.unwrap_or_else(|| self.rates.home_currency.clone());
+        self.input_splits = template.splits.iter().map(|s| SplitRow {
+            category: s.category.clone(),
+            subcategory: s.subcategory.clone(),
+            amount: s.amount.to_string(),
+        }).collect();
+        self.input_note = template.note;
+    }
+
+    // Captures the current form; a template with the same name is replaced
+    pub fn save_template(&mut self) {
+        let name = self.template_name.trim().to_string();
+        if name.is_empty() || self.input_desc.is_empty() {
+            return;
+        }
+        // The form's amount field already says what's wrong with it
+        let Some(amount) = positive_amount(&self.input_amount) else {
+            self.error = Some(AppError::BadAmount { field: t!("amount"), value: self.input_amount.clone() });
+            return;
+        };
+
+        let template = Template {
+            name: name.clone(),
+            description: self.input_desc.clone(),
+            amount,
+            trans_type: self.input_type,
+            category: self.input_category.clone(),
+            subcategory: self.input_subcategory.clone(),
+            currency: if self.input_currency == self.rates.home_currency {
+                None
+            } else {
+                Some(self.input_currency.clone())
+            },
+            splits: self.parsed_splits(amount).unwrap_or_default(),
+            note: self.input_note.trim().to_string(),
+        };
+
+        match self.templates.iter().position(|t| t.name == name) {
+            Some(i) => self.templates[i] = template,
+            None => self.templates.push(template),
+        }
+        self.template_name.clear();
+        self.save_data();
+    }
+
+    // Expenses dated on or before the form date, newest first
+    pub fn refund_picker(&mut self, ui: &mut egui::Ui) {
+        let selected = self.input_refund_of
+            .and_then(|id| self.transaction_index(id))
+            .map(|idx| self.transactions[idx].description.clone())
+            .unwrap_or_else(|| t!("none"));
+
+        let mut candidates: Vec<&Transaction> = self.transactions.iter()
+            .filter(|t| t.trans_type == TransactionType::Expense && t.date.date_naive() <= self.input_date)
+            .collect();
+        candidates.sort_by_key(|t| std::cmp::Reverse(t.date));
+
+        egui::ComboBox::from_id_salt("refund_dropdown")
+            .selected_text(selected)
+            .height(300.0)
+            .show_ui(ui, |ui| {
+                ui.selectable_value(&mut self.input_refund_of, None, t!("none"));
+                for t in candidates.into_iter().take(100) {
+                    let label = format!("{} {} ({})", i18n::date(t.date.date_naive()), t.description, self.money_format.money(t.amount));
+                    ui.selectable_value(&mut self.input_refund_of, Some(t.id), label);
+                }
+            });
+    }
+
+    pub fn show_attachment_viewer(&mut self, ctx: &egui::Context) {
+        let Some(name) = self.preview_attachment.clone() else { return };
+
+        let mut open = true;
+        egui::Window::new(t!("receipt"))
+            .open(&mut open)
+            .resizable(true)
+            .default_size([500.0, 600.0])
+            .show(ctx, |ui| {
+                ui.label(&name);
+                egui::ScrollArea::both().show(ui, |ui| {
+                    ui.add(egui::Image::new(attachments::attachment_uri(&name)).shrink_to_fit());
+                });
+            });
+
+        if !open {
+            self.preview_attachment = None;
+        }
+    }
+
+    // Reset the add/edit form for the next entry
+    pub fn clear_inputs(&mut self) {
+        self.input_desc.clear();
+        self.input_amount.clear();
+        self.input_splits.clear();
+        self.input_note.clear();
+        self.input_attachments.clear();
+        self.input_goal = None;
+        self.input_refund_of = None;
+        self.attachment_error = None;
+        self.receipt_status = None;
+        self.duplicate_of = None;
+        self.input_date = Local::now().date_naive();
+        self.draft_id = Uuid::new_v4();
+    }
+
+    // Attaches the picked photo and reads it in the background, see receipt.rs
+    fn scan_receipt(&mut self, ctx: &egui::Context) {
+        let Some(path) = attachments::pick_images().into_iter().next() else { return };
+        let owner = self.editing_id.unwrap_or(self.draft_id);
+        match attachments::import_attachment(&path, owner) {
+            Ok(name) => {
+                let day_first = i18n::language() == i18n::Language::Portuguese;
+                self.receipt_scan = Some(receipt::scan(ctx, attachments::attachment_path(&name), day_first));
+                self.input_attachments.push(name);
+                self.receipt_status = None;
+            }
+            Err(e) => self.attachment_error = Some(t!("attach-failed", path = path.display().to_string(), error = e.to_string())),
+        }
+    }
+
+    // Fills the add form with what the receipt showed, for the user to check and add
+    pub fn poll_receipt_scan(&mut self) {
+        let Some(scan) = &self.receipt_scan else { return };
+        let Some(result) = scan.poll() else { return };
+        self.receipt_scan = None;
+        let found = match result {
+            Ok(found) => found,
+            Err(e) => {
+                tracing::warn!("receipt not read: {}", e);
+                self.receipt_status = Some(t!("receipt-failed", error = e.to_string()));
+                return;
+            }
+        };
+        if found.merchant.is_none() && found.total.is_none() && found.date.is_none() {
+            self.receipt_status = Some(t!("receipt-nothing-found"));
+            return;
+        }
+        self.input_type = TransactionType::Expense;
+        if let Some(merchant) = found.merchant.filter(|_| self.input_desc.trim().is_empty()) {
+            self.input_desc = merchant;
+        }
+        if let Some(total) = found.total {
+            self.input_amount = format!("{:.2}", total);
+        }
+        if let Some(date) = found.date {
+            self.input_date = date;
+        }
+        self.receipt_status = Some(t!("receipt-filled"));
+    }
+
+    pub fn show_split_editor(&mut self, ui: &mut egui::Ui) {
+        ui.label(t!("splits-label"));
+
+        let mut to_remove = None;
+        let mut allocated = 0.0;
+        for (index, row) in self.input_splits.iter_mut().enumerate() {
+            ui.horizontal(|ui| {
+                ui.add_space(20.0);
+                category_picker(ui, ("split_cat", index), &self.categories, self.input_type,
+                    &mut row.category, &mut row.subcategory);
+                let problem = amount_problem(&row.amount);
+                let field = ui.add(egui::TextEdit::singleline(&mut row.amount)
+                    .desired_width(80.0)
+                    .text_color_opt(problem.as_ref().map(|_| ui.visuals().error_fg_color)));
+                if ui.button("🗑").clicked() {
+                    to_remove = Some(index);
+                }
+                if let Some(problem) = problem {
+                    show_amount_problem(ui, &field, problem);
+                }
+            });
+            allocated += calc::evaluate(&row.amount).unwrap_or(0.0);
+        }
+
+        if let Some(index) = to_remove {
+            self.input_splits.remove(index);
+        }
+
+        ui.horizontal(|ui| {
+            ui.add_space(20.0);
+            if ui.button(t!("add-split")).clicked() {
+                self.input_splits.push(SplitRow {
+                    category: self.input_category.clone(),
+                    ..Default::default()
+                });
+            }
+
+            let total = calc::evaluate(&self.input_amount).unwrap_or(0.0);
+            let remaining = total - allocated;
+            let color = if remaining.abs() < 0.005 { theme::income(ui.visuals()) } else { theme::expense(ui.visuals()) };
+            ui.colored_label(color, t!("split-remaining", amount = self.money_format.number(remaining)));
+        });
+    }
+
+    // Splits from the form, or None if they don't add up to the transaction amount.
+    // A single row is the same as no split at all.
+    pub fn parsed_splits(&self, amount: f64) -> Option<Vec<Split>> {
+        if self.input_splits.len() < 2 {
+            return Some(Vec::new());
+        }
+
+        let mut splits = Vec::new();
+        for row in &self.input_splits {
+            let split_amount = positive_amount(&row.amount)?;
+            splits.push(Split {
+                category: row.category.clone(),
+                subcategory: row.subcategory.clone(),
+                amount: split_amount,
+            });
+        }
+
+        let allocated: f64 = splits.iter().map(|s| s.amount).sum();
+        if (allocated - amount).abs() >= 0.005 {
+            return None;
+        }
+        Some(splits)
+    }
+}
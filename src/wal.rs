@@ -0,0 +1,80 @@
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+use crate::audit::{self, Entry};
+use crate::crypto::LineKey;
+use crate::model::Transaction;
+
+// Write-ahead log: the transaction changes of every save, flushed to disk before the
+// ledger itself is written (in the background, or later by autosave). Emptied once the
+// ledger is on disk, so whatever is left in it at startup didn't make it there and is
+// put back on top of the ledger. It's written like the audit journal, sealed for an
+// encrypted ledger, and read back with audit::read.
+
+pub fn wal_path(data_path: &Path) -> PathBuf {
+    let stem = data_path.file_stem()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_else(|| "finance_data".to_string());
+    data_path.with_file_name(format!("{}-wal.jsonl", stem))
+}
+
+pub fn append(path: &Path, entries: &[Entry], key: Option<&LineKey>) -> io::Result<()> {
+    let mut file = std::fs::OpenOptions::new().create(true).append(true).open(path)?;
+    file.write_all(&audit::lines(entries, key)?)?;
+    // Not much of a log if it's still in a buffer when the power goes
+    file.sync_data()
+}
+
+// Called once the ledger has everything the log has
+pub fn clear(path: &Path) -> io::Result<()> {
+    match std::fs::remove_file(path) {
+        Err(e) if e.kind() != io::ErrorKind::NotFound => Err(e),
+        _ => Ok(()),
+    }
+}
+
+// Applies logged changes in order and returns how many there were. Applying one the
+// ledger already has changes nothing, so a crash after the write but before the clear
+// is harmless.
+pub fn replay(transactions: &mut Vec<Transaction>, entries: Vec<Entry>) -> usize {
+    let count = entries.len();
+    for entry in entries {
+        let id = entry.after.as_ref().or(entry.before.as_ref()).map(|t| t.id);
+        let Some(id) = id else { continue };
+        let existing = transactions.iter().position(|t| t.id == id);
+        match (entry.after, existing) {
+            (Some(after), Some(i)) => transactions[i] = after,
+            (Some(after), None) => transactions.push(after),
+            (None, Some(i)) => {
+                transactions.remove(i);
+            }
+            (None, None) => {}
+        }
+    }
+    count
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::audit::Cause;
+    use crate::model::test_transaction;
+
+    #[test]
+    fn replaying_twice_changes_nothing() {
+        let kept = test_transaction("rent", 900.0);
+        let removed = test_transaction("coffee", 4.5);
+        let mut edited = kept.clone();
+        edited.amount = 950.0;
+        let added = test_transaction("lunch", 12.0);
+
+        let base = audit::index(&[kept.clone(), removed.clone()]);
+        let entries = audit::changes(&base, &[edited.clone(), added.clone()], Cause::Edit);
+        let mut ledger = vec![kept, removed];
+        assert_eq!(replay(&mut ledger, entries.clone()), 3);
+        assert!(ledger == vec![edited.clone(), added.clone()]);
+        // As after a crash between writing the ledger and clearing the log
+        replay(&mut ledger, entries);
+        assert!(ledger == vec![edited, added]);
+    }
+}
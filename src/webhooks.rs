@@ -0,0 +1,185 @@
+use chrono::{Duration, Local};
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use crate::audit::Entry;
+use crate::i18n::{self, t};
+use crate::model::TransactionType;
+use crate::FinanceApp;
+
+// JSON POSTs to URLs of the user's choosing when something happens, for ntfy, Discord,
+// Home Assistant and the like. Every payload has "event", a readable "message" (also as
+// "content", which is what Discord shows) and the numbers behind it.
+
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+pub struct Webhook {
+    pub url: String,
+    #[serde(default)]
+    pub budget_exceeded: bool,
+    #[serde(default)]
+    pub large_transaction: bool,
+    #[serde(default)]
+    pub weekly_summary: bool,
+}
+
+impl Webhook {
+    pub fn new(url: &str) -> Self {
+        Self { url: url.trim().to_string(), budget_exceeded: true, large_transaction: true, weekly_summary: true }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum Event {
+    BudgetExceeded,
+    LargeTransaction,
+    WeeklySummary,
+}
+
+impl Event {
+    fn name(&self) -> &'static str {
+        match self {
+            Event::BudgetExceeded => "budget_exceeded",
+            Event::LargeTransaction => "large_transaction",
+            Event::WeeklySummary => "weekly_summary",
+        }
+    }
+
+    fn wanted_by(&self, hook: &Webhook) -> bool {
+        match self {
+            Event::BudgetExceeded => hook.budget_exceeded,
+            Event::LargeTransaction => hook.large_transaction,
+            Event::WeeklySummary => hook.weekly_summary,
+        }
+    }
+}
+
+// Posts still on their way, which the command line waits for before it exits
+static SENDING: AtomicUsize = AtomicUsize::new(0);
+
+fn post(url: &str, payload: &serde_json::Value) {
+    let mut request = ehttp::Request::post(url, payload.to_string().into_bytes());
+    request.headers = ehttp::Headers::new(&[("Content-Type", "application/json")]);
+    let url = url.to_string();
+    SENDING.fetch_add(1, Ordering::SeqCst);
+    ehttp::fetch(request, move |result| {
+        match result {
+            Ok(response) if response.ok => tracing::info!(url = %url, "webhook sent"),
+            Ok(response) => tracing::warn!(url = %url, "webhook refused: {} {}", response.status, response.status_text),
+            Err(e) => tracing::warn!(url = %url, "webhook failed: {}", e),
+        }
+        SENDING.fetch_sub(1, Ordering::SeqCst);
+    });
+}
+
+// Gives posts a few seconds to go out, for processes about to exit
+pub fn wait() {
+    let until = std::time::Instant::now() + std::time::Duration::from_secs(5);
+    while SENDING.load(Ordering::SeqCst) > 0 && std::time::Instant::now() < until {
+        std::thread::sleep(std::time::Duration::from_millis(50));
+    }
+}
+
+// From the Send test button, whatever events the webhook is for
+pub fn send_test(hook: &Webhook) {
+    let message = t!("webhook-test");
+    post(hook.url.trim(), &serde_json::json!({ "event": "test", "message": message, "content": message }));
+}
+
+impl FinanceApp {
+    // To every webhook that wants this event. `details` is merged into the payload.
+    pub fn send_webhooks(&self, event: Event, message: String, details: serde_json::Value) {
+        let mut payload = serde_json::json!({
+            "event": event.name(),
+            "message": message,
+            "content": message,
+            "currency": self.rates.home_currency,
+        });
+        if let (Some(payload), serde_json::Value::Object(details)) = (payload.as_object_mut(), details) {
+            payload.extend(details);
+        }
+        for hook in self.settings.webhooks.iter().filter(|h| event.wanted_by(h) && !h.url.trim().is_empty()) {
+            post(hook.url.trim(), &payload);
+        }
+    }
+
+    // Called with every save's changes once they're on disk: budgets that went over
+    // their limit and large transactions that were added
+    pub fn webhooks_for_changes(&self, entries: &[Entry]) {
+        if self.settings.webhooks.is_empty() {
+            return;
+        }
+        for (category, limit, before, after) in self.budget_changes(entries) {
+            if before <= limit && after > limit {
+                self.send_webhooks(
+                    Event::BudgetExceeded,
+                    t!("webhook-budget-exceeded", category = category.clone(), spent = self.money(after), limit = self.money(limit)),
+                    serde_json::json!({ "category": category, "spent": after, "limit": limit }),
+                );
+            }
+        }
+        let threshold = self.settings.large_transaction;
+        if threshold <= 0.0 {
+            return;
+        }
+        for t in entries.iter().filter(|e| e.before.is_none()).filter_map(|e| e.after.as_ref()) {
+            let amount = self.home_amount(t);
+            if amount < threshold {
+                continue;
+            }
+            self.send_webhooks(
+                Event::LargeTransaction,
+                t!("webhook-large-transaction", description = t.description.clone(), amount = self.money(self.signed_amount(t))),
+                serde_json::json!({
+                    "description": t.description,
+                    "amount": amount,
+                    "type": if t.trans_type == TransactionType::Income { "income" } else { "expense" },
+                    "category": t.category_path(),
+                    "date": t.date.date_naive(),
+                }),
+            );
+        }
+    }
+
+    // Last week's totals, once per week the window is opened in
+    pub fn send_weekly_summary(&mut self) {
+        // The window that has the ledger open sends it
+        if self.read_only || !self.settings.webhooks.iter().any(|h| h.weekly_summary) {
+            return;
+        }
+        let (this_week, _) = self.settings.week_containing(Local::now().date_naive());
+        if self.settings.last_weekly_summary.is_some_and(|sent| sent >= this_week) {
+            return;
+        }
+        let (from, to) = (this_week - Duration::days(7), this_week - Duration::days(1));
+        let mut income = 0.0;
+        for t in self.transactions.iter().filter(|t| !t.is_scheduled() && (from..=to).contains(&t.date.date_naive())) {
+            if t.trans_type == TransactionType::Income && t.refund_of.is_none() {
+                income += self.home_amount(t);
+            }
+        }
+        let mut categories: Vec<(String, f64)> = self.category_spending(from, to).into_iter().collect();
+        categories.sort_by(|a, b| b.1.total_cmp(&a.1));
+        let expenses: f64 = categories.iter().map(|(_, amount)| amount).sum();
+        self.send_webhooks(
+            Event::WeeklySummary,
+            t!(
+                "webhook-weekly-summary",
+                from = i18n::date(from),
+                to = i18n::date(to),
+                income = self.money(income),
+                expenses = self.money(expenses),
+                net = self.money(income - expenses),
+            ),
+            serde_json::json!({
+                "from": from,
+                "to": to,
+                "income": income,
+                "expenses": expenses,
+                "categories": categories.iter().map(|(c, amount)| serde_json::json!({ "category": c, "amount": amount })).collect::<Vec<_>>(),
+            }),
+        );
+        tracing::info!(from = %from, "weekly summary sent");
+        self.settings.last_weekly_summary = Some(this_week);
+        self.save_settings();
+    }
+}